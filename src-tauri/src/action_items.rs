@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::events::{self, ActionItemAddedEvent};
+use crate::gemini_client::extract_json;
+use crate::session_manager::{SessionData, SessionManager};
+
+// ============================================================================
+// ACTION ITEM TRACKER - Consolidated Tasks/Deadlines across a Session
+// ============================================================================
+
+const TRACKED_CATEGORIES: &[&str] = &["TASK", "ACTION_ITEM", "DEADLINE"];
+const DUPLICATE_SIMILARITY: f32 = 0.75;        // Jaccard similarity treated as the same item
+
+/// Items from the live pipeline are tracked under this id when no session is running
+pub const LIVE_SESSION_ID: &str = "live";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrackedActionItem {
+    pub id: String,
+    pub session_id: String,
+    pub description: String,
+    pub categories: Vec<String>,
+    pub assignee: Option<String>,
+    pub due_date: Option<String>,
+    pub speaker: String,
+    pub confidence: f32,
+    pub start_ms: Option<u64>,
+    pub mentions: u32,
+    pub created_at: String,
+    // Jira/GitHub issue created for this item
+    #[serde(default)]
+    pub issue_url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ActionItemState {
+    pub items: StdMutex<HashMap<String, Vec<TrackedActionItem>>>,
+}
+
+impl ActionItemState {
+    /// Add an item, merging it into one with the same id or an existing
+    /// fuzzy duplicate. Returns the item when it is new.
+    pub fn track(&self, item: TrackedActionItem) -> Option<TrackedActionItem> {
+        let mut items = self.items.lock().unwrap();
+        let list = items.entry(item.session_id.clone()).or_default();
+
+        if let Some(existing) = list.iter_mut()
+            .find(|e| e.id == item.id || similarity(&e.description, &item.description) >= DUPLICATE_SIMILARITY)
+        {
+            existing.mentions += 1;
+            if existing.assignee.is_none() { existing.assignee = item.assignee; }
+            if existing.due_date.is_none() { existing.due_date = item.due_date; }
+            for cat in item.categories {
+                if !existing.categories.contains(&cat) { existing.categories.push(cat); }
+            }
+            existing.confidence = existing.confidence.max(item.confidence);
+            return None;
+        }
+
+        list.push(item.clone());
+        Some(item)
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<Vec<TrackedActionItem>> {
+        self.items.lock().unwrap().get(session_id).cloned()
+    }
+}
+
+// ============================================================================
+// Fuzzy Matching
+// ============================================================================
+
+// Carry no meaning of their own; left in, they make unrelated tasks look alike
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "that", "this", "with", "from", "into", "about", "will", "would",
+    "should", "can", "could", "need", "needs", "have", "has", "our", "your", "their", "its",
+    "are", "was", "were", "been", "let", "lets", "make", "sure", "also", "please",
+];
+
+fn normalize_words(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2 && !STOPWORDS.contains(w))
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// Jaccard similarity of the two word sets (0.0 - 1.0). A short item
+/// doesn't match a longer one just because its words all appear there.
+fn similarity(a: &str, b: &str) -> f32 {
+    let (wa, wb) = (normalize_words(a), normalize_words(b));
+    let union = wa.union(&wb).count();
+    if union == 0 {
+        return if a.trim().eq_ignore_ascii_case(b.trim()) { 1.0 } else { 0.0 };
+    }
+    wa.intersection(&wb).count() as f32 / union as f32
+}
+
+// ============================================================================
+// Extraction
+// ============================================================================
+
+fn tracked_categories(categories: &[String]) -> Vec<String> {
+    categories.iter()
+        .filter(|c| TRACKED_CATEGORIES.contains(&c.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Items from a timed segment get the same id whether they were tracked live
+/// or rebuilt from the stored session, so issue links find them again
+fn item_id(session_id: &str, speaker: &str, start_ms: Option<u64>) -> String {
+    match start_ms {
+        Some(ms) => format!("{}:{}:{}", session_id, speaker, ms),
+        None => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+fn new_item(session_id: &str, description: &str, speaker: &str, categories: Vec<String>, start_ms: Option<u64>) -> TrackedActionItem {
+    TrackedActionItem {
+        id: item_id(session_id, speaker, start_ms),
+        session_id: session_id.to_string(),
+        description: description.trim().to_string(),
+        categories,
+        assignee: None,
+        due_date: None,
+        speaker: speaker.to_string(),
+        confidence: 0.5,
+        start_ms,
+        mentions: 1,
+        created_at: Utc::now().to_rfc3339(),
+        issue_url: None,
+    }
+}
+
+/// Build an action item from a raw Gemini intelligence response, if it has a tracked category
+pub fn item_from_intelligence(
+    session_id: &str,
+    transcript: &str,
+    speaker: &str,
+    intelligence: &str,
+    start_ms: Option<u64>,
+) -> Option<TrackedActionItem> {
+    let parsed: serde_json::Value = serde_json::from_str(extract_json(intelligence)).ok()?;
+
+    let categories: Vec<String> = parsed["category"].as_array()?
+        .iter()
+        .filter_map(|c| c.as_str().map(|s| s.to_string()))
+        .collect();
+    let categories = tracked_categories(&categories);
+    if categories.is_empty() {
+        return None;
+    }
+
+    let description = parsed["summary"].as_str()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(transcript);
+
+    let mut item = new_item(session_id, description, speaker, categories, start_ms);
+    item.confidence = parsed["confidence"].as_f64().unwrap_or(0.5) as f32;
+
+    if let Some(entities) = parsed["entities"].as_array() {
+        for entity in entities {
+            let name = entity["name"].as_str().unwrap_or_default();
+            if name.is_empty() { continue; }
+            match entity["type"].as_str() {
+                Some("PERSON") if item.assignee.is_none() => item.assignee = Some(name.to_string()),
+                Some("DATE") if item.due_date.is_none() => item.due_date = Some(name.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    Some(item)
+}
+
+/// Rebuild the tracker for a stored session from its categorized transcripts
+pub(crate) fn items_from_session(session: &SessionData) -> Vec<TrackedActionItem> {
+    let state = ActionItemState::default();
+    for t in &session.transcripts {
+        let categories = tracked_categories(t.category.as_deref().unwrap_or_default());
+        if categories.is_empty() { continue; }
+
+        let mut item = new_item(&session.id, &t.text, &t.speaker_id, categories, t.start_ms);
+        item.confidence = t.confidence;
+        state.track(item);
+    }
+
+    let mut items = state.get(&session.id).unwrap_or_default();
+    for item in &mut items {
+        // Links saved before items had stable ids only know the description
+        item.issue_url = session.issue_links.iter()
+            .find(|l| match &l.item_id {
+                Some(id) => *id == item.id,
+                None => l.description == item.description,
+            })
+            .map(|l| l.url.clone());
+    }
+    items
+}
+
+/// Feed a pipeline intelligence result into the tracker, emitting new items
+pub fn ingest_intelligence(
+    app: &AppHandle,
+    session_id: Option<&str>,
+    transcript: &str,
+    speaker: &str,
+    intelligence: &str,
+    start_ms: Option<u64>,
+) {
+    let Some(item) = item_from_intelligence(session_id.unwrap_or(LIVE_SESSION_ID), transcript, speaker, intelligence, start_ms) else {
+        return;
+    };
+
+    let state = app.state::<ActionItemState>();
+    if let Some(added) = state.track(item) {
+        info!("[ACTION] New action item: '{}' (assignee: {:?}, due: {:?})",
+                 added.description, added.assignee, added.due_date);
+        events::emit(app, &ActionItemAddedEvent { item: added });
+    }
+}
+
+/// Track an item the user marked explicitly (voice command), emitting it if new
+pub fn track_manual(app: &AppHandle, session_id: &str, description: &str, speaker: &str, start_ms: Option<u64>) {
+    let mut item = new_item(session_id, description, speaker, vec!["ACTION_ITEM".to_string()], start_ms);
+    item.confidence = 1.0;
+
+    if let Some(added) = app.state::<ActionItemState>().track(item) {
+        info!("[ACTION] Marked action item: '{}'", added.description);
+        events::emit(app, &ActionItemAddedEvent { item: added });
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_action_items(
+    state: tauri::State<'_, ActionItemState>,
+    session_id: String,
+) -> Result<Vec<TrackedActionItem>, String> {
+    if let Some(items) = state.get(&session_id) {
+        return Ok(items);
+    }
+
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(&session_id)?;
+    let items = items_from_session(&session);
+
+    state.items.lock().unwrap().insert(session_id, items.clone());
+    Ok(items)
+}
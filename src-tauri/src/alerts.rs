@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tracing::{error, info};
+use crate::dedupe::tokens;
+use crate::events::{self, KeywordAlertEvent};
+use crate::gemini_client::extract_json;
+use crate::hallucination::normalized_words;
+use crate::settings::SettingsState;
+
+// ============================================================================
+// ALERTS - Desktop Notifications for Urgent Intelligence
+// ============================================================================
+
+const NOTIFICATION_COOLDOWN_SECS: u64 = 20;  // Don't machine-gun during a heated discussion
+const MAX_BODY_CHARS: usize = 180;
+// Words of the segment kept on each side of a watched term
+const KEYWORD_CONTEXT_WORDS: usize = 12;
+const MAX_WATCH_KEYWORDS: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AlertRules {
+    pub enabled: bool,
+    // Matched against both the category list and the tone (e.g. "URGENT")
+    pub categories: Vec<String>,
+    pub min_confidence: f32,
+}
+
+impl Default for AlertRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            categories: vec!["DEADLINE".to_string(), "RISK".to_string(), "URGENT".to_string()],
+            min_confidence: 0.7,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AlertState {
+    last_sent: StdMutex<Option<Instant>>,
+}
+
+/// Which rule a segment tripped, if any
+fn matching_rule(rules: &AlertRules, parsed: &serde_json::Value) -> Option<String> {
+    let confidence = parsed["confidence"].as_f64().unwrap_or(0.0) as f32;
+    if confidence < rules.min_confidence {
+        return None;
+    }
+
+    let tone = parsed["tone"].as_str().map(|t| t.to_string());
+    parsed["category"].as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.as_str().map(|s| s.to_string()))
+        .chain(tone)
+        .find(|label| rules.categories.iter().any(|r| r.eq_ignore_ascii_case(label)))
+}
+
+/// Fire a native notification when an intelligence result matches the alert rules
+pub fn notify_if_urgent(app: &AppHandle, transcript: &str, speaker: &str, intelligence: &str) {
+    let rules = app.state::<SettingsState>().get().alerts;
+    if !rules.enabled { return; }
+
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(extract_json(intelligence)) else { return; };
+    let Some(label) = matching_rule(&rules, &parsed) else { return; };
+
+    {
+        let state = app.state::<AlertState>();
+        let mut last_sent = state.last_sent.lock().unwrap();
+        if last_sent.map(|t| t.elapsed() < Duration::from_secs(NOTIFICATION_COOLDOWN_SECS)).unwrap_or(false) {
+            return;
+        }
+        *last_sent = Some(Instant::now());
+    }
+
+    let text = parsed["summary"].as_str().filter(|s| !s.trim().is_empty()).unwrap_or(transcript);
+    let body: String = if text.chars().count() > MAX_BODY_CHARS {
+        format!("{}…", text.chars().take(MAX_BODY_CHARS).collect::<String>())
+    } else {
+        text.to_string()
+    };
+
+    info!("[ALERT] {} from {}: {}", label, speaker, body);
+    if let Err(e) = app.notification()
+        .builder()
+        .title(format!("⚠ {} - {}", label, speaker))
+        .body(body)
+        .show()
+    {
+        error!("[ALERT] ✗ Notification failed: {}", e);
+    }
+}
+
+// ============================================================================
+// Watched Keywords
+// ============================================================================
+//
+// Plain matching on the transcript, so a watched term ("budget", a name)
+// alerts even when the model doesn't flag the segment. Terms match whole
+// words, case-insensitively; "Sam" also matches "Sam's".
+
+/// Start of the first occurrence of `term` in `words`
+fn find_term(words: &[String], term: &[String]) -> Option<usize> {
+    if term.is_empty() || term.len() > words.len() {
+        return None;
+    }
+    (0..=words.len() - term.len()).find(|&start| {
+        term.iter().zip(&words[start..]).all(|(t, w)| w == t || w.strip_suffix("'s") == Some(t))
+    })
+}
+
+/// Emit a keyword alert for every watched term in a transcribed segment
+pub fn watch_keywords(
+    app: &AppHandle,
+    text: &str,
+    speaker: &str,
+    segment_id: Option<&str>,
+    session_id: Option<&str>,
+    start_ms: Option<u64>,
+) {
+    let keywords = app.state::<SettingsState>().get().watch_keywords;
+    if keywords.is_empty() { return; }
+
+    let tokens = tokens(text);
+    let words: Vec<String> = tokens.iter().map(|(_, norm)| norm.clone()).collect();
+    for keyword in keywords {
+        let term = normalized_words(&keyword);
+        let Some(start) = find_term(&words, &term) else { continue; };
+        let end = start + term.len();
+        let context: Vec<&str> = tokens[start.saturating_sub(KEYWORD_CONTEXT_WORDS)..(end + KEYWORD_CONTEXT_WORDS).min(tokens.len())]
+            .iter()
+            .map(|(token, _)| *token)
+            .collect();
+
+        info!("[ALERT] Keyword '{}' from {}", keyword, speaker);
+        events::emit(app, &KeywordAlertEvent {
+            keyword,
+            session_id: session_id.map(str::to_string),
+            segment_id: segment_id.map(str::to_string),
+            speaker: speaker.to_string(),
+            context: context.join(" "),
+            text: text.to_string(),
+            start_ms,
+        });
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_alert_rules(settings: tauri::State<'_, SettingsState>) -> AlertRules {
+    settings.get().alerts
+}
+
+#[tauri::command]
+pub fn set_alert_rules(
+    settings: tauri::State<'_, SettingsState>,
+    rules: AlertRules,
+) -> Result<AlertRules, String> {
+    if !(0.0..=1.0).contains(&rules.min_confidence) {
+        return Err("min_confidence must be between 0 and 1".to_string());
+    }
+    let rules = AlertRules {
+        categories: rules.categories.iter()
+            .map(|c| c.trim().to_uppercase())
+            .filter(|c| !c.is_empty())
+            .collect(),
+        ..rules
+    };
+    let settings = settings.update(|s| s.alerts = rules)?;
+    info!("[ALERT] Rules: {} ≥ {:.2}", settings.alerts.categories.join("|"), settings.alerts.min_confidence);
+    Ok(settings.alerts)
+}
+
+#[tauri::command]
+pub fn get_watch_keywords(settings: tauri::State<'_, SettingsState>) -> Vec<String> {
+    settings.get().watch_keywords
+}
+
+/// Replace the watched keywords and phrases; blanks and repeats are dropped
+#[tauri::command]
+pub fn set_watch_keywords(
+    settings: tauri::State<'_, SettingsState>,
+    keywords: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for keyword in keywords {
+        let keyword = keyword.split_whitespace().collect::<Vec<_>>().join(" ");
+        if normalized_words(&keyword).is_empty() {
+            continue;
+        }
+        if !cleaned.iter().any(|k| normalized_words(k) == normalized_words(&keyword)) {
+            cleaned.push(keyword);
+        }
+    }
+    if cleaned.len() > MAX_WATCH_KEYWORDS {
+        return Err(format!("At most {} watched keywords", MAX_WATCH_KEYWORDS));
+    }
+    let settings = settings.update(|s| s.watch_keywords = cleaned)?;
+    info!("[ALERT] Watching {} keyword(s)", settings.watch_keywords.len());
+    Ok(settings.watch_keywords)
+}
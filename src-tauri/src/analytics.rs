@@ -0,0 +1,239 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::bookmarks::format_offset;
+use crate::session_manager::{SessionData, SessionManager};
+
+// ============================================================================
+// ANALYTICS - Talk Time and Engagement per Speaker
+// ============================================================================
+//
+// Meeting-health numbers from segment offsets and speaker labels alone, so
+// they work for imported recordings and old sessions too. Only segments with
+// both offsets count; a segment is treated as continuous speech.
+//
+// - Interruption: a speaker starts while another speaker's segment is still
+//   running (needs per-participant tracks or overlapping segments to show up)
+// - Monologue: one speaker's consecutive segments with no one else in between
+//   and no pause longer than MONOLOGUE_MAX_PAUSE_MS
+
+const MONOLOGUE_MAX_PAUSE_MS: u64 = 3000;
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SpeakerAnalytics {
+    pub speaker: String,
+    pub talk_ms: u64,
+    // Share of all speaking time, 0.0 - 1.0
+    pub talk_share: f32,
+    pub segments: usize,
+    pub words: usize,
+    pub words_per_minute: f32,
+    // Times this speaker cut in on someone else / was cut in on
+    pub interruptions: usize,
+    pub interrupted: usize,
+    pub longest_monologue_ms: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Monologue {
+    pub speaker: String,
+    pub start_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SessionAnalytics {
+    pub session_id: String,
+    pub duration_ms: u64,
+    // Time at least one person was speaking
+    pub speech_ms: u64,
+    pub silence_ms: u64,
+    pub silence_ratio: f32,
+    // Time two or more people spoke at once
+    pub overlap_ms: u64,
+    pub turns: usize,
+    pub interruptions: usize,
+    pub longest_monologue: Option<Monologue>,
+    // Sorted by talk time, most first
+    pub speakers: Vec<SpeakerAnalytics>,
+    // Segments without offsets, left out of every number above
+    pub untimed_segments: usize,
+}
+
+/// Total length covered by (sorted by start) intervals, and how much of it overlaps
+fn coverage(intervals: &[(u64, u64)]) -> (u64, u64) {
+    let (mut covered, mut overlap) = (0u64, 0u64);
+    let mut reach: Option<(u64, u64)> = None;
+    for &(start, end) in intervals {
+        match reach {
+            Some((from, to)) if start < to => {
+                overlap += end.min(to) - start;
+                reach = Some((from, to.max(end)));
+            }
+            Some((from, to)) => {
+                covered += to - from;
+                reach = Some((start, end));
+            }
+            None => reach = Some((start, end)),
+        }
+    }
+    if let Some((from, to)) = reach {
+        covered += to - from;
+    }
+    (covered, overlap)
+}
+
+pub fn compute(session: &SessionData) -> SessionAnalytics {
+    let mut timed: Vec<(u64, u64, &str, usize)> = session.transcripts.iter()
+        .filter_map(|t| match (t.start_ms, t.end_ms) {
+            (Some(start), Some(end)) if end >= start => {
+                Some((start, end, t.speaker_id.as_str(), t.text.split_whitespace().count()))
+            }
+            _ => None,
+        })
+        .collect();
+    timed.sort_by_key(|(start, end, _, _)| (*start, *end));
+    let untimed_segments = session.transcripts.len() - timed.len();
+
+    let mut speakers: HashMap<&str, SpeakerAnalytics> = HashMap::new();
+    let mut turns = 0;
+    let mut interruptions = 0;
+    let mut longest: Option<Monologue> = None;
+    // Segment still running (latest end so far) and its speaker
+    let mut running: Option<(u64, &str)> = None;
+    // Current monologue: speaker, start, end
+    let mut monologue: Option<(&str, u64, u64)> = None;
+
+    let mut close_monologue = |monologue: Option<(&str, u64, u64)>, speakers: &mut HashMap<&str, SpeakerAnalytics>| {
+        let Some((speaker, start, end)) = monologue else { return; };
+        let duration_ms = end - start;
+        if let Some(stats) = speakers.get_mut(speaker) {
+            stats.longest_monologue_ms = stats.longest_monologue_ms.max(duration_ms);
+        }
+        if longest.as_ref().is_none_or(|m| duration_ms > m.duration_ms) {
+            longest = Some(Monologue { speaker: speaker.to_string(), start_ms: start, duration_ms });
+        }
+    };
+
+    for &(start, end, speaker, words) in &timed {
+        let stats = speakers.entry(speaker).or_insert_with(|| SpeakerAnalytics {
+            speaker: speaker.to_string(),
+            ..Default::default()
+        });
+        stats.talk_ms += end - start;
+        stats.segments += 1;
+        stats.words += words;
+
+        if let Some((running_end, running_speaker)) = running {
+            if running_speaker != speaker && start < running_end {
+                interruptions += 1;
+                stats.interruptions += 1;
+                if let Some(other) = speakers.get_mut(running_speaker) {
+                    other.interrupted += 1;
+                }
+            }
+        }
+        running = match running {
+            Some((running_end, running_speaker)) if running_end > end => Some((running_end, running_speaker)),
+            _ => Some((end, speaker)),
+        };
+
+        monologue = match monologue {
+            Some((current, from, to)) if current == speaker && start <= to + MONOLOGUE_MAX_PAUSE_MS => {
+                Some((current, from, to.max(end)))
+            }
+            previous => {
+                if previous.is_none_or(|(current, _, _)| current != speaker) {
+                    turns += 1;
+                }
+                close_monologue(previous, &mut speakers);
+                Some((speaker, start, end))
+            }
+        };
+    }
+    close_monologue(monologue, &mut speakers);
+
+    let intervals: Vec<(u64, u64)> = timed.iter().map(|(start, end, _, _)| (*start, *end)).collect();
+    let (speech_ms, overlap_ms) = coverage(&intervals);
+    let last_end = timed.iter().map(|(_, end, _, _)| *end).max().unwrap_or(0);
+    let duration_ms = (session.metadata.duration_seconds * 1000).max(last_end);
+    let silence_ms = duration_ms.saturating_sub(speech_ms);
+    let total_talk: u64 = speakers.values().map(|s| s.talk_ms).sum();
+
+    let mut speakers: Vec<SpeakerAnalytics> = speakers.into_values()
+        .map(|mut s| {
+            s.talk_share = if total_talk > 0 { s.talk_ms as f32 / total_talk as f32 } else { 0.0 };
+            s.words_per_minute = if s.talk_ms > 0 { s.words as f32 * 60_000.0 / s.talk_ms as f32 } else { 0.0 };
+            s
+        })
+        .collect();
+    speakers.sort_by(|a, b| b.talk_ms.cmp(&a.talk_ms).then_with(|| a.speaker.cmp(&b.speaker)));
+
+    SessionAnalytics {
+        session_id: session.id.clone(),
+        duration_ms,
+        speech_ms,
+        silence_ms,
+        silence_ratio: if duration_ms > 0 { silence_ms as f32 / duration_ms as f32 } else { 0.0 },
+        overlap_ms,
+        turns,
+        interruptions,
+        longest_monologue: longest,
+        speakers,
+        untimed_segments,
+    }
+}
+
+// ============================================================================
+// Export Rendering
+// ============================================================================
+
+pub const SPEAKER_COLUMNS: [&str; 6] = ["Speaker", "Talk time", "Share", "Words/min", "Interruptions", "Longest monologue"];
+
+/// One row per speaker, in SPEAKER_COLUMNS order
+pub fn speaker_rows(analytics: &SessionAnalytics) -> Vec<Vec<String>> {
+    analytics.speakers.iter()
+        .map(|s| vec![
+            s.speaker.clone(),
+            format_offset(s.talk_ms),
+            format!("{:.0}%", s.talk_share * 100.0),
+            format!("{:.0}", s.words_per_minute),
+            s.interruptions.to_string(),
+            format_offset(s.longest_monologue_ms),
+        ])
+        .collect()
+}
+
+/// Whole-meeting numbers as short lines
+pub fn overview_lines(analytics: &SessionAnalytics) -> Vec<String> {
+    let mut lines = vec![
+        format!("Speaking time: {} of {}", format_offset(analytics.speech_ms), format_offset(analytics.duration_ms)),
+        format!("Silence: {:.0}%", analytics.silence_ratio * 100.0),
+        format!("Turns: {}, interruptions: {}", analytics.turns, analytics.interruptions),
+    ];
+    if let Some(m) = &analytics.longest_monologue {
+        lines.push(format!("Longest monologue: {} by {} at {}", format_offset(m.duration_ms), m.speaker, format_offset(m.start_ms)));
+    }
+    lines
+}
+
+pub fn to_csv(analytics: &SessionAnalytics) -> String {
+    let mut csv = String::from("Speaker,TalkMs,TalkShare,Segments,Words,WordsPerMinute,Interruptions,Interrupted,LongestMonologueMs\n");
+    for s in &analytics.speakers {
+        csv.push_str(&format!(
+            "\"{}\",{},{:.3},{},{},{:.1},{},{},{}\n",
+            s.speaker.replace('"', "\"\""), s.talk_ms, s.talk_share, s.segments, s.words,
+            s.words_per_minute, s.interruptions, s.interrupted, s.longest_monologue_ms,
+        ));
+    }
+    csv
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_session_analytics(session_id: String) -> Result<SessionAnalytics, String> {
+    let session = SessionManager::new()?.load_session(&session_id)?;
+    Ok(compute(&session))
+}
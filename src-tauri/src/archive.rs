@@ -0,0 +1,341 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use crate::embeddings;
+use crate::encryption;
+use crate::event_journal::{self, JournaledEvent};
+use crate::gemini_client::GeminiState;
+use crate::live_session::LiveSessionState;
+use crate::recorder;
+use crate::session_manager::{valid_session_id, ExportManager, SessionData, SessionManager};
+
+// ============================================================================
+// ARCHIVE - Portable Session Zip (Audio, Transcript, Intelligence, Summary)
+// ============================================================================
+//
+// One zip per session for moving it to another machine or a teammate:
+//   manifest.json      format version, session, file list with SHA-256
+//   session.json       the full session; the only file import needs
+//   transcript.json    segments with speaker, timing and language
+//   intelligence.json  insights, knowledge graph and reanalysis versions
+//   summary.json       when the session has one
+//   subtitles.srt/.vtt
+//   recording.wav      when the session was recorded
+//   events.jsonl       the event journal, for replay
+// Everything in the zip is plaintext; import re-encrypts it if encryption is
+// on. Archives come from other people, so nothing in them is trusted: an id
+// that isn't a plain session id is rejected before any path is built, and a
+// session whose id already exists is imported as a copy with a new id.
+
+const FORMAT: &str = "cognivox-session-archive";
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+const SESSION: &str = "session.json";
+const RECORDING: &str = "recording.wav";
+const EVENTS: &str = "events.jsonl";
+// Upfront allocation for an entry; the manifest's sizes aren't trusted
+const MAX_PREALLOC_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArchiveFile {
+    pub name: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    pub session_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub exported_at: String,
+    pub files: Vec<ArchiveFile>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ImportedArchive {
+    pub session_id: String,
+    pub title: String,
+    // The archive's id was taken, so the session got a new one
+    pub renamed_from: Option<String>,
+    pub has_recording: bool,
+    pub events: usize,
+}
+
+fn zip_error(e: ZipError) -> String {
+    format!("Failed to write archive: {}", e)
+}
+
+struct ArchiveWriter {
+    zip: ZipWriter<File>,
+    files: Vec<ArchiveFile>,
+}
+
+impl ArchiveWriter {
+    fn add(&mut self, name: &str, content: &[u8]) -> Result<(), String> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        self.zip.start_file(name, options).map_err(zip_error)?;
+        self.zip.write_all(content).map_err(|e| format!("Failed to write archive: {}", e))?;
+        self.files.push(ArchiveFile {
+            name: name.to_string(),
+            bytes: content.len() as u64,
+            sha256: hex::encode(Sha256::digest(content)),
+        });
+        Ok(())
+    }
+
+    fn add_json(&mut self, name: &str, value: &impl Serialize) -> Result<(), String> {
+        let json = serde_json::to_vec_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        self.add(name, &json)
+    }
+
+    /// Streamed and stored uncompressed; recordings can be gigabytes
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<(), String> {
+        let mut source = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        self.zip.start_file(name, SimpleFileOptions::default().large_file(true)).map_err(zip_error)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut bytes = 0u64;
+        loop {
+            let read = source.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if read == 0 { break; }
+            hasher.update(&buffer[..read]);
+            self.zip.write_all(&buffer[..read]).map_err(|e| format!("Failed to write archive: {}", e))?;
+            bytes += read as u64;
+        }
+        self.files.push(ArchiveFile { name: name.to_string(), bytes, sha256: hex::encode(hasher.finalize()) });
+        Ok(())
+    }
+}
+
+/// Write the session archive into the exports folder; returns its path
+pub fn export(session_id: &str) -> Result<String, String> {
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(session_id)?;
+    let path = manager.export_path(&session.id, "zip")?;
+    let file = File::create(&path).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut archive = ArchiveWriter { zip: ZipWriter::new(file), files: Vec::new() };
+
+    archive.add_json(SESSION, &session)?;
+    archive.add_json("transcript.json", &session.transcripts)?;
+    archive.add_json("intelligence.json", &serde_json::json!({
+        "insights": session.insights,
+        "psychosomatic": session.psychosomatic,
+        "graph_nodes": session.graph_nodes,
+        "graph_edges": session.graph_edges,
+        "analyses": session.analyses,
+    }))?;
+    if let Some(summary) = &session.summary {
+        archive.add_json("summary.json", summary)?;
+    }
+    archive.add("subtitles.srt", ExportManager::export_to_srt(&session)?.as_bytes())?;
+    archive.add("subtitles.vtt", ExportManager::export_to_vtt(&session)?.as_bytes())?;
+
+    match session.recording_path.as_deref().map(Path::new) {
+        Some(wav) if wav.exists() => archive.add_file(RECORDING, wav)?,
+        Some(wav) => warn!("[ARCHIVE] Recording {} is missing, archiving without audio", wav.display()),
+        None => {}
+    }
+    if manager.events_path(&session.id)?.exists() {
+        let mut jsonl = String::new();
+        for event in event_journal::load(&session.id)? {
+            jsonl.push_str(&serde_json::to_string(&event).map_err(|e| e.to_string())?);
+            jsonl.push('\n');
+        }
+        archive.add(EVENTS, jsonl.as_bytes())?;
+    }
+
+    let manifest = ArchiveManifest {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        session_id: session.id.clone(),
+        title: session.metadata.title.clone(),
+        created_at: session.created_at.clone(),
+        exported_at: Utc::now().to_rfc3339(),
+        files: archive.files.clone(),
+    };
+    archive.add_json(MANIFEST, &manifest)?;
+    archive.zip.finish().map_err(zip_error)?;
+
+    let path = path.to_string_lossy().to_string();
+    info!("[ARCHIVE] ✓ Session {} archived to {} ({} file(s))", session.id, path, manifest.files.len());
+    Ok(path)
+}
+
+/// Contents of `name`, checked against the manifest; None if it isn't in the archive
+fn read_entry(zip: &mut ZipArchive<File>, manifest: &ArchiveManifest, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let Some(expected) = manifest.files.iter().find(|f| f.name == name) else { return Ok(None) };
+    let entry = zip.by_name(name).map_err(|e| format!("Archive is missing {}: {}", name, e))?;
+    let mut content = Vec::with_capacity(expected.bytes.min(MAX_PREALLOC_BYTES) as usize);
+    // One byte past the manifest size is enough to tell it's been tampered with
+    entry
+        .take(expected.bytes.saturating_add(1))
+        .read_to_end(&mut content)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    if content.len() as u64 > expected.bytes {
+        return Err(format!("{} is larger than the manifest says", name));
+    }
+    if hex::encode(Sha256::digest(&content)) != expected.sha256 {
+        return Err(format!("{} is corrupted (checksum mismatch)", name));
+    }
+    Ok(Some(content))
+}
+
+/// Id the imported session is stored under, and the archive's id if that was taken
+fn import_id(archive_id: &str, taken: impl Fn(&str) -> bool) -> Result<(String, Option<String>), String> {
+    if !valid_session_id(archive_id) {
+        return Err("Archive has an invalid session id".to_string());
+    }
+    if taken(archive_id) {
+        return Ok((uuid::Uuid::new_v4().to_string(), Some(archive_id.to_string())));
+    }
+    Ok((archive_id.to_string(), None))
+}
+
+/// Journal lines for the imported session, pointed at its (possibly new) id
+fn journal_lines(jsonl: &[u8], old_id: &str, new_id: &str) -> Result<Vec<String>, String> {
+    let text = std::str::from_utf8(jsonl).map_err(|e| format!("{} is not UTF-8: {}", EVENTS, e))?;
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let mut event: JournaledEvent = serde_json::from_str(l).map_err(|e| format!("Invalid event in {}: {}", EVENTS, e))?;
+            if event.payload["session_id"].as_str() == Some(old_id) {
+                event.payload["session_id"] = Value::String(new_id.to_string());
+            }
+            let line = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+            encryption::seal_line(&line)
+        })
+        .collect()
+}
+
+/// Write the recording and event journal for `session`, recording each file in
+/// `written` as soon as it exists; returns the number of journaled events
+fn store_files(
+    zip: &mut ZipArchive<File>,
+    manifest: &ArchiveManifest,
+    manager: &SessionManager,
+    session: &mut SessionData,
+    original_id: &str,
+    written: &mut Vec<PathBuf>,
+) -> Result<usize, String> {
+    if let Some(expected) = manifest.files.iter().find(|f| f.name == RECORDING) {
+        let wav = recorder::recording_path(&session.id)?;
+        let mut entry = zip.by_name(RECORDING).map_err(|e| format!("Archive is missing {}: {}", RECORDING, e))?;
+        let mut out = File::create(&wav).map_err(|e| format!("Failed to create {}: {}", wav.display(), e))?;
+        written.push(wav.clone());
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = entry.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", RECORDING, e))?;
+            if read == 0 { break; }
+            hasher.update(&buffer[..read]);
+            out.write_all(&buffer[..read]).map_err(|e| format!("Failed to write {}: {}", wav.display(), e))?;
+        }
+        if hex::encode(hasher.finalize()) != expected.sha256 {
+            return Err(format!("{} is corrupted (checksum mismatch)", RECORDING));
+        }
+        session.recording_path = Some(wav.to_string_lossy().to_string());
+    }
+
+    let Some(jsonl) = read_entry(zip, manifest, EVENTS)? else { return Ok(0) };
+    let lines = journal_lines(&jsonl, original_id, &session.id)?;
+    let path = manager.events_path(&session.id)?;
+    let mut journal = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to write event journal: {}", e))?;
+    written.push(path);
+    for line in &lines {
+        writeln!(journal, "{}", line).map_err(|e| format!("Failed to write event journal: {}", e))?;
+    }
+    Ok(lines.len())
+}
+
+/// Store the session from an archive written by `export`
+pub fn import(app: &AppHandle, path: &Path) -> Result<ImportedArchive, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("Not a zip archive: {}", e))?;
+
+    let manifest: ArchiveManifest = {
+        let mut entry = zip.by_name(MANIFEST).map_err(|_| "Not a cognivox session archive (no manifest)".to_string())?;
+        let mut json = String::new();
+        entry.read_to_string(&mut json).map_err(|e| format!("Failed to read manifest: {}", e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid manifest: {}", e))?
+    };
+    if manifest.format != FORMAT {
+        return Err("Not a cognivox session archive".to_string());
+    }
+    if manifest.version > FORMAT_VERSION {
+        return Err(format!("Archive format v{} needs a newer version of cognivox", manifest.version));
+    }
+
+    let json = read_entry(&mut zip, &manifest, SESSION)?.ok_or("Archive has no session.json")?;
+    let mut session: SessionData = serde_json::from_slice(&json).map_err(|e| format!("Invalid session.json: {}", e))?;
+    let manager = SessionManager::new()?;
+    let original_id = session.id.clone();
+    let (id, renamed_from) = import_id(&original_id, |id| manager.session_exists(id))?;
+    session.id = id;
+
+    session.recording_path = None;
+    session.vault_note = None;
+    // A failed import leaves no orphaned recording or journal behind
+    let mut written = Vec::new();
+    let stored = store_files(&mut zip, &manifest, &manager, &mut session, &original_id, &mut written)
+        .and_then(|events| manager.save_session(&session).map(|_| events));
+    if stored.is_err() {
+        for path in &written {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let events = stored?;
+
+    if app.state::<GeminiState>().api_key.lock().unwrap().is_some() {
+        embeddings::index_in_background(app, &session.id);
+    }
+
+    info!(
+        "[ARCHIVE] ✓ Imported '{}' as {} ({} segment(s), {} event(s), {})",
+        session.metadata.title, session.id, session.transcripts.len(), events,
+        if session.recording_path.is_some() { "with audio" } else { "no audio" }
+    );
+    Ok(ImportedArchive {
+        session_id: session.id,
+        title: session.metadata.title,
+        renamed_from,
+        has_recording: session.recording_path.is_some(),
+        events,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Zip a stored session with its audio, transcript, intelligence and summary; returns the path
+#[tauri::command]
+pub fn export_session_archive(live: tauri::State<'_, LiveSessionState>, session_id: String) -> Result<String, String> {
+    if live.active_id().as_deref() == Some(session_id.as_str()) {
+        return Err("End the live session before archiving it".to_string());
+    }
+    export(&session_id)
+}
+
+#[tauri::command]
+pub fn import_session_archive(app: AppHandle, path: String) -> Result<ImportedArchive, String> {
+    import(&app, Path::new(&path))
+}
@@ -1,8 +1,17 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use crossbeam_channel::{unbounded, Sender, Receiver};
 use serde::{Serialize, Deserialize};
+use rubato::{FftFixedIn, Resampler};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+use crate::audio_channel::{AudioSender, ChannelStats};
+use crate::live_session::LiveSessionState;
+use crate::levels::InputLevel;
+use crate::loopback;
+use crate::settings::SettingsState;
 
 /// Tagged audio chunk with source information for speaker diarization
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,25 +20,68 @@ pub struct TaggedAudio {
     pub source: AudioSource,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AudioSource {
     Microphone,  // User's voice
-    System,      // Other speakers (WASAPI loopback)
+    System,      // Other speakers (system audio loopback)
 }
 
 // Audio state for Tauri
 pub struct AudioState {
     pub is_recording: Mutex<bool>,
     pub stream_control: Mutex<Option<Sender<()>>>,
-    pub audio_tx: Mutex<Option<Sender<TaggedAudio>>>,
+    pub audio_tx: Mutex<Option<AudioSender>>,
     pub current_volume: Arc<Mutex<f32>>,
     pub capture_mode: Mutex<CaptureMode>,
+    pub noise_suppression: Mutex<bool>,
+    // Subtract loopback audio from the mic when both are captured
+    pub echo_cancellation: Mutex<bool>,
+    pub input_level: Mutex<InputLevel>,
+    // Resampling state for audio pushed by the frontend, per track
+    pub(crate) pushed: Mutex<HashMap<AudioSource, PushedStream>>,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+impl AudioState {
+    pub fn noise_suppression_enabled(&self) -> bool {
+        self.noise_suppression.lock().map(|v| *v).unwrap_or(false)
+    }
+
+    pub fn echo_cancellation_enabled(&self) -> bool {
+        self.echo_cancellation.lock().map(|v| *v).unwrap_or(false)
+    }
+
+    /// Fill and drop counts of the channel into the audio loop
+    pub fn queue_stats(&self) -> ChannelStats {
+        self.audio_tx.lock().ok()
+            .and_then(|tx| tx.as_ref().map(AudioSender::stats))
+            .unwrap_or_default()
+    }
+
+    /// Signal the capture thread to drop its streams. Returns false if nothing was running.
+    pub fn stop_capture(&self) -> Result<bool, String> {
+        let mut is_rec = self.is_recording.lock().map_err(|e| e.to_string())?;
+        if !*is_rec {
+            return Ok(false);
+        }
+
+        let mut control = self.stream_control.lock().map_err(|e| e.to_string())?;
+        if let Some(tx) = control.take() {
+            let _ = tx.send(());
+        }
+
+        *is_rec = false;
+        Ok(true)
+    }
+}
+
+// Serialized with the names set_capture_mode accepts
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
 pub enum CaptureMode {
+    #[serde(rename = "mic")]
     MicOnly,
-    SystemOnly,  // WASAPI Loopback on Windows
+    #[serde(rename = "system")]
+    SystemOnly,  // Loopback only (see loopback.rs)
+    #[serde(rename = "both")]
     Both,
 }
 
@@ -41,11 +93,16 @@ impl Default for AudioState {
             audio_tx: Mutex::new(None),
             current_volume: Arc::new(Mutex::new(0.0)),
             capture_mode: Mutex::new(CaptureMode::Both),
+            noise_suppression: Mutex::new(false),
+            echo_cancellation: Mutex::new(false),
+            input_level: Mutex::new(InputLevel::default()),
+            pushed: Mutex::new(HashMap::new()),
         }
     }
 }
 
-const TARGET_SAMPLE_RATE: u32 = 16000;
+/// Rate every capture stream is converted to before it reaches the pipeline
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
 const MICRO_CHUNK_SAMPLES: usize = 160;
 const SILENCE_THRESHOLD: f32 = 0.0001;  // Very low - let processing loop handle speech detection
 const SILENCE_SKIP_CHUNKS: usize = 500;  // ~5 seconds before skipping (was 30 = 300ms)
@@ -76,12 +133,8 @@ pub fn list_audio_devices() -> Result<Vec<String>, String> {
         }
     }
     
-    // Check WASAPI availability (Windows only)
-    #[cfg(target_os = "windows")]
-    {
-        names.push("--- WASAPI Loopback ---".to_string());
-        names.push("  ✓ Available for System Audio Capture".to_string());
-    }
+    names.push("--- System Audio Loopback ---".to_string());
+    names.push(format!("  {}", loopback::describe_support()));
     
     Ok(names)
 }
@@ -96,34 +149,201 @@ pub fn set_capture_mode(state: tauri::State<'_, AudioState>, mode: String) -> Re
         _ => return Err("Invalid mode".to_string()),
     };
     *capture_mode = new_mode;
-    println!("[AUDIO] Capture mode: {:?}", new_mode);
+    info!("[AUDIO] Capture mode: {:?}", new_mode);
     Ok(format!("Mode: {:?}", new_mode))
 }
 
+#[tauri::command]
+pub fn set_noise_suppression(
+    state: tauri::State<'_, AudioState>,
+    settings: tauri::State<'_, SettingsState>,
+    enabled: bool,
+) -> Result<bool, String> {
+    settings.update(|s| s.noise_suppression = enabled)?;
+    *state.noise_suppression.lock().map_err(|e| e.to_string())? = enabled;
+    info!("[AUDIO] Noise suppression: {}", if enabled { "on" } else { "off" });
+    Ok(enabled)
+}
+
+#[tauri::command]
+pub fn set_echo_cancellation(
+    state: tauri::State<'_, AudioState>,
+    settings: tauri::State<'_, SettingsState>,
+    enabled: bool,
+) -> Result<bool, String> {
+    settings.update(|s| s.echo_cancellation = enabled)?;
+    *state.echo_cancellation.lock().map_err(|e| e.to_string())? = enabled;
+    info!("[AUDIO] Echo cancellation: {}", if enabled { "on" } else { "off" });
+    Ok(enabled)
+}
+
 #[tauri::command]
 pub fn get_current_volume(state: tauri::State<'_, AudioState>) -> Result<f32, String> {
     let volume = state.current_volume.lock().map_err(|e| e.to_string())?;
     Ok(*volume)
 }
 
+/// Smoothed level of the audio reaching the segmenter, with gain warnings
+#[tauri::command]
+pub fn get_input_level(state: tauri::State<'_, AudioState>) -> Result<InputLevel, String> {
+    let level = state.input_level.lock().map_err(|e| e.to_string())?;
+    Ok(*level)
+}
+
 fn calculate_rms(samples: &[f32]) -> f32 {
     if samples.is_empty() { return 0.0; }
     (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
 }
 
-fn to_mono(data: &[f32], channels: u16) -> Vec<f32> {
+pub(crate) fn to_mono(data: &[f32], channels: u16) -> Vec<f32> {
     data.chunks(channels as usize)
         .map(|ch| ch.iter().sum::<f32>() / channels as f32)
         .collect()
 }
 
-fn decimate(samples: Vec<f32>, from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if from_rate == to_rate { return samples; }
-    let factor = from_rate / to_rate;
-    if factor == 0 { return samples; }
-    samples.into_iter().step_by(factor as usize).collect()
+const RESAMPLER_CHUNK_FRAMES: usize = 1024;
+
+/// Streaming converter from the device rate to TARGET_SAMPLE_RATE.
+/// Uses rubato's FFT resampler (anti-aliased); falls back to linear
+/// interpolation if the rate pair can't be handled.
+pub(crate) struct StreamResampler {
+    from_rate: u32,
+    fft: Option<FftFixedIn<f32>>,
+    pending: Vec<f32>,
+    // Linear fallback: fractional read position into `pending`
+    position: f64,
+}
+
+impl StreamResampler {
+    pub(crate) fn new(from_rate: u32) -> Self {
+        let fft = if from_rate == TARGET_SAMPLE_RATE {
+            None
+        } else {
+            match FftFixedIn::<f32>::new(from_rate as usize, TARGET_SAMPLE_RATE as usize, RESAMPLER_CHUNK_FRAMES, 1, 1) {
+                Ok(r) => Some(r),
+                Err(e) => {
+                    warn!("[AUDIO] FFT resampler unavailable ({}), using linear interpolation", e);
+                    None
+                }
+            }
+        };
+        info!("[AUDIO] Resampling {} Hz → {} Hz", from_rate, TARGET_SAMPLE_RATE);
+        Self { from_rate, fft, pending: Vec::new(), position: 0.0 }
+    }
+
+    pub(crate) fn process(&mut self, mono: Vec<f32>) -> Vec<f32> {
+        if self.from_rate == TARGET_SAMPLE_RATE { return mono; }
+        self.pending.extend(mono);
+
+        if let Some(fft) = self.fft.as_mut() {
+            let mut out = Vec::new();
+            while self.pending.len() >= fft.input_frames_next() {
+                let frames = fft.input_frames_next();
+                match fft.process(&[&self.pending[..frames]], None) {
+                    Ok(mut channels) => out.append(&mut channels[0]),
+                    Err(e) => warn!("[AUDIO] Resample error: {}", e),
+                }
+                self.pending.drain(..frames);
+            }
+            return out;
+        }
+
+        self.linear()
+    }
+
+    fn linear(&mut self) -> Vec<f32> {
+        let step = self.from_rate as f64 / TARGET_SAMPLE_RATE as f64;
+        let mut out = Vec::with_capacity((self.pending.len() as f64 / step) as usize + 1);
+        while self.position + 1.0 < self.pending.len() as f64 {
+            let i = self.position as usize;
+            let frac = (self.position - i as f64) as f32;
+            out.push(self.pending[i] + (self.pending[i + 1] - self.pending[i]) * frac);
+            self.position += step;
+        }
+        // Keep the last sample so interpolation is continuous across callbacks
+        let consumed = (self.position as usize).min(self.pending.len());
+        self.pending.drain(..consumed);
+        self.position -= consumed as f64;
+        out
+    }
+
+    /// End of a recording: push the partial last chunk through with silence.
+    /// The caller trims the padding (file import).
+    pub(crate) fn flush(&mut self) -> Vec<f32> {
+        self.process(vec![0.0; RESAMPLER_CHUNK_FRAMES * 2])
+    }
 }
 
+/// Shared handles every capture stream feeds into
+#[derive(Clone)]
+pub(crate) struct StreamContext {
+    pub tx: Option<AudioSender>,
+    pub volume: Arc<Mutex<f32>>,
+}
+
+/// Build an input stream that downmixes, resamples and sends tagged micro-chunks.
+/// Each stream keeps its own buffer so mic and system audio stay on separate tracks.
+pub(crate) fn build_tagged_stream(
+    device: &cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    source: AudioSource,
+    ctx: &StreamContext,
+) -> Option<cpal::Stream> {
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+    
+    let tx = ctx.tx.clone();
+    let vol = ctx.volume.clone();
+    let mut resampler = StreamResampler::new(sample_rate);
+    let mut buffer: Vec<f32> = Vec::new();
+    let mut silence_count: usize = 0;
+    
+    device.build_input_stream(
+        &config.into(),
+        move |data: &[f32], _| {
+            if data.is_empty() { return; }
+            
+            let mono = to_mono(data, channels);
+            let resampled = resampler.process(mono);
+            if resampled.is_empty() { return; }
+            
+            let rms = calculate_rms(&resampled);
+            if let Ok(mut v) = vol.lock() { *v = rms; }
+            
+            // Silence detection
+            if rms < SILENCE_THRESHOLD {
+                silence_count += 1;
+                if silence_count > SILENCE_SKIP_CHUNKS { return; }
+            } else {
+                silence_count = 0;
+            }
+            
+            // Buffer and send tagged chunks
+            buffer.extend(resampled);
+            while buffer.len() >= MICRO_CHUNK_SAMPLES {
+                let chunk: Vec<f32> = buffer.drain(..MICRO_CHUNK_SAMPLES).collect();
+                if let Some(ref tx) = tx {
+                    tx.send(TaggedAudio { samples: chunk, source });
+                }
+            }
+        },
+        move |e| error!("[AUDIO] {:?} stream error: {}", source, e),
+        None
+    ).ok()
+}
+
+/// A pushed track's resampler and its not-yet-sent remainder. Kept between
+/// calls so chunk boundaries don't click; rebuilt if the rate changes.
+pub(crate) struct PushedStream {
+    sample_rate: u32,
+    resampler: StreamResampler,
+    buffer: Vec<f32>,
+}
+
+// getUserMedia/AudioContext rates range from 8 kHz telephony to 192 kHz interfaces
+pub(crate) const MIN_PUSH_SAMPLE_RATE: u32 = 8000;
+pub(crate) const MAX_PUSH_SAMPLE_RATE: u32 = 192_000;
+
 #[tauri::command]
 pub fn start_audio_capture(state: tauri::State<'_, AudioState>) -> Result<String, String> {
     let mut is_rec = state.is_recording.lock().map_err(|e| e.to_string())?;
@@ -137,251 +357,46 @@ pub fn start_audio_capture(state: tauri::State<'_, AudioState>) -> Result<String
         *control = Some(stop_tx);
     }
     
-    let audio_tx = state.audio_tx.lock().map_err(|e| e.to_string())?.clone();
+    let ctx = StreamContext {
+        tx: state.audio_tx.lock().map_err(|e| e.to_string())?.clone(),
+        volume: state.current_volume.clone(),
+    };
     let capture_mode = *state.capture_mode.lock().map_err(|e| e.to_string())?;
-    let volume = state.current_volume.clone();
 
-    println!("[AUDIO] Starting capture. Mode: {:?}", capture_mode);
+    info!("[AUDIO] Starting capture. Mode: {:?}", capture_mode);
 
     thread::spawn(move || {
-        let buffer: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
-        let silence_count: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
-        
         // === MICROPHONE CAPTURE ===
         let mic_stream = if capture_mode == CaptureMode::MicOnly || capture_mode == CaptureMode::Both {
             let host = cpal::default_host();
-            if let Some(device) = host.default_input_device() {
-                let name = device.name().unwrap_or_default();
-                println!("[AUDIO] Mic: {}", name);
-                
-                if let Ok(config) = device.default_input_config() {
-                    let channels = config.channels();
-                    let sample_rate = config.sample_rate().0;
-                    
-                    let tx = audio_tx.clone();
-                    let buf = buffer.clone();
-                    let sil = silence_count.clone();
-                    let vol = volume.clone();
-                    
-                    let stream = device.build_input_stream(
-                        &config.into(),
-                        move |data: &[f32], _| {
-                            if data.is_empty() { return; }
-                            
-                            let mono = to_mono(data, channels);
-                            let resampled = decimate(mono, sample_rate, TARGET_SAMPLE_RATE);
-                            
-                            let rms = calculate_rms(&resampled);
-                            if let Ok(mut v) = vol.lock() { *v = rms; }
-                            
-                            // Silence detection
-                            if let Ok(mut count) = sil.lock() {
-                                if rms < SILENCE_THRESHOLD {
-                                    *count += 1;
-                                    if *count > SILENCE_SKIP_CHUNKS { return; }
-                                } else {
-                                    *count = 0;
-                                }
-                            }
-                            
-                            // Buffer and send tagged chunks (Microphone source)
-                            if let Ok(mut b) = buf.lock() {
-                                b.extend(resampled);
-                                while b.len() >= MICRO_CHUNK_SAMPLES {
-                                    let chunk: Vec<f32> = b.drain(..MICRO_CHUNK_SAMPLES).collect();
-                                    if let Some(ref tx) = tx {
-                                        let _ = tx.send(TaggedAudio {
-                                            samples: chunk,
-                                            source: AudioSource::Microphone,
-                                        });
-                                    }
-                                }
-                            }
-                        },
-                        |e| eprintln!("[AUDIO] Mic error: {}", e),
-                        None
-                    ).ok();
-                    stream
-                } else { None }
-            } else { None }
+            host.default_input_device().and_then(|device| {
+                info!("[AUDIO] Mic: {}", device.name().unwrap_or_default());
+                let config = device.default_input_config().ok()?;
+                build_tagged_stream(&device, config, AudioSource::Microphone, &ctx)
+            })
         } else { None };
         
-        // === SYSTEM AUDIO (WASAPI LOOPBACK) - Windows Only ===
-        #[cfg(target_os = "windows")]
+        // === SYSTEM AUDIO (LOOPBACK) ===
         let loopback_stream = if capture_mode == CaptureMode::SystemOnly || capture_mode == CaptureMode::Both {
-            use cpal::available_hosts;
-            
-            println!("[AUDIO] Attempting system audio capture...");
-            
-            // Try WASAPI host first
-            let wasapi_host = available_hosts()
-                .into_iter()
-                .find(|h| h.name().contains("WASAPI"))
-                .and_then(|id| cpal::host_from_id(id).ok());
-            
-            let host = wasapi_host.unwrap_or_else(|| {
-                println!("[AUDIO] WASAPI not available, using default host");
-                cpal::default_host()
-            });
-            
-            // Strategy 1: Try loopback from default output device
-            let loopback_result = host.default_output_device().and_then(|device| {
-                let name = device.name().unwrap_or_default();
-                println!("[AUDIO] Trying loopback on output device: {}", name);
-                
-                device.default_output_config().ok().and_then(|config| {
-                    let channels = config.channels();
-                    let sample_rate = config.sample_rate().0;
-                    
-                    let tx = audio_tx.clone();
-                    let buf = buffer.clone();
-                    let sil = silence_count.clone();
-                    let vol = volume.clone();
-                    
-                    device.build_input_stream(
-                        &config.into(),
-                        move |data: &[f32], _| {
-                            if data.is_empty() { return; }
-                            
-                            let mono = to_mono(data, channels);
-                            let resampled = decimate(mono, sample_rate, TARGET_SAMPLE_RATE);
-                            
-                            let rms = calculate_rms(&resampled);
-                            if let Ok(mut v) = vol.lock() { *v = rms; }
-                            
-                            if let Ok(mut count) = sil.lock() {
-                                if rms < SILENCE_THRESHOLD {
-                                    *count += 1;
-                                    if *count > SILENCE_SKIP_CHUNKS { return; }
-                                } else {
-                                    *count = 0;
-                                }
-                            }
-                            
-                            // Buffer and send tagged chunks (System/loopback source)
-                            if let Ok(mut b) = buf.lock() {
-                                b.extend(resampled);
-                                while b.len() >= MICRO_CHUNK_SAMPLES {
-                                    let chunk: Vec<f32> = b.drain(..MICRO_CHUNK_SAMPLES).collect();
-                                    if let Some(ref tx) = tx {
-                                        let _ = tx.send(TaggedAudio {
-                                            samples: chunk,
-                                            source: AudioSource::System,
-                                        });
-                                    }
-                                }
-                            }
-                        },
-                        |e| eprintln!("[AUDIO] Loopback stream error: {}", e),
-                        None
-                    ).ok()
-                })
-            });
-            
-            if loopback_result.is_some() {
-                println!("[AUDIO] ✓ WASAPI loopback stream created successfully");
-                loopback_result
-            } else {
-                // Strategy 2: Try finding Stereo Mix or similar virtual device
-                println!("[AUDIO] Loopback failed, searching for Stereo Mix...");
-                
-                let stereo_mix = host.input_devices().ok().and_then(|devices| {
-                    devices.into_iter().find(|d| {
-                        if let Ok(name) = d.name() {
-                            let name_lower = name.to_lowercase();
-                            name_lower.contains("stereo mix") ||
-                            name_lower.contains("what u hear") ||
-                            name_lower.contains("wave out") ||
-                            name_lower.contains("loopback")
-                        } else {
-                            false
-                        }
-                    })
-                });
-                
-                if let Some(device) = stereo_mix {
-                    let name = device.name().unwrap_or_default();
-                    println!("[AUDIO] Found virtual capture device: {}", name);
-                    
-                    device.default_input_config().ok().and_then(|config| {
-                        let channels = config.channels();
-                        let sample_rate = config.sample_rate().0;
-                        
-                        let tx = audio_tx.clone();
-                        let buf = buffer.clone();
-                        let sil = silence_count.clone();
-                        let vol = volume.clone();
-                        
-                        device.build_input_stream(
-                            &config.into(),
-                            move |data: &[f32], _| {
-                                if data.is_empty() { return; }
-                                
-                                let mono = to_mono(data, channels);
-                                let resampled = decimate(mono, sample_rate, TARGET_SAMPLE_RATE);
-                                
-                                let rms = calculate_rms(&resampled);
-                                if let Ok(mut v) = vol.lock() { *v = rms; }
-                                
-                                if let Ok(mut count) = sil.lock() {
-                                    if rms < SILENCE_THRESHOLD {
-                                        *count += 1;
-                                        if *count > SILENCE_SKIP_CHUNKS { return; }
-                                    } else {
-                                        *count = 0;
-                                    }
-                                }
-                                
-                                // Buffer and send tagged chunks (System/Stereo Mix source)
-                                if let Ok(mut b) = buf.lock() {
-                                    b.extend(resampled);
-                                    while b.len() >= MICRO_CHUNK_SAMPLES {
-                                        let chunk: Vec<f32> = b.drain(..MICRO_CHUNK_SAMPLES).collect();
-                                        if let Some(ref tx) = tx {
-                                            let _ = tx.send(TaggedAudio {
-                                                samples: chunk,
-                                                source: AudioSource::System,
-                                            });
-                                        }
-                                    }
-                                }
-                            },
-                            |e| eprintln!("[AUDIO] Stereo Mix error: {}", e),
-                            None
-                        ).ok()
-                    })
-                } else {
-                    eprintln!("[AUDIO] ✗ System audio capture not available");
-                    eprintln!("[AUDIO] To enable system audio capture:");
-                    eprintln!("  1. Right-click Sound icon in system tray → Sounds");
-                    eprintln!("  2. Go to Recording tab");
-                    eprintln!("  3. Right-click → Show Disabled Devices");
-                    eprintln!("  4. Enable 'Stereo Mix' if available");
-                    None
-                }
-            }
+            loopback::open_loopback_stream(&ctx)
         } else { None };
         
-        #[cfg(not(target_os = "windows"))]
-        let loopback_stream: Option<cpal::Stream> = None;
-        
         // Play streams
         if let Some(ref s) = mic_stream { 
             if s.play().is_ok() {
-                println!("[AUDIO] ✓ Mic stream active");
+                info!("[AUDIO] ✓ Mic stream active");
             }
         }
         
-        #[cfg(target_os = "windows")]
         if let Some(ref s) = loopback_stream { 
             if s.play().is_ok() {
-                println!("[AUDIO] ✓ Loopback stream active");
+                info!("[AUDIO] ✓ Loopback stream active");
             }
         }
         
-        println!("[AUDIO] Capture running...");
+        info!("[AUDIO] Capture running...");
         let _ = stop_rx.recv();
-        println!("[AUDIO] Capture stopped");
+        info!("[AUDIO] Capture stopped");
     });
 
     *is_rec = true;
@@ -390,16 +405,70 @@ pub fn start_audio_capture(state: tauri::State<'_, AudioState>) -> Result<String
 
 #[tauri::command]
 pub fn stop_audio_capture(state: tauri::State<'_, AudioState>) -> Result<String, String> {
-    let mut is_rec = state.is_recording.lock().map_err(|e| e.to_string())?;
-    if !*is_rec {
-        return Ok("Not recording".to_string());
+    if state.stop_capture()? {
+        Ok("Stopped".to_string())
+    } else {
+        Ok("Not recording".to_string())
     }
-    
-    let mut control = state.stream_control.lock().map_err(|e| e.to_string())?;
-    if let Some(tx) = control.take() {
-        let _ = tx.send(());
+}
+
+/// Feed audio captured elsewhere (e.g. getUserMedia in the webview) into the
+/// segmentation pipeline, exactly as a native capture stream would. `samples`
+/// are interleaved f32 at `sample_rate`. Needs a live session (start_session),
+/// which owns the audio loop and the clock segment offsets are counted from.
+/// Returns the number of 16 kHz samples queued.
+#[tauri::command]
+pub fn push_audio_chunk(
+    app: AppHandle,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    channels: Option<u16>,
+    source: Option<AudioSource>,
+) -> Result<usize, String> {
+    if !(MIN_PUSH_SAMPLE_RATE..=MAX_PUSH_SAMPLE_RATE).contains(&sample_rate) {
+        return Err(format!(
+            "Unsupported sample rate {} Hz (expected {}-{})",
+            sample_rate, MIN_PUSH_SAMPLE_RATE, MAX_PUSH_SAMPLE_RATE,
+        ));
+    }
+    // Without a session the loop would start as a side effect and never be ended
+    if app.state::<LiveSessionState>().active_id().is_none() {
+        return Err("No session is running - call start_session before pushing audio".to_string());
+    }
+    let channels = channels.unwrap_or(1).max(1);
+    let source = source.unwrap_or(AudioSource::Microphone);
+    let state = app.state::<AudioState>();
+    // Native and pushed audio on the same track would interleave into noise
+    if *state.is_recording.lock().map_err(|e| e.to_string())? {
+        return Err("Native audio capture is running - stop it before pushing audio".to_string());
+    }
+    let tx = state.audio_tx.lock().map_err(|e| e.to_string())?.clone().ok_or("Audio channel unavailable")?;
+    if samples.is_empty() {
+        return Ok(0);
     }
 
-    *is_rec = false;
-    Ok("Stopped".to_string())
+    let resampled = {
+        let mut pushed = state.pushed.lock().map_err(|e| e.to_string())?;
+        let stream = pushed.entry(source).or_insert_with(|| PushedStream {
+            sample_rate,
+            resampler: StreamResampler::new(sample_rate),
+            buffer: Vec::new(),
+        });
+        if stream.sample_rate != sample_rate {
+            *stream = PushedStream { sample_rate, resampler: StreamResampler::new(sample_rate), buffer: Vec::new() };
+        }
+        let resampled = stream.resampler.process(to_mono(&samples, channels));
+        stream.buffer.extend(resampled);
+        let whole = stream.buffer.len() - stream.buffer.len() % MICRO_CHUNK_SAMPLES;
+        stream.buffer.drain(..whole).collect::<Vec<f32>>()
+    };
+    if let Ok(mut v) = state.current_volume.lock() {
+        *v = calculate_rms(&resampled);
+    }
+
+    // A stalled loop drops the oldest audio; the loop reports it
+    for chunk in resampled.chunks(MICRO_CHUNK_SAMPLES) {
+        tx.send(TaggedAudio { samples: chunk.to_vec(), source });
+    }
+    Ok(resampled.len())
 }
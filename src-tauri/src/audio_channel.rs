@@ -0,0 +1,171 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::AppHandle;
+use tracing::warn;
+use crate::audio_capture::{TaggedAudio, TARGET_SAMPLE_RATE};
+use crate::events::{self, AudioBackpressureEvent};
+
+// ============================================================================
+// AUDIO CHANNEL - Bounded Ring Buffer between Capture and the Audio Loop
+// ============================================================================
+//
+// Capture callbacks must never block, so a full buffer drops its OLDEST
+// chunks to make room: live captions stay current and memory stays flat
+// when the loop stalls (slow Whisper, no loop running). Every drop is
+// counted; the loop reports new drops, and a buffer filling past
+// HIGH_WATER, as cognivox:audio_backpressure.
+
+// Audio held for the loop before the oldest is dropped
+pub const AUDIO_QUEUE_SECS: usize = 30;
+const HIGH_WATER: f32 = 0.5;
+// At most one warning this often; drops in between are summed into the next
+const WARNING_INTERVAL_SECS: u64 = 5;
+
+#[derive(Default)]
+struct Ring {
+    chunks: VecDeque<TaggedAudio>,
+    samples: usize,
+    dropped_chunks: u64,
+    dropped_samples: u64,
+}
+
+struct Shared {
+    ring: Mutex<Ring>,
+    capacity_samples: usize,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct ChannelStats {
+    pub chunks: usize,
+    pub buffered_ms: u64,
+    pub capacity_ms: u64,
+    // Since startup
+    pub dropped_chunks: u64,
+    pub dropped_ms: u64,
+}
+
+impl ChannelStats {
+    pub fn fill(&self) -> f32 {
+        if self.capacity_ms == 0 { 0.0 } else { self.buffered_ms as f32 / self.capacity_ms as f32 }
+    }
+}
+
+fn samples_to_ms(samples: u64) -> u64 {
+    samples * 1000 / TARGET_SAMPLE_RATE as u64
+}
+
+impl Shared {
+    fn stats(&self) -> ChannelStats {
+        let ring = self.ring.lock().unwrap();
+        ChannelStats {
+            chunks: ring.chunks.len(),
+            buffered_ms: samples_to_ms(ring.samples as u64),
+            capacity_ms: samples_to_ms(self.capacity_samples as u64),
+            dropped_chunks: ring.dropped_chunks,
+            dropped_ms: samples_to_ms(ring.dropped_samples),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AudioSender {
+    shared: Arc<Shared>,
+}
+
+pub struct AudioReceiver {
+    shared: Arc<Shared>,
+}
+
+/// 16 kHz audio channel holding up to `capacity_secs`
+pub fn channel(capacity_secs: usize) -> (AudioSender, AudioReceiver) {
+    let shared = Arc::new(Shared {
+        ring: Mutex::new(Ring::default()),
+        capacity_samples: capacity_secs * TARGET_SAMPLE_RATE as usize,
+    });
+    (AudioSender { shared: shared.clone() }, AudioReceiver { shared })
+}
+
+impl AudioSender {
+    /// Queue a chunk, dropping the oldest ones if it doesn't fit.
+    /// Returns how many chunks were dropped to make room.
+    pub fn send(&self, audio: TaggedAudio) -> usize {
+        let mut ring = self.shared.ring.lock().unwrap();
+        ring.samples += audio.samples.len();
+        ring.chunks.push_back(audio);
+        let mut dropped = 0;
+        while ring.samples > self.shared.capacity_samples && ring.chunks.len() > 1 {
+            let Some(oldest) = ring.chunks.pop_front() else { break };
+            ring.samples -= oldest.samples.len();
+            ring.dropped_chunks += 1;
+            ring.dropped_samples += oldest.samples.len() as u64;
+            dropped += 1;
+        }
+        dropped
+    }
+
+    pub fn stats(&self) -> ChannelStats {
+        self.shared.stats()
+    }
+}
+
+impl AudioReceiver {
+    /// Everything queued so far, oldest first
+    pub fn drain(&self) -> VecDeque<TaggedAudio> {
+        let mut ring = self.shared.ring.lock().unwrap();
+        ring.samples = 0;
+        std::mem::take(&mut ring.chunks)
+    }
+
+    pub fn stats(&self) -> ChannelStats {
+        self.shared.stats()
+    }
+}
+
+/// Turns channel stats into throttled backpressure warnings for one loop run
+pub struct BackpressureWatch {
+    // Drop totals already reported (or from before this loop started)
+    reported_chunks: u64,
+    reported_ms: u64,
+    last_warning: Option<Instant>,
+    backlogged: bool,
+}
+
+impl BackpressureWatch {
+    pub fn new(stats: &ChannelStats) -> Self {
+        Self { reported_chunks: stats.dropped_chunks, reported_ms: stats.dropped_ms, last_warning: None, backlogged: false }
+    }
+
+    pub fn check(&mut self, app: &AppHandle, stats: &ChannelStats) {
+        let new_chunks = stats.dropped_chunks - self.reported_chunks;
+        let filling = stats.fill() >= HIGH_WATER;
+        let entered_backlog = filling && !self.backlogged;
+        self.backlogged = filling;
+        if new_chunks == 0 && !entered_backlog {
+            return;
+        }
+        if self.last_warning.is_some_and(|at| at.elapsed().as_secs() < WARNING_INTERVAL_SECS) {
+            return;
+        }
+
+        let dropped_ms = stats.dropped_ms - self.reported_ms;
+        let message = if new_chunks > 0 {
+            format!("Audio pipeline can't keep up - dropped {:.1}s of audio", dropped_ms as f32 / 1000.0)
+        } else {
+            format!("Audio pipeline is falling behind ({:.1}s buffered)", stats.buffered_ms as f32 / 1000.0)
+        };
+        warn!("[AUDIO] ⚠️ {}", message);
+        events::emit(app, &AudioBackpressureEvent {
+            dropped_chunks: new_chunks,
+            dropped_ms,
+            total_dropped_ms: stats.dropped_ms,
+            buffered_ms: stats.buffered_ms,
+            capacity_ms: stats.capacity_ms,
+            message,
+        });
+        self.reported_chunks = stats.dropped_chunks;
+        self.reported_ms = stats.dropped_ms;
+        self.last_warning = Some(Instant::now());
+    }
+}
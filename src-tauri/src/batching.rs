@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+use crate::gemini_client::{annotate_segment, call_gemini_with_text, RequestConfig};
+use crate::whisper_client::SegmentLanguage;
+
+// ============================================================================
+// MICRO-BATCHING - One Intelligence Request for a Burst of Short Segments
+// ============================================================================
+//
+// Quick back-and-forth ("yes", "sounds good", "Friday?") yields many 1-3s
+// segments, each costing a request and a rate-limit slot. Segments arriving
+// within BATCH_WINDOW_MS of the first are sent together as a numbered list,
+// and the model answers with one intelligence object per segment.
+
+const BATCH_WINDOW_MS: u64 = 2500;             // Oldest segment waits at most this long
+const SHORT_SEGMENT_TOKENS: usize = 40;        // Longer segments close the batch immediately
+const MAX_BATCH_TOKENS: usize = 200;
+const MAX_BATCH_SEGMENTS: usize = 6;
+const MAX_BATCH_OUTPUT_TOKENS: u32 = 8192;
+
+/// A transcribed live segment waiting for intelligence extraction
+pub(crate) struct LiveSegment {
+    pub segment_id: String,
+    pub session_id: Option<String>,
+    pub transcript: String,
+    pub speaker: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    // Whisper's confidence in the transcript
+    pub stt_confidence: f32,
+    pub language: Option<SegmentLanguage>,
+    // For latency metrics
+    pub speech_end: Instant,
+    pub transcribed_at: Instant,
+}
+
+impl LiveSegment {
+    /// "[speaker]: text", the form Gemini and the context window see
+    pub fn annotated(&self) -> String {
+        annotate_segment(&self.speaker, &self.transcript, self.stt_confidence)
+    }
+
+    // Rough estimate, ~4 characters per token
+    fn tokens(&self) -> usize {
+        self.transcript.len().div_ceil(4)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SegmentBatch {
+    segments: Vec<LiveSegment>,
+    opened: Option<Instant>,
+    tokens: usize,
+    // Set by a long segment: nothing else is worth waiting for
+    closed: bool,
+}
+
+impl SegmentBatch {
+    pub fn push(&mut self, segment: LiveSegment) {
+        let tokens = segment.tokens();
+        self.opened.get_or_insert_with(Instant::now);
+        self.tokens += tokens;
+        self.closed |= tokens > SHORT_SEGMENT_TOKENS;
+        self.segments.push(segment);
+    }
+
+    /// Ready to send: window elapsed, token/segment budget used up, or a flush
+    pub fn is_due(&self, flushing: bool) -> bool {
+        if self.segments.is_empty() {
+            return false;
+        }
+        flushing
+            || self.closed
+            || self.tokens >= MAX_BATCH_TOKENS
+            || self.segments.len() >= MAX_BATCH_SEGMENTS
+            || self.opened.is_some_and(|t| t.elapsed() >= Duration::from_millis(BATCH_WINDOW_MS))
+    }
+
+    pub fn take(&mut self) -> Vec<LiveSegment> {
+        let segments = std::mem::take(&mut self.segments);
+        *self = Self::default();
+        segments
+    }
+}
+
+/// Analyze several segments in one request. Returns one intelligence JSON per
+/// segment, in order, or an error if the response can't be split back up.
+pub(crate) async fn call_gemini_batch(
+    config: &RequestConfig,
+    system_prompt: &str,
+    segments: &[String],
+    context: &[String],
+) -> Result<Vec<String>, String> {
+    let count = segments.len();
+    let numbered = segments.iter()
+        .enumerate()
+        .map(|(i, s)| format!("SEGMENT {}: {}", i + 1, s))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let transcript = format!(
+        "{}\n\nThese are {} separate segments. Respond with a JSON array of exactly {} objects, \
+         one per SEGMENT in the same order, each in the usual format.",
+        numbered, count, count
+    );
+
+    // Room for one object per segment
+    let mut config = config.clone();
+    config.generation.max_output_tokens = config.generation.max_output_tokens
+        .saturating_mul(count as u32)
+        .min(MAX_BATCH_OUTPUT_TOKENS);
+    // One schema-shaped object per segment
+    config.intelligence_schema = config.intelligence_schema
+        .map(|schema| Arc::new(serde_json::json!({ "type": "ARRAY", "items": *schema })));
+
+    info!("[BATCH] Analyzing {} segments in one request", count);
+    let response = call_gemini_with_text(&config, system_prompt, &transcript, context).await?;
+    split_response(&response, count)
+}
+
+fn split_response(response: &str, count: usize) -> Result<Vec<String>, String> {
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if end > start => &response[start..=end],
+        _ => return Err("Batch response is not a JSON array".to_string()),
+    };
+    let items: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid batch response: {}", e))?;
+    if items.len() != count {
+        return Err(format!("Batch response had {} results for {} segments", items.len(), count));
+    }
+    Ok(items.iter().map(|item| item.to_string()).collect())
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::events::{self, BookmarkAddedEvent};
+use crate::live_session::LiveSessionState;
+use crate::session_manager::SessionManager;
+
+// ============================================================================
+// BOOKMARKS - Highlighted Moments in a Live Session
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BookmarkSource {
+    Voice,
+    Manual,
+    Hotkey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Bookmark {
+    pub id: String,
+    // Offset from the start of the session
+    pub at_ms: u64,
+    pub note: Option<String>,
+    // Segment being spoken when the bookmark was set
+    pub segment_id: Option<String>,
+    pub speaker: Option<String>,
+    pub text: Option<String>,
+    pub source: BookmarkSource,
+    pub created_at: String,
+}
+
+/// Bookmark the current moment of the active session
+pub fn add_to_active(app: &AppHandle, note: Option<String>, source: BookmarkSource) -> Result<Bookmark, String> {
+    let live = app.state::<LiveSessionState>();
+    let session_id = live.active_id().ok_or("No session is running")?;
+    let at_ms = live.elapsed_ms().unwrap_or(0);
+    let segment = live.last_segment().filter(|s| s.session_id.as_deref() == Some(session_id.as_str()));
+
+    let bookmark = Bookmark {
+        id: uuid::Uuid::new_v4().to_string(),
+        at_ms,
+        note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        segment_id: segment.as_ref().map(|s| s.segment_id.clone()),
+        speaker: segment.as_ref().map(|s| s.speaker.clone()),
+        text: segment.map(|s| s.text),
+        source,
+        created_at: Utc::now().to_rfc3339(),
+    };
+
+    SessionManager::new()?.update_session(&session_id, |session| {
+        session.bookmarks.push(bookmark.clone());
+        Ok(())
+    })?;
+
+    info!("[BOOKMARK] ★ {:?} bookmark at {}s in {}", source, at_ms / 1000, session_id);
+    events::emit(app, &BookmarkAddedEvent { session_id, bookmark: bookmark.clone() });
+    Ok(bookmark)
+}
+
+/// "mm:ss" (or "h:mm:ss") offset for exports
+pub fn format_offset(at_ms: u64) -> String {
+    let secs = at_ms / 1000;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Bookmark the current moment of the running session, with an optional note
+#[tauri::command]
+pub fn add_bookmark(app: AppHandle, note: Option<String>) -> Result<Bookmark, String> {
+    add_to_active(&app, note, BookmarkSource::Manual)
+}
+
+#[tauri::command]
+pub fn get_bookmarks(session_id: String) -> Result<Vec<Bookmark>, String> {
+    let mut bookmarks = SessionManager::new()?.load_session(&session_id)?.bookmarks;
+    bookmarks.sort_by_key(|b| b.at_ms);
+    Ok(bookmarks)
+}
@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::events::{self, CalendarEventAttached};
+use crate::gemini_client::GeminiState;
+use crate::network::NetworkState;
+use crate::session_manager::SessionManager;
+use crate::settings::{redact, SettingsState, REDACTED};
+
+// ============================================================================
+// CALENDAR - Link Sessions to the Meeting Happening Now (ICS feed)
+// ============================================================================
+//
+// Works with any iCalendar feed, including Google Calendar's "secret address
+// in iCal format". Recurring events (RRULE) are not expanded; only concrete
+// occurrences in the feed are matched.
+
+const EARLY_JOIN_MINUTES: i64 = 10;  // Sessions started shortly before a meeting still match
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CalendarConfig {
+    pub ics_url: Option<String>,
+    // Left out of the attendee list (your own address)
+    pub self_email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub attendees: Vec<String>,
+    pub organizer: Option<String>,
+    pub location: Option<String>,
+}
+
+// ============================================================================
+// ICS Parsing
+// ============================================================================
+
+struct IcsProperty {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl IcsProperty {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v.as_str())
+    }
+}
+
+/// RFC 5545 line unfolding: continuation lines start with a space or tab
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(raw.to_string());
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<IcsProperty> {
+    // The value starts at the first ':' outside a quoted parameter
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|&(_, c)| {
+        if c == '"' { in_quotes = !in_quotes; }
+        c == ':' && !in_quotes
+    })?.0;
+
+    let (head, value) = (&line[..split], &line[split + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.trim_matches('"').to_string()))
+        .collect();
+    Some(IcsProperty { name, params, value: value.to_string() })
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n").replace("\\N", "\n")
+        .replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\")
+}
+
+/// UTC ("...Z") or floating/TZID local time. All-day dates return None.
+fn parse_ics_time(prop: &IcsProperty) -> Option<DateTime<Utc>> {
+    let value = prop.value.trim();
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Utc.from_utc_datetime(&naive));
+    }
+    // TZID is treated as the machine's local zone - good enough for "now"
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local.from_local_datetime(&naive).earliest().map(|t| t.with_timezone(&Utc))
+}
+
+fn person_name(prop: &IcsProperty) -> String {
+    prop.param("CN")
+        .map(|cn| cn.to_string())
+        .unwrap_or_else(|| prop.value.trim_start_matches("mailto:").trim_start_matches("MAILTO:").to_string())
+}
+
+fn parse_events(ics: &str, self_email: Option<&str>) -> Vec<(DateTime<Utc>, DateTime<Utc>, CalendarEvent)> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<IcsProperty>> = None;
+
+    for line in unfold(ics) {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => {
+                let Some(props) = current.take() else { continue; };
+                let find = |name: &str| props.iter().find(|p| p.name == name);
+
+                let (Some(start), Some(end)) = (
+                    find("DTSTART").and_then(parse_ics_time),
+                    find("DTEND").and_then(parse_ics_time),
+                ) else { continue; };
+                if find("STATUS").map(|p| p.value.eq_ignore_ascii_case("CANCELLED")).unwrap_or(false) {
+                    continue;
+                }
+
+                let attendees = props.iter()
+                    .filter(|p| p.name == "ATTENDEE")
+                    .filter(|p| {
+                        let email = p.value.to_lowercase();
+                        self_email.map(|me| !email.ends_with(&me.to_lowercase())).unwrap_or(true)
+                    })
+                    .map(person_name)
+                    .collect();
+
+                events.push((start, end, CalendarEvent {
+                    uid: find("UID").map(|p| p.value.clone()).unwrap_or_default(),
+                    title: find("SUMMARY").map(|p| unescape(&p.value)).unwrap_or_else(|| "Meeting".to_string()),
+                    start: start.to_rfc3339(),
+                    end: end.to_rfc3339(),
+                    attendees,
+                    organizer: find("ORGANIZER").map(person_name),
+                    location: find("LOCATION").map(|p| unescape(&p.value)).filter(|l| !l.is_empty()),
+                }));
+            }
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    if let Some(prop) = parse_property(&line) {
+                        props.push(prop);
+                    }
+                }
+            }
+        }
+    }
+    events
+}
+
+/// Meeting in progress (or about to start) at `now`; the latest start wins
+fn event_at(events: Vec<(DateTime<Utc>, DateTime<Utc>, CalendarEvent)>, now: DateTime<Utc>) -> Option<CalendarEvent> {
+    events.into_iter()
+        .filter(|(start, end, _)| *start - Duration::minutes(EARLY_JOIN_MINUTES) <= now && now < *end)
+        .max_by_key(|(start, _, _)| *start)
+        .map(|(_, _, event)| event)
+}
+
+async fn fetch_current_event(app: &AppHandle) -> Result<Option<CalendarEvent>, String> {
+    let config = app.state::<SettingsState>().get().calendar;
+    let url = config.ics_url.ok_or("No calendar configured")?;
+
+    let ics = app.state::<NetworkState>().client()?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Calendar fetch failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Calendar fetch failed: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Calendar read failed: {}", e))?;
+
+    let events = parse_events(&ics, config.self_email.as_deref());
+    info!("[CALENDAR] Parsed {} timed event(s)", events.len());
+    Ok(event_at(events, Utc::now()))
+}
+
+/// Find the meeting happening now, hand its attendees to Gemini and,
+/// if the session is already stored, name and link it.
+pub async fn attach_current_event(app: &AppHandle, session_id: Option<&str>) -> Result<Option<CalendarEvent>, String> {
+    let Some(event) = fetch_current_event(app).await? else {
+        info!("[CALENDAR] No meeting in progress");
+        return Ok(None);
+    };
+    info!("[CALENDAR] ✓ Current meeting: '{}' ({} attendee(s))", event.title, event.attendees.len());
+
+    let mut participants = event.attendees.clone();
+    if let Some(organizer) = &event.organizer {
+        if !participants.contains(organizer) {
+            participants.push(organizer.clone());
+        }
+    }
+    *app.state::<GeminiState>().participants.lock().unwrap() = participants;
+
+    if let Some(session_id) = session_id {
+        let manager = SessionManager::new()?;
+        if manager.session_exists(session_id) {
+            manager.update_session(session_id, |session| {
+                session.metadata.title = event.title.clone();
+                session.metadata.auto_titled = false;
+                session.metadata.default_title = false;
+                session.calendar_event = Some(event.clone());
+                Ok(())
+            })?;
+        }
+    }
+
+    events::emit(app, &CalendarEventAttached {
+        session_id: session_id.map(|s| s.to_string()),
+        event: event.clone(),
+    });
+    Ok(Some(event))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_calendar_config(settings: tauri::State<'_, SettingsState>) -> CalendarConfig {
+    let config = settings.get().calendar;
+    CalendarConfig {
+        // Secret ICS addresses grant read access to the whole calendar
+        ics_url: redact(config.ics_url.as_deref()),
+        ..config
+    }
+}
+
+#[tauri::command]
+pub fn set_calendar_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: CalendarConfig,
+) -> Result<String, String> {
+    let ics_url = match config.ics_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()) {
+        Some(u) if u == REDACTED => settings.get().calendar.ics_url,
+        Some(u) => {
+            let u = match u.strip_prefix("webcal://") {
+                Some(rest) => format!("https://{}", rest),
+                None => u,
+            };
+            url::Url::parse(&u).map_err(|e| format!("Invalid calendar URL: {}", e))?;
+            Some(u)
+        }
+        None => None,
+    };
+    let self_email = config.self_email.map(|e| e.trim().to_string()).filter(|e| !e.is_empty());
+
+    settings.update(|s| s.calendar = CalendarConfig { ics_url, self_email })?;
+    Ok("Calendar settings saved".to_string())
+}
+
+#[tauri::command]
+pub async fn get_current_meeting(app: AppHandle) -> Result<Option<CalendarEvent>, String> {
+    fetch_current_event(&app).await
+}
+
+#[tauri::command]
+pub async fn attach_calendar_event(
+    app: AppHandle,
+    session_id: Option<String>,
+) -> Result<Option<CalendarEvent>, String> {
+    attach_current_event(&app, session_id.as_deref()).await
+}
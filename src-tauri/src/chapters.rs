@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::bookmarks::format_offset;
+use crate::embeddings::{self, cosine};
+use crate::events::{self, ChaptersReadyEvent, PipelineState};
+use crate::gemini_client::{call_gemini, extract_json, GeminiState};
+use crate::session_manager::{SessionData, SessionManager};
+
+// ============================================================================
+// CHAPTERS - Topic Segmentation of a Stored Session
+// ============================================================================
+//
+// Boundaries come from the session's embedding index (TextTiling over segment
+// vectors): the similarity between the few segments before and after every
+// gap, with a chapter starting at the deepest dips. One model request then
+// names all chapters at once.
+
+// Segments compared on each side of a gap
+const WINDOW: usize = 4;
+// No boundary is placed closer than this to another one or to either end
+const MIN_CHAPTER_SEGMENTS: usize = 6;
+const MIN_CHAPTER_MS: u64 = 2 * 60 * 1000;
+// Transcript sent for titling, split evenly across chapters
+const TITLE_BUDGET_CHARS: usize = 12_000;
+
+const TITLE_PROMPT: &str = r#"You are naming the chapters of ONE meeting transcript that was already split by topic.
+
+INPUT: Chapters in order, each with transcript lines formatted as "[Speaker]: text". Long chapters are cut short.
+OUTPUT: JSON only, no markdown.
+
+FORMAT:
+{"chapters":[{"title":"3-6 word topic title","summary":"one sentence on what was discussed"}]}
+
+RULES:
+- Exactly one entry per input chapter, in the same order
+- Titles name the topic, not the speakers
+- Write in the language of the transcript"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Chapter {
+    pub title: String,
+    pub summary: String,
+    // Offsets from the start of the session, when the segments have them
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    // Transcript indexes, inclusive
+    pub first_segment: usize,
+    pub last_segment: usize,
+}
+
+#[derive(Deserialize, Default)]
+struct TitleResponse {
+    #[serde(default)]
+    chapters: Vec<ChapterTitle>,
+}
+
+#[derive(Deserialize, Default)]
+struct ChapterTitle {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    summary: String,
+}
+
+/// Sum of the vectors; cosine only needs the direction
+fn summed(vectors: &[(usize, Vec<f32>)]) -> Vec<f32> {
+    let Some((_, first)) = vectors.first() else { return Vec::new(); };
+    let mut sum = vec![0.0f32; first.len()];
+    for (_, v) in vectors {
+        for (s, x) in sum.iter_mut().zip(v) {
+            *s += x;
+        }
+    }
+    sum
+}
+
+/// How far each gap's similarity dips below the peaks on either side
+fn depth_scores(scores: &[f32]) -> Vec<f32> {
+    (0..scores.len())
+        .map(|g| {
+            let mut left = scores[g];
+            for &s in scores[..g].iter().rev() {
+                if s < left { break; }
+                left = s;
+            }
+            let mut right = scores[g];
+            for &s in &scores[g + 1..] {
+                if s < right { break; }
+                right = s;
+            }
+            (left - scores[g]) + (right - scores[g])
+        })
+        .collect()
+}
+
+/// Offset of an indexed point; `points.len()` stands for the end of the session
+fn point_ms(session: &SessionData, points: &[(usize, Vec<f32>)], p: usize) -> Option<u64> {
+    match points.get(p) {
+        Some((i, _)) => session.transcripts[*i].start_ms,
+        None => session.transcripts.last().and_then(|t| t.end_ms),
+    }
+}
+
+/// Transcript indexes where a new chapter starts (the first one excluded)
+fn boundaries(session: &SessionData, points: &[(usize, Vec<f32>)]) -> Vec<usize> {
+    let n = points.len();
+    if n < MIN_CHAPTER_SEGMENTS * 2 {
+        return Vec::new();
+    }
+
+    // Score of gap g sits between points g and g + 1
+    let scores: Vec<f32> = (1..n)
+        .map(|i| cosine(&summed(&points[i.saturating_sub(WINDOW)..i]), &summed(&points[i..(i + WINDOW).min(n)])))
+        .collect();
+    let depths = depth_scores(&scores);
+    let average = depths.iter().sum::<f32>() / depths.len() as f32;
+    let deviation = (depths.iter().map(|d| (d - average).powi(2)).sum::<f32>() / depths.len() as f32).sqrt();
+    let cutoff = average - deviation / 2.0;
+
+    let mut candidates: Vec<(usize, f32)> = depths.iter()
+        .enumerate()
+        .filter(|(_, d)| **d > 0.0 && **d > cutoff)
+        .map(|(g, d)| (g + 1, *d))
+        .collect();
+    candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let far_enough = |a: usize, b: usize| {
+        let (low, high) = (a.min(b), a.max(b));
+        high - low >= MIN_CHAPTER_SEGMENTS
+            && match (point_ms(session, points, low), point_ms(session, points, high)) {
+                (Some(from), Some(to)) => to.saturating_sub(from) >= MIN_CHAPTER_MS,
+                _ => true,
+            }
+    };
+    let mut accepted: Vec<usize> = vec![0, n];
+    for (p, _) in candidates {
+        if accepted.iter().all(|&other| far_enough(p, other)) {
+            accepted.push(p);
+        }
+    }
+    accepted.sort_unstable();
+    accepted[1..accepted.len() - 1].iter().map(|&p| points[p].0).collect()
+}
+
+/// Inclusive transcript ranges, one per chapter
+fn ranges(len: usize, starts: &[usize]) -> Vec<(usize, usize)> {
+    let mut bounds = vec![0];
+    bounds.extend(starts);
+    bounds.push(len);
+    bounds.windows(2).map(|w| (w[0], w[1] - 1)).collect()
+}
+
+fn title_input(session: &SessionData, ranges: &[(usize, usize)]) -> String {
+    let per_chapter = TITLE_BUDGET_CHARS / ranges.len();
+    ranges.iter()
+        .enumerate()
+        .map(|(n, &(first, last))| {
+            let mut text = format!("CHAPTER {}:\n", n + 1);
+            for t in &session.transcripts[first..=last] {
+                if text.len() >= per_chapter { break; }
+                text.push_str(&format!("[{}]: {}\n", t.speaker_id, t.text.trim()));
+            }
+            text
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split a stored session into titled chapters, store and announce them
+pub async fn generate(app: &AppHandle, session_id: &str) -> Result<Vec<Chapter>, String> {
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(session_id)?;
+    if session.transcripts.is_empty() {
+        return Err("Session has no transcripts to split into chapters".to_string());
+    }
+
+    events::emit_status(app, PipelineState::Summarizing, "Finding chapters...");
+    let points = embeddings::session_vectors(app, session_id).await?;
+    let ranges = ranges(session.transcripts.len(), &boundaries(&session, &points));
+    info!("[CHAPTERS] Session {} split into {} chapter(s)", session_id, ranges.len());
+
+    let config = app.state::<GeminiState>().request_config(app)?;
+    let response = call_gemini(&config, TITLE_PROMPT, &title_input(&session, &ranges))
+        .await?
+        .ok_or("Empty response from model")?;
+    let titles: TitleResponse = serde_json::from_str(extract_json(&response))
+        .map_err(|e| format!("Invalid chapters JSON: {}", e))?;
+    if titles.chapters.len() != ranges.len() {
+        warn!("[CHAPTERS] Expected {} titles, got {}", ranges.len(), titles.chapters.len());
+    }
+    let mut titles = titles.chapters.into_iter();
+
+    let chapters: Vec<Chapter> = ranges.iter()
+        .enumerate()
+        .map(|(n, &(first, last))| {
+            let named = titles.next().unwrap_or_default();
+            let segments = &session.transcripts[first..=last];
+            Chapter {
+                title: Some(named.title.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| format!("Chapter {}", n + 1)),
+                summary: named.summary.trim().to_string(),
+                start_ms: segments.iter().find_map(|t| t.start_ms),
+                end_ms: segments.iter().rev().find_map(|t| t.end_ms),
+                first_segment: first,
+                last_segment: last,
+            }
+        })
+        .collect();
+
+    // Reload: the session may have been saved while the model was busy
+    manager.update_session(session_id, |session| {
+        session.chapters = chapters.clone();
+        Ok(())
+    })?;
+
+    events::emit(app, &ChaptersReadyEvent { session_id: session_id.to_string(), chapters: chapters.clone() });
+    events::emit_status(app, PipelineState::Ready, format!("{} chapter(s) ready ✓", chapters.len()));
+    Ok(chapters)
+}
+
+/// "00:00 - 12:30 Title" per chapter
+pub fn chapter_lines(chapters: &[Chapter]) -> Vec<String> {
+    chapters.iter()
+        .map(|c| match (c.start_ms, c.end_ms) {
+            (Some(start), Some(end)) => format!("{} - {} {}", format_offset(start), format_offset(end), c.title),
+            _ => c.title.clone(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn generate_chapters(app: AppHandle, session_id: String) -> Result<Vec<Chapter>, String> {
+    generate(&app, &session_id).await
+}
+
+/// Stored chapters; empty until generate_chapters has run
+#[tauri::command]
+pub fn get_chapters(session_id: String) -> Result<Vec<Chapter>, String> {
+    Ok(SessionManager::new()?.load_session(&session_id)?.chapters)
+}
@@ -0,0 +1,304 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+use crate::audio_capture::TARGET_SAMPLE_RATE;
+use crate::bookmarks::format_offset;
+use crate::dedupe::{DedupeVerdict, Deduper};
+use crate::file_import::{decode_to_target, DEFAULT_SPEAKER};
+use crate::gemini_client::{
+    annotate_segment, build_intelligence_prompt, call_gemini_with_text, segment_recording, stitch_overlap,
+    RequestConfig, DEFAULT_CONTEXT_SEGMENTS, DEFAULT_MODEL,
+};
+use crate::hallucination;
+use crate::levels::normalize_segment;
+use crate::live_session::entry_from;
+use crate::network::{NetworkState, LOCAL_ONLY_ERROR};
+use crate::redaction::Redactor;
+use crate::session_manager::{ExportManager, SessionData};
+use crate::settings::{AppSettings, SettingsState};
+use crate::summarizer;
+use crate::whisper_client::{transcribe_audio, MODEL_SIZES};
+use crate::whisper_models;
+
+// ============================================================================
+// CLI - One-Shot Transcription and Analysis without the Desktop App
+// ============================================================================
+//
+//   cognivox transcribe <file> [--model base] [--language en] [--analyze]
+//                              [--summarize] [--out report.md] [--format md]
+//
+// Tauri is never started. Stored settings still apply (model directory,
+// decoding, privacy mode, redaction, hallucination filter, prompt). The
+// Gemini key comes from --api-key or GEMINI_API_KEY. Nothing is saved as a
+// session. Windows release builds have no console, so use --out there.
+
+const USAGE: &str = "Usage: cognivox transcribe <file> [options]
+
+Options:
+  --model <size>       Whisper model: tiny, base, small, medium (default: base)
+  --language <code>    Spoken language, e.g. en, de, auto (default: en)
+  --analyze            Per-segment tone and category via Gemini
+  --summarize          Meeting summary via Gemini
+  --out <path>         Write to a file instead of stdout
+  --format <fmt>       md, txt, json, srt or vtt (default: from --out, else txt)
+  --api-key <key>      Gemini API key (default: $GEMINI_API_KEY)
+  --provider-model <m> Gemini model (default: the app's default)";
+
+const API_KEY_ENV: &str = "GEMINI_API_KEY";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum OutputFormat {
+    Markdown,
+    Text,
+    Json,
+    Srt,
+    Vtt,
+}
+
+impl OutputFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "txt" | "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+}
+
+struct TranscribeArgs {
+    file: PathBuf,
+    model: String,
+    language: String,
+    analyze: bool,
+    summarize: bool,
+    out: Option<PathBuf>,
+    format: OutputFormat,
+    api_key: Option<String>,
+    provider_model: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<TranscribeArgs, String> {
+    let mut file = None;
+    let mut model = "base".to_string();
+    let mut language = "en".to_string();
+    let (mut analyze, mut summarize) = (false, false);
+    let (mut out, mut format, mut api_key, mut provider_model) = (None, None, None, None);
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || rest.next().cloned().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--model" => model = value()?.to_lowercase(),
+            "--language" => language = value()?,
+            "--analyze" => analyze = true,
+            "--summarize" => summarize = true,
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--format" => {
+                let name = value()?;
+                format = Some(OutputFormat::parse(&name).ok_or_else(|| format!("Unknown format: {}", name))?);
+            }
+            "--api-key" => api_key = Some(value()?),
+            "--provider-model" => provider_model = Some(value()?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            path if file.is_none() => file = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument: {}", extra)),
+        }
+    }
+
+    if !MODEL_SIZES.contains(&model.as_str()) {
+        return Err(format!("Unknown model '{}' (expected one of {})", model, MODEL_SIZES.join(", ")));
+    }
+    let format = format
+        .or_else(|| out.as_deref()
+            .and_then(|p: &Path| p.extension())
+            .and_then(|e| OutputFormat::parse(&e.to_string_lossy())))
+        .unwrap_or(OutputFormat::Text);
+    Ok(TranscribeArgs {
+        file: file.ok_or("No input file given")?,
+        model,
+        language,
+        analyze,
+        summarize,
+        out,
+        format,
+        api_key: api_key.or_else(|| std::env::var(API_KEY_ENV).ok()).filter(|k| !k.trim().is_empty()),
+        provider_model,
+    })
+}
+
+/// Gemini access for --analyze/--summarize, honoring local-only mode
+fn request_config(args: &TranscribeArgs, settings: &AppSettings, network: &NetworkState) -> Result<RequestConfig, String> {
+    let client = network.client()
+        .map_err(|_| format!("--analyze/--summarize need Gemini ({})", LOCAL_ONLY_ERROR))?;
+    let key = args.api_key.clone()
+        .ok_or_else(|| format!("--analyze/--summarize need a Gemini key (--api-key or {})", API_KEY_ENV))?;
+    let model = args.provider_model.clone()
+        .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let redactor = Redactor::new(settings.redaction.clone())?;
+    let mut config = RequestConfig::standalone(client, key, model, redactor);
+    config.intelligence_schema = settings.intelligence_schema.clone().map(Arc::new);
+    Ok(config)
+}
+
+fn plain_text(session: &SessionData) -> String {
+    session.transcripts.iter()
+        .map(|t| match t.start_ms {
+            Some(ms) => format!("[{}] {}", format_offset(ms), t.text),
+            None => t.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render(session: &SessionData, format: OutputFormat) -> Result<String, String> {
+    match format {
+        OutputFormat::Markdown => ExportManager::export_to_markdown(session),
+        OutputFormat::Json => ExportManager::export_to_json(session),
+        OutputFormat::Srt => ExportManager::export_to_srt(session),
+        OutputFormat::Vtt => ExportManager::export_to_vtt(session),
+        OutputFormat::Text => {
+            let mut text = plain_text(session);
+            if let Some(summary) = &session.summary {
+                text = format!("{}\n\nSUMMARY\n{}", text, summary.executive_summary);
+            }
+            Ok(text)
+        }
+    }
+}
+
+async fn transcribe(args: TranscribeArgs) -> Result<String, String> {
+    let settings = SettingsState::load().get();
+    let network = NetworkState::new(&settings.network, settings.privacy_mode);
+    let config = if args.analyze || args.summarize {
+        Some(request_config(&args, &settings, &network)?)
+    } else {
+        None
+    };
+
+    let cache = match &settings.whisper_model_dir {
+        Some(dir) => hf_hub::Cache::new(PathBuf::from(dir)),
+        None => hf_hub::Cache::default(),
+    };
+    let model_path = whisper_models::fetch_model(cache, &args.model, &settings.model_source, &network).await?;
+
+    let file = args.file.clone();
+    let samples = tauri::async_runtime::spawn_blocking(move || decode_to_target(&file))
+        .await
+        .map_err(|e| format!("Decode task failed: {}", e))??;
+    let segments = segment_recording(&samples, settings.segment_overlap_ms);
+    info!("[CLI] {} speech segment(s) in {:.1}s of audio", segments.len(), samples.len() as f32 / TARGET_SAMPLE_RATE as f32);
+
+    let title = args.file.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Recording".to_string());
+    let mut session = SessionData::new(title);
+    let system_prompt = build_intelligence_prompt(&settings);
+    let mut context: Vec<String> = Vec::new();
+    let mut previous_text: Option<String> = None;
+    let mut deduper = Deduper::default();
+
+    let total = segments.len();
+    for (index, (start, mut audio, continues)) in segments.into_iter().enumerate() {
+        let start_ms = start as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        let end_ms = start_ms + audio.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        normalize_segment(&mut audio);
+        info!("[CLI] Transcribing segment {}/{}", index + 1, total);
+
+        let result = match transcribe_audio(&model_path, &args.language, &settings.whisper_decoding, &audio).await {
+            Ok(result) if !result.text.trim().is_empty() => result,
+            Ok(_) => continue,
+            Err(e) => {
+                warn!("[CLI] ✗ Segment {}/{} not transcribed: {}", index + 1, total, e);
+                continue;
+            }
+        };
+        if let Some(reason) = hallucination::check(&settings.hallucinations, &result.text, result.no_speech_prob) {
+            info!("[CLI] Dropped segment {}/{} ({:?})", index + 1, total, reason);
+            continue;
+        }
+        let text = match previous_text.as_deref().filter(|_| continues) {
+            Some(previous) => stitch_overlap(previous, &result.text),
+            None => result.text.trim().to_string(),
+        };
+        previous_text = Some(text.clone());
+        let text = match deduper.check(&settings.dedupe, &text) {
+            DedupeVerdict::Keep => text,
+            DedupeVerdict::Trimmed(rest) => rest,
+            DedupeVerdict::Duplicate => {
+                info!("[CLI] Dropped segment {}/{} (Duplicate)", index + 1, total);
+                continue;
+            }
+        };
+
+        let segment_id = uuid::Uuid::new_v4().to_string();
+        let intelligence = match config.as_ref().filter(|_| args.analyze) {
+            Some(config) => {
+                let annotated = annotate_segment(DEFAULT_SPEAKER, &text, result.confidence);
+                let response = call_gemini_with_text(config, &system_prompt, &annotated, &context).await;
+                context.push(annotated);
+                if context.len() > DEFAULT_CONTEXT_SEGMENTS {
+                    context.remove(0);
+                }
+                response.map_err(|e| warn!("[CLI] Segment {}/{} not analyzed: {}", index + 1, total, e)).ok()
+            }
+            None => None,
+        };
+        session.add_transcript(entry_from(&segment_id, &text, DEFAULT_SPEAKER, intelligence.as_deref(), (Some(start_ms), Some(end_ms)), result.segment_language().as_ref()));
+    }
+
+    session.metadata.duration_seconds = samples.len() as u64 / TARGET_SAMPLE_RATE as u64;
+    session.metadata.total_transcripts = session.transcripts.len();
+    session.metadata.total_speakers = usize::from(!session.transcripts.is_empty());
+
+    if let Some(config) = config.filter(|_| args.summarize) {
+        session.summary = Some(summarizer::summarize_unsaved(config, &session).await?);
+    }
+    render(&session, args.format)
+}
+
+/// Entry for `cognivox <subcommand> ...`; returns the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => ("", args),
+    };
+    if command != "transcribe" || rest.iter().any(|a| a == "--help" || a == "-h") {
+        eprintln!("{}", USAGE);
+        return if command == "transcribe" { 0 } else { 2 };
+    }
+    let args = match parse_args(rest) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let out = args.out.clone();
+    let output = match tauri::async_runtime::block_on(transcribe(args)) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("cognivox: {}", e);
+            return 1;
+        }
+    };
+    match out {
+        Some(path) => match std::fs::write(&path, output) {
+            Ok(()) => {
+                info!("[CLI] ✓ Wrote {}", path.display());
+                0
+            }
+            Err(e) => {
+                eprintln!("cognivox: failed to write {}: {}", path.display(), e);
+                1
+            }
+        },
+        None => {
+            println!("{}", output);
+            0
+        }
+    }
+}
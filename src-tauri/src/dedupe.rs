@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::hallucination::{announce_discard, normalized_words, DiscardReason};
+use crate::settings::SettingsState;
+use crate::voice_commands::edit_distance;
+
+// ============================================================================
+// DEDUPE - Drop Transcripts that Repeat the Last Few Segments
+// ============================================================================
+//
+// Overlapping buffers, echo and retries can produce the same sentence twice
+// in a row, which then turns into duplicate action items. Each transcript
+// is compared with the last few: a near-copy (normalized edit distance) or
+// one wholly contained in a previous segment is dropped, and one that
+// starts by repeating the previous segment keeps only its new words.
+// Live segments are compared per session and speaker, so two people
+// agreeing in the same words both stay in the transcript.
+
+// Short replies ("Yes." "Yes.") are legitimately repeated
+const MIN_WORDS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DedupeRules {
+    pub enabled: bool,
+    // How many previous segments to compare against
+    pub window: usize,
+    // Older segments are never treated as repeated
+    pub max_age_secs: u64,
+    // 0.0 - 1.0; 1.0 only drops exact (normalized) repeats
+    pub min_similarity: f32,
+}
+
+impl Default for DedupeRules {
+    fn default() -> Self {
+        Self { enabled: true, window: 3, max_age_secs: 30, min_similarity: 0.85 }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DedupeVerdict {
+    Keep,
+    // The new words after a repeated previous segment
+    Trimmed(String),
+    Duplicate,
+}
+
+/// 1.0 for identical strings, 0.0 for nothing in common
+fn similarity(a: &str, b: &str) -> f32 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(a, b) as f32 / longest as f32
+}
+
+/// Original whitespace tokens with their normalized form, skipping tokens
+/// that normalize to nothing
+pub(crate) fn tokens(text: &str) -> Vec<(&str, String)> {
+    text.split_whitespace()
+        .filter_map(|token| normalized_words(token).into_iter().next().map(|norm| (token, norm)))
+        .collect()
+}
+
+/// Recent transcripts, normalized
+#[derive(Default)]
+pub struct Deduper {
+    recent: VecDeque<(Instant, Vec<String>)>,
+}
+
+impl Deduper {
+    pub fn check(&mut self, rules: &DedupeRules, text: &str) -> DedupeVerdict {
+        if !rules.enabled {
+            return DedupeVerdict::Keep;
+        }
+        let max_age = Duration::from_secs(rules.max_age_secs);
+        self.recent.retain(|(at, _)| at.elapsed() <= max_age);
+
+        let tokens = tokens(text);
+        let words: Vec<String> = tokens.iter().map(|(_, norm)| norm.clone()).collect();
+        let verdict = if words.len() < MIN_WORDS { DedupeVerdict::Keep } else { self.compare(rules, &tokens, &words) };
+
+        if verdict != DedupeVerdict::Duplicate && !words.is_empty() {
+            self.recent.push_back((Instant::now(), words));
+            while self.recent.len() > rules.window.max(1) {
+                self.recent.pop_front();
+            }
+        }
+        verdict
+    }
+
+    fn compare(&self, rules: &DedupeRules, tokens: &[(&str, String)], words: &[String]) -> DedupeVerdict {
+        let phrase = words.join(" ");
+        for (_, previous) in self.recent.iter().rev() {
+            let previous_phrase = previous.join(" ");
+            if similarity(&phrase, &previous_phrase) >= rules.min_similarity
+                || format!(" {} ", previous_phrase).contains(&format!(" {} ", phrase))
+            {
+                return DedupeVerdict::Duplicate;
+            }
+        }
+
+        // Only the latest segment can have been re-heard at the start of this one
+        let Some((_, last)) = self.recent.back() else { return DedupeVerdict::Keep; };
+        if last.len() >= MIN_WORDS
+            && words.len() > last.len()
+            && similarity(&words[..last.len()].join(" "), &last.join(" ")) >= rules.min_similarity
+        {
+            let rest: Vec<&str> = tokens[last.len()..].iter().map(|(token, _)| *token).collect();
+            return DedupeVerdict::Trimmed(rest.join(" "));
+        }
+        DedupeVerdict::Keep
+    }
+}
+
+#[derive(Default)]
+pub struct DedupeState {
+    // (session id, "" outside a session; speaker) -> their recent segments
+    dedupers: StdMutex<HashMap<(String, String), Deduper>>,
+}
+
+impl DedupeState {
+    /// Forget every speaker's history (a new session is starting)
+    pub fn clear(&self) {
+        self.dedupers.lock().unwrap().clear();
+    }
+}
+
+/// Check a live segment against the speaker's last few. Returns the text to
+/// keep (maybe trimmed), or None after announcing it as a duplicate.
+pub fn suppress_duplicate(
+    app: &AppHandle,
+    text: &str,
+    speaker: &str,
+    no_speech_prob: f32,
+    segment_id: Option<&str>,
+    session_id: Option<&str>,
+) -> Option<String> {
+    let rules = app.state::<SettingsState>().get().dedupe;
+    let key = (session_id.unwrap_or_default().to_string(), speaker.to_string());
+    let verdict = app.state::<DedupeState>().dedupers.lock().unwrap()
+        .entry(key)
+        .or_default()
+        .check(&rules, text);
+    resolve(app, verdict, text, no_speech_prob, segment_id, session_id)
+}
+
+/// Same as suppress_duplicate, against a caller-owned history (file imports)
+pub fn suppress_duplicate_in(
+    app: &AppHandle,
+    deduper: &mut Deduper,
+    text: &str,
+    no_speech_prob: f32,
+    session_id: Option<&str>,
+) -> Option<String> {
+    let verdict = deduper.check(&app.state::<SettingsState>().get().dedupe, text);
+    resolve(app, verdict, text, no_speech_prob, None, session_id)
+}
+
+fn resolve(
+    app: &AppHandle,
+    verdict: DedupeVerdict,
+    text: &str,
+    no_speech_prob: f32,
+    segment_id: Option<&str>,
+    session_id: Option<&str>,
+) -> Option<String> {
+    match verdict {
+        DedupeVerdict::Keep => Some(text.to_string()),
+        DedupeVerdict::Trimmed(rest) => {
+            info!("[DEDUPE] Trimmed repeated start: '{}' -> '{}'", text, rest);
+            Some(rest)
+        }
+        DedupeVerdict::Duplicate => {
+            announce_discard(app, text, DiscardReason::Duplicate, no_speech_prob, segment_id, session_id);
+            None
+        }
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_dedupe_rules(settings: tauri::State<'_, SettingsState>) -> DedupeRules {
+    settings.get().dedupe
+}
+
+#[tauri::command]
+pub fn set_dedupe_rules(
+    settings: tauri::State<'_, SettingsState>,
+    rules: DedupeRules,
+) -> Result<DedupeRules, String> {
+    if !(0.5..=1.0).contains(&rules.min_similarity) {
+        return Err("min_similarity must be between 0.5 and 1.0".to_string());
+    }
+    if rules.window == 0 {
+        return Err("window must be at least 1".to_string());
+    }
+    settings.update(|s| s.dedupe = rules.clone())?;
+    info!("[DEDUPE] Rules updated (enabled: {}, window {}, similarity {:.2})", rules.enabled, rules.window, rules.min_similarity);
+    Ok(rules)
+}
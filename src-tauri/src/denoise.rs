@@ -0,0 +1,60 @@
+use nnnoiseless::DenoiseState;
+use crate::audio_capture::TARGET_SAMPLE_RATE;
+
+// ============================================================================
+// DENOISE - RNNoise Suppression Ahead of VAD/Whisper
+// ============================================================================
+//
+// RNNoise runs on 480-sample frames at 48 kHz with i16-scaled floats, so the
+// 16 kHz pipeline audio is upsampled 3x, denoised, and brought back down.
+
+const RNNOISE_SAMPLE_RATE: u32 = 48000;
+const UPSAMPLE: usize = (RNNOISE_SAMPLE_RATE / TARGET_SAMPLE_RATE) as usize;
+const I16_SCALE: f32 = i16::MAX as f32;
+
+pub struct Denoiser {
+    state: Box<DenoiseState<'static>>,
+    input: Vec<f32>,       // 48 kHz samples waiting for a full frame
+    output: Vec<f32>,      // 48 kHz denoised samples not yet downsampled
+    last_sample: f32,      // For interpolating across calls
+}
+
+impl Denoiser {
+    pub fn new() -> Self {
+        Self {
+            state: DenoiseState::new(),
+            input: Vec::with_capacity(DenoiseState::FRAME_SIZE * 2),
+            output: Vec::with_capacity(DenoiseState::FRAME_SIZE * 2),
+            last_sample: 0.0,
+        }
+    }
+
+    /// Denoise 16 kHz mono samples. Output lags input by up to one
+    /// RNNoise frame (10 ms); leftovers are carried into the next call.
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        // Linear upsample to 48 kHz
+        for &s in samples {
+            for step in 1..=UPSAMPLE {
+                let t = step as f32 / UPSAMPLE as f32;
+                self.input.push((self.last_sample + (s - self.last_sample) * t) * I16_SCALE);
+            }
+            self.last_sample = s;
+        }
+
+        let mut frame_out = [0.0f32; DenoiseState::FRAME_SIZE];
+        while self.input.len() >= DenoiseState::FRAME_SIZE {
+            self.state.process_frame(&mut frame_out, &self.input[..DenoiseState::FRAME_SIZE]);
+            self.input.drain(..DenoiseState::FRAME_SIZE);
+            self.output.extend_from_slice(&frame_out);
+        }
+
+        // Average each group of 3 back down to 16 kHz (cheap low-pass)
+        let whole = self.output.len() / UPSAMPLE * UPSAMPLE;
+        let result = self.output[..whole]
+            .chunks(UPSAMPLE)
+            .map(|c| (c.iter().sum::<f32>() / UPSAMPLE as f32 / I16_SCALE).clamp(-1.0, 1.0))
+            .collect();
+        self.output.drain(..whole);
+        result
+    }
+}
@@ -0,0 +1,168 @@
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use crate::audio_capture::TARGET_SAMPLE_RATE;
+
+// ============================================================================
+// ECHO - Cancel Loopback Audio Picked Up by the Microphone
+// ============================================================================
+//
+// With mic and loopback both captured, whatever the speakers play reaches
+// the mic too and gets transcribed twice. The loopback stream is the exact
+// far-end signal, so an adaptive filter learns the speaker-room-mic path
+// from it and subtracts the predicted echo from the mic before VAD.
+//
+// Partitioned-block frequency-domain NLMS (the filter inside speex's and
+// WebRTC's older AEC): 16 ms blocks, enough partitions to cover ECHO_TAIL_MS
+// of delay plus reverb. Adaptation freezes while the near end is clearly
+// talking (Geigel detector) so the filter doesn't learn to cancel the user.
+
+const BLOCK: usize = 256;
+const FFT_SIZE: usize = BLOCK * 2;
+const ECHO_TAIL_MS: usize = 250;
+const PARTITIONS: usize = (ECHO_TAIL_MS * TARGET_SAMPLE_RATE as usize / 1000).div_ceil(BLOCK);
+const STEP_SIZE: f32 = 0.8;
+// Per-bin reference power smoothing
+const POWER_SMOOTHING: f32 = 0.9;
+const REGULARIZATION: f32 = 1e-6;
+// Near end is talking when the mic peak exceeds this fraction of the recent far-end peak
+const DOUBLE_TALK_THRESHOLD: f32 = 0.5;
+// Reference audio is dropped beyond this much lead over the mic, so a
+// stalled mic stream can't push the two out of alignment for good
+const MAX_REFERENCE_LAG: usize = TARGET_SAMPLE_RATE as usize / 2;
+
+pub struct EchoCanceller {
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    // Filter, one spectrum per partition
+    weights: Vec<Vec<Complex<f32>>>,
+    // Reference spectra, newest first
+    history: VecDeque<Vec<Complex<f32>>>,
+    power: Vec<f32>,
+    previous_reference: Vec<f32>,
+    // Per-block far-end peaks over the echo tail
+    far_peaks: VecDeque<f32>,
+    reference: VecDeque<f32>,
+    mic: Vec<f32>,
+}
+
+impl EchoCanceller {
+    pub fn new() -> Self {
+        let mut planner = FftPlanner::<f32>::new();
+        let zero = vec![Complex::new(0.0, 0.0); FFT_SIZE];
+        Self {
+            fft: planner.plan_fft_forward(FFT_SIZE),
+            ifft: planner.plan_fft_inverse(FFT_SIZE),
+            weights: vec![zero.clone(); PARTITIONS],
+            history: (0..PARTITIONS).map(|_| zero.clone()).collect(),
+            power: vec![0.0; FFT_SIZE],
+            previous_reference: vec![0.0; BLOCK],
+            far_peaks: VecDeque::with_capacity(PARTITIONS),
+            reference: VecDeque::new(),
+            mic: Vec::with_capacity(BLOCK * 2),
+        }
+    }
+
+    /// Far-end (loopback) audio, 16 kHz mono
+    pub fn push_reference(&mut self, samples: &[f32]) {
+        self.reference.extend(samples);
+        let excess = self.reference.len().saturating_sub(MAX_REFERENCE_LAG);
+        self.reference.drain(..excess);
+    }
+
+    /// Remove the echo from 16 kHz mono mic audio. Output lags input by up
+    /// to one block (16 ms); leftovers are carried into the next call.
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        self.mic.extend_from_slice(samples);
+        let whole = self.mic.len() / BLOCK * BLOCK;
+        let mut out = Vec::with_capacity(whole);
+        for start in (0..whole).step_by(BLOCK) {
+            let reference: Vec<f32> = (0..BLOCK)
+                .map(|_| self.reference.pop_front().unwrap_or(0.0))
+                .collect();
+            let block = self.mic[start..start + BLOCK].to_vec();
+            out.extend(self.process_block(&block, &reference));
+        }
+        self.mic.drain(..whole);
+        out
+    }
+
+    fn process_block(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        // Reference spectrum over the last two blocks (overlap-save)
+        let mut spectrum: Vec<Complex<f32>> = self.previous_reference.iter()
+            .chain(reference)
+            .map(|&s| Complex::new(s, 0.0))
+            .collect();
+        self.fft.process(&mut spectrum);
+        self.previous_reference.copy_from_slice(reference);
+        for (power, bin) in self.power.iter_mut().zip(&spectrum) {
+            *power = POWER_SMOOTHING * *power + (1.0 - POWER_SMOOTHING) * bin.norm_sqr();
+        }
+        self.history.pop_back();
+        self.history.push_front(spectrum);
+
+        let far_peak = reference.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        if self.far_peaks.len() == PARTITIONS {
+            self.far_peaks.pop_back();
+        }
+        self.far_peaks.push_front(far_peak);
+        let far_max = self.far_peaks.iter().fold(0.0f32, |m, p| m.max(*p));
+        // Nothing playing: nothing to cancel or learn from
+        if far_max == 0.0 {
+            return mic.to_vec();
+        }
+
+        // Echo estimate: last half of the filtered reference
+        let mut echo = vec![Complex::new(0.0, 0.0); FFT_SIZE];
+        for (weights, spectrum) in self.weights.iter().zip(&self.history) {
+            for ((e, w), x) in echo.iter_mut().zip(weights).zip(spectrum) {
+                *e += w * x;
+            }
+        }
+        self.ifft.process(&mut echo);
+        let scale = 1.0 / FFT_SIZE as f32;
+        let error: Vec<f32> = mic.iter()
+            .zip(&echo[BLOCK..])
+            .map(|(m, e)| m - e.re * scale)
+            .collect();
+
+        let mic_peak = mic.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        if mic_peak <= DOUBLE_TALK_THRESHOLD * far_max {
+            self.adapt(&error);
+        }
+        error.iter().map(|s| s.clamp(-1.0, 1.0)).collect()
+    }
+
+    /// Normalized, gradient-constrained update of every partition
+    fn adapt(&mut self, error: &[f32]) {
+        let mut error_spectrum: Vec<Complex<f32>> = std::iter::repeat_n(0.0, BLOCK)
+            .chain(error.iter().copied())
+            .map(|s| Complex::new(s, 0.0))
+            .collect();
+        self.fft.process(&mut error_spectrum);
+
+        let norm: Vec<f32> = self.power.iter()
+            .map(|p| STEP_SIZE / (PARTITIONS as f32 * p + REGULARIZATION))
+            .collect();
+        let scale = 1.0 / FFT_SIZE as f32;
+        for (weights, spectrum) in self.weights.iter_mut().zip(&self.history) {
+            let mut gradient: Vec<Complex<f32>> = spectrum.iter()
+                .zip(&error_spectrum)
+                .zip(&norm)
+                .map(|((x, e), n)| x.conj() * e * *n)
+                .collect();
+            // Keep the filter causal: zero the wrapped-around half in time
+            self.ifft.process(&mut gradient);
+            for g in gradient[BLOCK..].iter_mut() {
+                *g = Complex::new(0.0, 0.0);
+            }
+            for g in gradient[..BLOCK].iter_mut() {
+                *g *= scale;
+            }
+            self.fft.process(&mut gradient);
+            for (w, g) in weights.iter_mut().zip(&gradient) {
+                *w += g;
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tokio::time::Duration;
+use tracing::{error, info};
+use crate::encryption;
+use crate::gemini_client::{record_rate_limit, wait_for_slot, GeminiState, RequestConfig, GEMINI_REST_URL};
+use crate::session_manager::{SessionData, SessionManager};
+use crate::settings::app_data_dir;
+
+// ============================================================================
+// EMBEDDINGS - Vector Index & Semantic Search over Stored Sessions
+// ============================================================================
+//
+// One index file per session (GOD-V8/embeddings/{id}.json). Segments are
+// keyed by a hash of their text, so re-saving a session only embeds what
+// changed.
+
+const EMBEDDING_MODEL: &str = "text-embedding-004";
+const EMBED_BATCH_SIZE: usize = 100;  // batchEmbedContents limit
+const MIN_SEGMENT_CHARS: usize = 12;  // "ok", "yeah" etc. aren't worth indexing
+const DEFAULT_SEARCH_RESULTS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct IndexedSegment {
+    text_hash: String,
+    index: usize,
+    timestamp: String,
+    speaker: String,
+    start_ms: Option<u64>,
+    #[serde(default)]
+    language: Option<String>,
+    text: String,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct SessionIndex {
+    session_id: String,
+    title: String,
+    model: String,
+    segments: Vec<IndexedSegment>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SearchHit {
+    pub session_id: String,
+    pub session_title: String,
+    pub segment_index: usize,
+    pub timestamp: String,
+    pub speaker: String,
+    pub start_ms: Option<u64>,
+    pub language: Option<String>,
+    pub text: String,
+    pub score: f32,
+}
+
+/// Serializes index writes so concurrent saves don't clobber a file
+#[derive(Default)]
+pub struct EmbeddingState {
+    indexing: Mutex<()>,
+}
+
+fn index_dir() -> Result<PathBuf, String> {
+    let dir = app_data_dir()?.join("embeddings");
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create embeddings directory: {}", e))?;
+    Ok(dir)
+}
+
+/// Every session index file (sealed like sessions)
+pub fn index_files() -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(index_dir()?).map_err(|e| e.to_string())?;
+    Ok(entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect())
+}
+
+fn load_index(session_id: &str) -> Option<SessionIndex> {
+    let path = index_dir().ok()?.join(format!("{}.json", session_id));
+    read_index(&path).ok()
+}
+
+fn read_index(path: &std::path::Path) -> Result<SessionIndex, String> {
+    let bytes = encryption::open(fs::read(path).map_err(|e| e.to_string())?)?;
+    serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
+fn save_index(index: &SessionIndex) -> Result<(), String> {
+    let path = index_dir()?.join(format!("{}.json", index.session_id));
+    let json = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, encryption::seal(json.into_bytes())?).map_err(|e| format!("Failed to write index: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to commit index: {}", e))
+}
+
+fn text_hash(text: &str) -> String {
+    hex::encode(&Sha256::digest(text.as_bytes())[..12])
+}
+
+pub(crate) fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() { return 0.0; }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+}
+
+// ============================================================================
+// Gemini Embedding API
+// ============================================================================
+
+fn embed_request(text: &str, task_type: &str) -> serde_json::Value {
+    serde_json::json!({
+        "model": format!("models/{}", EMBEDDING_MODEL),
+        "content": { "parts": [{ "text": text }] },
+        "taskType": task_type
+    })
+}
+
+async fn embed_batch(config: &RequestConfig, texts: &[String], task_type: &str) -> Result<Vec<Vec<f32>>, String> {
+    let url = format!("{}/{}:batchEmbedContents?key={}", GEMINI_REST_URL, EMBEDDING_MODEL, config.key);
+    let requests: Vec<_> = texts.iter()
+        .map(|t| embed_request(&config.redactor.redact(t, &config.participants), task_type))
+        .collect();
+
+    wait_for_slot(config).await;
+    let response = config.client.post(&url)
+        .json(&serde_json::json!({ "requests": requests }))
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP: {}", e))?;
+
+    let status = response.status();
+    record_rate_limit(config, status.as_u16() == 429).await;
+    let body: serde_json::Value = response.json().await.map_err(|e| format!("Read: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Embedding API {}: {}", status, body["error"]["message"].as_str().unwrap_or("unknown error")));
+    }
+
+    let vectors: Vec<Vec<f32>> = body["embeddings"].as_array()
+        .ok_or("Embedding response missing 'embeddings'")?
+        .iter()
+        .map(|e| serde_json::from_value(e["values"].clone()).unwrap_or_default())
+        .collect();
+    if vectors.len() != texts.len() {
+        return Err(format!("Expected {} embeddings, got {}", texts.len(), vectors.len()));
+    }
+    Ok(vectors)
+}
+
+async fn embed_query(config: &RequestConfig, query: &str) -> Result<Vec<f32>, String> {
+    embed_batch(config, &[query.to_string()], "RETRIEVAL_QUERY").await?
+        .pop()
+        .ok_or_else(|| "Empty query embedding".to_string())
+}
+
+// ============================================================================
+// Indexing
+// ============================================================================
+
+async fn index_session_data(config: &RequestConfig, session: &SessionData) -> Result<usize, String> {
+    let existing: HashMap<String, Vec<f32>> = load_index(&session.id)
+        .filter(|idx| idx.model == EMBEDDING_MODEL)
+        .map(|idx| idx.segments.into_iter().map(|s| (s.text_hash, s.vector)).collect())
+        .unwrap_or_default();
+
+    let mut segments: Vec<IndexedSegment> = session.transcripts.iter()
+        .enumerate()
+        .filter(|(_, t)| t.text.trim().len() >= MIN_SEGMENT_CHARS)
+        .map(|(i, t)| IndexedSegment {
+            text_hash: text_hash(&t.text),
+            index: i,
+            timestamp: t.timestamp.clone(),
+            speaker: t.speaker_id.clone(),
+            start_ms: t.start_ms,
+            language: t.language.clone(),
+            text: t.text.clone(),
+            vector: Vec::new(),
+        })
+        .collect();
+
+    let mut missing: Vec<usize> = Vec::new();
+    for (i, seg) in segments.iter_mut().enumerate() {
+        match existing.get(&seg.text_hash) {
+            Some(v) => seg.vector = v.clone(),
+            None => missing.push(i),
+        }
+    }
+
+    for chunk in missing.chunks(EMBED_BATCH_SIZE) {
+        let texts: Vec<String> = chunk.iter().map(|&i| segments[i].text.clone()).collect();
+        let vectors = embed_batch(config, &texts, "RETRIEVAL_DOCUMENT").await?;
+        for (&i, v) in chunk.iter().zip(vectors) {
+            segments[i].vector = v;
+        }
+    }
+
+    save_index(&SessionIndex {
+        session_id: session.id.clone(),
+        title: session.metadata.title.clone(),
+        model: EMBEDDING_MODEL.to_string(),
+        segments,
+    })?;
+    Ok(missing.len())
+}
+
+/// Bring one session's index up to date (new/changed segments only)
+pub async fn index_session(app: &AppHandle, session_id: &str) -> Result<usize, String> {
+    let state = app.state::<EmbeddingState>();
+    let _guard = state.indexing.lock().await;
+    let config = app.state::<GeminiState>().request_config(&app)?;
+    let session = SessionManager::new()?.load_session(session_id)?;
+    let embedded = index_session_data(&config, &session).await?;
+    if embedded > 0 {
+        info!("[EMBED] ✓ Indexed {} new segment(s) for session {}", embedded, session_id);
+    }
+    Ok(embedded)
+}
+
+/// Up-to-date vectors of a stored session, by transcript index
+pub(crate) async fn session_vectors(app: &AppHandle, session_id: &str) -> Result<Vec<(usize, Vec<f32>)>, String> {
+    index_session(app, session_id).await?;
+    let index = load_index(session_id).ok_or("Session index not found")?;
+    let mut vectors: Vec<(usize, Vec<f32>)> = index.segments.into_iter()
+        .filter(|s| !s.vector.is_empty())
+        .map(|s| (s.index, s.vector))
+        .collect();
+    vectors.sort_by_key(|(i, _)| *i);
+    Ok(vectors)
+}
+
+/// Background indexing after a save; failures (e.g. offline) are retried on the next save
+pub fn index_in_background(app: &AppHandle, session_id: &str) {
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = index_session(&app, &session_id).await {
+            error!("[EMBED] ✗ Indexing {} failed: {}", session_id, e);
+        }
+    });
+}
+
+pub fn delete_index(session_id: &str) {
+    if let Ok(dir) = index_dir() {
+        let _ = fs::remove_file(dir.join(format!("{}.json", session_id)));
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Most relevant moments across all indexed sessions
+#[tauri::command]
+pub async fn semantic_search(
+    app: AppHandle,
+    query: String,
+    k: Option<usize>,
+    language: Option<String>,
+) -> Result<Vec<SearchHit>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Query is empty".to_string());
+    }
+    let k = k.unwrap_or(DEFAULT_SEARCH_RESULTS).max(1);
+
+    let config = app.state::<GeminiState>().request_config(&app)?;
+    let query_vector = embed_query(&config, query).await?;
+
+    let mut hits: Vec<SearchHit> = Vec::new();
+    let entries = fs::read_dir(index_dir()?).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let index = match read_index(&entry.path()) {
+            Ok(index) => index,
+            Err(e) if encryption::is_locked() => return Err(e),
+            Err(_) => continue,
+        };
+        if index.model != EMBEDDING_MODEL { continue; }
+
+        let wanted = |seg: &IndexedSegment| language.as_deref()
+            .is_none_or(|l| seg.language.as_deref().is_some_and(|sl| sl.eq_ignore_ascii_case(l)));
+        for seg in index.segments.into_iter().filter(wanted) {
+            hits.push(SearchHit {
+                score: cosine(&query_vector, &seg.vector),
+                session_id: index.session_id.clone(),
+                session_title: index.title.clone(),
+                segment_index: seg.index,
+                timestamp: seg.timestamp,
+                speaker: seg.speaker,
+                start_ms: seg.start_ms,
+                language: seg.language,
+                text: seg.text,
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    hits.truncate(k);
+    Ok(hits)
+}
+
+/// Index every stored session (backfill after enabling search or switching models)
+#[tauri::command]
+pub async fn reindex_embeddings(app: AppHandle) -> Result<usize, String> {
+    let sessions = SessionManager::new()?.list_sessions()?;
+    let mut embedded = 0;
+    for session in &sessions {
+        embedded += index_session(&app, &session.id).await?;
+    }
+    info!("[EMBED] Reindex complete: {} session(s), {} new segment(s)", sessions.len(), embedded);
+    Ok(embedded)
+}
@@ -0,0 +1,450 @@
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::embeddings;
+use crate::event_journal;
+use crate::live_session::LiveSessionState;
+use crate::response_cache;
+use crate::retry_queue::RetryQueueState;
+use crate::session_manager::SessionManager;
+use crate::settings::SettingsState;
+
+// ============================================================================
+// ENCRYPTION - AES-256-GCM for Transcripts at Rest
+// ============================================================================
+//
+// Session files, their crash and event journals, the search index and the
+// retry queue are sealed with a key derived from the user's passphrase
+// (PBKDF2-HMAC-SHA256). The derived key is kept in the OS keychain and
+// unlocks the store at startup; the passphrase is only needed where the
+// keychain has no key (another machine, a cleared keychain), and then
+// unlock_encryption re-derives it and stores it again. While locked,
+// nothing is written in plaintext.
+//
+// WAV recordings are not encrypted: they are streamed to disk as captured
+// and stay playable by other tools. Delete them (or don't record) when the
+// audio itself is sensitive.
+//
+// Enabling encryption or changing the passphrase re-seals every store into
+// staged copies first; only when all of them are written is the new key
+// config saved and the copies swapped in, so a failure leaves the store as
+// it was and the saved config always matches the files.
+//
+// Sealed file layout: MAGIC | nonce (12 bytes) | ciphertext + tag.
+
+const MAGIC: &[u8] = b"CVXENC1\n";
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 310_000;
+const SALT_LEN: usize = 16;
+// Known plaintext sealed with the key, to check a passphrase without touching sessions
+const CHECK_PLAINTEXT: &[u8] = b"cognivox";
+const LOCKED_ERROR: &str = "Sessions are encrypted - unlock them with your passphrase first";
+const KEYCHAIN_SERVICE: &str = "com.cognivox.encryption";
+const KEYCHAIN_ACCOUNT: &str = "session-key";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static KEY: RwLock<Option<[u8; KEY_LEN]>> = RwLock::new(None);
+
+/// Persisted in settings; nothing here is secret
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    pub enabled: bool,
+    // Base64
+    pub salt: Option<String>,
+    pub check: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+/// Called once at startup with the stored config; unlocks from the keychain when it has the key
+pub fn init(config: &EncryptionConfig) {
+    ENABLED.store(config.enabled, Ordering::SeqCst);
+    if !config.enabled {
+        return;
+    }
+    match keychain_key(config) {
+        Some(key) => {
+            *KEY.write().unwrap() = Some(key);
+            info!("[CRYPTO] Session store unlocked from the keychain");
+        }
+        None => info!("[CRYPTO] Session store is encrypted and locked"),
+    }
+}
+
+/// Headless modes (`--mcp`) can't prompt; COGNIVOX_PASSPHRASE unlocks the store
+/// for them when the keychain doesn't
+pub fn unlock_from_env(config: &EncryptionConfig) {
+    if !is_locked() { return; }
+    let Ok(passphrase) = std::env::var("COGNIVOX_PASSPHRASE") else {
+        warn!("[CRYPTO] Store is locked; set COGNIVOX_PASSPHRASE to read sessions");
+        return;
+    };
+    match verify(config, &passphrase) {
+        Ok(key) => {
+            *KEY.write().unwrap() = Some(key);
+            info!("[CRYPTO] Session store unlocked");
+        }
+        Err(e) => warn!("[CRYPTO] ✗ Could not unlock: {}", e),
+    }
+}
+
+/// Enabled, but neither the keychain nor the passphrase has unlocked it this run
+pub fn is_locked() -> bool {
+    ENABLED.load(Ordering::SeqCst) && KEY.read().unwrap().is_none()
+}
+
+fn status() -> EncryptionStatus {
+    EncryptionStatus {
+        enabled: ENABLED.load(Ordering::SeqCst),
+        unlocked: KEY.read().unwrap().is_some(),
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("non-zero iterations");
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key);
+    key
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new().fill(&mut bytes).map_err(|_| "System RNG unavailable".to_string())?;
+    Ok(bytes)
+}
+
+fn seal_with(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let sealing = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid key")?);
+    let nonce_bytes = random_bytes::<NONCE_LEN>()?;
+    let mut in_out = plaintext.to_vec();
+    sealing.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce_bytes), Aad::empty(), &mut in_out)
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + in_out.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&in_out);
+    Ok(out)
+}
+
+fn open_with(key: &[u8; KEY_LEN], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let body = &sealed[MAGIC.len()..];
+    if body.len() < NONCE_LEN {
+        return Err("Encrypted file is truncated".to_string());
+    }
+    let (nonce_bytes, ciphertext) = body.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce_bytes).map_err(|_| "Invalid nonce")?;
+    let opening = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid key")?);
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = opening.open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| "Decryption failed (wrong key or corrupted file)".to_string())?;
+    Ok(plaintext.to_vec())
+}
+
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Bytes to write to disk: sealed when encryption is on, as-is otherwise
+pub fn seal(plaintext: Vec<u8>) -> Result<Vec<u8>, String> {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return Ok(plaintext);
+    }
+    let key = KEY.read().unwrap().ok_or(LOCKED_ERROR)?;
+    seal_with(&key, &plaintext)
+}
+
+/// Bytes read from disk, decrypted if they were sealed
+pub fn open(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    if !is_sealed(&bytes) {
+        return Ok(bytes);
+    }
+    let key = KEY.read().unwrap().ok_or(LOCKED_ERROR)?;
+    open_with(&key, &bytes)
+}
+
+/// One journal line: base64 of the sealed bytes when encryption is on
+pub fn seal_line(line: &str) -> Result<String, String> {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return Ok(line.to_string());
+    }
+    Ok(BASE64.encode(seal(line.as_bytes().to_vec())?))
+}
+
+pub fn open_line(line: &str) -> Result<String, String> {
+    match BASE64.decode(line.trim()) {
+        Ok(bytes) if is_sealed(&bytes) => String::from_utf8(open(bytes)?).map_err(|e| e.to_string()),
+        _ => Ok(line.to_string()),
+    }
+}
+
+/// Key for `passphrase` if it matches the stored check value
+fn verify(config: &EncryptionConfig, passphrase: &str) -> Result<[u8; KEY_LEN], String> {
+    let salt = config.salt.as_deref().and_then(|s| BASE64.decode(s).ok()).ok_or("Encryption is not set up")?;
+    let check = config.check.as_deref().and_then(|s| BASE64.decode(s).ok()).ok_or("Encryption is not set up")?;
+    let key = derive_key(passphrase, &salt);
+    match open_with(&key, &check) {
+        Ok(plain) if plain == CHECK_PLAINTEXT => Ok(key),
+        _ => Err("Wrong passphrase".to_string()),
+    }
+}
+
+// ============================================================================
+// Keychain
+// ============================================================================
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+/// The stored key, if it still matches the config (a stale one from an
+/// earlier passphrase is ignored)
+fn keychain_key(config: &EncryptionConfig) -> Option<[u8; KEY_LEN]> {
+    let encoded = keychain_entry().ok()?.get_password().ok()?;
+    let key: [u8; KEY_LEN] = BASE64.decode(encoded).ok()?.try_into().ok()?;
+    let check = BASE64.decode(config.check.as_deref()?).ok()?;
+    match open_with(&key, &check) {
+        Ok(plain) if plain == CHECK_PLAINTEXT => Some(key),
+        _ => None,
+    }
+}
+
+/// Remember the key for the next start. Failing only means the passphrase
+/// is asked for again, so it's a warning.
+fn store_in_keychain(key: &[u8; KEY_LEN]) {
+    let result = keychain_entry().and_then(|entry| {
+        entry.set_password(&BASE64.encode(key))
+            .map_err(|e| format!("Could not store the key in the keychain: {}", e))
+    });
+    if let Err(e) = result {
+        warn!("[CRYPTO] {} - the passphrase will be needed after a restart", e);
+    }
+}
+
+/// Fresh salt, key and check value for a new passphrase
+fn new_key(passphrase: &str) -> Result<([u8; KEY_LEN], EncryptionConfig), String> {
+    if passphrase.chars().count() < 8 {
+        return Err("Passphrase must be at least 8 characters".to_string());
+    }
+    let salt = random_bytes::<SALT_LEN>()?;
+    let key = derive_key(passphrase, &salt);
+    let config = EncryptionConfig {
+        enabled: true,
+        salt: Some(BASE64.encode(salt)),
+        check: Some(BASE64.encode(seal_with(&key, CHECK_PLAINTEXT)?)),
+    };
+    Ok((key, config))
+}
+
+fn open_with_previous(previous: Option<&[u8; KEY_LEN]>, bytes: &[u8], path: &Path) -> Result<Vec<u8>, String> {
+    match (is_sealed(bytes), previous) {
+        (true, Some(key)) => open_with(key, bytes),
+        (true, None) => Err(format!("{} is sealed with an unknown key", path.display())),
+        (false, _) => Ok(bytes.to_vec()),
+    }
+}
+
+fn staged_path(path: &Path) -> PathBuf {
+    let mut staged = OsString::from(path.as_os_str());
+    staged.push(".rekey");
+    PathBuf::from(staged)
+}
+
+/// Write `path` re-sealed under `key` next to it; returns the staged copy
+fn stage(path: &Path, line_sealed: bool, previous: Option<&[u8; KEY_LEN]>, key: &[u8; KEY_LEN]) -> Result<PathBuf, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let resealed = if line_sealed {
+        let mut out = String::new();
+        let mut skipped = 0;
+        for line in String::from_utf8_lossy(&bytes).lines().filter(|l| !l.trim().is_empty()) {
+            let plaintext = match BASE64.decode(line.trim()) {
+                Ok(sealed) if is_sealed(&sealed) => match open_with_previous(previous, &sealed, path) {
+                    Ok(plaintext) => plaintext,
+                    // Torn by a crash or sealed under an older passphrase: unreadable either way
+                    Err(_) => {
+                        skipped += 1;
+                        continue;
+                    }
+                },
+                _ => line.as_bytes().to_vec(),
+            };
+            out.push_str(&BASE64.encode(seal_with(key, &plaintext)?));
+            out.push('\n');
+        }
+        if skipped > 0 {
+            warn!("[CRYPTO] Dropped {} unreadable line(s) of {}", skipped, path.display());
+        }
+        out.into_bytes()
+    } else {
+        seal_with(key, &open_with_previous(previous, &bytes, path)?)?
+    };
+    let staged = staged_path(path);
+    fs::write(&staged, resealed).map_err(|e| format!("Failed to write {}: {}", staged.display(), e))?;
+    Ok(staged)
+}
+
+/// Re-seal every store under `key` into staged copies. The previous key must
+/// still read them, so it's passed in explicitly. Nothing is replaced yet;
+/// on error the copies written so far are removed.
+fn stage_all(previous: Option<[u8; KEY_LEN]>, key: [u8; KEY_LEN]) -> Result<Vec<PathBuf>, String> {
+    let manager = SessionManager::new()?;
+    event_journal::close(None);
+    let mut stores: Vec<(PathBuf, bool)> = Vec::new();
+    stores.extend(manager.session_files()?.into_iter().map(|p| (p, false)));
+    stores.extend(embeddings::index_files()?.into_iter().map(|p| (p, false)));
+    stores.extend(manager.journal_files()?.into_iter().map(|p| (p, true)));
+
+    let mut staged = Vec::with_capacity(stores.len());
+    for (path, line_sealed) in &stores {
+        match stage(path, *line_sealed, previous.as_ref(), &key) {
+            Ok(copy) => staged.push(copy),
+            Err(e) => {
+                for copy in &staged {
+                    let _ = fs::remove_file(copy);
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(stores.into_iter().map(|(path, _)| path).collect())
+}
+
+fn discard_staged(stores: &[PathBuf]) {
+    for path in stores {
+        let _ = fs::remove_file(staged_path(path));
+    }
+}
+
+/// Swap the staged copies in; the new config must already be saved
+fn commit_staged(app: &AppHandle, stores: &[PathBuf]) -> usize {
+    let mut failed = 0;
+    event_journal::close(None);
+    for path in stores {
+        if let Err(e) = fs::rename(staged_path(path), path) {
+            warn!("[CRYPTO] ✗ {} not replaced: {}", path.display(), e);
+            failed += 1;
+        }
+    }
+    // Cached responses are cheap to refetch, so drop them rather than re-seal
+    if let Err(e) = response_cache::clear() {
+        warn!("[CRYPTO] Response cache not cleared: {}", e);
+    }
+    app.state::<RetryQueueState>().reseal();
+    stores.len() - failed
+}
+
+fn ensure_idle(app: &AppHandle) -> Result<(), String> {
+    if app.state::<LiveSessionState>().active_id().is_some() {
+        return Err("End the live session before changing encryption".to_string());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_encryption_status() -> EncryptionStatus {
+    status()
+}
+
+/// Turn on encryption and seal every existing store
+#[tauri::command]
+pub async fn enable_encryption(
+    app: AppHandle,
+    settings: tauri::State<'_, SettingsState>,
+    passphrase: String,
+) -> Result<EncryptionStatus, String> {
+    if settings.get().encryption.enabled {
+        return Err("Encryption is already enabled".to_string());
+    }
+    ensure_idle(&app)?;
+    let (key, config) = new_key(&passphrase)?;
+
+    let stores = tauri::async_runtime::spawn_blocking(move || stage_all(None, key))
+        .await
+        .map_err(|e| e.to_string())??;
+    if let Err(e) = settings.update(|s| s.encryption = config) {
+        discard_staged(&stores);
+        return Err(e);
+    }
+    *KEY.write().unwrap() = Some(key);
+    ENABLED.store(true, Ordering::SeqCst);
+    store_in_keychain(&key);
+    let count = commit_staged(&app, &stores);
+
+    info!("[CRYPTO] ✓ Encryption enabled, {} file(s) sealed", count);
+    Ok(status())
+}
+
+#[tauri::command]
+pub async fn unlock_encryption(
+    app: AppHandle,
+    settings: tauri::State<'_, SettingsState>,
+    passphrase: String,
+) -> Result<EncryptionStatus, String> {
+    let config = settings.get().encryption;
+    if !config.enabled {
+        return Err("Encryption is not enabled".to_string());
+    }
+    let key = tauri::async_runtime::spawn_blocking(move || verify(&config, &passphrase))
+        .await
+        .map_err(|e| e.to_string())??;
+    *KEY.write().unwrap() = Some(key);
+    store_in_keychain(&key);
+    app.state::<RetryQueueState>().unlock();
+    info!("[CRYPTO] Session store unlocked");
+    Ok(status())
+}
+
+/// Re-key: new salt and key, every store re-sealed
+#[tauri::command]
+pub async fn change_passphrase(
+    app: AppHandle,
+    settings: tauri::State<'_, SettingsState>,
+    old_passphrase: String,
+    new_passphrase: String,
+) -> Result<EncryptionStatus, String> {
+    let current = settings.get().encryption;
+    if !current.enabled {
+        return Err("Encryption is not enabled".to_string());
+    }
+    ensure_idle(&app)?;
+    let (old_key, (new_key, config)) = tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, String>((verify(&current, &old_passphrase)?, new_key(&new_passphrase)?))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let stores = tauri::async_runtime::spawn_blocking(move || stage_all(Some(old_key), new_key))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Re-encryption failed, passphrase unchanged: {}", e))?;
+    if let Err(e) = settings.update(|s| s.encryption = config) {
+        discard_staged(&stores);
+        return Err(e);
+    }
+    *KEY.write().unwrap() = Some(new_key);
+    store_in_keychain(&new_key);
+    let count = commit_staged(&app, &stores);
+
+    info!("[CRYPTO] ✓ Passphrase changed, {} file(s) re-sealed", count);
+    Ok(status())
+}
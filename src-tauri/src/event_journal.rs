@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::thread;
+use chrono::Utc;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+use crate::encryption;
+use crate::events::{self, AudioLevelEvent, CognivoxEvent, PipelineMetrics, ReplayEvent, ReplayPhase};
+use crate::live_session::LiveSessionState;
+use crate::session_manager::SessionManager;
+
+// ============================================================================
+// EVENT JOURNAL - Every cognivox:* Event of a Session, Replayable
+// ============================================================================
+//
+// events::emit appends each payload, exactly as the frontend got it, to
+// <id>.events.jsonl next to the session JSON: everything while the session
+// is live, and later events that name the session (chapters, summaries) as
+// long as its journal exists. Replay re-emits the payloads under their
+// original names with the original gaps (scaled by `speed`), straight to the
+// webview so they're neither journaled again nor sent to the HTTP stream.
+//
+// Lines are sealed like the recovery journal; lines sealed under an earlier
+// passphrase can't be read after it changes and are skipped.
+//
+// Writing happens on one background thread that keeps the live session's
+// journal open, so emitting never waits on the disk. Level meters and
+// metrics ticks are left out: they fire several times a second and replay
+// nothing worth seeing.
+
+// Too frequent to be worth keeping
+const NOT_JOURNALED: &[&str] = &[ReplayEvent::NAME, AudioLevelEvent::NAME, PipelineMetrics::NAME];
+
+const MIN_SPEED: f32 = 0.1;
+const MAX_SPEED: f32 = 100.0;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JournaledEvent {
+    // Unix milliseconds when it was emitted
+    pub at_ms: i64,
+    pub name: String,
+    pub payload: Value,
+}
+
+#[derive(Default)]
+pub struct EventJournalState {
+    // (replay number, session id, task)
+    replay: StdMutex<Option<(u64, String, JoinHandle<()>)>>,
+    next_replay: AtomicU64,
+}
+
+enum Job {
+    // `live` journals may be created and stay open; others are appended to once
+    Write { session_id: String, live: bool, event: JournaledEvent },
+    // Close one session's journal (all when None), then signal
+    Close { session_id: Option<String>, done: Sender<()> },
+}
+
+static WRITER: OnceLock<Sender<Job>> = OnceLock::new();
+
+fn writer() -> &'static Sender<Job> {
+    WRITER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let spawned = thread::Builder::new().name("event-journal".to_string()).spawn(move || {
+            let mut open: HashMap<String, File> = HashMap::new();
+            for job in rx {
+                match job {
+                    Job::Write { session_id, live, event } => {
+                        if live {
+                            // One session is live at a time
+                            open.retain(|id, _| *id == session_id);
+                        }
+                        if let Err(e) = write(&mut open, &session_id, live, &event) {
+                            warn!("[JOURNAL] {} not journaled for {}: {}", event.name, session_id, e);
+                        }
+                    }
+                    Job::Close { session_id, done } => {
+                        match session_id {
+                            Some(id) => { open.remove(&id); }
+                            None => open.clear(),
+                        }
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        if let Err(e) = spawned {
+            warn!("[JOURNAL] ✗ Writer thread not started: {}", e);
+        }
+        tx
+    })
+}
+
+fn write(open: &mut HashMap<String, File>, session_id: &str, live: bool, event: &JournaledEvent) -> Result<(), String> {
+    let line = encryption::seal_line(&serde_json::to_string(event).map_err(|e| e.to_string())?)?;
+    if let Some(file) = open.get_mut(session_id) {
+        return writeln!(file, "{}", line).map_err(|e| e.to_string());
+    }
+    let path = SessionManager::new()?.events_path(session_id)?;
+    let mut file = match OpenOptions::new().append(true).create(live).open(&path) {
+        Ok(file) => file,
+        // Ended sessions without a journal don't get one
+        Err(_) if !live => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+    writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+    if live {
+        open.insert(session_id.to_string(), file);
+    }
+    Ok(())
+}
+
+/// Queue an emitted event for the journal of the session it belongs to
+pub fn record(app: &AppHandle, name: &'static str, payload: &Value) {
+    if NOT_JOURNALED.contains(&name) {
+        return;
+    }
+    let live = app.try_state::<LiveSessionState>().and_then(|s| s.active_id());
+    let (session_id, live) = match (live, payload["session_id"].as_str()) {
+        (Some(id), _) => (id, true),
+        (None, Some(id)) => (id.to_string(), false),
+        (None, None) => return,
+    };
+    let event = JournaledEvent { at_ms: Utc::now().timestamp_millis(), name: name.to_string(), payload: payload.clone() };
+    let _ = writer().send(Job::Write { session_id, live, event });
+}
+
+/// Write out everything queued so far and close the journal of `session_id`
+/// (every journal when None), so the file can be replaced or removed
+pub fn close(session_id: Option<&str>) {
+    let (done, wait) = mpsc::channel();
+    let job = Job::Close { session_id: session_id.map(str::to_string), done };
+    if writer().send(job).is_ok() {
+        let _ = wait.recv();
+    }
+}
+
+/// Journaled events of a session, oldest first
+pub fn load(session_id: &str) -> Result<Vec<JournaledEvent>, String> {
+    if encryption::is_locked() {
+        return Err("Unlock encrypted sessions before reading their events".to_string());
+    }
+    let path = SessionManager::new()?.events_path(session_id)?;
+    let journal = fs::read_to_string(&path)
+        .map_err(|_| format!("No event journal for session {}", session_id))?;
+    let mut skipped = 0;
+    let events: Vec<JournaledEvent> = journal.lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| {
+            let event = encryption::open_line(l).ok().and_then(|l| serde_json::from_str(&l).ok());
+            if event.is_none() {
+                skipped += 1;
+            }
+            event
+        })
+        .collect();
+    if skipped > 0 {
+        warn!("[JOURNAL] Skipped {} unreadable line(s) in {}", skipped, path.display());
+    }
+    Ok(events)
+}
+
+async fn play(app: AppHandle, id: u64, session_id: String, journal: Vec<JournaledEvent>, speed: f32) {
+    let total = journal.len();
+    events::emit(&app, &ReplayEvent { session_id: session_id.clone(), phase: ReplayPhase::Started, events: total, speed });
+
+    let mut previous = journal.first().map(|e| e.at_ms).unwrap_or_default();
+    for event in journal {
+        let gap = (event.at_ms - previous).max(0) as f32 / speed;
+        if gap >= 1.0 {
+            sleep(Duration::from_millis(gap as u64)).await;
+        }
+        previous = event.at_ms;
+        if let Err(e) = app.emit(&event.name, event.payload) {
+            warn!("[JOURNAL] Replay of {} failed: {}", event.name, e);
+        }
+    }
+
+    let state = app.state::<EventJournalState>();
+    let mut slot = state.replay.lock().unwrap();
+    if slot.as_ref().is_some_and(|(current, _, _)| *current == id) {
+        *slot = None;
+    }
+    drop(slot);
+    events::emit(&app, &ReplayEvent { session_id: session_id.clone(), phase: ReplayPhase::Finished, events: total, speed });
+    info!("[JOURNAL] ✓ Replayed {} event(s) of {}", total, session_id);
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Play a session's events back into the UI; returns how many will be replayed.
+/// `speed` 2.0 plays twice as fast (default 1.0).
+#[tauri::command]
+pub fn replay_session_events(
+    app: AppHandle,
+    state: tauri::State<'_, EventJournalState>,
+    live: tauri::State<'_, LiveSessionState>,
+    session_id: String,
+    speed: Option<f32>,
+) -> Result<usize, String> {
+    if live.active_id().is_some() {
+        return Err("End the live session before replaying events".to_string());
+    }
+    let speed = speed.unwrap_or(1.0);
+    if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        return Err(format!("speed must be between {} and {}", MIN_SPEED, MAX_SPEED));
+    }
+    let journal = load(&session_id)?;
+    if journal.is_empty() {
+        return Err(format!("No events recorded for session {}", session_id));
+    }
+    let total = journal.len();
+
+    stop_session_replay(app.clone(), state.clone());
+    let id = state.next_replay.fetch_add(1, Ordering::SeqCst);
+    let task = tauri::async_runtime::spawn(play(app, id, session_id.clone(), journal, speed));
+    *state.replay.lock().unwrap() = Some((id, session_id.clone(), task));
+    info!("[JOURNAL] Replaying {} event(s) of {} at {}x", total, session_id, speed);
+    Ok(total)
+}
+
+#[tauri::command]
+pub fn stop_session_replay(app: AppHandle, state: tauri::State<'_, EventJournalState>) {
+    let Some((_, session_id, task)) = state.replay.lock().unwrap().take() else { return };
+    task.abort();
+    events::emit(&app, &ReplayEvent { session_id, phase: ReplayPhase::Stopped, events: 0, speed: 0.0 });
+}
+
+/// Write the journal as plain JSONL into the exports folder; returns the path
+#[tauri::command]
+pub fn export_session_events(session_id: String) -> Result<String, String> {
+    let mut jsonl = String::new();
+    for event in load(&session_id)? {
+        jsonl.push_str(&serde_json::to_string(&event).map_err(|e| e.to_string())?);
+        jsonl.push('\n');
+    }
+    SessionManager::new()?.write_export(&session_id, "events.jsonl", jsonl)
+}
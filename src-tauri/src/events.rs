@@ -0,0 +1,627 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+use tracing::error;
+use crate::action_items::TrackedActionItem;
+use crate::bookmarks::Bookmark;
+use crate::calendar::CalendarEvent;
+use crate::chapters::Chapter;
+use crate::event_journal;
+use crate::hallucination::DiscardReason;
+use crate::levels::{InputLevel, SegmentGain};
+use crate::metrics::SeriesStats;
+use crate::reanalysis::SegmentAnalysis;
+use crate::session_manager::SessionSummary;
+use crate::translation::TranslationProvider;
+use crate::voice_commands::VoiceCommand;
+
+// ============================================================================
+// EVENTS - Typed Payloads for every cognivox:* Event
+// ============================================================================
+//
+// Every payload goes out wrapped with `schema_version`. Bump it whenever a
+// field is renamed or removed; adding optional fields doesn't need a bump.
+
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+pub trait CognivoxEvent: Serialize {
+    const NAME: &'static str;
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, E: Serialize> {
+    schema_version: u32,
+    #[serde(flatten)]
+    event: &'a E,
+}
+
+/// The exact JSON the frontend receives, for webhooks and other sinks
+pub fn to_payload<E: CognivoxEvent>(event: &E) -> serde_json::Value {
+    serde_json::to_value(Envelope { schema_version: EVENT_SCHEMA_VERSION, event })
+        .unwrap_or(serde_json::Value::Null)
+}
+
+// Copies of every event for listeners outside the webview (HTTP API stream).
+// Slow listeners lose the oldest events rather than holding up the pipeline.
+static BROADCAST: OnceLock<broadcast::Sender<(&'static str, serde_json::Value)>> = OnceLock::new();
+const BROADCAST_CAPACITY: usize = 256;
+
+fn broadcaster() -> &'static broadcast::Sender<(&'static str, serde_json::Value)> {
+    BROADCAST.get_or_init(|| broadcast::channel(BROADCAST_CAPACITY).0)
+}
+
+/// (event name, payload) for every event emitted from now on
+pub fn subscribe() -> broadcast::Receiver<(&'static str, serde_json::Value)> {
+    broadcaster().subscribe()
+}
+
+pub fn emit<E: CognivoxEvent>(app: &AppHandle, event: &E) {
+    let payload = to_payload(event);
+    event_journal::record(app, E::NAME, &payload);
+    let listeners = broadcaster();
+    if listeners.receiver_count() > 0 {
+        let _ = listeners.send((E::NAME, payload.clone()));
+    }
+    if let Err(e) = app.emit(E::NAME, payload) {
+        error!("[EVENTS] ✗ Failed to emit {}: {}", E::NAME, e);
+    }
+}
+
+/// Send to one window only; not copied to the broadcast listeners
+pub fn emit_to<E: CognivoxEvent>(app: &AppHandle, label: &str, event: &E) {
+    if let Err(e) = app.emit_to(label, E::NAME, to_payload(event)) {
+        error!("[EVENTS] ✗ Failed to emit {} to {}: {}", E::NAME, label, e);
+    }
+}
+
+// Latest status line, for snapshots taken between events
+static LAST_STATUS: Mutex<Option<StatusEvent>> = Mutex::new(None);
+
+pub fn emit_status(app: &AppHandle, state: PipelineState, message: impl Into<String>) {
+    let event = StatusEvent { state, message: message.into() };
+    emit(app, &event);
+    *LAST_STATUS.lock().unwrap() = Some(event);
+}
+
+pub fn last_status() -> StatusEvent {
+    LAST_STATUS.lock().unwrap().clone().unwrap_or(StatusEvent {
+        state: PipelineState::Idle,
+        message: String::new(),
+    })
+}
+
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// cognivox:status
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineState {
+    Idle,
+    Connecting,
+    LoadingModel,
+    Ready,
+    Listening,
+    SpeechDetected,
+    Transcribing,
+    Analyzing,
+    Summarizing,
+    Paused,
+    RateLimited,
+    Error,
+}
+
+/// `state` is for logic, `message` is the human-readable line to display
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StatusEvent {
+    pub state: PipelineState,
+    pub message: String,
+}
+
+impl CognivoxEvent for StatusEvent {
+    const NAME: &'static str = "cognivox:status";
+}
+
+// ============================================================================
+// cognivox:whisper_transcription
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionSource {
+    Whisper,
+    // Scripted line from start_simulation
+    Simulated,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TranscriptionEvent {
+    // Live segments only; one-off transcribe_with_whisper calls have no segment
+    pub segment_id: Option<String>,
+    pub session_id: Option<String>,
+    pub text: String,
+    pub language: String,
+    // Per-segment detection probability; None when the language was fixed
+    pub language_probability: Option<f32>,
+    // Whisper token probabilities, 0.0-1.0
+    pub confidence: f32,
+    pub no_speech_prob: f32,
+    pub source: TranscriptionSource,
+    pub speaker: Option<String>,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    pub gain: Option<SegmentGain>,
+}
+
+impl CognivoxEvent for TranscriptionEvent {
+    const NAME: &'static str = "cognivox:whisper_transcription";
+}
+
+// ============================================================================
+// cognivox:segment_discarded
+// ============================================================================
+
+/// A transcript dropped as a hallucination or duplicate; it never reaches Gemini
+#[derive(Serialize, Clone, Debug)]
+pub struct SegmentDiscardedEvent {
+    pub segment_id: Option<String>,
+    pub session_id: Option<String>,
+    pub text: String,
+    pub reason: DiscardReason,
+    pub no_speech_prob: f32,
+}
+
+impl CognivoxEvent for SegmentDiscardedEvent {
+    const NAME: &'static str = "cognivox:segment_discarded";
+}
+
+// ============================================================================
+// cognivox:translated_caption
+// ============================================================================
+
+/// A finalized live segment in the user's chosen second language
+#[derive(Serialize, Clone, Debug)]
+pub struct TranslatedCaptionEvent {
+    pub segment_id: String,
+    pub session_id: Option<String>,
+    pub speaker: String,
+    pub text: String,
+    pub translation: String,
+    pub source_language: String,
+    pub target_language: String,
+    pub provider: TranslationProvider,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl CognivoxEvent for TranslatedCaptionEvent {
+    const NAME: &'static str = "cognivox:translated_caption";
+}
+
+// ============================================================================
+// cognivox:voice_command
+// ============================================================================
+
+/// A spoken command was recognized and run (or failed); it isn't analyzed as content
+#[derive(Serialize, Clone, Debug)]
+pub struct VoiceCommandEvent {
+    pub command: VoiceCommand,
+    pub segment_id: String,
+    pub session_id: Option<String>,
+    pub text: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+impl CognivoxEvent for VoiceCommandEvent {
+    const NAME: &'static str = "cognivox:voice_command";
+}
+
+// ============================================================================
+// cognivox:gemini_intelligence
+// ============================================================================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct IntelligenceEvent {
+    pub segment_id: Option<String>,
+    pub session_id: Option<String>,
+    pub transcript: String,
+    pub speaker: Option<String>,
+    // Raw model JSON; None while the segment waits in the retry queue
+    pub intelligence: Option<String>,
+    pub pending: bool,
+    pub retried: bool,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    pub timestamp: u64,
+}
+
+impl CognivoxEvent for IntelligenceEvent {
+    const NAME: &'static str = "cognivox:gemini_intelligence";
+}
+
+// ============================================================================
+// cognivox:api_error
+// ============================================================================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ApiErrorEvent {
+    pub code: u16,
+    pub message: String,
+}
+
+impl ApiErrorEvent {
+    /// Classify a Gemini error string so the frontend key manager knows whether to rotate
+    pub fn from_error(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let code = if message.contains("429") || message.contains("Rate limit") { 429 } else { 500 };
+        Self { code, message }
+    }
+}
+
+impl CognivoxEvent for ApiErrorEvent {
+    const NAME: &'static str = "cognivox:api_error";
+}
+
+// ============================================================================
+// cognivox:rate_limit
+// ============================================================================
+
+/// Sent when backoff grows after a 429 and again (backoff_secs = 0) once requests succeed
+#[derive(Serialize, Clone, Debug)]
+pub struct RateLimitEvent {
+    pub backoff_secs: u64,
+    // Unix ms before which no request will be sent
+    pub next_allowed_at: u64,
+    pub min_interval_ms: u64,
+}
+
+impl CognivoxEvent for RateLimitEvent {
+    const NAME: &'static str = "cognivox:rate_limit";
+}
+
+// ============================================================================
+// Remaining events
+// ============================================================================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AudioLevelEvent {
+    #[serde(flatten)]
+    pub level: InputLevel,
+    pub speaking: bool,
+    // False when no audio has arrived recently (device gone, stream stalled)
+    pub receiving: bool,
+}
+
+impl CognivoxEvent for AudioLevelEvent {
+    const NAME: &'static str = "cognivox:audio_level";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ActionItemAddedEvent {
+    #[serde(flatten)]
+    pub item: TrackedActionItem,
+}
+
+impl CognivoxEvent for ActionItemAddedEvent {
+    const NAME: &'static str = "cognivox:action_item_added";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MeetingSummaryEvent {
+    pub session_id: String,
+    pub summary: SessionSummary,
+}
+
+impl CognivoxEvent for MeetingSummaryEvent {
+    const NAME: &'static str = "cognivox:meeting_summary";
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStage {
+    Map,
+    Reduce,
+}
+
+/// One step of a map-reduce summary. `level` counts reduce rounds (0 for map);
+/// `resumed` steps were restored from an earlier interrupted run.
+#[derive(Serialize, Clone, Debug)]
+pub struct SummaryProgressEvent {
+    pub session_id: String,
+    pub stage: SummaryStage,
+    pub level: usize,
+    pub completed: usize,
+    pub total: usize,
+    pub resumed: usize,
+}
+
+impl CognivoxEvent for SummaryProgressEvent {
+    const NAME: &'static str = "cognivox:summary_progress";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CalendarEventAttached {
+    pub session_id: Option<String>,
+    pub event: CalendarEvent,
+}
+
+impl CognivoxEvent for CalendarEventAttached {
+    const NAME: &'static str = "cognivox:calendar_event";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LogEvent {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub timestamp: String,
+}
+
+impl CognivoxEvent for LogEvent {
+    const NAME: &'static str = "cognivox:log";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookmarkAddedEvent {
+    pub session_id: String,
+    pub bookmark: Bookmark,
+}
+
+impl CognivoxEvent for BookmarkAddedEvent {
+    const NAME: &'static str = "cognivox:bookmark_added";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ChaptersReadyEvent {
+    pub session_id: String,
+    pub chapters: Vec<Chapter>,
+}
+
+impl CognivoxEvent for ChaptersReadyEvent {
+    const NAME: &'static str = "cognivox:chapters_ready";
+}
+
+/// A watched keyword was said; fires regardless of how the segment is analyzed
+#[derive(Serialize, Clone, Debug)]
+pub struct KeywordAlertEvent {
+    pub keyword: String,
+    pub session_id: Option<String>,
+    pub segment_id: Option<String>,
+    pub speaker: String,
+    // The words around the match
+    pub context: String,
+    pub text: String,
+    pub start_ms: Option<u64>,
+}
+
+impl CognivoxEvent for KeywordAlertEvent {
+    const NAME: &'static str = "cognivox:keyword_alert";
+}
+
+/// A line in the transcript monitor window; a repeated segment_id replaces the earlier line
+#[derive(Serialize, Clone, Debug)]
+pub struct MonitorLineEvent {
+    pub segment_id: Option<String>,
+    pub speaker: String,
+    pub text: String,
+    pub start_ms: Option<u64>,
+    pub translation: Option<String>,
+}
+
+impl CognivoxEvent for MonitorLineEvent {
+    const NAME: &'static str = "cognivox:monitor_line";
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayPhase {
+    Started,
+    Finished,
+    Stopped,
+}
+
+/// Brackets a replay; the replayed events in between arrive under their own names
+#[derive(Serialize, Clone, Debug)]
+pub struct ReplayEvent {
+    pub session_id: String,
+    pub phase: ReplayPhase,
+    pub events: usize,
+    pub speed: f32,
+}
+
+impl CognivoxEvent for ReplayEvent {
+    const NAME: &'static str = "cognivox:replay";
+}
+
+/// One segment of a running reanalyze_session, as it completes
+#[derive(Serialize, Clone, Debug)]
+pub struct ReanalysisProgressEvent {
+    pub session_id: String,
+    pub done: usize,
+    pub total: usize,
+    pub analysis: SegmentAnalysis,
+}
+
+impl CognivoxEvent for ReanalysisProgressEvent {
+    const NAME: &'static str = "cognivox:reanalysis_progress";
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    SessionDuration,
+    RecordingSize,
+    DiskSpace,
+    Memory,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceAction {
+    Warned,
+    // The live session continues as new_session_id
+    RolledOver,
+    RecordingStopped,
+}
+
+/// A resource limit was approached or hit. `value` and `limit` are in minutes or MB.
+#[derive(Serialize, Clone, Debug)]
+pub struct ResourceWarningEvent {
+    pub kind: ResourceKind,
+    pub action: ResourceAction,
+    pub session_id: Option<String>,
+    pub new_session_id: Option<String>,
+    pub value: u64,
+    pub limit: u64,
+    pub message: String,
+}
+
+impl CognivoxEvent for ResourceWarningEvent {
+    const NAME: &'static str = "cognivox:resource_warning";
+}
+
+/// A session got a generated title and/or tags
+#[derive(Serialize, Clone, Debug)]
+pub struct SessionTitledEvent {
+    pub session_id: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    // False when the user's own title was kept and only tags were added
+    pub title_changed: bool,
+}
+
+impl CognivoxEvent for SessionTitledEvent {
+    const NAME: &'static str = "cognivox:session_titled";
+}
+
+/// The audio loop isn't draining capture fast enough; dropped_* count since the last warning
+#[derive(Serialize, Clone, Debug)]
+pub struct AudioBackpressureEvent {
+    pub dropped_chunks: u64,
+    pub dropped_ms: u64,
+    pub total_dropped_ms: u64,
+    pub buffered_ms: u64,
+    pub capacity_ms: u64,
+    pub message: String,
+}
+
+impl CognivoxEvent for AudioBackpressureEvent {
+    const NAME: &'static str = "cognivox:audio_backpressure";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ImportProgressEvent {
+    pub path: String,
+    pub segment: usize,
+    pub total: usize,
+}
+
+impl CognivoxEvent for ImportProgressEvent {
+    const NAME: &'static str = "cognivox:import_progress";
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderFileStatus {
+    Started,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FolderProgressEvent {
+    pub folder: String,
+    pub path: String,
+    pub index: usize,
+    pub total: usize,
+    pub status: FolderFileStatus,
+    pub session_id: Option<String>,
+    pub error: Option<String>,
+}
+
+impl CognivoxEvent for FolderProgressEvent {
+    const NAME: &'static str = "cognivox:folder_progress";
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPhase {
+    Started,
+    Ended,
+    // Rebuilt after a crash by recover_last_session
+    Recovered,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SessionEvent {
+    pub phase: SessionPhase,
+    pub session_id: String,
+    pub title: String,
+}
+
+impl CognivoxEvent for SessionEvent {
+    const NAME: &'static str = "cognivox:session";
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PipelineMetrics {
+    pub uptime_secs: u64,
+    pub segments_processed: u64,
+    pub segments_dropped: u64,
+    // Chunks waiting for the audio loop, and what the full channel dropped
+    pub audio_queue_depth: usize,
+    pub audio_buffered_ms: u64,
+    pub audio_chunks_dropped: u64,
+    pub audio_dropped_ms: u64,
+    pub retry_queue_depth: usize,
+    pub transcript_latency_ms: SeriesStats,
+    pub intelligence_latency_ms: SeriesStats,
+    pub end_to_end_latency_ms: SeriesStats,
+    pub whisper_rtf: SeriesStats,
+    pub gemini_requests: u64,
+    pub gemini_errors: u64,
+    pub gemini_error_rate: f64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+impl CognivoxEvent for PipelineMetrics {
+    const NAME: &'static str = "cognivox:metrics";
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownPhase {
+    Flushing,
+    // Buffered speech is out; the frontend should save its session now
+    SaveSession,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ShutdownEvent {
+    pub phase: ShutdownPhase,
+}
+
+impl CognivoxEvent for ShutdownEvent {
+    const NAME: &'static str = "cognivox:shutdown";
+}
+
+/// Tray "Open Last Summary": the main window shows this session's summary
+#[derive(Serialize, Clone, Debug)]
+pub struct TrayOpenSummaryEvent {
+    pub session_id: String,
+}
+
+impl CognivoxEvent for TrayOpenSummaryEvent {
+    const NAME: &'static str = "cognivox:tray_open_summary";
+}
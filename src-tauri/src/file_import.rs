@@ -0,0 +1,394 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::{info, warn};
+use crate::action_items;
+use crate::alerts;
+use crate::audio_capture::{to_mono, AudioState, StreamResampler, TARGET_SAMPLE_RATE};
+use crate::dedupe::{suppress_duplicate_in, Deduper};
+use crate::denoise::Denoiser;
+use crate::embeddings;
+use crate::events::{self, FolderFileStatus, FolderProgressEvent, ImportProgressEvent, PipelineState};
+use crate::gemini_client::{annotate_segment, build_intelligence_prompt, call_gemini_with_text, segment_recording, stitch_overlap, GeminiState};
+use crate::hallucination::discard_if_hallucinated;
+use crate::levels::normalize_segment;
+use crate::live_session::entry_from;
+use crate::network::NetworkState;
+use crate::retry_queue::{PendingSegment, RetryQueueState};
+use crate::session_manager::{SessionData, SessionManager};
+use crate::settings::{app_data_dir, SettingsState};
+use crate::speakers::SpeakerState;
+use crate::whisper_client::{transcribe_audio, WhisperState};
+
+// ============================================================================
+// FILE IMPORT - Transcribe Existing Recordings (WAV/MP3/M4A/FLAC/OGG)
+// ============================================================================
+//
+// Decodes the file with symphonia, downmixing and resampling to 16 kHz packet
+// by packet, then runs the live pipeline's stages offline: speech
+// segmentation, Whisper and Gemini. The result is stored as a regular
+// session. Segments Gemini can't analyze go to the retry queue and are
+// filled in later. Folder imports remember each file's size and mtime, so
+// running the same folder again only picks up new or changed files.
+
+pub(crate) const DEFAULT_SPEAKER: &str = "Speaker 1";
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "mp4", "aac", "flac", "ogg"];
+const IMPORTED_FILES: &str = "imported_files.json";
+
+/// Only one folder batch runs at a time; `cancel` stops it after the current file
+#[derive(Default)]
+pub struct FolderImportState {
+    running: AtomicBool,
+    cancel: AtomicBool,
+}
+
+/// Decode any supported file to mono f32 at TARGET_SAMPLE_RATE (blocking),
+/// resampling as it goes so the native-rate audio is never held in full
+pub(crate) fn decode_to_target(path: &Path) -> Result<Vec<f32>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unsupported audio file: {}", e))?;
+    let mut format = probed.format;
+    let track = format.tracks().iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or("No audio track found")?;
+    let track_id = track.id;
+    let sample_rate = track.codec_params.sample_rate.ok_or("Unknown sample rate")?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported codec: {}", e))?;
+
+    let mut resampler = StreamResampler::new(sample_rate);
+    let mut samples = Vec::new();
+    let mut frames: u64 = 0;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(format!("Failed to read audio: {}", e)),
+        };
+        if packet.track_id() != track_id { continue; }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buf.copy_interleaved_ref(decoded);
+                let mono = to_mono(buf.samples(), spec.channels.count() as u16);
+                frames += mono.len() as u64;
+                samples.extend(resampler.process(mono));
+            }
+            // A corrupt packet only costs a few milliseconds of audio
+            Err(SymphoniaError::DecodeError(e)) => warn!("[IMPORT] Skipping bad packet: {}", e),
+            Err(e) => return Err(format!("Failed to decode audio: {}", e)),
+        }
+    }
+    samples.extend(resampler.flush());
+    samples.truncate((frames * TARGET_SAMPLE_RATE as u64 / sample_rate as u64) as usize);
+    info!("[IMPORT] Decoded {:.1}s at {} Hz", frames as f32 / sample_rate as f32, sample_rate);
+    Ok(samples)
+}
+
+/// 16 kHz mono samples for a file, denoised when noise suppression is on
+pub(crate) async fn load_samples(app: &AppHandle, path: PathBuf) -> Result<Vec<f32>, String> {
+    let denoise = app.state::<AudioState>().noise_suppression_enabled();
+    tauri::async_runtime::spawn_blocking(move || {
+        let samples = decode_to_target(&path)?;
+        Ok(if denoise { Denoiser::new().process(&samples) } else { samples })
+    })
+    .await
+    .map_err(|e| format!("Decode task failed: {}", e))?
+}
+
+/// Run a file through segmentation → Whisper → Gemini and store it as a session
+pub async fn import_file(app: &AppHandle, path: &Path) -> Result<SessionData, String> {
+    let whisper = app.state::<WhisperState>();
+    if !*whisper.is_initialized.lock().unwrap() {
+        return Err("Whisper not initialized".to_string());
+    }
+    let model_path = whisper.model_path.lock().unwrap().clone().ok_or("Whisper model missing")?;
+    let language = whisper.language.lock().unwrap().clone();
+    let decoding = whisper.decoding.lock().unwrap().clone();
+
+    let file_label = path.display().to_string();
+    info!("[IMPORT] Transcribing {}", file_label);
+    events::emit_status(app, PipelineState::Transcribing, format!("Decoding {}...", file_label));
+    let samples = load_samples(app, path.to_path_buf()).await?;
+    let segments = segment_recording(&samples, app.state::<SettingsState>().get().segment_overlap_ms);
+    info!("[IMPORT] {} speech segment(s) in {:.1}s of audio",
+          segments.len(), samples.len() as f32 / TARGET_SAMPLE_RATE as f32);
+
+    let title = path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported recording".to_string());
+    let mut session = SessionData::new(title);
+    let manager = SessionManager::new()?;
+    manager.save_session(&session)?;
+
+    let system_prompt = build_intelligence_prompt(&app.state::<SettingsState>().get());
+    let mut context: Vec<String> = Vec::new();
+    let context_size = *app.state::<GeminiState>().context_size.lock().unwrap();
+    let mut speakers_seen = std::collections::HashSet::new();
+    let mut previous_text: Option<String> = None;
+    let mut deduper = Deduper::default();
+
+    let total = segments.len();
+    for (index, (start, mut audio, continues)) in segments.into_iter().enumerate() {
+        events::emit(app, &ImportProgressEvent { path: file_label.clone(), segment: index + 1, total });
+        events::emit_status(app, PipelineState::Transcribing, format!("Transcribing segment {}/{}...", index + 1, total));
+
+        let start_ms = start as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        let end_ms = start_ms + audio.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        normalize_segment(&mut audio);
+        let speaker = app.state::<SpeakerState>().identify(&audio)
+            .map(|(name, _)| name)
+            .unwrap_or_else(|| DEFAULT_SPEAKER.to_string());
+
+        let (text, stt_confidence, language) = match transcribe_audio(&model_path, &language, &decoding, &audio).await {
+            Ok(result) if !result.text.trim().is_empty() => {
+                if discard_if_hallucinated(app, &result.text, result.no_speech_prob, None, Some(&session.id)) {
+                    continue;
+                }
+                let text = match previous_text.as_deref().filter(|_| continues) {
+                    Some(previous) => stitch_overlap(previous, &result.text),
+                    None => result.text.trim().to_string(),
+                };
+                previous_text = Some(text.clone());
+                let Some(text) = suppress_duplicate_in(app, &mut deduper, &text, result.no_speech_prob, Some(&session.id)) else {
+                    continue;
+                };
+                (text, result.confidence, result.segment_language())
+            }
+            Ok(_) => continue,
+            Err(e) => {
+                warn!("[IMPORT] ✗ Segment {}/{} not transcribed: {}", index + 1, total, e);
+                continue;
+            }
+        };
+        let segment_id = uuid::Uuid::new_v4().to_string();
+        alerts::watch_keywords(app, &text, &speaker, Some(&segment_id), Some(&session.id), Some(start_ms));
+        let annotated = annotate_segment(&speaker, &text, stt_confidence);
+
+        let config = app.state::<GeminiState>().request_config(&app);
+        let result = match config {
+            Ok(config) => {
+                events::emit_status(app, PipelineState::Analyzing, format!("Analyzing segment {}/{}...", index + 1, total));
+                call_gemini_with_text(&config, &system_prompt, &annotated, &context).await
+            }
+            Err(e) => Err(e),
+        };
+        let intelligence = match result {
+            Ok(response) => {
+                action_items::ingest_intelligence(app, Some(&session.id), &text, &speaker, &response, Some(start_ms));
+                Some(response)
+            }
+            Err(e) if app.state::<NetworkState>().is_local_only() => {
+                warn!("[IMPORT] Segment {}/{} transcribed only: {}", index + 1, total, e);
+                None
+            }
+            Err(e) => {
+                warn!("[IMPORT] Segment {}/{} queued for analysis: {}", index + 1, total, e);
+                app.state::<RetryQueueState>().enqueue(PendingSegment::new(
+                    segment_id.clone(), Some(session.id.clone()), text.clone(), speaker.clone(),
+                    Some(start_ms), Some(end_ms), e,
+                ));
+                None
+            }
+        };
+
+        session.add_transcript(entry_from(&segment_id, &text, &speaker, intelligence.as_deref(), (Some(start_ms), Some(end_ms)), language.as_ref()));
+        speakers_seen.insert(speaker);
+        context.push(annotated);
+        if context.len() > context_size {
+            context.remove(0);
+        }
+        // Keep partial progress if a long import is interrupted
+        manager.save_session(&session)?;
+    }
+
+    session.metadata.duration_seconds = samples.len() as u64 / TARGET_SAMPLE_RATE as u64;
+    session.metadata.total_transcripts = session.transcripts.len();
+    session.metadata.total_speakers = speakers_seen.len();
+    session.updated_at = chrono::Utc::now().to_rfc3339();
+    manager.save_session(&session)?;
+
+    if app.state::<GeminiState>().api_key.lock().unwrap().is_some() {
+        embeddings::index_in_background(app, &session.id);
+    }
+    info!("[IMPORT] ✓ {} stored as session {} ({} segment(s))", file_label, session.id, session.transcripts.len());
+    events::emit_status(app, PipelineState::Ready, "Ready");
+    Ok(session)
+}
+
+pub(crate) fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Audio files directly inside `folder`, in name order
+fn audio_files(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(folder).map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && is_audio_file(p))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// What a folder import saw of a file; a different stamp means it changed
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+struct FileStamp {
+    len: u64,
+    modified_secs: u64,
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let meta = fs::metadata(path).ok()?;
+    let modified = meta.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(FileStamp { len: meta.len(), modified_secs: modified.as_secs() })
+}
+
+/// File path -> stamp when it was imported
+fn imported_files() -> HashMap<String, FileStamp> {
+    app_data_dir().ok()
+        .and_then(|dir| fs::read_to_string(dir.join(IMPORTED_FILES)).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn already_imported(imported: &HashMap<String, FileStamp>, path: &Path) -> bool {
+    let stamp = imported.get(&path.display().to_string());
+    stamp.is_some() && stamp.copied() == file_stamp(path)
+}
+
+fn remember_import(path: &Path) -> Result<(), String> {
+    let stamp = file_stamp(path).ok_or_else(|| format!("Can't stat {}", path.display()))?;
+    let mut imported = imported_files();
+    imported.insert(path.display().to_string(), stamp);
+    let json = serde_json::to_string_pretty(&imported).map_err(|e| e.to_string())?;
+    fs::write(app_data_dir()?.join(IMPORTED_FILES), json)
+        .map_err(|e| format!("Failed to save imported file list: {}", e))
+}
+
+async fn process_files(app: AppHandle, folder: String, files: Vec<PathBuf>) {
+    let state = app.state::<FolderImportState>();
+    let total = files.len();
+    let (mut done, mut failed) = (0, 0);
+
+    for (index, path) in files.iter().enumerate() {
+        let mut progress = FolderProgressEvent {
+            folder: folder.clone(),
+            path: path.display().to_string(),
+            index: index + 1,
+            total,
+            status: FolderFileStatus::Started,
+            session_id: None,
+            error: None,
+        };
+        if state.cancel.load(Ordering::SeqCst) {
+            progress.status = FolderFileStatus::Cancelled;
+            events::emit(&app, &progress);
+            continue;
+        }
+
+        info!("[IMPORT] File {}/{}: {}", index + 1, total, path.display());
+        events::emit(&app, &progress);
+        match import_file(&app, path).await {
+            Ok(session) => {
+                done += 1;
+                if let Err(e) = remember_import(path) {
+                    warn!("[IMPORT] {}", e);
+                }
+                progress.status = FolderFileStatus::Done;
+                progress.session_id = Some(session.id);
+            }
+            Err(e) => {
+                failed += 1;
+                warn!("[IMPORT] ✗ {}: {}", path.display(), e);
+                progress.status = FolderFileStatus::Failed;
+                progress.error = Some(e);
+            }
+        }
+        events::emit(&app, &progress);
+    }
+
+    info!("[IMPORT] Folder {} finished: {} imported, {} failed, {} skipped",
+          folder, done, failed, total - done - failed);
+    events::emit_status(&app, PipelineState::Ready, format!("Imported {} of {} file(s)", done, total));
+    state.running.store(false, Ordering::SeqCst);
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn transcribe_file(app: AppHandle, path: String) -> Result<SessionData, String> {
+    import_file(&app, Path::new(&path)).await.inspect_err(|e| {
+        events::emit_status(&app, PipelineState::Error, format!("Import failed: {}", e));
+    })
+}
+
+/// Queue every audio file in `path` not imported before (or changed since)
+/// for import, one session per file. Returns the queued files; progress
+/// arrives as cognivox:folder_progress.
+#[tauri::command]
+pub fn process_folder(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let mut files = audio_files(Path::new(&path))?;
+    if files.is_empty() {
+        return Err(format!("No audio files in {}", path));
+    }
+    let imported = imported_files();
+    let found = files.len();
+    files.retain(|f| !already_imported(&imported, f));
+    if files.is_empty() {
+        return Err(format!("All {} audio file(s) in {} were already imported", found, path));
+    }
+    if files.len() < found {
+        info!("[IMPORT] Skipping {} already imported file(s)", found - files.len());
+    }
+
+    let state = app.state::<FolderImportState>();
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err("A folder import is already running".to_string());
+    }
+    state.cancel.store(false, Ordering::SeqCst);
+
+    info!("[IMPORT] Queued {} file(s) from {}", files.len(), path);
+    let queued = files.iter().map(|p| p.display().to_string()).collect();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        process_files(app, path, files).await;
+    });
+    Ok(queued)
+}
+
+/// Stop a running folder import once the current file is done
+#[tauri::command]
+pub fn cancel_folder_processing(state: tauri::State<'_, FolderImportState>) -> bool {
+    let running = state.running.load(Ordering::SeqCst);
+    if running {
+        state.cancel.store(true, Ordering::SeqCst);
+    }
+    running
+}
@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use chrono::{DateTime, Utc};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use tracing::info;
+use crate::gemini_client::{GeminiState, call_gemini, extract_json};
+use crate::session_manager::{SessionData, SessionManager};
+use crate::summarizer;
+
+// ============================================================================
+// FOLLOW-UP EMAIL - Recap Draft from a Session Summary
+// ============================================================================
+//
+// The model writes the prose from the stored summary (generated first if the
+// session has none); the email itself is assembled here so the structured
+// fields, the mailto: link and the .eml file always agree.
+
+const FOLLOWUP_PROMPT: &str = r#"You are writing a follow-up email after a meeting, on behalf of the person who recorded it.
+
+INPUT: Meeting title, date, attendees and a JSON summary of the meeting.
+OUTPUT: JSON only, no markdown.
+
+FORMAT:
+{"subject":"...","greeting":"Hi all,","summary":"2-4 sentence recap","decisions":["..."],"action_items":[{"description":"...","owner":"name or null","due":"date or null"}],"closing":"..."}
+
+RULES:
+- Professional, friendly and concise; plain text, no markdown
+- Only include decisions and action items that appear in the summary
+- Keep owner names exactly as given; use null when unknown
+- Greet attendees by name when there are three or fewer
+- closing: one sentence inviting corrections, without a signature"#;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FollowupActionItem {
+    pub description: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FollowupResponse {
+    #[serde(default)]
+    subject: String,
+    #[serde(default)]
+    greeting: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    decisions: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<FollowupActionItem>,
+    #[serde(default)]
+    closing: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FollowupEmail {
+    pub session_id: String,
+    // Attendee email addresses known from the linked calendar event
+    pub to: Vec<String>,
+    pub subject: String,
+    pub greeting: String,
+    pub summary: String,
+    pub decisions: Vec<String>,
+    pub action_items: Vec<FollowupActionItem>,
+    pub closing: String,
+    // Plain-text body assembled from the fields above
+    pub body: String,
+    pub mailto: String,
+    pub eml: String,
+    pub eml_path: String,
+}
+
+fn meeting_date(session: &SessionData) -> String {
+    let start = session.calendar_event.as_ref().map(|e| e.start.as_str()).unwrap_or(&session.created_at);
+    DateTime::parse_from_rfc3339(start)
+        .map(|d| d.format("%A, %B %-d, %Y").to_string())
+        .unwrap_or_default()
+}
+
+fn recipients(session: &SessionData) -> Vec<String> {
+    let Some(event) = &session.calendar_event else { return Vec::new() };
+    event.attendees.iter()
+        .map(|a| a.trim().trim_start_matches("mailto:").to_string())
+        .filter(|a| a.contains('@') && !a.contains(' '))
+        .collect()
+}
+
+fn render_body(email: &FollowupResponse) -> String {
+    let mut body = format!("{}\n\n{}\n", email.greeting.trim(), email.summary.trim());
+    if !email.decisions.is_empty() {
+        body.push_str("\nDecisions:\n");
+        for d in &email.decisions {
+            body.push_str(&format!("- {}\n", d));
+        }
+    }
+    if !email.action_items.is_empty() {
+        body.push_str("\nAction items:\n");
+        for a in &email.action_items {
+            let mut line = format!("- {}", a.description);
+            if let Some(owner) = &a.owner { line.push_str(&format!(" ({})", owner)); }
+            if let Some(due) = &a.due { line.push_str(&format!(", due {}", due)); }
+            body.push_str(&line);
+            body.push('\n');
+        }
+    }
+    if !email.closing.trim().is_empty() {
+        body.push_str(&format!("\n{}\n", email.closing.trim()));
+    }
+    body
+}
+
+/// RFC 3986 percent-encoding for mailto: components (spaces as %20, not +)
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn mailto_link(to: &[String], subject: &str, body: &str) -> String {
+    format!(
+        "mailto:{}?subject={}&body={}",
+        to.iter().map(|a| percent_encode(a)).collect::<Vec<_>>().join(","),
+        percent_encode(subject),
+        percent_encode(&body.replace('\n', "\r\n")),
+    )
+}
+
+/// One header line's worth of text: CR/LF and other control characters would
+/// let a title or attendee name start headers of its own
+fn header_value(text: &str) -> String {
+    text.split(|c: char| c.is_control() || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Unsent draft: mail clients open it ready to edit and send
+fn eml_message(to: &[String], subject: &str, body: &str) -> String {
+    let subject = header_value(subject);
+    // Non-ASCII subjects need RFC 2047 encoding
+    let subject = if subject.is_ascii() {
+        subject
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(subject))
+    };
+    let mut headers = vec![
+        format!("Subject: {}", subject),
+        format!("Date: {}", Utc::now().to_rfc2822()),
+        "X-Unsent: 1".to_string(),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=UTF-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    if !to.is_empty() {
+        let to: Vec<String> = to.iter().map(|a| header_value(a)).collect();
+        headers.insert(0, format!("To: {}", to.join(", ")));
+    }
+    format!("{}\r\n\r\n{}", headers.join("\r\n"), body.replace('\n', "\r\n"))
+}
+
+async fn draft(app: &AppHandle, session_id: &str) -> Result<FollowupEmail, String> {
+    let manager = SessionManager::new()?;
+    let mut session = manager.load_session(session_id)?;
+    if session.summary.is_none() {
+        info!("[FOLLOWUP] No summary yet for {}, generating one first", session_id);
+        summarizer::summarize_session(app.clone(), session_id.to_string()).await?;
+        session = manager.load_session(session_id)?;
+    }
+    let summary = session.summary.as_ref().ok_or("Session has no summary")?;
+
+    let to = recipients(&session);
+    let attendees = session.calendar_event.as_ref()
+        .map(|e| e.attendees.join(", "))
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    let user_text = format!(
+        "MEETING: {}\nDATE: {}\nATTENDEES: {}\n\nSUMMARY:\n{}",
+        session.metadata.title,
+        meeting_date(&session),
+        attendees,
+        serde_json::to_string_pretty(summary).map_err(|e| e.to_string())?,
+    );
+
+    let config = app.state::<GeminiState>().request_config(app)?;
+    let text = call_gemini(&config, FOLLOWUP_PROMPT, &user_text)
+        .await?
+        .ok_or("Empty response from model")?;
+    let mut response: FollowupResponse = serde_json::from_str(extract_json(&text))
+        .map_err(|e| format!("Invalid follow-up email JSON: {}", e))?;
+    if response.subject.trim().is_empty() {
+        response.subject = format!("Follow-up: {}", session.metadata.title);
+    }
+
+    let body = render_body(&response);
+    let eml = eml_message(&to, &response.subject, &body);
+    let eml_path = manager.write_export(session_id, "eml", &eml)?;
+    info!("[FOLLOWUP] ✓ Drafted follow-up for {} ({} recipient(s))", session_id, to.len());
+
+    Ok(FollowupEmail {
+        session_id: session_id.to_string(),
+        mailto: mailto_link(&to, &response.subject, &body),
+        to,
+        subject: response.subject,
+        greeting: response.greeting,
+        summary: response.summary,
+        decisions: response.decisions,
+        action_items: response.action_items,
+        closing: response.closing,
+        body,
+        eml,
+        eml_path,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Recap email for a stored session, as fields plus mailto: and .eml forms
+#[tauri::command]
+pub async fn draft_followup_email(app: AppHandle, session_id: String) -> Result<FollowupEmail, String> {
+    draft(&app, &session_id).await
+}
@@ -1,37 +1,201 @@
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex as StdMutex};
-use tauri::{AppHandle, Emitter, Manager};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 use tokio::time::{Duration, interval, timeout, Instant, sleep};
-use crossbeam_channel::Receiver;
-use crate::whisper_client::{WhisperState, transcribe_audio};
-use crate::audio_capture::{TaggedAudio, AudioSource};
+use tracing::{debug, info, warn};
+use crate::whisper_client::{WhisperState, transcribe_audio, LOW_CONFIDENCE};
+use crate::audio_capture::{AudioState, AudioSource, TARGET_SAMPLE_RATE};
+use crate::audio_channel::{AudioReceiver, BackpressureWatch};
+use crate::dedupe;
+use crate::denoise::Denoiser;
+use crate::echo::EchoCanceller;
+use crate::events::{self, ApiErrorEvent, AudioLevelEvent, IntelligenceEvent, PipelineState, RateLimitEvent, TranscriptionEvent, TranscriptionSource};
+use crate::hallucination::discard_if_hallucinated;
+use crate::inflight::InFlight;
+use crate::levels::normalize_segment;
+use crate::live_session::{record_segment, LiveSessionState, RecentSegment};
+use crate::metrics::MetricsState;
+use crate::action_items;
+use crate::batching::{call_gemini_batch, LiveSegment, SegmentBatch};
+use crate::alerts;
+use crate::settings::{AppSettings, SettingsState};
+use crate::network::NetworkState;
+use crate::plugins::{self, PluginInput};
+use crate::redaction::Redactor;
+use crate::response_cache;
+use crate::recorder::RecorderState;
+use crate::retry_queue::{PendingSegment, RetryQueueState};
+use crate::schema;
+use crate::session_manager::dispatch_webhook;
+use crate::simulation::{self, MockProvider};
+use crate::speakers::SpeakerState;
+use crate::translation::{self, CaptionSegment};
+use crate::voice_commands;
 
 // ============================================================================
 // GEMINI CLIENT - Text-Only Intelligence Extraction (Post-Whisper)
 // ============================================================================
 
-const GEMINI_REST_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+pub(crate) const GEMINI_REST_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
 
-// RATE LIMITING CONFIG
-const MIN_REQUEST_INTERVAL_SECS: u64 = 1;      // Minimum 1 second between text requests (faster than audio)
+// RATE LIMITING CONFIG (defaults suit the free tier; see RateLimitConfig)
+const MIN_REQUEST_INTERVAL_MS: u64 = 1000;     // Minimum 1 second between text requests (faster than audio)
 const INITIAL_BACKOFF_SECS: u64 = 3;           // Start with 3 second backoff
 const MAX_BACKOFF_SECS: u64 = 60;              // Max 60 second backoff
+const DEFAULT_CONCURRENT_REQUESTS: usize = 2;
+const MAX_CONCURRENT_REQUESTS: usize = 8;
 const RATE_LIMIT_CODES: [&str; 3] = ["429", "RESOURCE_EXHAUSTED", "rate"];
 
 // AUDIO SEGMENTATION CONFIG (used before Whisper)
-const MIN_SPEECH_SECS: f32 = 0.5;              // Minimum 0.5s of speech (more sensitive)
-const SILENCE_TIMEOUT_SECS: f32 = 1.5;         // 1.5s silence = end
-const MAX_BATCH_SECS: f32 = 15.0;              // Max 15 seconds per batch
-const SPEECH_THRESHOLD: f32 = 0.0003;          // Very sensitive speech detection
-const SILENCE_THRESHOLD: f32 = 0.0001;         // Silence detection
+pub(crate) const MIN_SPEECH_SECS: f32 = 0.5;   // Minimum 0.5s of speech (more sensitive)
+pub(crate) const SILENCE_TIMEOUT_SECS: f32 = 1.5; // 1.5s silence = end
+pub(crate) const MAX_BATCH_SECS: f32 = 15.0;   // Max 15 seconds per batch
+const FLUSH_GEMINI_TIMEOUT_SECS: u64 = 8;      // When flushing, slower analysis goes to the retry queue
+pub(crate) const SPEECH_THRESHOLD: f32 = 0.0003; // Very sensitive speech detection
+pub(crate) const SILENCE_THRESHOLD: f32 = 0.0001; // Silence detection
+const LEVEL_EVENT_INTERVAL_MS: u64 = 100;       // VU meter update rate
+const MAX_SEGMENT_OVERLAP_MS: u64 = 3000;
+const MAX_OVERLAP_WORDS: usize = 8;            // Longest repeat trimmed when stitching
 
+// CONVERSATION CONTEXT CONFIG
+pub(crate) const DEFAULT_MODEL: &str = "gemini-2.0-flash";
+pub(crate) const DEFAULT_CONTEXT_SEGMENTS: usize = 5;     // Previous segments sent alongside each request
+const MAX_CONTEXT_SEGMENTS: usize = 20;
+
+const MODEL_CACHE_TTL_SECS: u64 = 3600;
 
 pub struct GeminiState {
-    pub audio_rx: StdMutex<Option<Receiver<TaggedAudio>>>,
+    pub audio_rx: StdMutex<Option<AudioReceiver>>,
     pub api_key: StdMutex<Option<String>>,
     pub is_connected: StdMutex<bool>,
     pub selected_model: StdMutex<String>,
+    // Rolling window of recent "[speaker]: text" segments for context
+    pub context_window: StdMutex<VecDeque<String>>,
+    pub context_size: StdMutex<usize>,
+    // ListModels result, refreshed after MODEL_CACHE_TTL_SECS or a key change
+    pub model_cache: StdMutex<Option<(Instant, Vec<ModelInfo>)>>,
+    pub generation_config: StdMutex<GenerationSettings>,
+    pub rate_limit: StdMutex<RateLimitConfig>,
+    // Pacing/backoff shared by every request, so a manual call can't jump a 429 backoff
+    pub(crate) limiter: Arc<Mutex<RateLimiter>>,
+    // Unix ms when the next request may go out, as last announced to the UI
+    pub(crate) next_allowed_at: AtomicU64,
+    // Off-the-record: the loop keeps draining audio but discards it
+    pub is_paused: StdMutex<bool>,
+    // Candidate speaker names from the linked calendar event
+    pub participants: StdMutex<Vec<String>>,
+    // PII masking applied to every outgoing request body
+    pub redactor: StdMutex<Arc<Redactor>>,
+    // Ask the audio loop to process its buffer now (and optionally exit)
+    pub(crate) flush_requested: AtomicBool,
+    pub(crate) stop_after_flush: AtomicBool,
+    pub(crate) flushed: tokio::sync::Notify,
+}
+
+/// Sampling and safety parameters applied to every generateContent request
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GenerationSettings {
+    pub temperature: f32,
+    pub top_p: Option<f32>,
+    pub max_output_tokens: u32,
+    pub safety_settings: Vec<SafetySetting>,
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            temperature: 0.3,
+            top_p: None,
+            max_output_tokens: 1024,
+            safety_settings: Vec::new(),
+        }
+    }
+}
+
+/// Request pacing and 429 backoff. Paid tiers can go much faster than the defaults.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub min_interval_ms: u64,
+    pub initial_backoff_secs: u64,
+    pub max_backoff_secs: u64,
+    // Live intelligence requests in flight at once; all share the pacing above
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: MIN_REQUEST_INTERVAL_MS,
+            initial_backoff_secs: INITIAL_BACKOFF_SECS,
+            max_backoff_secs: MAX_BACKOFF_SECS,
+            max_concurrent_requests: DEFAULT_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SafetySetting {
+    pub category: String,   // e.g. HARM_CATEGORY_HARASSMENT
+    pub threshold: String,  // e.g. BLOCK_ONLY_HIGH
+}
+
+const SAFETY_THRESHOLDS: &[&str] = &[
+    "HARM_BLOCK_THRESHOLD_UNSPECIFIED", "BLOCK_LOW_AND_ABOVE", "BLOCK_MEDIUM_AND_ABOVE",
+    "BLOCK_ONLY_HIGH", "BLOCK_NONE", "OFF",
+];
+
+/// When the last request went out and how long to back off before the next
+#[derive(Default)]
+pub(crate) struct RateLimiter {
+    backoff: u64,
+    last_request: Option<Instant>,
+}
+
+/// Snapshot of everything a single request needs from GeminiState
+#[derive(Clone)]
+pub(crate) struct RequestConfig {
+    // None outside the Tauri app (CLI): no metrics or rate-limit events
+    pub app: Option<AppHandle>,
+    pub client: reqwest::Client,
+    pub key: String,
+    pub model: String,
+    pub generation: GenerationSettings,
+    pub participants: Vec<String>,
+    pub redactor: Arc<Redactor>,
+    pub rate_limit: RateLimitConfig,
+    pub limiter: Arc<Mutex<RateLimiter>>,
+    // User output schema, enforced on intelligence requests only
+    pub intelligence_schema: Option<Arc<serde_json::Value>>,
+}
+
+impl RequestConfig {
+    /// Requests without a running app, using default generation and rate limits
+    pub(crate) fn standalone(client: reqwest::Client, key: String, model: String, redactor: Redactor) -> Self {
+        Self {
+            app: None,
+            client,
+            key,
+            model,
+            generation: GenerationSettings::default(),
+            participants: Vec::new(),
+            redactor: Arc::new(redactor),
+            rate_limit: RateLimitConfig::default(),
+            limiter: Arc::new(Mutex::new(RateLimiter::default())),
+            intelligence_schema: None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: String,
+    pub input_token_limit: Option<u32>,
+    pub output_token_limit: Option<u32>,
 }
 
 impl Default for GeminiState {
@@ -40,7 +204,103 @@ impl Default for GeminiState {
             audio_rx: StdMutex::new(None),
             api_key: StdMutex::new(None),
             is_connected: StdMutex::new(false),
-            selected_model: StdMutex::new("gemini-2.0-flash".to_string()),
+            selected_model: StdMutex::new(DEFAULT_MODEL.to_string()),
+            context_window: StdMutex::new(VecDeque::new()),
+            context_size: StdMutex::new(DEFAULT_CONTEXT_SEGMENTS),
+            model_cache: StdMutex::new(None),
+            generation_config: StdMutex::new(GenerationSettings::default()),
+            rate_limit: StdMutex::new(RateLimitConfig::default()),
+            limiter: Arc::new(Mutex::new(RateLimiter::default())),
+            next_allowed_at: AtomicU64::new(0),
+            is_paused: StdMutex::new(false),
+            participants: StdMutex::new(Vec::new()),
+            redactor: StdMutex::new(Arc::new(Redactor::default())),
+            flush_requested: AtomicBool::new(false),
+            stop_after_flush: AtomicBool::new(false),
+            flushed: tokio::sync::Notify::new(),
+        }
+    }
+}
+
+impl GeminiState {
+    pub(crate) fn request_config(&self, app: &AppHandle) -> Result<RequestConfig, String> {
+        let client = app.state::<NetworkState>().client()?;
+        let key = self.api_key.lock().unwrap().clone()
+            .filter(|k| !k.is_empty())
+            .ok_or("No API key configured")?;
+        Ok(RequestConfig {
+            app: Some(app.clone()),
+            client,
+            key,
+            model: self.selected_model.lock().unwrap().clone(),
+            generation: self.generation_config.lock().unwrap().clone(),
+            participants: self.participants.lock().unwrap().clone(),
+            redactor: self.redactor.lock().unwrap().clone(),
+            rate_limit: *self.rate_limit.lock().unwrap(),
+            limiter: self.limiter.clone(),
+            intelligence_schema: app.state::<SettingsState>().get().intelligence_schema.map(Arc::new),
+        })
+    }
+
+    pub(crate) fn set_redactor(&self, redactor: Redactor) {
+        *self.redactor.lock().unwrap() = Arc::new(redactor);
+    }
+
+    pub(crate) fn redact(&self, text: &str) -> String {
+        let redactor = self.redactor.lock().unwrap().clone();
+        redactor.redact(text, &self.participants.lock().unwrap())
+    }
+
+    /// Flip pause state; the audio loop announces the change. Returns the new state.
+    pub fn toggle_pause(&self) -> bool {
+        let mut paused = self.is_paused.lock().unwrap();
+        *paused = !*paused;
+        *paused
+    }
+
+    /// The loop owns the receiver while it runs
+    pub fn audio_loop_running(&self) -> bool {
+        self.audio_rx.lock().unwrap().is_none()
+    }
+
+    /// Have the audio loop process whatever speech is buffered, waiting up to
+    /// `limit`. With `stop` the loop exits afterwards. Returns false on timeout.
+    pub async fn flush_audio_loop(&self, stop: bool, limit: Duration) -> bool {
+        if !self.audio_loop_running() {
+            return true;
+        }
+        let notified = self.flushed.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        self.stop_after_flush.store(stop, Ordering::SeqCst);
+        self.flush_requested.store(true, Ordering::SeqCst);
+        timeout(limit, notified).await.is_ok()
+    }
+
+    fn flush_pending(&self) -> bool {
+        self.flush_requested.load(Ordering::SeqCst)
+    }
+
+    /// Called by the loop once the buffer is drained. Returns true if it should exit.
+    fn finish_flush(&self) -> bool {
+        self.flush_requested.store(false, Ordering::SeqCst);
+        let stop = self.stop_after_flush.swap(false, Ordering::SeqCst);
+        self.flushed.notify_waiters();
+        stop
+    }
+
+    /// Snapshot of the rolling context, oldest first
+    pub fn context_snapshot(&self) -> Vec<String> {
+        self.context_window.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Record an analyzed segment, evicting the oldest beyond the window size
+    pub fn push_context(&self, segment: String) {
+        let size = *self.context_size.lock().unwrap();
+        let mut window = self.context_window.lock().unwrap();
+        window.push_back(segment);
+        while window.len() > size {
+            window.pop_front();
         }
     }
 }
@@ -57,12 +317,49 @@ RULES:
 - JSON only, no markdown
 - CRITICAL: Keep the speaker tag exactly as provided in the input (e.g. "You" or "Speaker 2"). Do NOT reassign or change the speaker.
 - tone: NEUTRAL|URGENT|FRUSTRATED|EXCITED|POSITIVE|NEGATIVE|HESITANT|DOMINANT|EMPATHETIC
-- category: TASK|DECISION|DEADLINE|QUERY|ACTION_ITEM|RISK|SENTIMENT|URGENCY|INTERRUPTION|AGREEMENT|DISAGREEMENT|OFF_TOPIC|EMOTION_SHIFT|DOMINANCE_SHIFT|EMPATHY_GAP|TOPIC_DRIFT
+- category: {categories}
 - confidence: 0.0-1.0
 - entities: Extract ALL people, projects, topics, organizations, locations, dates mentioned
 - graph_edges: Create relationships between entities. E.g. {"from":"John","to":"Project X","relation":"works on"}, {"from":"You","to":"deadline","relation":"mentioned"}
 - Always include at least one graph_edge connecting the speaker to the main topic
-- For low-confidence or unclear: lower confidence value, not error"#;
+- PREVIOUS CONTEXT, when present, is only there to interpret short replies ("yes, let's do that"). Analyze and categorize ONLY the CURRENT SEGMENT
+- For low-confidence or unclear: lower confidence value, not error
+- A segment marked (low STT confidence) may contain misheard words; interpret it cautiously and lower confidence"#;
+
+/// System prompt for intelligence extraction: the user's custom prompt (or the
+/// built-in one) with `{categories}` filled from the configured taxonomy. An
+/// active meeting template supplies its own categories and guidance; a custom
+/// output schema is appended last.
+pub fn build_intelligence_prompt(settings: &AppSettings) -> String {
+    let categories = settings.active_categories().join("|");
+    let meeting = settings.active_template();
+    let template = meeting.and_then(|t| t.prompt.as_deref())
+        .or(settings.intelligence_prompt.as_deref())
+        .unwrap_or(COGNIVOX_INTELLIGENCE_PROMPT);
+    
+    let prompt = if template.contains("{categories}") {
+        template.replace("{categories}", &categories)
+    } else {
+        format!("{}\n- category: {}", template, categories)
+    };
+    let prompt = match meeting.filter(|t| !t.guidance.is_empty()) {
+        Some(t) => format!("{}\n\nMEETING TYPE: {}\n{}", prompt, t.name, t.guidance),
+        None => prompt,
+    };
+    match &settings.intelligence_schema {
+        Some(output_schema) => format!("{}\n\n{}", prompt, schema::prompt_section(output_schema)),
+        None => prompt,
+    }
+}
+
+/// "[speaker]: text", flagged when Whisper wasn't sure of what it heard
+pub(crate) fn annotate_segment(speaker: &str, text: &str, stt_confidence: f32) -> String {
+    if stt_confidence < LOW_CONFIDENCE {
+        format!("[{}] (low STT confidence): {}", speaker, text)
+    } else {
+        format!("[{}]: {}", speaker, text)
+    }
+}
 
 // ============================================================================
 // Structs
@@ -73,6 +370,8 @@ struct RestRequest {
     contents: Vec<Content>,
     system_instruction: Option<SystemInstruction>,
     generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    safety_settings: Vec<SafetySetting>,
 }
 
 #[derive(Serialize)]
@@ -91,7 +390,16 @@ struct SystemInstruction { parts: Vec<TextPart> }
 struct TextPart { text: String }
 
 #[derive(Serialize)]
-struct GenerationConfig { temperature: f32, max_output_tokens: i32 }
+struct GenerationConfig {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    max_output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+}
 
 #[derive(Deserialize, Debug)]
 struct RestResponse {
@@ -115,55 +423,168 @@ struct ApiError { message: Option<String>, code: Option<i32> }
 // Audio Helpers (Segmentation)
 // ============================================================================
 
-fn rms(samples: &[f32]) -> f32 {
+pub(crate) fn rms(samples: &[f32]) -> f32 {
     if samples.is_empty() { return 0.0; }
     (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
 }
 
+fn overlap_samples(overlap_ms: u64) -> usize {
+    (overlap_ms.min(MAX_SEGMENT_OVERLAP_MS) * TARGET_SAMPLE_RATE as u64 / 1000) as usize
+}
+
+/// Drop the words at the start of `next` that repeat the end of `previous`,
+/// i.e. what both transcripts heard in the carried-over audio
+pub(crate) fn stitch_overlap(previous: &str, next: &str) -> String {
+    let normalize = |w: &str| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    let tail: Vec<String> = previous.split_whitespace().map(normalize).collect();
+    let words: Vec<&str> = next.split_whitespace().collect();
+    let longest = MAX_OVERLAP_WORDS.min(tail.len()).min(words.len());
+    let repeated = (1..=longest).rev()
+        .find(|&k| tail[tail.len() - k..].iter().zip(&words[..k]).all(|(a, b)| *a == normalize(b)))
+        .unwrap_or(0);
+    if repeated > 0 {
+        debug!("[AUDIO] Stitched overlap: dropped {} repeated word(s)", repeated);
+    }
+    words[repeated..].join(" ")
+}
+
+/// Split a whole recording into speech segments with the live loop's thresholds.
+/// Returns (start sample, samples, continues previous) at TARGET_SAMPLE_RATE;
+/// a segment cut mid-speech hands its last `overlap_ms` to the next one.
+pub(crate) fn segment_recording(samples: &[f32], overlap_ms: u64) -> Vec<(usize, Vec<f32>, bool)> {
+    let rate = TARGET_SAMPLE_RATE as f32;
+    let frame_len = TARGET_SAMPLE_RATE as usize / 20;   // One 50ms loop tick
+    let overlap = overlap_samples(overlap_ms);
+    let mut segments = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut continues = false;
+    let mut last_speech = 0;
+
+    for (i, frame) in samples.chunks(frame_len).enumerate() {
+        let end = i * frame_len + frame.len();
+        let level = rms(frame);
+        match start {
+            None if level > SPEECH_THRESHOLD => {
+                start = Some(i * frame_len);
+                last_speech = end;
+            }
+            None => continue,
+            Some(_) if level > SILENCE_THRESHOLD => last_speech = end,
+            Some(_) => {}
+        }
+
+        let Some(s) = start else { continue };
+        let duration = (end - s) as f32 / rate;
+        let silence = (end - last_speech) as f32 / rate;
+        if (duration >= MIN_SPEECH_SECS && silence >= SILENCE_TIMEOUT_SECS) || duration >= MAX_BATCH_SECS {
+            segments.push((s, samples[s..end].to_vec(), continues));
+            // Cut mid-speech: start the next segment early so the cut word is heard whole
+            continues = silence < SILENCE_TIMEOUT_SECS;
+            start = continues.then(|| end.saturating_sub(overlap).max(s));
+        }
+    }
+    if let Some(s) = start {
+        segments.push((s, samples[s..].to_vec(), continues));
+    }
+    segments.retain(|(_, segment, _)| segment.len() as f32 / rate >= MIN_SPEECH_SECS);
+    segments
+}
+
 // ============================================================================
 // Text-Only API Call with Rate Limiting
 // ============================================================================
 
-async fn call_gemini_with_text(
-    key: &str,
-    model: &str,
+pub(crate) async fn call_gemini_with_text(
+    config: &RequestConfig,
+    system_prompt: &str,
     transcript: &str,
-    backoff: &mut u64,
-    last_request: &mut Instant,
+    context: &[String],
 ) -> Result<String, String> {
-    // Enforce minimum interval
-    let elapsed = last_request.elapsed();
-    let min_interval = Duration::from_secs(MIN_REQUEST_INTERVAL_SECS);
-    if elapsed < min_interval {
-        let wait = min_interval - elapsed;
-        println!("[GEMINI] Rate limit: waiting {:.1}s", wait.as_secs_f32());
-        sleep(wait).await;
+    let user_text = if context.is_empty() {
+        format!("Analyze this meeting transcript:\n\n{}", transcript)
+    } else {
+        format!(
+            "PREVIOUS CONTEXT (do not analyze):\n{}\n\nCURRENT SEGMENT (analyze this):\n{}",
+            context.join("\n"),
+            transcript
+        )
+    };
+    // Attendees help Gemini put names to "Speaker 2" and action item owners
+    let user_text = if config.participants.is_empty() {
+        user_text
+    } else {
+        format!("MEETING PARTICIPANTS: {}\n\n{}", config.participants.join(", "), user_text)
+    };
+    // Identical requests (re-imported files, retried segments) aren't billed twice
+    let output_schema = config.intelligence_schema.as_deref();
+    let cache_key = response_cache::key(&config.model, &config.generation, output_schema, system_prompt, &user_text);
+    let cached = response_cache::get(&cache_key);
+    if let Some(app) = &config.app {
+        app.state::<MetricsState>().record_cache_lookup(cached.is_some());
     }
-    
-    // Apply backoff if we had errors
-    if *backoff > 0 {
-        println!("[GEMINI] Backoff: waiting {}s", backoff);
-        sleep(Duration::from_secs(*backoff)).await;
+    if let Some(cached) = cached {
+        debug!("[GEMINI] Cache hit {}", &cache_key[..12]);
+        return Ok(cached);
+    }
+
+    let response = request_content(config, system_prompt, &user_text, output_schema).await?;
+    if let (Some(text), Some(output_schema)) = (&response, output_schema) {
+        let parsed: serde_json::Value = serde_json::from_str(text.trim())
+            .map_err(|e| format!("Response is not valid JSON: {}", e))?;
+        schema::validate(output_schema, &parsed)
+            .map_err(|e| format!("Response doesn't match the intelligence schema: {}", e))?;
+    }
+    if let Some(text) = &response {
+        response_cache::put(&cache_key, text);
     }
     
-    *last_request = Instant::now();
+    // Parsed OK but couldn't extract text - return a fallback JSON
+    Ok(response.unwrap_or_else(|| "{\"transcript\":\"\",\"tone\":\"NEUTRAL\",\"category\":[\"INFO\"],\"confidence\":0.3}".to_string()))
+}
+
+/// Single generateContent request with rate limiting. Returns `None` when the
+/// response parsed but carried no text.
+pub(crate) async fn call_gemini(
+    config: &RequestConfig,
+    system_prompt: &str,
+    user_text: &str,
+) -> Result<Option<String>, String> {
+    request_content(config, system_prompt, user_text, None).await
+}
+
+/// call_gemini, constrained to JSON matching `response_schema` when given
+async fn request_content(
+    config: &RequestConfig,
+    system_prompt: &str,
+    user_text: &str,
+    response_schema: Option<&serde_json::Value>,
+) -> Result<Option<String>, String> {
+    wait_for_slot(config).await;
     
+    // Local copies keep the original; only the upload is masked
+    let user_text = config.redactor.redact(user_text, &config.participants);
     let request = RestRequest {
         contents: vec![Content {
             parts: vec![
-                Part { text: Some(format!("Analyze this meeting transcript:\n\n{}", transcript)) },
+                Part { text: Some(user_text) },
             ],
         }],
         system_instruction: Some(SystemInstruction {
-            parts: vec![TextPart { text: COGNIVOX_INTELLIGENCE_PROMPT.into() }],
+            parts: vec![TextPart { text: system_prompt.into() }],
         }),
-        generation_config: GenerationConfig { temperature: 0.3, max_output_tokens: 1024 },
+        generation_config: GenerationConfig {
+            temperature: config.generation.temperature,
+            top_p: config.generation.top_p,
+            max_output_tokens: config.generation.max_output_tokens,
+            response_mime_type: response_schema.map(|_| "application/json"),
+            response_schema: response_schema.cloned(),
+        },
+        safety_settings: config.generation.safety_settings.clone(),
     };
     
-    let url = format!("{}/{}:generateContent?key={}", GEMINI_REST_URL, model, key);
+    let url = format!("{}/{}:generateContent?key={}", GEMINI_REST_URL, config.model, config.key);
     
-    let client = reqwest::Client::new();
-    let response = client.post(&url)
+    let response = config.client.post(&url)
         .json(&request)
         .timeout(Duration::from_secs(30))
         .send()
@@ -177,16 +598,11 @@ async fn call_gemini_with_text(
     let is_rate_limited = status.as_u16() == 429 
         || RATE_LIMIT_CODES.iter().any(|code| text.contains(code));
     
+    let backoff = record_rate_limit(config, is_rate_limited).await;
     if is_rate_limited {
-        // Exponential backoff
-        *backoff = (*backoff * 2).max(INITIAL_BACKOFF_SECS).min(MAX_BACKOFF_SECS);
-        println!("[GEMINI] ⚠️ Rate limited! Backoff now: {}s", backoff);
         return Err(format!("Rate limited. Waiting {}s before retry.", backoff));
     }
     
-    // Success - reset backoff
-    *backoff = 0;
-    
     // Parse response
     if let Ok(resp) = serde_json::from_str::<RestResponse>(&text) {
         if let Some(error) = resp.error {
@@ -197,20 +613,80 @@ async fn call_gemini_with_text(
                 if let Some(parts) = content.parts {
                     if let Some(part) = parts.into_iter().next() {
                         if let Some(t) = part.text {
-                            return Ok(t);
+                            return Ok(Some(t));
                         }
                     }
                 }
             }
         }
-        // Parsed OK but couldn't extract text - return a fallback JSON
-        return Ok(format!("{{\"transcript\":\"\",\"tone\":\"NEUTRAL\",\"category\":[\"INFO\"],\"confidence\":0.3}}"));
+        return Ok(None);
     }
     
     // Could not parse response at all - return error
     Err(format!("Failed to parse API response: {}", if text.len() > 200 { &text[..200] } else { &text }))
 }
 
+/// Wait out the minimum interval and any backoff, then claim the next slot.
+/// The lock is held while waiting, so concurrent callers queue up behind it.
+pub(crate) async fn wait_for_slot(config: &RequestConfig) {
+    let mut limiter = config.limiter.lock().await;
+    
+    // Enforce minimum interval
+    let min_interval = Duration::from_millis(config.rate_limit.min_interval_ms);
+    if let Some(elapsed) = limiter.last_request.map(|t| t.elapsed()) {
+        if elapsed < min_interval {
+            let wait = min_interval - elapsed;
+            info!("[GEMINI] Rate limit: waiting {:.1}s", wait.as_secs_f32());
+            sleep(wait).await;
+        }
+    }
+    
+    // Apply backoff if we had errors
+    if limiter.backoff > 0 {
+        info!("[GEMINI] Backoff: waiting {}s", limiter.backoff);
+        sleep(Duration::from_secs(limiter.backoff)).await;
+    }
+    
+    limiter.last_request = Some(Instant::now());
+}
+
+/// Grow the backoff after a 429, reset it after anything else. Returns the new backoff.
+pub(crate) async fn record_rate_limit(config: &RequestConfig, rate_limited: bool) -> u64 {
+    let limits = config.rate_limit;
+    let mut limiter = config.limiter.lock().await;
+    if rate_limited {
+        // Exponential backoff
+        limiter.backoff = (limiter.backoff * 2).max(limits.initial_backoff_secs).min(limits.max_backoff_secs);
+        warn!("[GEMINI] ⚠️ Rate limited! Backoff now: {}s", limiter.backoff);
+        emit_rate_limit(config, limiter.backoff);
+    } else if limiter.backoff > 0 {
+        limiter.backoff = 0;
+        emit_rate_limit(config, 0);
+    }
+    limiter.backoff
+}
+
+/// Tell the UI the current backoff and when the next request may go out
+fn emit_rate_limit(config: &RequestConfig, backoff_secs: u64) {
+    let wait_ms = config.rate_limit.min_interval_ms + backoff_secs * 1000;
+    let next_allowed_at = events::now_ms() + wait_ms;
+    let Some(app) = &config.app else { return; };
+    app.state::<GeminiState>().next_allowed_at.store(next_allowed_at, Ordering::SeqCst);
+    events::emit(app, &RateLimitEvent {
+        backoff_secs,
+        next_allowed_at,
+        min_interval_ms: config.rate_limit.min_interval_ms,
+    });
+}
+
+/// Strip markdown fences / chatter around the first JSON object in a response
+pub(crate) fn extract_json(text: &str) -> &str {
+    match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if end > start => &text[start..=end],
+        _ => text.trim(),
+    }
+}
+
 // ============================================================================
 // Main Connection
 // ============================================================================
@@ -222,37 +698,37 @@ pub async fn test_gemini_connection(
     key: String,
     model: Option<String>,
 ) -> Result<String, String> {
-    *state.api_key.lock().unwrap() = Some(key.clone());
+    let previous_key = state.api_key.lock().unwrap().replace(key.clone());
+    if previous_key.as_deref() != Some(key.as_str()) {
+        *state.model_cache.lock().unwrap() = None;
+    }
     
     let m = model.unwrap_or_else(|| state.selected_model.lock().unwrap().clone());
     *state.selected_model.lock().unwrap() = m.clone();
     
-    println!("========================================");
-    println!("[GEMINI] Model: {}", m);
-    println!("[GEMINI] Rate limits: {}s min interval, {}s initial backoff", 
-             MIN_REQUEST_INTERVAL_SECS, INITIAL_BACKOFF_SECS);
-    println!("========================================");
-    
-    let _ = app.emit("cognivox:status", "Testing...");
-    
-    // ALWAYS start audio processing loop first (before test), so it's ready
-    // even if the connection test fails due to rate limiting etc.
-    let audio_rx = state.audio_rx.lock().unwrap().take();
-    if let Some(rx) = audio_rx {
-        println!("[GEMINI] Starting audio processing loop...");
-        let app_clone = app.clone();
-        tokio::spawn(async move {
-            smart_audio_loop(rx, app_clone).await;
-        });
-    } else {
-        println!("[GEMINI] Audio loop already running (rx already taken)");
-    }
+    debug!("========================================");
+    info!("[GEMINI] Model: {}", m);
+    let limits = *state.rate_limit.lock().unwrap();
+    info!("[GEMINI] Rate limits: {}ms min interval, {}s initial backoff", 
+             limits.min_interval_ms, limits.initial_backoff_secs);
+    debug!("========================================");
     
-    // Quick test
+    // Rejected up front (e.g. local-only mode): report it instead of leaving "Testing..." up
+    let config = match state.request_config(&app) {
+        Ok(config) => config,
+        Err(e) => {
+            *state.is_connected.lock().unwrap() = false;
+            events::emit_status(&app, PipelineState::Error, format!("Test failed: {}", e));
+            return Err(e);
+        }
+    };
+    events::emit_status(&app, PipelineState::Connecting, "Testing...");
+
+    // Quick test, queued behind the pipeline's requests like any other
     let url = format!("{}/{}:generateContent?key={}", GEMINI_REST_URL, m, key);
-    let client = reqwest::Client::new();
+    wait_for_slot(&config).await;
     
-    let test_result = match client.post(&url)
+    let test_result = match config.client.post(&url)
         .json(&serde_json::json!({"contents":[{"parts":[{"text":"OK"}]}]}))
         .timeout(Duration::from_secs(10))
         .send().await 
@@ -260,92 +736,109 @@ pub async fn test_gemini_connection(
         Ok(r) => {
             let status = r.status();
             let _t = r.text().await.unwrap_or_default();
+            record_rate_limit(&config, status.as_u16() == 429).await;
             
              if status.as_u16() == 429 {
-                println!("[GEMINI] Rate limited (429) - audio loop still running");
-                let _ = app.emit("cognivox:status", "Rate limited - will retry on speech");
+                warn!("[GEMINI] Connection test rate limited (429)");
+                events::emit_status(&app, PipelineState::RateLimited, "Rate limited");
                 Err("Rate limited".to_string())
             } else if status.as_u16() == 403 {
-                println!("[GEMINI] Quota exhausted (403) - audio loop still running");
-                let _ = app.emit("cognivox:status", "Quota exhausted - will retry on speech");
-                Err("Quota exhausted".to_string())
+                warn!("[GEMINI] Connection test refused (403) - key invalid or quota exhausted");
+                events::emit_status(&app, PipelineState::Error, "Key rejected or quota exhausted");
+                Err("Key rejected or quota exhausted (HTTP 403)".to_string())
             } else if !status.is_success() {
-                println!("[GEMINI] HTTP error: {} - audio loop still running", status);
-                let _ = app.emit("cognivox:status", format!("HTTP {} - will retry", status));
+                warn!("[GEMINI] Connection test HTTP error: {}", status);
+                events::emit_status(&app, PipelineState::Error, format!("Test failed: HTTP {}", status));
                 Err(format!("HTTP {}", status))
             } else {
-                // Success - connected
-                println!("[GEMINI] Connection test passed");
-                *state.is_connected.lock().unwrap() = true;
-                let _ = app.emit("cognivox:status", "Connected ✓");
+                info!("[GEMINI] Connection test passed");
+                events::emit_status(&app, PipelineState::Ready, "Connected ✓");
                 Ok(())
             }
         }
         Err(e) => {
-            println!("[GEMINI] Connection test failed: {} - audio loop still running", e);
-            let _ = app.emit("cognivox:status", format!("Test failed: {} - will retry", e));
+            warn!("[GEMINI] Connection test failed: {}", e);
+            events::emit_status(&app, PipelineState::Error, format!("Test failed: {}", e));
             Err(e.to_string())
         }
     };
-    
-    // Return success even if test failed - the audio loop is running
-    // and will handle rate limiting/retries internally
-    match test_result {
-        Ok(()) => Ok(format!("Connected to {}", m)),
-        Err(e) => {
-            // Mark as connected anyway - the loop will handle errors gracefully
-            *state.is_connected.lock().unwrap() = true;
-            Ok(format!("Connected to {} (test: {})", m, e))
-        }
-    }
+
+    *state.is_connected.lock().unwrap() = test_result.is_ok();
+    test_result
+        .map(|()| format!("Connected to {}", m))
+        .map_err(|e| format!("Connection test failed for {}: {}", m, e))
 }
 
 // ============================================================================
 // Tauri Command: Process Whisper Transcript with Gemini
 // ============================================================================
 
+/// The intelligence JSON, or None when a plugin dropped the result
 #[tauri::command]
 pub async fn process_transcript_with_gemini(
     state: tauri::State<'_, GeminiState>,
     app: AppHandle,
     transcript: String,
     speaker: Option<String>,
-) -> Result<String, String> {
-    let key = state.api_key.lock().unwrap().clone()
-        .ok_or("No API key configured")?;
-    
-    let model = state.selected_model.lock().unwrap().clone();
+) -> Result<Option<String>, String> {
+    let config = state.request_config(&app)?;
     
-    println!("[GEMINI] Processing Whisper transcript: '{}'", 
+    info!("[GEMINI] Processing Whisper transcript: '{}'", 
              if transcript.len() > 100 { &transcript[..100] } else { &transcript });
     
-    let _ = app.emit("cognivox:status", "Extracting intelligence from transcript...");
+    events::emit_status(&app, PipelineState::Analyzing, "Extracting intelligence from transcript...");
     
-    let mut backoff: u64 = 0;
-    let mut last_request = Instant::now() - Duration::from_secs(MIN_REQUEST_INTERVAL_SECS);
+    let context = state.context_snapshot();
+    let annotated = match &speaker {
+        Some(tag) => format!("[{}]: {}", tag, transcript),
+        None => transcript.clone(),
+    };
     
-    match call_gemini_with_text(&key, &model, &transcript, &mut backoff, &mut last_request).await {
+    let system_prompt = build_intelligence_prompt(&app.state::<SettingsState>().get());
+    
+    let result = call_gemini_with_text(&config, &system_prompt, &annotated, &context).await;
+    app.state::<MetricsState>().record_gemini_result(result.is_ok());
+    match result {
         Ok(response) => {
-            println!("[GEMINI] ✓ Intelligence extracted");
-            let _ = app.emit("cognivox:gemini_intelligence", serde_json::json!({
-                "transcript": transcript,
-                "speaker": speaker,
-                "intelligence": response,
-                "timestamp": std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis()
-            }));
-            let _ = app.emit("cognivox:status", "Ready");
-            Ok(response)
+            state.push_context(annotated);
+            info!("[GEMINI] ✓ Intelligence extracted");
+            let input = PluginInput {
+                segment_id: None,
+                session_id: None,
+                transcript: &transcript,
+                speaker: speaker.as_deref().unwrap_or("Speaker"),
+                start_ms: None,
+                end_ms: None,
+                language: None,
+                retried: false,
+            };
+            let Some(response) = plugins::apply(&app, &input, response) else {
+                events::emit_status(&app, PipelineState::Ready, "Ready");
+                return Ok(None);
+            };
+            let event = IntelligenceEvent {
+                segment_id: None,
+                session_id: None,
+                transcript: transcript.clone(),
+                speaker: speaker.clone(),
+                intelligence: Some(response.clone()),
+                pending: false,
+                retried: false,
+                start_ms: None,
+                end_ms: None,
+                timestamp: events::now_ms(),
+            };
+            events::emit(&app, &event);
+            dispatch_webhook(&app, "gemini_intelligence", &events::to_payload(&event));
+            action_items::ingest_intelligence(&app, None, &transcript, speaker.as_deref().unwrap_or("Speaker"), &response, None);
+            alerts::notify_if_urgent(&app, &transcript, speaker.as_deref().unwrap_or("Speaker"), &response);
+            events::emit_status(&app, PipelineState::Ready, "Ready");
+            Ok(Some(response))
         }
         Err(e) => {
-            println!("[GEMINI] ✗ Error: {}", e);
-            let _ = app.emit("cognivox:status", format!("Intelligence extraction error: {}", e));
-            let _ = app.emit("cognivox:api_error", serde_json::json!({
-                "code": if e.contains("429") { 429 } else { 500 },
-                "message": e
-            }));
+            warn!("[GEMINI] ✗ Error: {}", e);
+            events::emit_status(&app, PipelineState::Error, format!("Intelligence extraction error: {}", e));
+            events::emit(&app, &ApiErrorEvent::from_error(e.clone()));
             Err(e)
         }
     }
@@ -354,6 +847,7 @@ pub async fn process_transcript_with_gemini(
 #[tauri::command]
 pub fn update_gemini_key(state: tauri::State<'_, GeminiState>, key: String) -> Result<(), String> {
     *state.api_key.lock().unwrap() = Some(key);
+    *state.model_cache.lock().unwrap() = None;
     Ok(())
 }
 
@@ -361,12 +855,27 @@ pub fn update_gemini_key(state: tauri::State<'_, GeminiState>, key: String) -> R
 // Smart Audio Loop: Audio -> Whisper -> Gemini
 // ============================================================================
 
-async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
-    println!("[WHISPER->GEMINI] Audio processing loop started");
-    println!("[WHISPER->GEMINI] Pipeline: Audio -> Whisper STT -> Gemini Intelligence");
-    println!("[WHISPER->GEMINI] Speaker diarization: Mic=You, System=Speaker 2");
+/// Start the audio loop unless it's already running. Sessions own its lifetime.
+pub fn ensure_audio_loop(app: &AppHandle) {
+    let audio_rx = app.state::<GeminiState>().audio_rx.lock().unwrap().take();
+    match audio_rx {
+        Some(rx) => {
+            info!("[GEMINI] Starting audio processing loop...");
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                smart_audio_loop(rx, app_clone).await;
+            });
+        }
+        None => info!("[GEMINI] Audio loop already running"),
+    }
+}
+
+async fn smart_audio_loop(rx: AudioReceiver, app: AppHandle) {
+    info!("[WHISPER->GEMINI] Audio processing loop started");
+    info!("[WHISPER->GEMINI] Pipeline: Audio -> Whisper STT -> Gemini Intelligence");
+    info!("[WHISPER->GEMINI] Speaker diarization: Mic=You, System=Speaker 2");
     
-    let _ = app.emit("cognivox:status", "Listening for speech...");
+    events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
     
     let mut buffer: Vec<f32> = Vec::new();
     let mut speaking = false;
@@ -380,20 +889,32 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
     let mut mic_sample_count: u64 = 0;
     let mut system_sample_count: u64 = 0;
     
-    // Rate limiting state
-    let mut backoff: u64 = 0;
-    let mut last_request = Instant::now() - Duration::from_secs(MIN_REQUEST_INTERVAL_SECS);
+    let mut batch = SegmentBatch::default();
+    let mut inflight: InFlight<AnalyzedBatch> = InFlight::new();
+    // Transcript of a segment cut mid-speech, to trim from the start of the next
+    let mut carried_text: Option<String> = None;
     let mut request_count = 0u32;
     let mut audio_received_count = 0u64;
     let mut last_level_log = Instant::now();
+    let mut denoiser: Option<Denoiser> = None;
+    let mut echo_canceller: Option<EchoCanceller> = None;
+    let mut last_level_emit = Instant::now();
+    let mut last_audio_at = Instant::now() - Duration::from_secs(1);
+    let mut was_paused = false;
+    // Drops from before this run (no loop draining the channel) aren't reported
+    let mut backpressure = BackpressureWatch::new(&rx.stats());
     
     let mut tick = interval(Duration::from_millis(50)); // More frequent polling
     let mut total_samples_received: u64 = 0;
     
-    println!("[AUDIO] ========================================");
-    println!("[AUDIO] Speech threshold: {}, Silence threshold: {}", SPEECH_THRESHOLD, SILENCE_THRESHOLD);
-    println!("[AUDIO] Min speech: {}s, Silence timeout: {}s", MIN_SPEECH_SECS, SILENCE_TIMEOUT_SECS);
-    println!("[AUDIO] ========================================");
+    // Offsets are counted from the session start; this clock only stands in
+    // for audio that arrives with no session running
+    let loop_clock = Instant::now();
+    
+    debug!("[AUDIO] ========================================");
+    info!("[AUDIO] Speech threshold: {}, Silence threshold: {}", SPEECH_THRESHOLD, SILENCE_THRESHOLD);
+    info!("[AUDIO] Min speech: {}s, Silence timeout: {}s", MIN_SPEECH_SECS, SILENCE_TIMEOUT_SECS);
+    debug!("[AUDIO] ========================================");
     
     loop {
         tick.tick().await;
@@ -401,9 +922,24 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
         if processing { continue; }
         
         // Collect tagged audio
+        backpressure.check(&app, &rx.stats());
+        if app.state::<AudioState>().echo_cancellation_enabled() {
+            echo_canceller.get_or_insert_with(EchoCanceller::new);
+        } else {
+            echo_canceller = None;
+        }
         let mut new: Vec<f32> = Vec::new();
-        while let Ok(tagged) = rx.try_recv() {
-            let source_rms = rms(&tagged.samples) as f64;
+        for tagged in rx.drain() {
+            // Loopback is the echo reference; only the mic copy is removed
+            let samples = match (tagged.source, echo_canceller.as_mut()) {
+                (AudioSource::System, Some(echo)) => {
+                    echo.push_reference(&tagged.samples);
+                    tagged.samples
+                }
+                (AudioSource::Microphone, Some(echo)) => echo.process(&tagged.samples),
+                _ => tagged.samples,
+            };
+            let source_rms = rms(&samples) as f64;
             match tagged.source {
                 AudioSource::Microphone => {
                     mic_energy += source_rms;
@@ -414,19 +950,75 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
                     system_sample_count += 1;
                 }
             }
-            new.extend(tagged.samples);
+            new.extend(samples);
+        }
+        
+        // Session ending or app quitting: whatever speech is buffered gets processed now
+        let flushing = app.state::<GeminiState>().flush_pending();
+        
+        // Paused: drop the audio and any half-collected segment
+        let paused = *app.state::<GeminiState>().is_paused.lock().unwrap();
+        if paused {
+            if !was_paused {
+                info!("[AUDIO] ⏸ Listening paused - discarding audio");
+                events::emit_status(&app, PipelineState::Paused, "Paused");
+                buffer.clear();
+                speaking = false;
+                speech_start = None;
+                last_speech = None;
+                mic_energy = 0.0;
+                system_energy = 0.0;
+                mic_sample_count = 0;
+                system_sample_count = 0;
+                was_paused = true;
+            }
+            // Segments transcribed before the pause still get analyzed
+            if batch.is_due(flushing) {
+                queue_analysis(&app, &mut inflight, batch.take(), flushing);
+            }
+            emit_ready(&app, &mut inflight);
+            if flushing {
+                drain_analysis(&app, &mut inflight).await;
+                if app.state::<GeminiState>().finish_flush() {
+                    break;
+                }
+            }
+            continue;
+        } else if was_paused {
+            info!("[AUDIO] ▶ Listening resumed");
+            events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
+            was_paused = false;
+        }
+        
+        // Optional WAV recording of everything captured, speech or not
+        if !new.is_empty() {
+            app.state::<RecorderState>().append(&new);
+        }
+        
+        // Optional RNNoise pass so fans/keyboards don't trip the speech threshold
+        if app.state::<AudioState>().noise_suppression_enabled() {
+            let denoiser = denoiser.get_or_insert_with(Denoiser::new);
+            if !new.is_empty() {
+                new = denoiser.process(&new);
+            }
+        } else {
+            denoiser = None;
         }
         
         // Process new audio if available (but DON'T skip the processing check below)
         if !new.is_empty() {
+            last_audio_at = Instant::now();
+            if let Ok(mut meter) = app.state::<AudioState>().input_level.lock() {
+                meter.update(&new);
+            }
             audio_received_count += 1;
             total_samples_received += new.len() as u64;
             let level = rms(&new);
             
             // Log audio level every 1 second for better diagnostics
             if last_level_log.elapsed() > Duration::from_secs(1) {
-                let buffer_duration = buffer.len() as f32 / 16000.0;
-                println!("[AUDIO] Level: {:.6} (threshold: {:.6}) | Speaking: {} | Buffer: {:.1}s | Total samples: {}", 
+                let buffer_duration = buffer.len() as f32 / TARGET_SAMPLE_RATE as f32;
+                debug!("[AUDIO] Level: {:.6} (threshold: {:.6}) | Speaking: {} | Buffer: {:.1}s | Total samples: {}", 
                          level, SPEECH_THRESHOLD, speaking, buffer_duration, total_samples_received);
                 last_level_log = Instant::now();
             }
@@ -436,8 +1028,8 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
                 if !speaking {
                     speaking = true;
                     speech_start = Some(Instant::now());
-                    println!("[AUDIO] >>> SPEECH STARTED (level: {:.6} > threshold: {:.6}) <<<", level, SPEECH_THRESHOLD);
-                    let _ = app.emit("cognivox:status", "Speech detected...");
+                    info!("[AUDIO] >>> SPEECH STARTED (level: {:.6} > threshold: {:.6}) <<<", level, SPEECH_THRESHOLD);
+                    events::emit_status(&app, PipelineState::SpeechDetected, "Speech detected...");
                 }
                 last_speech = Some(Instant::now());
                 buffer.extend(new);
@@ -449,6 +1041,17 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
             }
         }
         
+        // Live meter for the frontend, throttled
+        if last_level_emit.elapsed() >= Duration::from_millis(LEVEL_EVENT_INTERVAL_MS) {
+            last_level_emit = Instant::now();
+            let meter = *app.state::<AudioState>().input_level.lock().unwrap();
+            events::emit(&app, &AudioLevelEvent {
+                level: meter,
+                speaking,
+                receiving: last_audio_at.elapsed() < Duration::from_millis(500),
+            });
+        }
+        
         // CRITICAL: Always check if we should process, even when no new audio arrives.
         // This ensures buffered speech gets transcribed when audio stops (e.g., recording ends
         // or silence filtering kicks in). Previously, `if new.is_empty() { continue; }` 
@@ -458,16 +1061,17 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
             let silence = last_speech.map(|s| s.elapsed().as_secs_f32()).unwrap_or(0.0);
             
             let should = (duration >= MIN_SPEECH_SECS && silence >= SILENCE_TIMEOUT_SECS)
-                || duration >= MAX_BATCH_SECS;
+                || duration >= MAX_BATCH_SECS
+                || flushing;
             
             if should {
-                println!("[AUDIO] >>> PROCESSING TRIGGER: duration={:.1}s, silence={:.1}s <<<", duration, silence);
+                info!("[AUDIO] >>> PROCESSING TRIGGER: duration={:.1}s, silence={:.1}s <<<", duration, silence);
             }
             should
         } else { false };
         
         if should_process && !buffer.is_empty() {
-            let duration = buffer.len() as f32 / 16000.0;
+            let duration = buffer.len() as f32 / TARGET_SAMPLE_RATE as f32;
             
             if duration >= MIN_SPEECH_SECS {
                 processing = true;
@@ -478,17 +1082,45 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
                 let avg_system = if system_sample_count > 0 { system_energy / system_sample_count as f64 } else { 0.0 };
                 let dominant_speaker = if avg_mic >= avg_system { "You" } else { "Speaker 2" };
                 
-                println!("[AUDIO] ========================================");
-                println!("[AUDIO] >>> PROCESSING {:.1}s AUDIO (request #{}) <<<", duration, request_count);
-                println!("[DIARIZATION] Mic energy: {:.6}, System energy: {:.6} -> Speaker: {}", avg_mic, avg_system, dominant_speaker);
-                println!("[AUDIO] ========================================");
-                let _ = app.emit("cognivox:status", format!("Whisper transcribing {:.1}s audio...", duration));
+                debug!("[AUDIO] ========================================");
+                info!("[AUDIO] >>> PROCESSING {:.1}s AUDIO (request #{}) <<<", duration, request_count);
+                info!("[DIARIZATION] Mic energy: {:.6}, System energy: {:.6} -> Speaker: {}", avg_mic, avg_system, dominant_speaker);
+                debug!("[AUDIO] ========================================");
+                events::emit_status(&app, PipelineState::Transcribing, format!("Whisper transcribing {:.1}s audio...", duration));
+                
+                // Session time at speech start, so subtitles line up with a recording of the meeting
+                let since_speech_ms = speech_start.map(|s| s.elapsed().as_millis() as u64).unwrap_or(0);
+                let now_ms = app.state::<LiveSessionState>().elapsed_ms()
+                    .unwrap_or_else(|| loop_clock.elapsed().as_millis() as u64);
+                let start_ms = now_ms.saturating_sub(since_speech_ms);
+                let end_ms = start_ms + (duration * 1000.0) as u64;
+                let segment_id = uuid::Uuid::new_v4().to_string();
+                let session_id = app.state::<LiveSessionState>().active_id();
+                let speech_end = last_speech.unwrap_or_else(Instant::now).into_std();
+                let previous_text = carried_text.take();
+                let cut_mid_speech = !flushing
+                    && last_speech.is_some_and(|s| s.elapsed().as_secs_f32() < SILENCE_TIMEOUT_SECS);
                 
-                let audio = buffer.clone();
+                let mut audio = buffer.clone();
                 buffer.clear();
-                speaking = false;
-                speech_start = None;
-                last_speech = None;
+                let overlap = if cut_mid_speech {
+                    overlap_samples(app.state::<SettingsState>().get().segment_overlap_ms).min(audio.len())
+                } else { 0 };
+                // Still speaking: the next segment starts with this one's tail
+                buffer.extend_from_slice(&audio[audio.len() - overlap..]);
+                
+                // Consistent loudness for Whisper regardless of mic gain
+                let gain = normalize_segment(&mut audio);
+                info!("[AUDIO] Segment level: {:.1} dBFS, peak {:.3} -> gain {:+.1} dB{}",
+                         gain.input_rms_dbfs, gain.input_peak, gain.gain_db,
+                         if gain.clipped { " (CLIPPED)" } else { "" });
+                if overlap > 0 {
+                    speech_start = Some(Instant::now() - Duration::from_millis(overlap as u64 * 1000 / TARGET_SAMPLE_RATE as u64));
+                } else {
+                    speaking = false;
+                    speech_start = None;
+                    last_speech = None;
+                }
                 
                 // Reset energy counters for next segment
                 mic_energy = 0.0;
@@ -496,128 +1128,149 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
                 mic_sample_count = 0;
                 system_sample_count = 0;
                 
-                let speaker_tag = dominant_speaker.to_string();
+                // Enrolled voices override the mic/system guess
+                let speakers = app.state::<SpeakerState>();
+                speakers.remember_segment(&audio);
+                let speaker_tag = match speakers.identify(&audio) {
+                    Some((name, score)) => {
+                        info!("[DIARIZATION] Voice match: {} ({:.2})", name, score);
+                        name
+                    }
+                    None => dominant_speaker.to_string(),
+                };
                 
                 // Get Whisper state
                 let whisper_state = app.state::<WhisperState>();
                 let is_init = *whisper_state.is_initialized.lock().unwrap();
                 if !is_init {
-                    println!("[WHISPER] ✗ Not initialized - CANNOT TRANSCRIBE");
-                    let _ = app.emit("cognivox:status", "Whisper not initialized");
+                    warn!("[WHISPER] ✗ Not initialized - CANNOT TRANSCRIBE");
+                    events::emit_status(&app, PipelineState::Error, "Whisper not initialized");
+                    app.state::<MetricsState>().record_dropped();
                     processing = false;
                     continue;
                 }
                 let model_path = match whisper_state.model_path.lock().unwrap().clone() {
                     Some(p) => p,
                     None => {
-                        println!("[WHISPER] ✗ Model path missing - CANNOT TRANSCRIBE");
-                        let _ = app.emit("cognivox:status", "Whisper model missing");
+                        warn!("[WHISPER] ✗ Model path missing - CANNOT TRANSCRIBE");
+                        events::emit_status(&app, PipelineState::Error, "Whisper model missing");
+                        app.state::<MetricsState>().record_dropped();
                         processing = false;
                         continue;
                     }
                 };
                 let language = whisper_state.language.lock().unwrap().clone();
-                println!("[WHISPER] Using language: '{}', model: {:?}", language, model_path);
+                let decoding = whisper_state.decoding.lock().unwrap().clone();
+                info!("[WHISPER] Using language: '{}', model: {:?}", language, model_path);
                 
                 // Transcribe with Whisper
-                let transcription = match transcribe_audio(&model_path, &language, &audio).await {
+                let transcribe_started = std::time::Instant::now();
+                let (transcription, stt_confidence, segment_language) = match transcribe_audio(&model_path, &language, &decoding, &audio).await {
                     Ok(result) => {
-                        println!("[WHISPER] ========================================");
-                        println!("[WHISPER] ✓ TRANSCRIPTION SUCCESS:");
-                        println!("[WHISPER]   Text: '{}'", &result.text);
-                        println!("[WHISPER]   Language: {}, Confidence: {:.2}", result.language, result.confidence);
-                        println!("[WHISPER] ========================================");
-                        println!("[WHISPER] >>> EMITTING cognivox:whisper_transcription EVENT <<<");
-                        let _ = app.emit("cognivox:whisper_transcription", serde_json::json!({
-                            "text": result.text.clone(),
-                            "language": result.language,
-                            "confidence": result.confidence,
-                            "source": "whisper",
-                            "speaker": speaker_tag.clone()
-                        }));
-                        result.text
+                        app.state::<MetricsState>().record_transcription(speech_end, duration, transcribe_started.elapsed());
+                        debug!("[WHISPER] ========================================");
+                        info!("[WHISPER] ✓ TRANSCRIPTION SUCCESS:");
+                        debug!("[WHISPER]   Text: '{}'", &result.text);
+                        debug!("[WHISPER]   Language: {}, Confidence: {:.2}", result.language, result.confidence);
+                        debug!("[WHISPER] ========================================");
+                        if !result.text.trim().is_empty()
+                            && discard_if_hallucinated(&app, &result.text, result.no_speech_prob, Some(&segment_id), session_id.as_deref())
+                        {
+                            events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
+                            app.state::<MetricsState>().record_dropped();
+                            processing = false;
+                            continue;
+                        }
+                        let text = match &previous_text {
+                            Some(previous) => stitch_overlap(previous, &result.text),
+                            None => result.text.clone(),
+                        };
+                        let Some(text) = dedupe::suppress_duplicate(&app, &text, &speaker_tag, result.no_speech_prob, Some(&segment_id), session_id.as_deref()) else {
+                            events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
+                            app.state::<MetricsState>().record_dropped();
+                            processing = false;
+                            continue;
+                        };
+                        translation::caption_segment(&app, CaptionSegment {
+                            segment_id: segment_id.clone(),
+                            session_id: session_id.clone(),
+                            speaker: speaker_tag.clone(),
+                            text: text.clone(),
+                            start_ms,
+                            end_ms,
+                        }, &result.language, &audio);
+                        debug!("[WHISPER] >>> EMITTING cognivox:whisper_transcription EVENT <<<");
+                        events::emit(&app, &TranscriptionEvent {
+                            segment_id: Some(segment_id.clone()),
+                            session_id: session_id.clone(),
+                            text: text.clone(),
+                            language: result.language.clone(),
+                            language_probability: result.language_probability,
+                            confidence: result.confidence,
+                            no_speech_prob: result.no_speech_prob,
+                            source: TranscriptionSource::Whisper,
+                            speaker: Some(speaker_tag.clone()),
+                            start_ms: Some(start_ms),
+                            end_ms: Some(end_ms),
+                            gain: Some(gain),
+                        });
+                        alerts::watch_keywords(&app, &text, &speaker_tag, Some(&segment_id), session_id.as_deref(), Some(start_ms));
+                        (text, result.confidence, result.segment_language())
                     }
                     Err(e) => {
-                        println!("[WHISPER] ✗ TRANSCRIPTION FAILED: {}", e);
-                        let _ = app.emit("cognivox:status", format!("Whisper error: {}", e));
+                        warn!("[WHISPER] ✗ TRANSCRIPTION FAILED: {}", e);
+                        events::emit_status(&app, PipelineState::Error, format!("Whisper error: {}", e));
+                        app.state::<MetricsState>().record_dropped();
                         processing = false;
                         continue;
                     }
                 };
                 
+                if overlap > 0 {
+                    carried_text = Some(transcription.clone());
+                }
                 if transcription.trim().is_empty() {
-                    println!("[WHISPER] Empty transcription result, skipping Gemini");
-                    let _ = app.emit("cognivox:status", "Listening for speech...");
+                    info!("[WHISPER] Empty transcription result, skipping Gemini");
+                    events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
+                    app.state::<MetricsState>().record_dropped();
                     processing = false;
                     continue;
                 }
-                
-                let _ = app.emit("cognivox:status", "Extracting intelligence...");
-                
-                // Get current key and model from state
-                let (key, model) = {
-                    let state = app.state::<GeminiState>();
-                    let k: String = state.api_key.lock().unwrap().clone().unwrap_or_default();
-                    let m = state.selected_model.lock().unwrap().clone();
-                    (k, m)
+                // Spoken commands run here instead of reaching Gemini
+                let mut recent = RecentSegment {
+                    segment_id: segment_id.clone(),
+                    session_id: session_id.clone(),
+                    speaker: speaker_tag.clone(),
+                    text: transcription,
+                    start_ms,
+                    end_ms,
                 };
-
-                if key.is_empty() {
-                    println!("[GEMINI] ✗ Error: No API key configured");
-                    let _ = app.emit("cognivox:status", "Error: No API key");
-                    let _ = app.emit("cognivox:api_error", serde_json::json!({"code": 401, "message": "No API key configured"}));
+                let Some(transcription) = voice_commands::intercept(&app, &recent) else {
+                    events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
                     processing = false;
                     continue;
-                }
-                
-                // Include speaker tag in the transcript text sent to Gemini
-                let speaker_annotated_transcript = format!("[{}]: {}", speaker_tag, transcription);
-                
-                match call_gemini_with_text(&key, &model, &speaker_annotated_transcript, &mut backoff, &mut last_request).await {
-                    Ok(response) => {
-                        println!("[GEMINI] ========================================");
-                        println!("[GEMINI] ✓ INTELLIGENCE EXTRACTED:");
-                        println!("[GEMINI]   Response: '{}'", if response.len() > 150 { &response[..150] } else { &response });
-                        println!("[GEMINI] ========================================");
-                        println!("[GEMINI] >>> EMITTING cognivox:gemini_intelligence EVENT <<<");
-                        println!("[GEMINI]   transcript: '{}', speaker: '{}'", &transcription, &speaker_tag);
-                        let _ = app.emit("cognivox:gemini_intelligence", serde_json::json!({
-                            "transcript": transcription.clone(),
-                            "speaker": speaker_tag.clone(),
-                            "intelligence": response
-                        }));
-                        let _ = app.emit("cognivox:status", "Listening for speech...");
-                    }
-                    Err(e) => {
-                        println!("[GEMINI] ✗ API Error: {}", e);
-                        println!("[GEMINI] >>> EMITTING FALLBACK cognivox:gemini_intelligence EVENT <<<");
-                        
-                        // STILL emit the transcript so user sees it even if Gemini failed
-                        let _ = app.emit("cognivox:gemini_intelligence", serde_json::json!({
-                            "transcript": transcription.clone(),
-                            "speaker": speaker_tag.clone(),
-                            "intelligence": format!("{{\"transcript\":\"{}\",\"speaker\":\"{}\",\"tone\":\"NEUTRAL\",\"category\":[\"INFO\"],\"confidence\":0.5}}", 
-                                transcription.replace('"', "'").replace('\n', " "), speaker_tag)
-                        }));
-                        
-                        let _ = app.emit("cognivox:status", format!("Gemini error: {}. Transcript saved.", e));
-                        
-                        // Emit error for frontend rotation
-                        let code = if e.contains("429") || e.contains("Rate limit") { 429 } else { 500 };
-                        let _ = app.emit("cognivox:api_error", serde_json::json!({
-                            "code": code,
-                            "message": e
-                        }));
-
-                        // Extra wait on error
-                        sleep(Duration::from_secs(2)).await;
-                        let _ = app.emit("cognivox:status", "Listening for speech...");
-                    }
-                }
+                };
+                recent.text = transcription.clone();
+                app.state::<LiveSessionState>().remember_segment(recent);
+                // Short replies wait briefly so a burst of them costs one request
+                batch.push(LiveSegment {
+                    segment_id,
+                    session_id,
+                    transcript: transcription,
+                    speaker: speaker_tag,
+                    start_ms,
+                    end_ms,
+                    stt_confidence,
+                    language: segment_language,
+                    speech_end,
+                    transcribed_at: std::time::Instant::now(),
+                });
+                events::emit_status(&app, PipelineState::Listening, "Listening for speech...");
                 
                 processing = false;
             } else {
-                println!("[AUDIO] Discarding short segment ({:.1}s)", duration);
+                info!("[AUDIO] Discarding short segment ({:.1}s)", duration);
+                app.state::<MetricsState>().record_dropped();
                 buffer.clear();
                 speaking = false;
                 speech_start = None;
@@ -625,12 +1278,367 @@ async fn smart_audio_loop(rx: Receiver<TaggedAudio>, app: AppHandle) {
             }
         }
         
+        // Intelligence runs alongside transcription; results still go out in segment order
+        if batch.is_due(flushing) {
+            queue_analysis(&app, &mut inflight, batch.take(), flushing);
+        }
+        emit_ready(&app, &mut inflight);
+        
         // Prevent buffer from growing too large
-        let max_samples = (MAX_BATCH_SECS * 16000.0) as usize;
+        let max_samples = (MAX_BATCH_SECS * TARGET_SAMPLE_RATE as f32) as usize;
         if buffer.len() > max_samples {
             buffer.drain(0..buffer.len() - max_samples);
         }
+        
+        if flushing {
+            drain_analysis(&app, &mut inflight).await;
+            if app.state::<GeminiState>().finish_flush() {
+                break;
+            }
+        }
+    }
+    
+    // Hand the receiver back so the next session can restart the loop
+    info!("[WHISPER->GEMINI] Audio processing loop stopped");
+    *app.state::<GeminiState>().audio_rx.lock().unwrap() = Some(rx);
+}
+
+/// When flushing, slower analysis is deferred to the retry queue
+async fn unless_deferred<T>(flushing: bool, request: impl std::future::Future<Output = Result<T, String>>) -> Result<T, String> {
+    if flushing {
+        timeout(Duration::from_secs(FLUSH_GEMINI_TIMEOUT_SECS), request).await
+            .unwrap_or_else(|_| Err("Deferred while flushing".to_string()))
+    } else {
+        request.await
+    }
+}
+
+/// Intelligence results for a batch of live segments, waiting to be emitted in order
+pub(crate) struct AnalyzedBatch {
+    segments: Vec<LiveSegment>,
+    // Err: no request could be made at all (no key, local-only mode)
+    results: Result<Vec<Result<String, String>>, String>,
+}
+
+/// Queue intelligence for a batch. The context is fixed here, so requests that
+/// overlap still see the same history a serial pipeline would have sent.
+fn queue_analysis(app: &AppHandle, inflight: &mut InFlight<AnalyzedBatch>, segments: Vec<LiveSegment>, flushing: bool) {
+    let gemini = app.state::<GeminiState>();
+    let context = gemini.context_snapshot();
+    // Keep segments in context even if analysis fails - later replies still refer to them
+    for segment in &segments {
+        gemini.push_context(segment.annotated());
+    }
+    let app = app.clone();
+    inflight.push(async move { request_intelligence(&app, segments, context, flushing).await });
+}
+
+fn concurrency_limit(app: &AppHandle) -> usize {
+    app.state::<GeminiState>().rate_limit.lock().unwrap().max_concurrent_requests
+}
+
+/// Emit whatever finished next in segment order
+fn emit_ready(app: &AppHandle, inflight: &mut InFlight<AnalyzedBatch>) {
+    for batch in inflight.poll(concurrency_limit(app)) {
+        emit_intelligence(app, batch);
+    }
+}
+
+/// Flushing: everything queued is analyzed (or deferred) and emitted before the flush completes
+async fn drain_analysis(app: &AppHandle, inflight: &mut InFlight<AnalyzedBatch>) {
+    for batch in inflight.drain(concurrency_limit(app)).await {
+        emit_intelligence(app, batch);
+    }
+}
+
+/// Analyze one segment outside the audio loop and emit the result right away
+pub(crate) async fn analyze_segment(app: &AppHandle, segment: LiveSegment) {
+    let gemini = app.state::<GeminiState>();
+    let context = gemini.context_snapshot();
+    gemini.push_context(segment.annotated());
+    let batch = request_intelligence(app, vec![segment], context, false).await;
+    emit_intelligence(app, batch);
+}
+
+/// Intelligence for a batch of live segments: one request for the lot, or one
+/// per segment if the batched response can't be split back up
+async fn request_intelligence(app: &AppHandle, segments: Vec<LiveSegment>, context: Vec<String>, flushing: bool) -> AnalyzedBatch {
+    events::emit_status(app, PipelineState::Analyzing, "Extracting intelligence...");
+    
+    // Simulation: no key needed, no quota used
+    if simulation::is_active() {
+        let mut results = Vec::with_capacity(segments.len());
+        for segment in &segments {
+            results.push(Ok(MockProvider::analyze(app, &segment.transcript, &segment.speaker).await));
+        }
+        return AnalyzedBatch { segments, results: Ok(results) };
+    }
+    
+    // Get current key, model and generation config from state
+    let config = match app.state::<GeminiState>().request_config(app) {
+        Ok(c) => c,
+        Err(e) => return AnalyzedBatch { segments, results: Err(e) },
+    };
+    
+    let system_prompt = build_intelligence_prompt(&app.state::<SettingsState>().get());
+    let results: Vec<Result<String, String>> = if segments.len() == 1 {
+        let result = unless_deferred(flushing, call_gemini_with_text(&config, &system_prompt, &segments[0].annotated(), &context)).await;
+        app.state::<MetricsState>().record_gemini_result(result.is_ok());
+        vec![result]
+    } else {
+        let annotated: Vec<String> = segments.iter().map(LiveSegment::annotated).collect();
+        let batched = unless_deferred(flushing, call_gemini_batch(&config, &system_prompt, &annotated, &context)).await;
+        app.state::<MetricsState>().record_gemini_result(batched.is_ok());
+        match batched {
+            Ok(responses) => responses.into_iter().map(Ok).collect(),
+            // Deferred or rate limited: a retry per segment is the fallback anyway
+            Err(e) if flushing || e.starts_with("Rate limited") => segments.iter().map(|_| Err(e.clone())).collect(),
+            Err(e) => {
+                warn!("[BATCH] ✗ {} - analyzing segments one by one", e);
+                let mut results = Vec::with_capacity(segments.len());
+                for text in &annotated {
+                    let result = call_gemini_with_text(&config, &system_prompt, text, &context).await;
+                    app.state::<MetricsState>().record_gemini_result(result.is_ok());
+                    results.push(result);
+                }
+                results
+            }
+        }
+    };
+    
+    if let Some(e) = results.iter().rev().find_map(|r| r.as_ref().err()) {
+        let api_error = ApiErrorEvent::from_error(e.clone());
+        let status_state = if api_error.code == 429 { PipelineState::RateLimited } else { PipelineState::Error };
+        events::emit_status(app, status_state, format!("Gemini error: {}. Queued for retry.", e));
+        
+        // Emit error for frontend rotation
+        events::emit(app, &api_error);
+        
+        // Extra wait on error, holding this request's slot
+        if !flushing {
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+    AnalyzedBatch { segments, results: Ok(results) }
+}
+
+/// Record and announce one analyzed batch. Called in segment order.
+fn emit_intelligence(app: &AppHandle, batch: AnalyzedBatch) {
+    let segments = batch.segments;
+    let results = match batch.results {
+        Ok(results) => results,
+        Err(e) => {
+            warn!("[GEMINI] ✗ Error: {}", e);
+            let local_only = app.state::<NetworkState>().is_local_only();
+            for segment in segments {
+                if let Some(session_id) = &segment.session_id {
+                    record_segment(session_id, &segment.segment_id, &segment.transcript, &segment.speaker, None, (Some(segment.start_ms), Some(segment.end_ms)), segment.language.as_ref());
+                }
+                // Local-only: transcript stays on the machine, and is never queued for a later upload
+                if !local_only {
+                    // Analyzed later, once a key is configured
+                    app.state::<RetryQueueState>().enqueue(PendingSegment::new(
+                        segment.segment_id, segment.session_id, segment.transcript, segment.speaker,
+                        Some(segment.start_ms), Some(segment.end_ms), e.clone(),
+                    ));
+                }
+            }
+            // A deliberate skip, not a key failure: an api_error would make the key manager disable keys
+            if local_only {
+                events::emit_status(app, PipelineState::Listening, "Local-only mode: transcript kept, intelligence skipped");
+            } else {
+                events::emit_status(app, PipelineState::Error, "Error: No API key");
+                events::emit(app, &ApiErrorEvent { code: 401, message: e });
+            }
+            return;
+        }
+    };
+    
+    for (segment, result) in segments.into_iter().zip(results) {
+        match result {
+            Ok(response) => {
+                let response = voice_commands::apply_mark(app, &segment.segment_id, response);
+                let input = PluginInput {
+                    segment_id: Some(&segment.segment_id),
+                    session_id: segment.session_id.as_deref(),
+                    transcript: &segment.transcript,
+                    speaker: &segment.speaker,
+                    start_ms: Some(segment.start_ms),
+                    end_ms: Some(segment.end_ms),
+                    language: segment.language.as_ref(),
+                    retried: false,
+                };
+                let Some(response) = plugins::apply(app, &input, response) else {
+                    plugins::emit_dropped(app, &input);
+                    app.state::<RetryQueueState>().mark_online();
+                    continue;
+                };
+                debug!("[GEMINI] ========================================");
+                info!("[GEMINI] ✓ INTELLIGENCE EXTRACTED:");
+                debug!("[GEMINI]   Response: '{}'", if response.len() > 150 { &response[..150] } else { &response });
+                debug!("[GEMINI] ========================================");
+                debug!("[GEMINI] >>> EMITTING cognivox:gemini_intelligence EVENT <<<");
+                debug!("[GEMINI]   transcript: '{}', speaker: '{}'", &segment.transcript, &segment.speaker);
+                if let Some(session_id) = &segment.session_id {
+                    record_segment(session_id, &segment.segment_id, &segment.transcript, &segment.speaker, Some(&response), (Some(segment.start_ms), Some(segment.end_ms)), segment.language.as_ref());
+                }
+                let event = IntelligenceEvent {
+                    segment_id: Some(segment.segment_id),
+                    session_id: segment.session_id.clone(),
+                    transcript: segment.transcript.clone(),
+                    speaker: Some(segment.speaker.clone()),
+                    intelligence: Some(response.clone()),
+                    pending: false,
+                    retried: false,
+                    start_ms: Some(segment.start_ms),
+                    end_ms: Some(segment.end_ms),
+                    timestamp: events::now_ms(),
+                };
+                events::emit(app, &event);
+                dispatch_webhook(app, "gemini_intelligence", &events::to_payload(&event));
+                action_items::ingest_intelligence(app, segment.session_id.as_deref(), &segment.transcript, &segment.speaker, &response, Some(segment.start_ms));
+                alerts::notify_if_urgent(app, &segment.transcript, &segment.speaker, &response);
+                app.state::<MetricsState>().record_intelligence(segment.speech_end, segment.transcribed_at);
+                app.state::<RetryQueueState>().mark_online();
+            }
+            Err(e) => {
+                warn!("[GEMINI] ✗ API Error: {}", e);
+                debug!("[GEMINI] >>> EMITTING PENDING cognivox:gemini_intelligence EVENT <<<");
+                
+                // STILL emit the transcript so user sees it; intelligence follows from the retry queue
+                events::emit(app, &IntelligenceEvent {
+                    segment_id: Some(segment.segment_id.clone()),
+                    session_id: segment.session_id.clone(),
+                    transcript: segment.transcript.clone(),
+                    speaker: Some(segment.speaker.clone()),
+                    intelligence: None,
+                    pending: true,
+                    retried: false,
+                    start_ms: Some(segment.start_ms),
+                    end_ms: Some(segment.end_ms),
+                    timestamp: events::now_ms(),
+                });
+                if let Some(session_id) = &segment.session_id {
+                    record_segment(session_id, &segment.segment_id, &segment.transcript, &segment.speaker, None, (Some(segment.start_ms), Some(segment.end_ms)), segment.language.as_ref());
+                }
+                app.state::<RetryQueueState>().enqueue(PendingSegment::new(
+                    segment.segment_id, segment.session_id, segment.transcript, segment.speaker,
+                    Some(segment.start_ms), Some(segment.end_ms), e,
+                ));
+            }
+        }
+    }
+    
+    events::emit_status(app, PipelineState::Listening, "Listening for speech...");
+}
+
+#[tauri::command]
+pub fn pause_listening(state: tauri::State<'_, GeminiState>) -> Result<String, String> {
+    *state.is_paused.lock().unwrap() = true;
+    Ok("Paused".to_string())
+}
+
+/// Resume after `pause_listening`; the loop and connection stay up while paused
+#[tauri::command]
+pub fn resume_listening(state: tauri::State<'_, GeminiState>) -> Result<String, String> {
+    *state.is_paused.lock().unwrap() = false;
+    Ok("Listening".to_string())
+}
+
+#[tauri::command]
+pub fn set_context_window(state: tauri::State<'_, GeminiState>, size: usize) -> Result<String, String> {
+    let size = size.min(MAX_CONTEXT_SEGMENTS);
+    *state.context_size.lock().unwrap() = size;
+    
+    let mut window = state.context_window.lock().unwrap();
+    while window.len() > size {
+        window.pop_front();
+    }
+    info!("[GEMINI] Context window: {} segment(s)", size);
+    Ok(format!("Context window: {} segments", size))
+}
+
+/// How much audio to carry over when a long utterance is cut at MAX_BATCH_SECS
+#[tauri::command]
+pub fn set_segment_overlap(settings: tauri::State<'_, SettingsState>, overlap_ms: u64) -> Result<u64, String> {
+    let overlap_ms = overlap_ms.min(MAX_SEGMENT_OVERLAP_MS);
+    settings.update(|s| s.segment_overlap_ms = overlap_ms)?;
+    info!("[AUDIO] Segment overlap: {}ms", overlap_ms);
+    Ok(overlap_ms)
+}
+
+#[tauri::command]
+pub fn clear_conversation_context(state: tauri::State<'_, GeminiState>) -> Result<(), String> {
+    state.context_window.lock().unwrap().clear();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_generation_config(state: tauri::State<'_, GeminiState>) -> GenerationSettings {
+    state.generation_config.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_generation_config(
+    state: tauri::State<'_, GeminiState>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    max_output_tokens: Option<u32>,
+    safety_settings: Option<Vec<SafetySetting>>,
+) -> Result<GenerationSettings, String> {
+    if let Some(t) = temperature {
+        if !(0.0..=2.0).contains(&t) { return Err("temperature must be between 0.0 and 2.0".to_string()); }
+    }
+    if let Some(p) = top_p {
+        if !(0.0..=1.0).contains(&p) { return Err("top_p must be between 0.0 and 1.0".to_string()); }
+    }
+    if let Some(m) = max_output_tokens {
+        if m == 0 || m > 65536 { return Err("max_output_tokens must be between 1 and 65536".to_string()); }
+    }
+    if let Some(settings) = &safety_settings {
+        for s in settings {
+            if !s.category.starts_with("HARM_CATEGORY_") {
+                return Err(format!("Invalid safety category: {}", s.category));
+            }
+            if !SAFETY_THRESHOLDS.contains(&s.threshold.as_str()) {
+                return Err(format!("Invalid safety threshold: {}", s.threshold));
+            }
+        }
     }
+    
+    let mut config = state.generation_config.lock().unwrap();
+    if let Some(t) = temperature { config.temperature = t; }
+    if top_p.is_some() { config.top_p = top_p; }
+    if let Some(m) = max_output_tokens { config.max_output_tokens = m; }
+    if let Some(settings) = safety_settings { config.safety_settings = settings; }
+    
+    info!("[GEMINI] Generation config: {:?}", *config);
+    Ok(config.clone())
+}
+
+#[tauri::command]
+pub fn get_rate_limit_config(state: tauri::State<'_, GeminiState>) -> RateLimitConfig {
+    *state.rate_limit.lock().unwrap()
+}
+
+#[tauri::command]
+pub fn set_rate_limit_config(
+    state: tauri::State<'_, GeminiState>,
+    config: RateLimitConfig,
+) -> Result<RateLimitConfig, String> {
+    if config.initial_backoff_secs == 0 {
+        return Err("initial_backoff_secs must be at least 1".to_string());
+    }
+    if config.max_backoff_secs < config.initial_backoff_secs {
+        return Err("max_backoff_secs must be at least initial_backoff_secs".to_string());
+    }
+    if config.max_concurrent_requests == 0 || config.max_concurrent_requests > MAX_CONCURRENT_REQUESTS {
+        return Err(format!("max_concurrent_requests must be between 1 and {}", MAX_CONCURRENT_REQUESTS));
+    }
+    
+    *state.rate_limit.lock().unwrap() = config;
+    info!("[GEMINI] Rate limit config: {:?}", config);
+    Ok(config)
 }
 
 #[tauri::command]
@@ -639,12 +1647,97 @@ pub fn set_gemini_model(state: tauri::State<'_, GeminiState>, model: String) ->
     Ok(format!("Model: {}", model))
 }
 
+// ============================================================================
+// Model Listing (ListModels API)
+// ============================================================================
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListModelsResponse {
+    #[serde(default)]
+    models: Vec<ApiModel>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiModel {
+    name: String,
+    display_name: Option<String>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+    input_token_limit: Option<u32>,
+    output_token_limit: Option<u32>,
+}
+
+async fn fetch_models(client: &reqwest::Client, key: &str) -> Result<Vec<ModelInfo>, String> {
+    let mut models = Vec::new();
+    let mut page_token: Option<String> = None;
+    
+    loop {
+        let mut url = format!("{}?key={}&pageSize=100", GEMINI_REST_URL, key);
+        if let Some(token) = &page_token {
+            url.push_str(&format!("&pageToken={}", token));
+        }
+        
+        let response = client.get(&url)
+            .timeout(Duration::from_secs(15))
+            .send()
+            .await
+            .map_err(|e| format!("HTTP: {}", e))?;
+        
+        let status = response.status();
+        let text = response.text().await.map_err(|e| format!("Read: {}", e))?;
+        if !status.is_success() {
+            // Error bodies can be localized; cut on a char boundary
+            let excerpt: String = text.chars().take(200).collect();
+            return Err(format!("ListModels failed (HTTP {}): {}", status, excerpt));
+        }
+        
+        let page: ListModelsResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse model list: {}", e))?;
+        
+        models.extend(page.models.into_iter()
+            .filter(|m| m.supported_generation_methods.iter().any(|g| g == "generateContent"))
+            .map(|m| {
+                let id = m.name.trim_start_matches("models/").to_string();
+                ModelInfo {
+                    name: m.display_name.unwrap_or_else(|| id.clone()),
+                    id,
+                    input_token_limit: m.input_token_limit,
+                    output_token_limit: m.output_token_limit,
+                }
+            }));
+        
+        match page.next_page_token.filter(|t| !t.is_empty()) {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+    
+    Ok(models)
+}
+
 #[tauri::command]
-pub fn get_available_models() -> Vec<serde_json::Value> {
-    vec![
-        serde_json::json!({"id": "gemini-2.0-flash", "name": "⚡ Gemini 2.0 Flash (Stable)"}),
-        serde_json::json!({"id": "gemini-2.0-flash-lite", "name": "🔥 Gemini 2.0 Flash Lite"}),
-        serde_json::json!({"id": "gemini-2.5-flash-preview-04-17", "name": "💎 Gemini 2.5 Flash Preview"}),
-        serde_json::json!({"id": "gemini-1.5-flash", "name": "🌟 Gemini 1.5 Flash (Fallback)"}),
-    ]
+pub async fn get_available_models(
+    state: tauri::State<'_, GeminiState>,
+    network: tauri::State<'_, NetworkState>,
+    refresh: Option<bool>,
+) -> Result<Vec<ModelInfo>, String> {
+    if !refresh.unwrap_or(false) {
+        if let Some((fetched_at, models)) = state.model_cache.lock().unwrap().as_ref() {
+            if fetched_at.elapsed() < Duration::from_secs(MODEL_CACHE_TTL_SECS) {
+                return Ok(models.clone());
+            }
+        }
+    }
+    
+    let key = state.api_key.lock().unwrap().clone()
+        .ok_or("No API key configured")?;
+    
+    let models = fetch_models(&network.client()?, &key).await?;
+    info!("[GEMINI] {} generateContent models available", models.len());
+    
+    *state.model_cache.lock().unwrap() = Some((Instant::now(), models.clone()));
+    Ok(models)
 }
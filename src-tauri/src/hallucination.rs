@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::events::{self, SegmentDiscardedEvent};
+use crate::settings::SettingsState;
+
+// ============================================================================
+// HALLUCINATION FILTER - Drop Whisper Output that isn't Speech
+// ============================================================================
+//
+// On silence and noise Whisper falls back to phrases from its training data
+// ("Thanks for watching!") or loops on one phrase. Such segments are dropped
+// before they reach Gemini or the session.
+
+const DEFAULT_BLOCKLIST: &[&str] = &[
+    "thank you for watching",
+    "thanks for watching",
+    "thank you so much for watching",
+    "please subscribe",
+    "like and subscribe",
+    "don't forget to subscribe",
+    "see you in the next video",
+    "subtitles by the amara.org community",
+    "transcription by castingwords",
+    "you",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HallucinationRules {
+    pub enabled: bool,
+    // Segments Whisper itself considers likely non-speech
+    pub max_no_speech_prob: f32,
+    // A phrase repeated back-to-back more often than this is a decoding loop
+    pub max_repeats: usize,
+    // Whole-segment matches; phrases of 3+ words also match at the end of a segment
+    pub blocklist: Vec<String>,
+}
+
+impl Default for HallucinationRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_no_speech_prob: 0.6,
+            max_repeats: 3,
+            blocklist: DEFAULT_BLOCKLIST.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscardReason {
+    NoSpeech,
+    Blocklisted,
+    Repetition,
+    // Repeats one of the last few segments (see dedupe.rs)
+    Duplicate,
+}
+
+/// Lowercase words with punctuation and [BLANK_AUDIO]/(music) style annotations removed
+pub(crate) fn normalized_words(text: &str) -> Vec<String> {
+    let mut depth = 0i32;
+    let stripped: String = text.chars()
+        .filter_map(|c| match c {
+            '[' | '(' => { depth += 1; None }
+            ']' | ')' => { depth = (depth - 1).max(0); None }
+            _ if depth > 0 => None,
+            _ => Some(c),
+        })
+        .collect();
+    stripped.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Longest run of one phrase (1-4 words) repeated back-to-back
+fn max_consecutive_repeats(words: &[String]) -> usize {
+    let mut best = 1;
+    for n in 1..=4 {
+        if words.len() < n * 2 { break; }
+        for start in 0..n {
+            let chunks: Vec<&[String]> = words[start..].chunks_exact(n).collect();
+            let mut run = 1;
+            for pair in chunks.windows(2) {
+                run = if pair[0] == pair[1] { run + 1 } else { 1 };
+                best = best.max(run);
+            }
+        }
+    }
+    best
+}
+
+/// Why a transcript should be dropped, if it should
+pub fn check(rules: &HallucinationRules, text: &str, no_speech_prob: f32) -> Option<DiscardReason> {
+    if !rules.enabled {
+        return None;
+    }
+    let words = normalized_words(text);
+    if words.is_empty() || no_speech_prob > rules.max_no_speech_prob {
+        return Some(DiscardReason::NoSpeech);
+    }
+
+    let phrase = words.join(" ");
+    let blocklisted = rules.blocklist.iter()
+        .map(|entry| normalized_words(entry).join(" "))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| phrase == entry
+            || (entry.split(' ').count() >= 3 && phrase.ends_with(&format!(" {}", entry))));
+    if blocklisted {
+        return Some(DiscardReason::Blocklisted);
+    }
+
+    if max_consecutive_repeats(&words) > rules.max_repeats {
+        return Some(DiscardReason::Repetition);
+    }
+    None
+}
+
+/// Check a live/imported segment; when it's dropped, say so and return true
+pub fn discard_if_hallucinated(
+    app: &AppHandle,
+    text: &str,
+    no_speech_prob: f32,
+    segment_id: Option<&str>,
+    session_id: Option<&str>,
+) -> bool {
+    let Some(reason) = check(&app.state::<SettingsState>().get().hallucinations, text, no_speech_prob) else {
+        return false;
+    };
+    announce_discard(app, text, reason, no_speech_prob, segment_id, session_id);
+    true
+}
+
+pub(crate) fn announce_discard(
+    app: &AppHandle,
+    text: &str,
+    reason: DiscardReason,
+    no_speech_prob: f32,
+    segment_id: Option<&str>,
+    session_id: Option<&str>,
+) {
+    info!("[WHISPER] Discarded segment ({:?}, no-speech {:.2}): '{}'", reason, no_speech_prob, text);
+    events::emit(app, &SegmentDiscardedEvent {
+        segment_id: segment_id.map(|s| s.to_string()),
+        session_id: session_id.map(|s| s.to_string()),
+        text: text.to_string(),
+        reason,
+        no_speech_prob,
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_hallucination_rules(settings: tauri::State<'_, SettingsState>) -> HallucinationRules {
+    settings.get().hallucinations
+}
+
+#[tauri::command]
+pub fn set_hallucination_rules(
+    settings: tauri::State<'_, SettingsState>,
+    rules: HallucinationRules,
+) -> Result<HallucinationRules, String> {
+    if !(0.0..=1.0).contains(&rules.max_no_speech_prob) {
+        return Err("max_no_speech_prob must be between 0.0 and 1.0".to_string());
+    }
+    if rules.max_repeats == 0 {
+        return Err("max_repeats must be at least 1".to_string());
+    }
+    let rules = HallucinationRules {
+        blocklist: rules.blocklist.into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect(),
+        ..rules
+    };
+
+    settings.update(|s| s.hallucinations = rules.clone())?;
+    info!("[WHISPER] Hallucination filter updated (enabled: {}, {} blocked phrase(s))", rules.enabled, rules.blocklist.len());
+    Ok(rules)
+}
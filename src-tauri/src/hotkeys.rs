@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{info, warn};
+use crate::audio_capture::AudioState;
+use crate::bookmarks::{self, BookmarkSource};
+use crate::gemini_client::GeminiState;
+use crate::live_session::{self, LiveSessionState};
+use crate::settings::SettingsState;
+
+// ============================================================================
+// HOTKEYS - Global Shortcuts (work while the window is in the background)
+// ============================================================================
+
+/// Accelerator strings, e.g. "CommandOrControl+Shift+L". None disables the action.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    pub toggle_listening: Option<String>,
+    pub pause: Option<String>,
+    pub bookmark: Option<String>,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            toggle_listening: Some("CommandOrControl+Shift+L".to_string()),
+            pause: Some("CommandOrControl+Shift+P".to_string()),
+            bookmark: Some("CommandOrControl+Shift+B".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum HotkeyAction {
+    ToggleListening,
+    Pause,
+    Bookmark,
+}
+
+fn run_action(app: &AppHandle, action: HotkeyAction) {
+    info!("[HOTKEY] {:?}", action);
+    match action {
+        HotkeyAction::ToggleListening => {
+            // Same path as the tray menu; the frontend follows the session events
+            let recording = *app.state::<AudioState>().is_recording.lock().unwrap();
+            if recording || app.state::<LiveSessionState>().active_id().is_some() {
+                live_session::stop_listening(app);
+            } else if let Err(e) = live_session::start_listening(app) {
+                warn!("[HOTKEY] Not started: {}", e);
+            }
+        }
+        HotkeyAction::Pause => {
+            app.state::<GeminiState>().toggle_pause();
+        }
+        HotkeyAction::Bookmark => {
+            if let Err(e) = bookmarks::add_to_active(app, None, BookmarkSource::Hotkey) {
+                warn!("[HOTKEY] Bookmark not added: {}", e);
+            }
+        }
+    }
+}
+
+/// Replace all registered shortcuts with the given config
+pub fn register_hotkeys(app: &AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts.unregister_all().map_err(|e| e.to_string())?;
+
+    let bindings = [
+        (&config.toggle_listening, HotkeyAction::ToggleListening),
+        (&config.pause, HotkeyAction::Pause),
+        (&config.bookmark, HotkeyAction::Bookmark),
+    ];
+    for (accelerator, action) in bindings {
+        let Some(accelerator) = accelerator.as_deref().filter(|a| !a.trim().is_empty()) else { continue; };
+        shortcuts
+            .on_shortcut(accelerator, move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    run_action(app, action);
+                }
+            })
+            .map_err(|e| format!("Can't register '{}' for {:?}: {}", accelerator, action, e))?;
+        info!("[HOTKEY] {} → {:?}", accelerator, action);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_hotkeys(settings: tauri::State<'_, SettingsState>) -> HotkeyConfig {
+    settings.get().hotkeys
+}
+
+/// Remap shortcuts. On failure the previous mapping is restored.
+#[tauri::command]
+pub fn set_hotkeys(
+    app: AppHandle,
+    settings: tauri::State<'_, SettingsState>,
+    hotkeys: HotkeyConfig,
+) -> Result<HotkeyConfig, String> {
+    if let Err(e) = register_hotkeys(&app, &hotkeys) {
+        let _ = register_hotkeys(&app, &settings.get().hotkeys);
+        return Err(e);
+    }
+    let settings = settings.update(|s| s.hotkeys = hotkeys)?;
+    Ok(settings.hotkeys)
+}
@@ -0,0 +1,387 @@
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Path as UrlPath, Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::Stream;
+use ring::hmac;
+use ring::rand::SystemRandom;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{broadcast::error::RecvError, oneshot};
+use tracing::{error, info, warn};
+use crate::audio_capture::{self, AudioState, TARGET_SAMPLE_RATE};
+use crate::events;
+use crate::file_import::load_samples;
+use crate::live_session;
+use crate::mcp;
+use crate::pipeline_status;
+use crate::session_manager::{valid_session_id, SessionManager};
+use crate::settings::{app_data_dir, redact, unredact, SettingsState};
+use crate::whisper_client::{self, transcribe_audio, WhisperState};
+use crate::whisper_models;
+
+// ============================================================================
+// HTTP API - Headless Control over the LAN
+// ============================================================================
+//
+// Off unless enabled in settings or the app is started with `--headless`
+// (no window; Whisper is loaded at startup). Every route needs
+// `Authorization: Bearer <token>` when a token is set; `?token=` works too,
+// since EventSource can't send headers.
+//
+//   GET  /status             pipeline snapshot
+//   GET  /sessions           stored sessions, newest first
+//   GET  /sessions/{id}      one full session
+//   POST /sessions/start     start a live session (and capture)
+//   POST /sessions/end       end it; the summary follows in the background
+//   GET  /search?q=          transcript text search
+//   POST /transcribe         body = audio file; returns the text
+//   GET  /events             server-sent cognivox:* events
+
+// Uploaded recordings; a 2 h 16 kHz WAV is ~230 MB
+const MAX_UPLOAD_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    // host:port; anything but loopback requires a token
+    pub bind: String,
+    pub token: Option<String>,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1:8787".to_string(),
+            token: None,
+        }
+    }
+}
+
+impl HttpApiConfig {
+    fn redacted(&self) -> Self {
+        Self {
+            token: redact(self.token.as_deref()),
+            ..self.clone()
+        }
+    }
+
+    fn address(&self) -> Result<SocketAddr, String> {
+        let addr: SocketAddr = self.bind.trim().parse()
+            .map_err(|_| format!("Invalid bind address '{}' (expected host:port, e.g. 0.0.0.0:8787)", self.bind))?;
+        if !addr.ip().is_loopback() && self.token.is_none() {
+            return Err("A token is required when listening beyond localhost".to_string());
+        }
+        Ok(addr)
+    }
+}
+
+struct RunningServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+pub struct HttpApiState {
+    server: StdMutex<Option<RunningServer>>,
+}
+
+impl HttpApiState {
+    fn stop(&self) {
+        if let Some(server) = self.server.lock().unwrap().take() {
+            let _ = server.shutdown.send(());
+            info!("[HTTP] Stopped listening on {}", server.addr);
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct HttpApiStatus {
+    pub running: bool,
+    pub address: Option<String>,
+}
+
+/// `--headless`: no window, API on, Whisper loaded at startup
+pub fn headless_requested() -> bool {
+    std::env::args().any(|a| a == "--headless")
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+type ApiResult = Result<Json<Value>, (StatusCode, String)>;
+
+fn bad_request(e: String) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, e)
+}
+
+fn server_error(e: String) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, e)
+}
+
+fn to_json<T: Serialize>(value: T) -> ApiResult {
+    serde_json::to_value(value).map(Json).map_err(|e| server_error(e.to_string()))
+}
+
+/// Whether a request presents `expected` as a bearer token or a (percent-
+/// encoded) `?token=`. Compared in constant time through ring's HMAC verify,
+/// since its verify_slices_are_equal is deprecated.
+fn presents_token(expected: &str, authorization: Option<&str>, query: Option<&str>) -> bool {
+    let Ok(key) = hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new()) else { return false };
+    let tag = hmac::sign(&key, expected.as_bytes());
+    let matches = |given: &str| hmac::verify(&key, given.as_bytes(), tag.as_ref()).is_ok();
+    let bearer = authorization.and_then(|v| v.strip_prefix("Bearer "));
+    let from_query = query.and_then(|q| {
+        url::form_urlencoded::parse(q.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    });
+    bearer.is_some_and(matches) || from_query.is_some_and(|t| matches(&t))
+}
+
+async fn require_token(State(token): State<Option<String>>, request: Request, next: Next) -> Response {
+    let Some(token) = token else { return next.run(request).await; };
+    let authorization = request.headers().get(header::AUTHORIZATION).and_then(|v| v.to_str().ok());
+    if presents_token(&token, authorization, request.uri().query()) {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, "Missing or invalid token").into_response()
+    }
+}
+
+async fn status(State(app): State<AppHandle>) -> ApiResult {
+    to_json(pipeline_status::get_pipeline_status(app))
+}
+
+#[derive(Deserialize)]
+struct ListQuery {
+    limit: Option<usize>,
+}
+
+async fn list_sessions(Query(query): Query<ListQuery>) -> ApiResult {
+    let manager = SessionManager::new().map_err(server_error)?;
+    mcp::list_sessions(&manager, &json!({ "limit": query.limit })).map(Json).map_err(server_error)
+}
+
+async fn get_session(UrlPath(id): UrlPath<String>) -> ApiResult {
+    if !valid_session_id(&id) {
+        return Err(bad_request(format!("Invalid session id: {}", id)));
+    }
+    let session = SessionManager::new()
+        .and_then(|m| m.load_session(&id))
+        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+    to_json(session)
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StartRequest {
+    title: Option<String>,
+    // Start native capture too (default); false when audio arrives another way
+    capture: Option<bool>,
+}
+
+async fn start_session(State(app): State<AppHandle>, body: Option<Json<StartRequest>>) -> ApiResult {
+    let request = body.map(|Json(b)| b).unwrap_or_default();
+    let session = live_session::start_session(app.clone(), request.title).map_err(bad_request)?;
+    if request.capture.unwrap_or(true) {
+        if let Err(e) = audio_capture::start_audio_capture(app.state()) {
+            warn!("[HTTP] Session started without capture: {}", e);
+        }
+    }
+    to_json(session)
+}
+
+async fn end_session(State(app): State<AppHandle>) -> ApiResult {
+    if let Err(e) = app.state::<AudioState>().stop_capture() {
+        warn!("[HTTP] Failed to stop capture: {}", e);
+    }
+    let session = live_session::end_session(app).await.map_err(bad_request)?;
+    to_json(session)
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+    session_id: Option<String>,
+    language: Option<String>,
+    limit: Option<usize>,
+}
+
+async fn search(Query(query): Query<SearchQuery>) -> ApiResult {
+    if query.session_id.as_deref().is_some_and(|id| !valid_session_id(id)) {
+        return Err(bad_request("Invalid session id".to_string()));
+    }
+    let manager = SessionManager::new().map_err(server_error)?;
+    let args = json!({ "query": query.q, "session_id": query.session_id, "language": query.language, "limit": query.limit });
+    mcp::search_transcripts(&manager, &args).map(Json).map_err(server_error)
+}
+
+/// File extension symphonia should probe for, from the upload's Content-Type
+fn upload_extension(headers: &HeaderMap) -> &'static str {
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("");
+    match content_type.split(';').next().unwrap_or("").trim() {
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/aac" => "aac",
+        "audio/ogg" => "ogg",
+        "audio/flac" | "audio/x-flac" => "flac",
+        _ => "wav",
+    }
+}
+
+async fn transcribe(State(app): State<AppHandle>, headers: HeaderMap, body: Bytes) -> ApiResult {
+    if body.is_empty() {
+        return Err(bad_request("Request body must be an audio file".to_string()));
+    }
+    let whisper = app.state::<WhisperState>();
+    if !*whisper.is_initialized.lock().unwrap() {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, "Whisper not initialized".to_string()));
+    }
+    let model_path = whisper.model_path.lock().unwrap().clone()
+        .ok_or_else(|| server_error("Whisper model missing".to_string()))?;
+    let language = whisper.language.lock().unwrap().clone();
+    let decoding = whisper.decoding.lock().unwrap().clone();
+
+    let upload_dir = app_data_dir().map_err(server_error)?.join("uploads");
+    std::fs::create_dir_all(&upload_dir).map_err(|e| server_error(e.to_string()))?;
+    let upload = upload_dir.join(format!("{}.{}", uuid::Uuid::new_v4(), upload_extension(&headers)));
+    std::fs::write(&upload, &body).map_err(|e| server_error(format!("Failed to store upload: {}", e)))?;
+    let samples = load_samples(&app, upload.clone()).await;
+    let _ = std::fs::remove_file(&upload);
+    let samples = samples.map_err(bad_request)?;
+
+    let result = transcribe_audio(&model_path, &language, &decoding, &samples).await.map_err(server_error)?;
+    Ok(Json(json!({
+        "text": result.text.trim(),
+        "language": result.language,
+        "confidence": result.confidence,
+        "duration_secs": samples.len() as f32 / TARGET_SAMPLE_RATE as f32,
+    })))
+}
+
+async fn event_stream() -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures_util::stream::unfold(events::subscribe(), |mut rx| async move {
+        let event = match rx.recv().await {
+            Ok((name, payload)) => Event::default().event(name).data(payload.to_string()),
+            // Too slow to keep up; say so instead of silently skipping
+            Err(RecvError::Lagged(missed)) => Event::default().comment(format!("missed {} events", missed)),
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(event), rx))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+fn router(app: AppHandle, token: Option<String>) -> Router {
+    Router::new()
+        .route("/status", get(status))
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/start", post(start_session))
+        .route("/sessions/end", post(end_session))
+        .route("/sessions/{id}", get(get_session))
+        .route("/search", get(search))
+        .route("/transcribe", post(transcribe).layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES)))
+        .route("/events", get(event_stream))
+        .layer(middleware::from_fn_with_state(token, require_token))
+        .with_state(app)
+}
+
+// ============================================================================
+// Server lifecycle
+// ============================================================================
+
+/// (Re)start the server with `config`; stops it when disabled
+pub async fn apply(app: &AppHandle, config: &HttpApiConfig) -> Result<HttpApiStatus, String> {
+    let state = app.state::<HttpApiState>();
+    state.stop();
+    if !config.enabled {
+        return Ok(HttpApiStatus { running: false, address: None });
+    }
+
+    let addr = config.address()?;
+    let listener = tokio::net::TcpListener::bind(addr).await
+        .map_err(|e| format!("Failed to listen on {}: {}", addr, e))?;
+    let (shutdown, stopped) = oneshot::channel::<()>();
+    let router = router(app.clone(), config.token.clone());
+    tauri::async_runtime::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = stopped.await;
+        });
+        if let Err(e) = server.await {
+            error!("[HTTP] ✗ Server stopped: {}", e);
+        }
+    });
+
+    *state.server.lock().unwrap() = Some(RunningServer { addr, shutdown });
+    info!("[HTTP] ✓ API listening on http://{}", addr);
+    Ok(HttpApiStatus { running: true, address: Some(addr.to_string()) })
+}
+
+/// Startup: honor the stored config, forcing the API on when headless
+pub fn start_in_background(app: &AppHandle, headless: bool) {
+    let mut config = app.state::<SettingsState>().get().http_api;
+    config.enabled |= headless;
+    if !config.enabled {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = apply(&app, &config).await {
+            error!("[HTTP] ✗ API not started: {}", e);
+        }
+        if headless {
+            load_whisper(&app).await;
+        }
+    });
+}
+
+/// Headless has no UI to pick a model: reuse the last one loaded, else base
+async fn load_whisper(app: &AppHandle) {
+    let cache = app.state::<WhisperState>().model_cache();
+    let size = whisper_models::last_used_size(&cache).unwrap_or("base");
+    if let Err(e) = whisper_client::initialize_whisper(app.state(), app.clone(), Some(size.to_string())).await {
+        error!("[HTTP] ✗ Whisper not loaded: {}", e);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_http_api_config(settings: tauri::State<'_, SettingsState>) -> HttpApiConfig {
+    settings.get().http_api.redacted()
+}
+
+#[tauri::command]
+pub fn get_http_api_status(state: tauri::State<'_, HttpApiState>) -> HttpApiStatus {
+    let server = state.server.lock().unwrap();
+    HttpApiStatus {
+        running: server.is_some(),
+        address: server.as_ref().map(|s| s.addr.to_string()),
+    }
+}
+
+/// Save and apply; the server restarts on the new address/token
+#[tauri::command]
+pub async fn set_http_api_config(app: AppHandle, config: HttpApiConfig) -> Result<HttpApiStatus, String> {
+    let stored = app.state::<SettingsState>().get().http_api;
+    let token = unredact(config.token, stored.token);
+    let config = HttpApiConfig { bind: config.bind.trim().to_string(), token, ..config };
+    config.address()?;
+
+    app.state::<SettingsState>().update(|s| s.http_api = config.clone())?;
+    apply(&app, &config).await
+}
@@ -0,0 +1,96 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+// ============================================================================
+// IN-FLIGHT REQUESTS - Bounded Concurrency, Results in Submission Order
+// ============================================================================
+//
+// Up to `limit` jobs run at once; the rest wait their turn. Results are
+// handed back strictly in the order jobs were pushed, so a slow request
+// holds back the ones after it instead of being overtaken. The limit is
+// passed on every poll, so changing it takes effect immediately.
+
+type Job<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+pub(crate) struct InFlight<T> {
+    waiting: VecDeque<(u64, Job<T>)>,
+    running: usize,
+    next_seq: u64,
+    next_out: u64,
+    finished: BTreeMap<u64, T>,
+    tx: UnboundedSender<(u64, T)>,
+    rx: UnboundedReceiver<(u64, T)>,
+}
+
+impl<T: Send + 'static> InFlight<T> {
+    pub fn new() -> Self {
+        let (tx, rx) = unbounded_channel();
+        Self {
+            waiting: VecDeque::new(),
+            running: 0,
+            next_seq: 0,
+            next_out: 0,
+            finished: BTreeMap::new(),
+            tx,
+            rx,
+        }
+    }
+
+    /// Queue a job; it starts on a later poll once a slot is free
+    pub fn push(&mut self, job: impl Future<Output = T> + Send + 'static) {
+        self.waiting.push_back((self.next_seq, Box::pin(job)));
+        self.next_seq += 1;
+    }
+
+    /// Jobs queued, running or finished but not yet handed back
+    pub fn pending(&self) -> usize {
+        (self.next_seq - self.next_out) as usize
+    }
+
+    fn collect(&mut self, seq: u64, result: T) {
+        self.running -= 1;
+        self.finished.insert(seq, result);
+    }
+
+    fn start_waiting(&mut self, limit: usize) {
+        while self.running < limit.max(1) {
+            let Some((seq, job)) = self.waiting.pop_front() else { break; };
+            let tx = self.tx.clone();
+            self.running += 1;
+            tauri::async_runtime::spawn(async move {
+                let _ = tx.send((seq, job.await));
+            });
+        }
+    }
+
+    fn take_in_order(&mut self) -> Vec<T> {
+        let mut ready = Vec::new();
+        while let Some(result) = self.finished.remove(&self.next_out) {
+            ready.push(result);
+            self.next_out += 1;
+        }
+        ready
+    }
+
+    /// Start queued jobs up to `limit` and return results that are next in order
+    pub fn poll(&mut self, limit: usize) -> Vec<T> {
+        while let Ok((seq, result)) = self.rx.try_recv() {
+            self.collect(seq, result);
+        }
+        self.start_waiting(limit);
+        self.take_in_order()
+    }
+
+    /// Wait for every queued and running job; results in order
+    pub async fn drain(&mut self, limit: usize) -> Vec<T> {
+        let mut ready = self.poll(limit);
+        while self.pending() > 0 {
+            let Some((seq, result)) = self.rx.recv().await else { break; };
+            self.collect(seq, result);
+            ready.extend(self.poll(limit));
+        }
+        ready
+    }
+}
@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use chrono::{NaiveDate, Utc};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::action_items::{ActionItemState, TrackedActionItem, LIVE_SESSION_ID};
+use crate::network::NetworkState;
+use crate::session_manager::{IssueLink, SessionManager};
+use crate::settings::{redact, SettingsState, REDACTED};
+
+// ============================================================================
+// ISSUE TRACKERS - Jira / GitHub Issues from Action Items
+// ============================================================================
+//
+// API tokens live in the OS keychain (Keychain, Credential Manager, Secret
+// Service); settings.json only keeps the non-secret parts, and tokens saved
+// there by older versions are moved over at startup. The created issue's URL
+// is written onto the tracked item and, for stored sessions, into the session
+// file under the item's id so it survives the tracker being rebuilt.
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const KEYCHAIN_SERVICE: &str = "com.cognivox.issue-trackers";
+const MAX_TITLE_CHARS: usize = 120;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct JiraConfig {
+    // https://your-team.atlassian.net
+    pub base_url: String,
+    pub email: String,
+    pub api_token: Option<String>,
+    pub project_key: String,
+    // "Task" when empty
+    pub issue_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GitHubConfig {
+    pub owner: String,
+    pub repo: String,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct IssueTrackerConfig {
+    pub jira: JiraConfig,
+    pub github: GitHubConfig,
+    // Speaker/assignee name -> Jira accountId or GitHub login
+    pub assignees: HashMap<String, String>,
+}
+
+impl IssueTrackerConfig {
+    fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        config.jira.api_token = redact(stored_token(IssueTarget::Jira).as_deref());
+        config.github.token = redact(stored_token(IssueTarget::Github).as_deref());
+        config
+    }
+
+    fn assignee(&self, name: Option<&str>) -> Option<&String> {
+        let name = name?.trim();
+        self.assignees.iter()
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueTarget {
+    Jira,
+    Github,
+}
+
+impl IssueTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            IssueTarget::Jira => "jira",
+            IssueTarget::Github => "github",
+        }
+    }
+}
+
+// ============================================================================
+// Keychain
+// ============================================================================
+
+fn keychain_entry(target: IssueTarget) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, target.as_str())
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+fn stored_token(target: IssueTarget) -> Option<String> {
+    keychain_entry(target).ok()?.get_password().ok()
+}
+
+/// Save or (None) forget a tracker's token
+fn store_token(target: IssueTarget, token: Option<&str>) -> Result<(), String> {
+    let entry = keychain_entry(target)?;
+    let result = match token {
+        Some(token) => entry.set_password(token),
+        None => match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            other => other,
+        },
+    };
+    result.map_err(|e| format!("Could not update the {} token in the keychain: {}", target.as_str(), e))
+}
+
+/// Move tokens an older version saved in settings.json into the keychain
+pub fn move_tokens_to_keychain(settings: &SettingsState) {
+    let config = settings.get().issue_trackers;
+    let plaintext = [
+        (IssueTarget::Jira, config.jira.api_token),
+        (IssueTarget::Github, config.github.token),
+    ];
+    for (target, token) in plaintext {
+        let Some(token) = token else { continue };
+        if let Err(e) = store_token(target, Some(&token)) {
+            warn!("[ISSUES] {} token left in settings: {}", target.as_str(), e);
+            continue;
+        }
+        let result = settings.update(|s| match target {
+            IssueTarget::Jira => s.issue_trackers.jira.api_token = None,
+            IssueTarget::Github => s.issue_trackers.github.token = None,
+        });
+        match result {
+            Ok(_) => info!("[ISSUES] Moved the {} token to the keychain", target.as_str()),
+            Err(e) => warn!("[ISSUES] {} token copied to the keychain but not removed from settings: {}", target.as_str(), e),
+        }
+    }
+}
+
+fn title(item: &TrackedActionItem) -> String {
+    let line = item.description.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_TITLE_CHARS - 1).collect();
+    format!("{}…", cut)
+}
+
+/// Only ISO dates can go in a due-date field; anything else stays in the text
+fn iso_due_date(item: &TrackedActionItem) -> Option<String> {
+    let due = item.due_date.as_deref()?.trim();
+    NaiveDate::parse_from_str(due, "%Y-%m-%d").ok().map(|d| d.to_string())
+}
+
+fn description_lines(item: &TrackedActionItem) -> Vec<String> {
+    let mut lines = vec![item.description.clone(), String::new()];
+    lines.push(format!("Raised by: {}", item.speaker));
+    if let Some(assignee) = &item.assignee { lines.push(format!("Assignee: {}", assignee)); }
+    if let Some(due) = &item.due_date { lines.push(format!("Due: {}", due)); }
+    if let Some(ms) = item.start_ms {
+        lines.push(format!("At: {:02}:{:02} into the meeting", ms / 60_000, (ms / 1000) % 60));
+    }
+    lines.push("Created from a Cognivox action item".to_string());
+    lines
+}
+
+async fn create_github_issue(
+    client: &reqwest::Client,
+    config: &IssueTrackerConfig,
+    item: &TrackedActionItem,
+) -> Result<(String, String), String> {
+    let gh = &config.github;
+    let token = stored_token(IssueTarget::Github).ok_or("GitHub is not configured")?;
+    if gh.owner.is_empty() || gh.repo.is_empty() {
+        return Err("GitHub owner and repo are required".to_string());
+    }
+
+    let mut body = serde_json::json!({
+        "title": title(item),
+        "body": description_lines(item).join("\n"),
+    });
+    if let Some(login) = config.assignee(item.assignee.as_deref()) {
+        body["assignees"] = serde_json::json!([login]);
+    }
+
+    let resp = client.post(format!("{}/repos/{}/{}/issues", GITHUB_API_URL, gh.owner, gh.repo))
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "Cognivox")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {}", e))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("GitHub error {}: {}", status, body));
+    }
+    let created: serde_json::Value = resp.json().await.map_err(|e| format!("Invalid GitHub response: {}", e))?;
+    let url = created["html_url"].as_str().ok_or("GitHub response has no issue URL")?;
+    let number = created["number"].as_u64().map(|n| format!("#{}", n)).unwrap_or_default();
+    Ok((number, url.to_string()))
+}
+
+async fn create_jira_issue(
+    client: &reqwest::Client,
+    config: &IssueTrackerConfig,
+    item: &TrackedActionItem,
+) -> Result<(String, String), String> {
+    let jira = &config.jira;
+    let token = stored_token(IssueTarget::Jira).ok_or("Jira is not configured")?;
+    if jira.base_url.is_empty() || jira.email.is_empty() || jira.project_key.is_empty() {
+        return Err("Jira base URL, email and project key are required".to_string());
+    }
+    let base_url = jira.base_url.trim_end_matches('/');
+
+    // API v3 descriptions are Atlassian Document Format
+    let paragraphs: Vec<serde_json::Value> = description_lines(item).into_iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| serde_json::json!({ "type": "paragraph", "content": [{ "type": "text", "text": l }] }))
+        .collect();
+    let mut fields = serde_json::json!({
+        "project": { "key": jira.project_key },
+        "summary": title(item),
+        "issuetype": { "name": if jira.issue_type.is_empty() { "Task" } else { jira.issue_type.as_str() } },
+        "description": { "type": "doc", "version": 1, "content": paragraphs },
+    });
+    if let Some(account_id) = config.assignee(item.assignee.as_deref()) {
+        fields["assignee"] = serde_json::json!({ "accountId": account_id });
+    }
+    if let Some(due) = iso_due_date(item) {
+        fields["duedate"] = serde_json::json!(due);
+    }
+
+    let resp = client.post(format!("{}/rest/api/3/issue", base_url))
+        .basic_auth(&jira.email, Some(token))
+        .json(&serde_json::json!({ "fields": fields }))
+        .send()
+        .await
+        .map_err(|e| format!("Jira request failed: {}", e))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Jira error {}: {}", status, body));
+    }
+    let created: serde_json::Value = resp.json().await.map_err(|e| format!("Invalid Jira response: {}", e))?;
+    let key = created["key"].as_str().ok_or("Jira response has no issue key")?;
+    Ok((key.to_string(), format!("{}/browse/{}", base_url, key)))
+}
+
+fn find_item(state: &ActionItemState, item_id: &str) -> Option<TrackedActionItem> {
+    state.items.lock().unwrap()
+        .values()
+        .flatten()
+        .find(|i| i.id == item_id)
+        .cloned()
+}
+
+/// Remember the issue on the tracked item and in its session file
+fn record_issue(app: &AppHandle, item: &TrackedActionItem, link: IssueLink) -> Result<(), String> {
+    let state = app.state::<ActionItemState>();
+    if let Some(tracked) = state.items.lock().unwrap()
+        .get_mut(&item.session_id)
+        .and_then(|list| list.iter_mut().find(|i| i.id == item.id))
+    {
+        tracked.issue_url = Some(link.url.clone());
+    }
+
+    if item.session_id == LIVE_SESSION_ID {
+        return Ok(());
+    }
+    SessionManager::new()?.update_session(&item.session_id, |session| {
+        session.issue_links.retain(|l| l.item_id != link.item_id);
+        session.issue_links.push(link);
+        Ok(())
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_issue_tracker_config(settings: tauri::State<'_, SettingsState>) -> IssueTrackerConfig {
+    settings.get().issue_trackers.redacted()
+}
+
+#[tauri::command]
+pub fn set_issue_tracker_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: IssueTrackerConfig,
+) -> Result<String, String> {
+    let tokens = [
+        (IssueTarget::Jira, config.jira.api_token),
+        (IssueTarget::Github, config.github.token),
+    ];
+    let config = IssueTrackerConfig {
+        jira: JiraConfig {
+            base_url: config.jira.base_url.trim().trim_end_matches('/').to_string(),
+            email: config.jira.email.trim().to_string(),
+            api_token: None,
+            project_key: config.jira.project_key.trim().to_uppercase(),
+            issue_type: config.jira.issue_type.trim().to_string(),
+        },
+        github: GitHubConfig {
+            owner: config.github.owner.trim().to_string(),
+            repo: config.github.repo.trim().to_string(),
+            token: None,
+        },
+        assignees: config.assignees.into_iter()
+            .filter(|(k, v)| !k.trim().is_empty() && !v.trim().is_empty())
+            .collect(),
+    };
+    if !config.jira.base_url.is_empty() && !config.jira.base_url.starts_with("https://") {
+        return Err("Jira base URL must start with https://".to_string());
+    }
+
+    for (target, token) in tokens {
+        match token.as_deref().map(str::trim) {
+            // Unchanged
+            Some(REDACTED) => {}
+            Some("") | None => store_token(target, None)?,
+            Some(token) => store_token(target, Some(token))?,
+        }
+    }
+    settings.update(|s| s.issue_trackers = config)?;
+    Ok("Issue tracker settings saved".to_string())
+}
+
+/// Create a Jira or GitHub issue for a tracked action item; returns its URL
+#[tauri::command]
+pub async fn create_issue_from_action_item(
+    app: AppHandle,
+    item_id: String,
+    target: IssueTarget,
+) -> Result<String, String> {
+    let item = find_item(&app.state::<ActionItemState>(), &item_id)
+        .ok_or("Action item not found - load the session's action items first")?;
+    if let Some(url) = &item.issue_url {
+        return Err(format!("An issue already exists for this item: {}", url));
+    }
+
+    let config = app.state::<SettingsState>().get().issue_trackers;
+    let client = app.state::<NetworkState>().client()?;
+    let (key, url) = match target {
+        IssueTarget::Github => create_github_issue(&client, &config, &item).await?,
+        IssueTarget::Jira => create_jira_issue(&client, &config, &item).await?,
+    };
+    info!("[ISSUES] ✓ Created {} issue {} for action item {}", target.as_str(), key, item.id);
+
+    record_issue(&app, &item, IssueLink {
+        item_id: Some(item.id.clone()),
+        description: item.description.clone(),
+        tracker: target.as_str().to_string(),
+        key,
+        url: url.clone(),
+        created_at: Utc::now().to_rfc3339(),
+    })?;
+    Ok(url)
+}
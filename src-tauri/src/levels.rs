@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+// ============================================================================
+// LEVELS - Input Level Metering & Segment Loudness Normalization
+// ============================================================================
+
+const TARGET_SEGMENT_RMS: f32 = 0.1;        // ~ -20 dBFS, comfortable for Whisper
+const MAX_SEGMENT_GAIN: f32 = 30.0;         // Don't amplify pure noise beyond ~+30 dB
+const PEAK_CEILING: f32 = 0.95;             // Leave headroom after gain
+const CLIP_LEVEL: f32 = 0.99;
+const CLIP_WARN_RATIO: f32 = 0.001;         // >0.1% of samples at full scale = clipping
+const QUIET_WARN_DBFS: f32 = -50.0;         // Speech this quiet needs more mic gain
+const LEVEL_SMOOTHING: f32 = 0.3;           // EMA weight for the meter
+
+fn to_dbfs(value: f32) -> f32 {
+    if value <= 1e-9 { -180.0 } else { 20.0 * value.log10() }
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0f32, |m, s| m.max(s.abs()))
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() { return 0.0; }
+    (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// Smoothed input level so the UI can warn about mic gain
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct InputLevel {
+    pub rms: f32,
+    pub peak: f32,
+    pub rms_dbfs: f32,
+    pub clipping: bool,
+    pub too_quiet: bool,
+}
+
+impl InputLevel {
+    /// Fold a fresh block of samples into the meter
+    pub fn update(&mut self, samples: &[f32]) {
+        if samples.is_empty() { return; }
+        let block_rms = rms(samples);
+        let block_peak = peak(samples);
+        let clipped = samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count();
+
+        self.rms = self.rms + (block_rms - self.rms) * LEVEL_SMOOTHING;
+        self.peak = block_peak.max(self.peak * (1.0 - LEVEL_SMOOTHING));
+        self.rms_dbfs = to_dbfs(self.rms);
+        self.clipping = clipped as f32 / samples.len() as f32 > CLIP_WARN_RATIO;
+        self.too_quiet = self.rms_dbfs < QUIET_WARN_DBFS;
+    }
+}
+
+/// What normalization did to a segment
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct SegmentGain {
+    pub input_rms_dbfs: f32,
+    pub input_peak: f32,
+    pub gain_db: f32,
+    pub clipped: bool,
+}
+
+/// Bring a finalized speech segment to a consistent RMS loudness,
+/// limited so the peak stays under the ceiling.
+pub fn normalize_segment(samples: &mut [f32]) -> SegmentGain {
+    let input_rms = rms(samples);
+    let input_peak = peak(samples);
+    let clipped_count = samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count();
+
+    let mut gain = if input_rms > 0.0 { TARGET_SEGMENT_RMS / input_rms } else { 1.0 };
+    gain = gain.min(MAX_SEGMENT_GAIN);
+    if input_peak > 0.0 {
+        gain = gain.min(PEAK_CEILING / input_peak);
+    }
+
+    if (gain - 1.0).abs() > 0.01 {
+        for s in samples.iter_mut() {
+            *s *= gain;
+        }
+    }
+
+    SegmentGain {
+        input_rms_dbfs: to_dbfs(input_rms),
+        input_peak,
+        gain_db: to_dbfs(gain),
+        clipped: !samples.is_empty() && clipped_count as f32 / samples.len() as f32 > CLIP_WARN_RATIO,
+    }
+}
@@ -1,136 +1,395 @@
+mod action_items;
+mod alerts;
+mod analytics;
+mod archive;
 mod audio_capture;
+mod audio_channel;
+mod batching;
+mod bookmarks;
+mod calendar;
+mod chapters;
+mod dedupe;
+mod cli;
+mod denoise;
+mod echo;
+mod embeddings;
+mod encryption;
+mod event_journal;
+mod events;
+mod file_import;
+mod followup;
 mod gemini_client;
+mod hallucination;
+mod hotkeys;
+mod http_api;
+mod inflight;
+mod issues;
+mod levels;
+mod live_session;
+mod logging;
+mod loopback;
+mod mcp;
+mod metrics;
+mod network;
+mod overlay;
+mod participants;
+mod pipeline_status;
+mod plugins;
+mod whisper_benchmark;
 mod whisper_client;
+mod whisper_models;
 mod processing_engine;
+mod reanalysis;
+mod recorder;
+mod recovery;
+mod redaction;
+mod report;
+mod resources;
+mod response_cache;
+mod retry_queue;
+mod schema;
 mod session_manager;
-use audio_capture::{AudioState, TaggedAudio};
+mod settings;
+mod shutdown;
+mod simulation;
+mod slack;
+mod speakers;
+mod summarizer;
+mod task_export;
+mod templates;
+mod transcript_monitor;
+mod titling;
+mod translation;
+mod tray;
+mod vault;
+mod voice_commands;
+use action_items::ActionItemState;
+use alerts::AlertState;
+use audio_capture::AudioState;
+use dedupe::DedupeState;
+use embeddings::EmbeddingState;
+use event_journal::EventJournalState;
+use file_import::FolderImportState;
 use gemini_client::GeminiState;
+use http_api::HttpApiState;
+use live_session::LiveSessionState;
+use metrics::MetricsState;
+use network::NetworkState;
+use participants::ParticipantState;
+use plugins::PluginState;
+use reanalysis::ReanalysisState;
+use recorder::RecorderState;
+use resources::ResourceState;
+use retry_queue::RetryQueueState;
+use session_manager::WebhookManager;
+use settings::SettingsState;
+use shutdown::ShutdownState;
+use speakers::SpeakerState;
+use transcript_monitor::TranscriptMonitorState;
+use voice_commands::VoiceCommandState;
 use whisper_client::WhisperState;
-use std::sync::Mutex;
-use crossbeam_channel::unbounded;
-use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState},
-    Emitter, Manager,
-};
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
+use tracing::error;
 
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Headless MCP server for Claude Desktop and other agents (`--mcp`)
+pub fn run_mcp_server() {
+    logging::init(true);
+    let encryption = SettingsState::load().get().encryption;
+    encryption::init(&encryption);
+    encryption::unlock_from_env(&encryption);
+    mcp::run_stdio();
+}
+
+/// `cognivox transcribe ...` without a window; returns the exit code
+pub fn run_cli(args: &[String]) -> i32 {
+    logging::init(true);
+    cli::run(args)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let (audio_tx, audio_rx) = unbounded::<TaggedAudio>();
+    logging::init(false);
+    let (audio_tx, audio_rx) = audio_channel::channel(audio_channel::AUDIO_QUEUE_SECS);
+
+    let settings_state = SettingsState::load();
+    if let Err(e) = logging::apply_level(&settings_state.get().log_level) {
+        tracing::warn!("[LOG] {}", e);
+    }
+    encryption::init(&settings_state.get().encryption);
+    let network_state = NetworkState::new(&settings_state.get().network, settings_state.get().privacy_mode);
+    let webhook_manager = WebhookManager::new(settings_state.get().webhooks);
 
     let audio_state = AudioState {
         audio_tx: Mutex::new(Some(audio_tx)),
+        noise_suppression: Mutex::new(settings_state.get().noise_suppression),
+        echo_cancellation: Mutex::new(settings_state.get().echo_cancellation),
         ..Default::default()
     };
 
+    let redactor = redaction::Redactor::new(settings_state.get().redaction).unwrap_or_else(|e| {
+        tracing::warn!("[REDACT] Stored rules invalid, using defaults: {}", e);
+        redaction::Redactor::default()
+    });
     let gemini_state = GeminiState {
         audio_rx: Mutex::new(Some(audio_rx)),
+        redactor: Mutex::new(Arc::new(redactor)),
         ..Default::default()
     };
 
-    let whisper_state = WhisperState::default();
+    let whisper_state = WhisperState {
+        model_dir: Mutex::new(settings_state.get().whisper_model_dir.map(std::path::PathBuf::from)),
+        decoding: Mutex::new(settings_state.get().whisper_decoding),
+        ..Default::default()
+    };
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
-            // Create tray menu
-            let show_i = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
-            let record_i = MenuItem::with_id(app, "record", "Start Recording", true, None::<&str>)?;
-            let stop_i = MenuItem::with_id(app, "stop", "Stop Recording", true, None::<&str>)?;
-            let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+            logging::attach_app(app.handle().clone());
+            tray::build_tray(app)?;
+            
+            retry_queue::spawn_retry_worker(app.handle().clone());
+            metrics::spawn_metrics_emitter(app.handle().clone());
+            resources::spawn_resource_monitor(app.handle().clone());
             
-            let menu = Menu::with_items(app, &[&show_i, &record_i, &stop_i, &quit_i])?;
+            let hotkey_config = app.state::<SettingsState>().get().hotkeys;
+            if let Err(e) = hotkeys::register_hotkeys(app.handle(), &hotkey_config) {
+                error!("[HOTKEY] ✗ {}", e);
+            }
+            issues::move_tokens_to_keychain(&app.state::<SettingsState>());
             
-            // Build tray icon
-            let _tray = TrayIconBuilder::new()
-                .icon(app.default_window_icon().unwrap().clone())
-                .menu(&menu)
-                .tooltip("Meeting Mind - Intelligence Engine")
-                .on_menu_event(|app, event| {
-                    match event.id.as_ref() {
-                        "show" => {
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
-                        "record" => {
-                            println!("[TRAY] Start recording triggered");
-                            // Emit event to frontend
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.emit("tray:record", ());
-                            }
-                        }
-                        "stop" => {
-                            println!("[TRAY] Stop recording triggered");
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.emit("tray:stop", ());
-                            }
-                        }
-                        "quit" => {
-                            app.exit(0);
-                        }
-                        _ => {}
-                    }
-                })
-                .on_tray_icon_event(|tray, event| {
-                    if let TrayIconEvent::Click {
-                        button: MouseButton::Left,
-                        button_state: MouseButtonState::Up,
-                        ..
-                    } = event
-                    {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                })
-                .build(app)?;
+            // Meeting-room boxes: no window, driven over the HTTP API
+            let headless = http_api::headless_requested();
+            if headless {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            }
+            http_api::start_in_background(app.handle(), headless);
             
-            println!("[STATION 6] Tray icon initialized - Shadow mode ready");
+            if let Some(interrupted) = recovery::interrupted_session(None) {
+                tracing::warn!("[RECOVERY] Session '{}' was interrupted - recover_last_session can restore it", interrupted.title);
+            }
             
             Ok(())
         })
         .manage(audio_state)
         .manage(gemini_state)
         .manage(whisper_state)
+        .manage(ActionItemState::default())
+        .manage(settings_state)
+        .manage(network_state)
+        .manage(RetryQueueState::load())
+        .manage(RecorderState::default())
+        .manage(webhook_manager)
+        .manage(EmbeddingState::default())
+        .manage(SpeakerState::load())
+        .manage(AlertState::default())
+        .manage(MetricsState::default())
+        .manage(ShutdownState::default())
+        .manage(LiveSessionState::default())
+        .manage(FolderImportState::default())
+        .manage(VoiceCommandState::default())
+        .manage(HttpApiState::default())
+        .manage(PluginState::load())
+        .manage(ParticipantState::default())
+        .manage(DedupeState::default())
+        .manage(TranscriptMonitorState::default())
+        .manage(EventJournalState::default())
+        .manage(ReanalysisState::default())
+        .manage(ResourceState::default())
         .invoke_handler(tauri::generate_handler![
             greet, 
             audio_capture::list_audio_devices,
             audio_capture::start_audio_capture,
+            audio_capture::push_audio_chunk,
+            participants::push_participant_audio,
+            participants::close_participant_track,
+            participants::list_participant_tracks,
             audio_capture::stop_audio_capture,
             audio_capture::set_capture_mode,
+            audio_capture::set_noise_suppression,
+            audio_capture::set_echo_cancellation,
+            audio_capture::get_input_level,
             audio_capture::get_current_volume,
             gemini_client::test_gemini_connection,
             gemini_client::update_gemini_key,
             gemini_client::set_gemini_model,
+            gemini_client::get_generation_config,
+            gemini_client::set_generation_config,
+            gemini_client::set_segment_overlap,
+            gemini_client::get_rate_limit_config,
+            gemini_client::set_rate_limit_config,
             gemini_client::get_available_models,
             gemini_client::process_transcript_with_gemini,
+            gemini_client::pause_listening,
+            gemini_client::resume_listening,
+            gemini_client::set_context_window,
+            gemini_client::clear_conversation_context,
             whisper_client::initialize_whisper,
             whisper_client::set_whisper_language,
+            whisper_client::get_whisper_decoding_config,
+            whisper_client::set_whisper_decoding_config,
+            whisper_benchmark::benchmark_whisper,
+            whisper_models::list_whisper_models,
+            whisper_models::delete_whisper_model,
+            whisper_models::set_whisper_model_dir,
+            whisper_models::get_model_source,
+            whisper_models::set_model_source,
+            http_api::get_http_api_config,
+            http_api::set_http_api_config,
+            http_api::get_http_api_status,
+            plugins::list_plugins,
+            plugins::reload_plugins,
+            plugins::set_plugin_config,
             whisper_client::get_whisper_status,
             whisper_client::transcribe_audio_chunk,
+            file_import::transcribe_file,
+            file_import::process_folder,
+            file_import::cancel_folder_processing,
             processing_engine::validate_json_schema,
             processing_engine::update_processing_settings,
             processing_engine::get_recent_intelligence,
-            processing_engine::clear_intelligence_cache,
             processing_engine::inject_manual_intelligence,
             session_manager::save_session,
+            session_manager::rename_session,
             session_manager::load_session,
             session_manager::list_sessions,
             session_manager::delete_session,
             session_manager::export_session,
+            session_manager::export_subtitles,
+            report::export_report,
+            analytics::get_session_analytics,
+            chapters::generate_chapters,
+            chapters::get_chapters,
+            session_manager::get_webhooks,
+            session_manager::set_webhooks,
+            session_manager::test_webhook,
+            slack::get_slack_config,
+            slack::set_slack_config,
+            slack::post_to_slack,
+            vault::get_vault_config,
+            vault::get_vault_template,
+            vault::set_vault_config,
+            vault::export_to_vault,
+            calendar::get_calendar_config,
+            calendar::set_calendar_config,
+            calendar::get_current_meeting,
+            calendar::attach_calendar_event,
+            embeddings::semantic_search,
+            embeddings::reindex_embeddings,
+            speakers::list_speakers,
+            speakers::enroll_speaker,
+            speakers::remove_speaker,
+            overlay::open_caption_overlay,
+            overlay::position_caption_overlay,
+            overlay::close_caption_overlay,
+            transcript_monitor::list_displays,
+            transcript_monitor::open_transcript_monitor,
+            transcript_monitor::close_transcript_monitor,
+            transcript_monitor::get_transcript_monitor,
+            transcript_monitor::get_transcript_monitor_lines,
+            event_journal::replay_session_events,
+            event_journal::stop_session_replay,
+            event_journal::export_session_events,
+            archive::export_session_archive,
+            archive::import_session_archive,
+            reanalysis::reanalyze_session,
+            reanalysis::cancel_reanalysis,
+            reanalysis::get_session_analyses,
+            reanalysis::compare_session_analyses,
+            resources::get_resource_usage,
+            resources::get_resource_limits,
+            resources::set_resource_limits,
+            titling::generate_session_title,
+            titling::get_auto_title_config,
+            titling::set_auto_title_config,
+            hotkeys::get_hotkeys,
+            hotkeys::set_hotkeys,
+            alerts::get_alert_rules,
+            alerts::set_alert_rules,
+            alerts::get_watch_keywords,
+            alerts::set_watch_keywords,
+            metrics::get_pipeline_metrics,
+            pipeline_status::get_pipeline_status,
+            simulation::start_simulation,
+            simulation::stop_simulation,
+            response_cache::clear_intelligence_cache,
+            logging::get_recent_logs,
+            logging::set_log_level,
+            shutdown::confirm_shutdown_saved,
+            live_session::start_session,
+            live_session::end_session,
+            live_session::get_active_session,
+            recovery::get_interrupted_session,
+            recovery::recover_last_session,
             session_manager::generate_session_summary,
-            session_manager::get_session_summary
+            session_manager::get_session_summary,
+            summarizer::generate_meeting_summary,
+            followup::draft_followup_email,
+            action_items::get_action_items,
+            task_export::export_action_items,
+            issues::get_issue_tracker_config,
+            issues::set_issue_tracker_config,
+            issues::create_issue_from_action_item,
+            settings::get_intelligence_config,
+            settings::set_intelligence_prompt,
+            schema::get_intelligence_schema,
+            schema::set_intelligence_schema,
+            settings::set_categories,
+            templates::get_meeting_templates,
+            templates::set_meeting_type,
+            templates::save_meeting_template,
+            templates::delete_meeting_template,
+            templates::reset_meeting_templates,
+            voice_commands::get_voice_command_config,
+            voice_commands::set_voice_command_config,
+            bookmarks::add_bookmark,
+            bookmarks::get_bookmarks,
+            translation::get_translation_config,
+            translation::set_translation_config,
+            hallucination::get_hallucination_rules,
+            hallucination::set_hallucination_rules,
+            dedupe::get_dedupe_rules,
+            dedupe::set_dedupe_rules,
+            encryption::get_encryption_status,
+            encryption::enable_encryption,
+            encryption::unlock_encryption,
+            encryption::change_passphrase,
+            redaction::get_redaction_rules,
+            redaction::set_redaction_rules,
+            redaction::preview_redaction,
+            network::get_network_config,
+            network::set_network_config,
+            network::get_privacy_mode,
+            network::set_privacy_mode,
+            retry_queue::get_retry_queue,
+            retry_queue::retry_pending_now,
+            retry_queue::clear_retry_queue,
+            recorder::start_recording,
+            recorder::stop_recording
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Flush buffered speech and let the frontend save before quitting
+            match event {
+                tauri::RunEvent::ExitRequested { api, .. } if !shutdown::should_exit(app) => api.prevent_exit(),
+                // Closing the main window quits; on Windows/Linux that never reaches ExitRequested first
+                tauri::RunEvent::WindowEvent {
+                    label,
+                    event: tauri::WindowEvent::CloseRequested { api, .. },
+                    ..
+                } if label == shutdown::MAIN_WINDOW && !shutdown::should_exit(app) => api.prevent_close(),
+                _ => {}
+            }
+        });
 }
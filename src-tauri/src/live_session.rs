@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tokio::time::{sleep, timeout, Duration, Instant};
+use tracing::{info, warn};
+use crate::audio_capture::{self, AudioState};
+use crate::calendar;
+use crate::dedupe::DedupeState;
+use crate::events::{self, SessionEvent, SessionPhase};
+use crate::event_journal;
+use crate::gemini_client::{self, extract_json, GeminiState};
+use crate::participants;
+use crate::recorder::{self, RecorderState};
+use crate::recovery;
+use crate::session_manager::{SessionData, SessionManager, TranscriptEntry};
+use crate::settings::SettingsState;
+use crate::summarizer;
+use crate::titling;
+use crate::whisper_client::SegmentLanguage;
+
+// ============================================================================
+// LIVE SESSION - start_session / end_session Lifecycle
+// ============================================================================
+//
+// A live session owns the audio loop: start_session creates the stored
+// record and starts the loop, end_session flushes buffered speech, stops the
+// loop and kicks off the meeting summary. Segments are appended to the stored
+// record as they are analyzed, so a session survives without the frontend.
+
+const CAPTURE_DRAIN_MS: u64 = 250;
+const END_FLUSH_TIMEOUT_SECS: u64 = 20;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ActiveSession {
+    pub id: String,
+    pub title: String,
+    pub started_at: String,
+    #[serde(skip)]
+    started: Option<Instant>,
+}
+
+/// Latest finalized live segment, for commands that refer to "that"
+#[derive(Serialize, Clone, Debug)]
+pub struct RecentSegment {
+    pub segment_id: String,
+    pub session_id: Option<String>,
+    pub speaker: String,
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+#[derive(Default)]
+pub struct LiveSessionState {
+    active: StdMutex<Option<ActiveSession>>,
+    last_segment: StdMutex<Option<RecentSegment>>,
+}
+
+impl LiveSessionState {
+    pub fn active(&self) -> Option<ActiveSession> {
+        self.active.lock().unwrap().clone()
+    }
+
+    pub fn active_id(&self) -> Option<String> {
+        self.active.lock().unwrap().as_ref().map(|s| s.id.clone())
+    }
+
+    /// Milliseconds since the active session started
+    pub fn elapsed_ms(&self) -> Option<u64> {
+        self.active.lock().unwrap().as_ref()?.started.map(|s| s.elapsed().as_millis() as u64)
+    }
+
+    /// Follow a title change of the stored record
+    pub fn rename(&self, session_id: &str, title: &str) {
+        if let Some(active) = self.active.lock().unwrap().as_mut().filter(|a| a.id == session_id) {
+            active.title = title.to_string();
+        }
+    }
+
+    pub fn remember_segment(&self, segment: RecentSegment) {
+        *self.last_segment.lock().unwrap() = Some(segment);
+    }
+
+    pub fn last_segment(&self) -> Option<RecentSegment> {
+        self.last_segment.lock().unwrap().clone()
+    }
+}
+
+fn default_title() -> String {
+    format!("Session {}", chrono::Local::now().format("%Y-%m-%d %H:%M"))
+}
+
+/// Append an analyzed (or pending) segment to a stored session.
+/// `intelligence` is the raw model JSON when analysis succeeded.
+pub fn record_segment(
+    session_id: &str,
+    segment_id: &str,
+    transcript: &str,
+    speaker: &str,
+    intelligence: Option<&str>,
+    offsets: (Option<u64>, Option<u64>),
+    language: Option<&SegmentLanguage>,
+) {
+    let result = SessionManager::new().and_then(|manager| manager.update_session(session_id, |session| {
+        let mut entry = entry_from(segment_id, transcript, speaker, intelligence, offsets, language);
+        // Journal first: the JSON rewrite below is the step a crash can interrupt
+        recovery::append(session_id, &entry);
+
+        // A retried segment replaces its pending entry
+        match session.transcripts.iter_mut().find(|t| t.segment_id.as_deref() == Some(segment_id)) {
+            Some(existing) => {
+                // Retries don't carry the language; keep the pending entry's
+                if entry.language.is_none() {
+                    entry.language = existing.language.take();
+                    entry.language_probability = existing.language_probability;
+                }
+                *existing = entry;
+            }
+            None => session.add_transcript(entry),
+        }
+        Ok(())
+    }));
+    if let Err(e) = result {
+        warn!("[SESSION] Segment {} not stored in {}: {}", segment_id, session_id, e);
+    }
+}
+
+pub(crate) fn entry_from(
+    segment_id: &str,
+    transcript: &str,
+    speaker: &str,
+    intelligence: Option<&str>,
+    (start_ms, end_ms): (Option<u64>, Option<u64>),
+    language: Option<&SegmentLanguage>,
+) -> TranscriptEntry {
+    let parsed = intelligence
+        .and_then(|i| serde_json::from_str::<serde_json::Value>(extract_json(i)).ok())
+        .unwrap_or(serde_json::Value::Null);
+    TranscriptEntry {
+        timestamp: Utc::now().to_rfc3339(),
+        speaker_id: speaker.to_string(),
+        text: transcript.to_string(),
+        tone: parsed["tone"].as_str().map(|s| s.to_string()),
+        category: parsed["category"].as_array().map(|c| {
+            c.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect()
+        }),
+        confidence: parsed["confidence"].as_f64().unwrap_or(0.0) as f32,
+        start_ms,
+        end_ms,
+        segment_id: Some(segment_id.to_string()),
+        language: language.map(|l| l.code.clone()),
+        language_probability: language.and_then(|l| l.probability),
+    }
+}
+
+/// Close out the active session: flush buffered speech, stop the audio loop,
+/// finalize the recording and update the stored record. Returns the session.
+pub async fn finish_active(app: &AppHandle) -> Result<Option<SessionData>, String> {
+    let Some(active) = app.state::<LiveSessionState>().active() else {
+        return Ok(None);
+    };
+
+    if app.state::<AudioState>().stop_capture()? {
+        sleep(Duration::from_millis(CAPTURE_DRAIN_MS)).await;
+    }
+    let gemini = app.state::<GeminiState>();
+    if !gemini.flush_audio_loop(true, Duration::from_secs(END_FLUSH_TIMEOUT_SECS)).await {
+        warn!("[SESSION] Flush timed out after {}s", END_FLUSH_TIMEOUT_SECS);
+    }
+    let tracks = participants::flush_all(app, Some(active.id.clone()));
+    if timeout(Duration::from_secs(END_FLUSH_TIMEOUT_SECS), tracks).await.is_err() {
+        warn!("[SESSION] Participant tracks not flushed after {}s", END_FLUSH_TIMEOUT_SECS);
+    }
+
+    let recorder = app.state::<RecorderState>();
+    if recorder.is_active() {
+        if let Err(e) = recorder::finish_recording(&recorder) {
+            warn!("[SESSION] Recording not finalized: {}", e);
+        }
+    }
+
+    // Only now stop scoping segments to it - the flush above still belongs here
+    *app.state::<LiveSessionState>().active.lock().unwrap() = None;
+
+    let session = close_record(app, &active)?;
+    recovery::end(&session.id);
+    Ok(Some(session))
+}
+
+/// Fill in the stored record's totals once no more segments go to it
+fn close_record(app: &AppHandle, active: &ActiveSession) -> Result<SessionData, String> {
+    let session = SessionManager::new()?.update_session(&active.id, |session| {
+        let speakers: HashSet<&str> = session.transcripts.iter().map(|t| t.speaker_id.as_str()).collect();
+        session.metadata.total_speakers = speakers.len();
+        session.metadata.total_transcripts = session.transcripts.len();
+        session.metadata.duration_seconds = active.started.map(|s| s.elapsed().as_secs()).unwrap_or(0);
+        Ok(session.clone())
+    })?;
+
+    info!("[SESSION] ■ Ended {} ({} segment(s), {}s)",
+          session.id, session.transcripts.len(), session.metadata.duration_seconds);
+    events::emit(app, &SessionEvent {
+        phase: SessionPhase::Ended,
+        session_id: session.id.clone(),
+        title: session.metadata.title.clone(),
+    });
+    event_journal::close(Some(&session.id));
+    Ok(session)
+}
+
+/// Stored record and crash journal for a session that starts now
+fn open_record(title: String, default_title: bool) -> Result<ActiveSession, String> {
+    let mut session = SessionData::new(title.clone());
+    session.metadata.default_title = default_title;
+    SessionManager::new()?.save_session(&session)?;
+
+    let started = ActiveSession {
+        id: session.id.clone(),
+        title,
+        started_at: session.created_at.clone(),
+        started: Some(Instant::now()),
+    };
+    if let Err(e) = recovery::begin(&started) {
+        warn!("[SESSION] Crash recovery unavailable: {}", e);
+    }
+    Ok(started)
+}
+
+/// "Standup (part 3)" after "Standup (part 2)" or "Standup"
+fn next_part_title(title: &str) -> String {
+    let part = title.strip_suffix(')')
+        .and_then(|t| t.rsplit_once(" (part "))
+        .and_then(|(base, n)| n.parse::<u32>().ok().map(|n| (base, n)));
+    match part {
+        Some((base, n)) => format!("{} (part {})", base, n + 1),
+        None => format!("{} (part 2)", title),
+    }
+}
+
+/// Continue the live session in a new stored record without stopping capture,
+/// e.g. when it hits a resource limit. Returns the new session.
+pub fn rollover(app: &AppHandle) -> Result<ActiveSession, String> {
+    let state = app.state::<LiveSessionState>();
+    let mut active = state.active.lock().unwrap();
+    let previous = active.clone().ok_or("No session is running")?;
+    // Parts of an untitled session get titled on their own
+    let untitled = SessionManager::new()?.load_session(&previous.id)
+        .map(|s| s.metadata.default_title || s.metadata.auto_titled)
+        .unwrap_or(false);
+    let next = open_record(next_part_title(&previous.title), untitled)?;
+    // Buffered track speech belongs to the part that ends; the next part's tracks start at 0
+    participants::close_all_tracks(app, Some(previous.id.clone()));
+    *active = Some(next.clone());
+    drop(active);
+
+    let recorder = app.state::<RecorderState>();
+    if recorder.is_active() {
+        if let Err(e) = recorder::finish_recording(&recorder) {
+            warn!("[SESSION] Recording not finalized: {}", e);
+        }
+        if let Err(e) = recorder.start(next.id.clone()) {
+            warn!("[SESSION] Recording not continued: {}", e);
+        }
+    }
+
+    let session = close_record(app, &previous)?;
+    recovery::end(&session.id);
+    wrap_up_in_background(app, &session);
+    titling::schedule_early_title(app, &next.id);
+    info!("[SESSION] ● Rolled over {} into {} '{}'", previous.id, next.id, next.title);
+    events::emit(app, &SessionEvent {
+        phase: SessionPhase::Started,
+        session_id: next.id.clone(),
+        title: next.title.clone(),
+    });
+    Ok(next)
+}
+
+/// Summary, then a final title that can draw on it
+fn wrap_up_in_background(app: &AppHandle, session: &SessionData) {
+    let has_key = app.state::<GeminiState>().api_key.lock().unwrap().is_some();
+    if has_key && !session.transcripts.is_empty() {
+        let app = app.clone();
+        let session_id = session.id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = summarizer::summarize_session(app.clone(), session_id.clone()).await {
+                warn!("[SESSION] Summary failed: {}", e);
+            }
+            titling::title_in_background(&app, &session_id);
+        });
+    }
+}
+
+/// Start a session with native capture (tray and hotkeys). The frontend
+/// follows along through cognivox:session.
+pub fn start_listening(app: &AppHandle) -> Result<ActiveSession, String> {
+    let session = start_session(app.clone(), None)?;
+    if let Err(e) = audio_capture::start_audio_capture(app.state()) {
+        // A session nothing is captured into is no use
+        stop_listening(app);
+        return Err(format!("Capture failed to start: {}", e));
+    }
+    Ok(session)
+}
+
+/// Stop capture and end the live session (tray and hotkeys); the summary
+/// follows in the background as with end_session
+pub fn stop_listening(app: &AppHandle) {
+    if let Err(e) = app.state::<AudioState>().stop_capture() {
+        warn!("[SESSION] Failed to stop capture: {}", e);
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = end_session(app).await {
+            warn!("[SESSION] Not ended: {}", e);
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_active_session(state: tauri::State<'_, LiveSessionState>) -> Option<ActiveSession> {
+    state.active()
+}
+
+#[tauri::command]
+pub fn start_session(app: AppHandle, title: Option<String>) -> Result<ActiveSession, String> {
+    let state = app.state::<LiveSessionState>();
+    let mut active = state.active.lock().unwrap();
+    if let Some(current) = active.as_ref() {
+        return Err(format!("Session '{}' is already running", current.title));
+    }
+
+    let title = title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    let is_default = title.is_none();
+    let title = title.unwrap_or_else(default_title);
+    let started = open_record(title.clone(), is_default)?;
+    *active = Some(started.clone());
+    drop(active);
+
+    // Fresh context and speaker hints for the new meeting
+    let gemini = app.state::<GeminiState>();
+    gemini.context_window.lock().unwrap().clear();
+    gemini.participants.lock().unwrap().clear();
+    app.state::<DedupeState>().clear();
+    gemini_client::ensure_audio_loop(&app);
+
+    if app.state::<SettingsState>().get().calendar.ics_url.is_some() {
+        let app = app.clone();
+        let session_id = started.id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = calendar::attach_current_event(&app, Some(&session_id)).await {
+                warn!("[SESSION] Calendar lookup failed: {}", e);
+            }
+        });
+    }
+
+    titling::schedule_early_title(&app, &started.id);
+    info!("[SESSION] ● Started {} '{}'", started.id, title);
+    events::emit(&app, &SessionEvent {
+        phase: SessionPhase::Started,
+        session_id: started.id.clone(),
+        title,
+    });
+    Ok(started)
+}
+
+/// End the live session and summarize it in the background
+#[tauri::command]
+pub async fn end_session(app: AppHandle) -> Result<SessionData, String> {
+    let session = finish_active(&app).await?.ok_or("No session is running")?;
+    wrap_up_in_background(&app, &session);
+    Ok(session)
+}
@@ -0,0 +1,197 @@
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Mutex as StdMutex, OnceLock};
+use tauri::AppHandle;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::reload;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Registry;
+use crate::events::{self, LogEvent};
+use crate::settings::{app_data_dir, SettingsState};
+
+// ============================================================================
+// LOGGING - tracing to console, a rolling file, and the frontend
+// ============================================================================
+//
+// Console output keeps the familiar "[TAG] message" lines. The same records
+// go to GOD-V8/logs/cognivox.log.YYYY-MM-DD (kept for LOG_FILES_KEPT days)
+// and into an in-memory ring for get_recent_logs. Warnings and errors are
+// also forwarded as cognivox:log events.
+
+const RECENT_CAPACITY: usize = 2000;
+const LOG_FILES_KEPT: usize = 7;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+static RECENT: StdMutex<VecDeque<LogEntry>> = StdMutex::new(VecDeque::new());
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static RELOAD: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+static FILE_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+thread_local! {
+    // Emitting cognivox:log can itself log; don't recurse into it
+    static FORWARDING: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.message, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Keeps recent records in memory and forwards warnings/errors to the UI
+struct RecentLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let level = *event.metadata().level();
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: level.to_string(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        };
+
+        if let Ok(mut recent) = RECENT.lock() {
+            if recent.len() == RECENT_CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back(entry.clone());
+        }
+
+        if level <= Level::WARN {
+            if let Some(app) = APP.get() {
+                FORWARDING.with(|forwarding| {
+                    if forwarding.replace(true) { return; }
+                    events::emit(app, &LogEvent {
+                        level: entry.level,
+                        target: entry.target,
+                        message: entry.message,
+                        timestamp: entry.timestamp,
+                    });
+                    forwarding.set(false);
+                });
+            }
+        }
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level.trim().parse::<LevelFilter>()
+        .map_err(|_| format!("Unknown log level '{}' (use error/warn/info/debug/trace/off)", level))
+}
+
+/// Install the global subscriber. With `stderr_console` the console layer writes
+/// to stderr, for modes where stdout carries a protocol (MCP).
+pub fn init(stderr_console: bool) {
+    let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+
+    let console_writer = if stderr_console {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
+    let console = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .without_time()
+        .with_writer(console_writer);
+
+    let file = app_data_dir()
+        .map(|dir| dir.join("logs"))
+        .and_then(|dir| {
+            tracing_appender::rolling::Builder::new()
+                .rotation(tracing_appender::rolling::Rotation::DAILY)
+                .filename_prefix("cognivox.log")
+                .max_log_files(LOG_FILES_KEPT)
+                .build(dir)
+                .map_err(|e| e.to_string())
+        })
+        .map(|appender| {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let _ = FILE_GUARD.set(guard);
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+        });
+    let file_error = file.as_ref().err().cloned();
+
+    let installed = tracing_subscriber::registry()
+        .with(filter)
+        .with(console)
+        .with(file.ok())
+        .with(RecentLayer)
+        .try_init();
+    if installed.is_ok() {
+        let _ = RELOAD.set(handle);
+    }
+    if let Some(e) = file_error {
+        tracing::warn!("[LOG] File logging disabled: {}", e);
+    }
+}
+
+/// Start forwarding warnings/errors to the frontend
+pub fn attach_app(app: AppHandle) {
+    let _ = APP.set(app);
+}
+
+pub fn apply_level(level: &str) -> Result<(), String> {
+    let filter = parse_level(level)?;
+    let handle = RELOAD.get().ok_or("Logging not initialized")?;
+    handle.modify(|f| *f = filter).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Newest-last log entries at `level` or more severe (default: all kept, last 200)
+#[tauri::command]
+pub fn get_recent_logs(level: Option<String>, n: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let min = match level.as_deref() {
+        Some(l) => parse_level(l)?,
+        None => LevelFilter::TRACE,
+    };
+    let n = n.unwrap_or(200);
+    let recent = RECENT.lock().map_err(|e| e.to_string())?;
+    let mut entries: Vec<LogEntry> = recent.iter()
+        .rev()
+        .filter(|e| e.level.parse::<Level>().map(|l| l <= min).unwrap_or(true))
+        .take(n)
+        .cloned()
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn set_log_level(settings: tauri::State<'_, SettingsState>, level: String) -> Result<String, String> {
+    let level = level.trim().to_lowercase();
+    apply_level(&level)?;
+    settings.update(|s| s.log_level = level.clone())?;
+    tracing::info!("[LOG] Level set to {}", level);
+    Ok(level)
+}
@@ -0,0 +1,181 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+use tracing::{error, info, warn};
+use crate::audio_capture::{AudioSource, StreamContext, build_tagged_stream};
+
+// ============================================================================
+// LOOPBACK - System Audio Capture (the other side of the call)
+// ============================================================================
+//
+// Windows: WASAPI loopback on the default output device, falling back to a
+//          "Stereo Mix"-style virtual input.
+// macOS:   CoreAudio has no loopback; capture from a virtual device
+//          (BlackHole, Loopback, Soundflower) routed via a Multi-Output Device.
+// Linux:   PulseAudio/PipeWire monitor source of the default sink. The ALSA
+//          "pulse" stream is moved onto the monitor by name once it exists,
+//          so the mic (also on "pulse") keeps the default source.
+//
+// Every path feeds the same pipeline as the mic, tagged AudioSource::System.
+
+// Virtual/loopback input device name fragments, matched case-insensitively
+const VIRTUAL_DEVICE_HINTS: &[&str] = &[
+    "stereo mix", "what u hear", "wave out", "loopback",
+    "blackhole", "soundflower", "monitor of",
+];
+
+fn find_virtual_input(host: &cpal::Host) -> Option<cpal::Device> {
+    host.input_devices().ok()?.find(|d| {
+        d.name()
+            .map(|name| {
+                let lower = name.to_lowercase();
+                VIRTUAL_DEVICE_HINTS.iter().any(|hint| lower.contains(hint))
+            })
+            .unwrap_or(false)
+    })
+}
+
+fn open_virtual_input(host: &cpal::Host, ctx: &StreamContext) -> Option<cpal::Stream> {
+    let device = find_virtual_input(host)?;
+    info!("[LOOPBACK] Found virtual capture device: {}", device.name().unwrap_or_default());
+    let config = device.default_input_config().ok()?;
+    build_tagged_stream(&device, config, AudioSource::System, ctx)
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn open_loopback_stream(ctx: &StreamContext) -> Option<cpal::Stream> {
+    info!("[LOOPBACK] Attempting WASAPI loopback capture...");
+
+    let host = cpal::available_hosts()
+        .into_iter()
+        .find(|h| h.name().contains("WASAPI"))
+        .and_then(|id| cpal::host_from_id(id).ok())
+        .unwrap_or_else(|| {
+            info!("[LOOPBACK] WASAPI not available, using default host");
+            cpal::default_host()
+        });
+
+    // Strategy 1: an input stream on the output device is WASAPI loopback
+    let loopback = host.default_output_device().and_then(|device| {
+        info!("[LOOPBACK] Trying loopback on output device: {}", device.name().unwrap_or_default());
+        let config = device.default_output_config().ok()?;
+        build_tagged_stream(&device, config, AudioSource::System, ctx)
+    });
+    if loopback.is_some() {
+        info!("[LOOPBACK] ✓ WASAPI loopback stream created");
+        return loopback;
+    }
+
+    // Strategy 2: Stereo Mix or similar virtual device
+    warn!("[LOOPBACK] Loopback failed, searching for Stereo Mix...");
+    let stream = open_virtual_input(&host, ctx);
+    if stream.is_none() {
+        error!("[LOOPBACK] ✗ System audio capture not available");
+        warn!("[LOOPBACK] To enable system audio capture:");
+        warn!("  1. Right-click Sound icon in system tray → Sounds");
+        warn!("  2. Go to Recording tab");
+        warn!("  3. Right-click → Show Disabled Devices");
+        warn!("  4. Enable 'Stereo Mix' if available");
+    }
+    stream
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn open_loopback_stream(ctx: &StreamContext) -> Option<cpal::Stream> {
+    info!("[LOOPBACK] Searching for a virtual loopback device...");
+    let stream = open_virtual_input(&cpal::default_host(), ctx);
+    if stream.is_none() {
+        error!("[LOOPBACK] ✗ No loopback device found");
+        warn!("[LOOPBACK] Install BlackHole (https://existential.audio/blackhole/), then in");
+        warn!("  Audio MIDI Setup create a Multi-Output Device with your speakers + BlackHole");
+        warn!("  and select it as the system output.");
+    }
+    stream
+}
+
+#[cfg(target_os = "linux")]
+fn pactl(args: &[&str]) -> Option<String> {
+    std::process::Command::new("pactl")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// Indexes of the record streams PulseAudio/PipeWire currently knows about
+#[cfg(target_os = "linux")]
+fn source_outputs() -> Option<std::collections::HashSet<String>> {
+    let list = pactl(&["list", "short", "source-outputs"])?;
+    Some(list.lines().filter_map(|l| l.split_whitespace().next()).map(str::to_string).collect())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn open_loopback_stream(ctx: &StreamContext) -> Option<cpal::Stream> {
+    // ALSA may already expose a "Monitor of ..." device
+    let host = cpal::default_host();
+    if let Some(stream) = open_virtual_input(&host, ctx) {
+        return Some(stream);
+    }
+
+    info!("[LOOPBACK] Looking up PulseAudio monitor source...");
+
+    let sink = pactl(&["get-default-sink"]).filter(|s| !s.is_empty());
+    let Some(sink) = sink else {
+        error!("[LOOPBACK] ✗ pactl unavailable - is PulseAudio/PipeWire running?");
+        return None;
+    };
+    let monitor = format!("{}.monitor", sink);
+    info!("[LOOPBACK] Monitor source: {}", monitor);
+
+    let Some(device) = host.input_devices().ok()?
+        .find(|d| d.name().map(|n| n == "pulse").unwrap_or(false))
+    else {
+        warn!("[LOOPBACK] No ALSA 'pulse' device - install the PulseAudio ALSA plugin (pipewire-alsa)");
+        return None;
+    };
+    let config = device.default_input_config().ok()?;
+
+    // The plugin connects to the default source while the stream is built;
+    // the record stream that appears is ours, and goes to the monitor instead
+    let before = source_outputs().unwrap_or_default();
+    let Some(stream) = build_tagged_stream(&device, config, AudioSource::System, ctx) else {
+        error!("[LOOPBACK] ✗ Failed to open monitor source {}", monitor);
+        return None;
+    };
+    let ours: Vec<String> = source_outputs().unwrap_or_default()
+        .into_iter()
+        .filter(|index| !before.contains(index))
+        .collect();
+    if ours.is_empty() || !ours.iter().all(|index| pactl(&["move-source-output", index, &monitor]).is_some()) {
+        // Left on the default source it would record the mic a second time
+        error!("[LOOPBACK] ✗ Could not move the stream to {}", monitor);
+        return None;
+    }
+    info!("[LOOPBACK] ✓ PulseAudio monitor stream created");
+    Some(stream)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub(crate) fn open_loopback_stream(_ctx: &StreamContext) -> Option<cpal::Stream> {
+    error!("[LOOPBACK] ✗ System audio capture is not supported on this platform");
+    None
+}
+
+/// Human-readable loopback status for the device list
+pub fn describe_support() -> String {
+    #[cfg(target_os = "windows")]
+    { "✓ WASAPI loopback available for system audio capture".to_string() }
+
+    #[cfg(target_os = "macos")]
+    {
+        match find_virtual_input(&cpal::default_host()).and_then(|d| d.name().ok()) {
+            Some(name) => format!("✓ Virtual loopback device: {}", name),
+            None => "✗ No loopback device (install BlackHole)".to_string(),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    { "✓ PulseAudio/PipeWire monitor source (requires pactl)".to_string() }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    { "✗ Not supported on this platform".to_string() }
+}
@@ -2,5 +2,12 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    if std::env::args().any(|a| a == "--mcp") {
+        return god_v8_lib::run_mcp_server();
+    }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "transcribe") {
+        std::process::exit(god_v8_lib::run_cli(&args));
+    }
     god_v8_lib::run()
 }
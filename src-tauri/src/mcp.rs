@@ -0,0 +1,214 @@
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use tracing::info;
+use crate::action_items::items_from_session;
+use crate::session_manager::{SessionData, SessionManager};
+
+// ============================================================================
+// MCP - Model Context Protocol Server over stdio
+// ============================================================================
+//
+// Started with `--mcp`; no window is created. Claude Desktop config:
+//   "cognivox": { "command": "<path to app binary>", "args": ["--mcp"] }
+//
+// stdout carries JSON-RPC only, so diagnostics go to stderr.
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_sessions",
+            "description": "List recorded meetings, newest first, with title, date and length.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "limit": { "type": "integer", "description": "Maximum sessions to return" }
+                }
+            }
+        },
+        {
+            "name": "search_transcripts",
+            "description": "Find transcript segments containing the query text across all meetings.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "session_id": { "type": "string", "description": "Restrict to one session" },
+                    "language": { "type": "string", "description": "Only segments spoken in this language, e.g. \"de\"" },
+                    "limit": { "type": "integer" }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "get_action_items",
+            "description": "Action items, tasks and decisions detected in a meeting (`detected`), plus those in its summary (`from_summary`, empty until summarized).",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "session_id": { "type": "string" } },
+                "required": ["session_id"]
+            }
+        },
+        {
+            "name": "get_session_summary",
+            "description": "Executive summary, decisions, risks and next steps for a meeting.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "session_id": { "type": "string" } },
+                "required": ["session_id"]
+            }
+        }
+    ])
+}
+
+fn str_arg<'a>(args: &'a Value, name: &str) -> Result<&'a str, String> {
+    args[name].as_str().ok_or_else(|| format!("Missing argument: {}", name))
+}
+
+fn limit_arg(args: &Value, default: usize) -> usize {
+    args["limit"].as_u64().map(|l| l as usize).unwrap_or(default)
+}
+
+pub(crate) fn list_sessions(manager: &SessionManager, args: &Value) -> Result<Value, String> {
+    let sessions: Vec<Value> = manager.list_sessions()?
+        .iter()
+        .take(limit_arg(args, usize::MAX))
+        .map(|s| json!({
+            "session_id": s.id,
+            "title": s.metadata.title,
+            "created_at": s.created_at,
+            "duration_seconds": s.metadata.duration_seconds,
+            "segments": s.transcripts.len(),
+            "has_summary": s.summary.is_some()
+        }))
+        .collect();
+    Ok(json!(sessions))
+}
+
+pub(crate) fn search_transcripts(manager: &SessionManager, args: &Value) -> Result<Value, String> {
+    let query = str_arg(args, "query")?.to_lowercase();
+    let limit = limit_arg(args, DEFAULT_SEARCH_LIMIT);
+    let sessions: Vec<SessionData> = match args["session_id"].as_str() {
+        Some(id) => vec![manager.load_session(id)?],
+        None => manager.list_sessions()?,
+    };
+
+    let matches: Vec<Value> = sessions.iter()
+        .flat_map(|s| s.transcripts.iter().map(move |t| (s, t)))
+        .filter(|(_, t)| t.text.to_lowercase().contains(&query))
+        .filter(|(_, t)| args["language"].as_str().is_none_or(|l| t.language.as_deref().is_some_and(|tl| tl.eq_ignore_ascii_case(l))))
+        .take(limit)
+        .map(|(s, t)| json!({
+            "session_id": s.id,
+            "session_title": s.metadata.title,
+            "timestamp": t.timestamp,
+            "start_ms": t.start_ms,
+            "speaker": t.speaker_id,
+            "language": t.language,
+            "text": t.text
+        }))
+        .collect();
+    Ok(json!(matches))
+}
+
+fn get_action_items(manager: &SessionManager, args: &Value) -> Result<Value, String> {
+    let session = manager.load_session(str_arg(args, "session_id")?)?;
+    // The stored summary may have items the live tracker didn't see
+    let from_summary = session.summary.as_ref().map(|s| s.action_items.clone()).unwrap_or_default();
+    Ok(json!({ "detected": items_from_session(&session), "from_summary": from_summary }))
+}
+
+fn get_session_summary(manager: &SessionManager, args: &Value) -> Result<Value, String> {
+    let mut session = manager.load_session(str_arg(args, "session_id")?)?;
+    let generated = session.summary.is_none();
+    if generated {
+        session.generate_local_summary();
+    }
+    Ok(json!({
+        "session_id": session.id,
+        "title": session.metadata.title,
+        "summary": session.summary,
+        "generated_locally": generated
+    }))
+}
+
+fn call_tool(name: &str, args: &Value) -> Result<Value, String> {
+    let manager = SessionManager::new()?;
+    match name {
+        "list_sessions" => list_sessions(&manager, args),
+        "search_transcripts" => search_transcripts(&manager, args),
+        "get_action_items" => get_action_items(&manager, args),
+        "get_session_summary" => get_session_summary(&manager, args),
+        _ => Err(format!("Unknown tool: {}", name)),
+    }
+}
+
+/// Handle one JSON-RPC message; notifications get no response
+fn handle_message(msg: &Value) -> Option<Value> {
+    let id = msg.get("id").cloned()?;
+    let method = msg["method"].as_str().unwrap_or_default();
+
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "cognivox", "version": env!("CARGO_PKG_VERSION") }
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tool_definitions() }),
+        "tools/call" => {
+            let name = msg["params"]["name"].as_str().unwrap_or_default();
+            let args = msg["params"].get("arguments").cloned().unwrap_or(json!({}));
+            info!("[MCP] tools/call {}", name);
+            // Tool failures are reported in-band so the model can see them
+            match call_tool(name, &args) {
+                Ok(value) => json!({
+                    "content": [{ "type": "text", "text": serde_json::to_string_pretty(&value).unwrap_or_default() }]
+                }),
+                Err(e) => json!({
+                    "content": [{ "type": "text", "text": e }],
+                    "isError": true
+                }),
+            }
+        }
+        _ => {
+            return Some(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("Method not found: {}", method) }
+            }));
+        }
+    };
+
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+/// Serve MCP on stdin/stdout until the client closes the pipe
+pub fn run_stdio() {
+    info!("[MCP] Cognivox MCP server ready (protocol {})", PROTOCOL_VERSION);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break; };
+        if line.trim().is_empty() { continue; }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(msg) => handle_message(&msg),
+            Err(e) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": format!("Parse error: {}", e) }
+            })),
+        };
+
+        if let Some(response) = response {
+            if writeln!(stdout, "{}", response).and_then(|_| stdout.flush()).is_err() {
+                break;
+            }
+        }
+    }
+    info!("[MCP] Client disconnected");
+}
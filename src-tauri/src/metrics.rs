@@ -0,0 +1,178 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use crate::audio_capture::AudioState;
+use crate::audio_channel::ChannelStats;
+use crate::events::{self, PipelineMetrics};
+use crate::retry_queue::RetryQueueState;
+
+// ============================================================================
+// METRICS - Pipeline Latency, Throughput & Health
+// ============================================================================
+
+const WINDOW: usize = 50;                  // Recent samples kept per latency series
+const METRICS_EVENT_INTERVAL_SECS: u64 = 5;
+
+/// Rolling window of recent samples
+#[derive(Default)]
+struct Series {
+    samples: VecDeque<f64>,
+}
+
+impl Series {
+    fn push(&mut self, value: f64) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    fn stats(&self) -> SeriesStats {
+        if self.samples.is_empty() {
+            return SeriesStats::default();
+        }
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let p95_index = ((sorted.len() as f64 * 0.95).ceil() as usize).saturating_sub(1);
+        SeriesStats {
+            count: sorted.len(),
+            avg: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p95: sorted[p95_index],
+            last: *self.samples.back().unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct SeriesStats {
+    pub count: usize,
+    pub avg: f64,
+    pub p95: f64,
+    pub last: f64,
+}
+
+#[derive(Default)]
+struct Counters {
+    segments_processed: u64,
+    segments_dropped: u64,
+    gemini_requests: u64,
+    gemini_errors: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    counters: Counters,
+    transcript_latency_ms: Series,       // speech end → transcript
+    intelligence_latency_ms: Series,     // transcript → intelligence
+    end_to_end_latency_ms: Series,       // speech end → intelligence
+    whisper_rtf: Series,                 // transcribe time / audio duration
+}
+
+pub struct MetricsState {
+    inner: StdMutex<Inner>,
+    started: Instant,
+}
+
+impl Default for MetricsState {
+    fn default() -> Self {
+        Self { inner: StdMutex::new(Inner::default()), started: Instant::now() }
+    }
+}
+
+impl MetricsState {
+    fn with<R>(&self, f: impl FnOnce(&mut Inner) -> R) -> R {
+        f(&mut self.inner.lock().unwrap())
+    }
+
+    /// A segment was discarded before producing a transcript (too short, empty, Whisper failure)
+    pub fn record_dropped(&self) {
+        self.with(|m| m.counters.segments_dropped += 1);
+    }
+
+    /// Whisper finished a segment of `audio_secs` that went quiet at `speech_end`
+    pub fn record_transcription(&self, speech_end: Instant, audio_secs: f32, transcribe_time: Duration) {
+        self.with(|m| {
+            m.counters.segments_processed += 1;
+            m.transcript_latency_ms.push(speech_end.elapsed().as_secs_f64() * 1000.0);
+            if audio_secs > 0.0 {
+                m.whisper_rtf.push(transcribe_time.as_secs_f64() / audio_secs as f64);
+            }
+        });
+    }
+
+    pub fn record_gemini_result(&self, ok: bool) {
+        self.with(|m| {
+            m.counters.gemini_requests += 1;
+            if !ok { m.counters.gemini_errors += 1; }
+        });
+    }
+
+    /// Intelligence request answered from (or missing in) the response cache
+    pub fn record_cache_lookup(&self, hit: bool) {
+        self.with(|m| if hit { m.counters.cache_hits += 1 } else { m.counters.cache_misses += 1 });
+    }
+
+    /// Gemini answered for a live segment transcribed at `transcribed_at`
+    pub fn record_intelligence(&self, speech_end: Instant, transcribed_at: Instant) {
+        self.with(|m| {
+            m.intelligence_latency_ms.push(transcribed_at.elapsed().as_secs_f64() * 1000.0);
+            m.end_to_end_latency_ms.push(speech_end.elapsed().as_secs_f64() * 1000.0);
+        });
+    }
+
+    pub fn snapshot(&self, retry_queue_depth: usize, audio: ChannelStats) -> PipelineMetrics {
+        self.with(|m| PipelineMetrics {
+            uptime_secs: self.started.elapsed().as_secs(),
+            segments_processed: m.counters.segments_processed,
+            segments_dropped: m.counters.segments_dropped,
+            audio_queue_depth: audio.chunks,
+            audio_buffered_ms: audio.buffered_ms,
+            audio_chunks_dropped: audio.dropped_chunks,
+            audio_dropped_ms: audio.dropped_ms,
+            retry_queue_depth,
+            transcript_latency_ms: m.transcript_latency_ms.stats(),
+            intelligence_latency_ms: m.intelligence_latency_ms.stats(),
+            end_to_end_latency_ms: m.end_to_end_latency_ms.stats(),
+            whisper_rtf: m.whisper_rtf.stats(),
+            gemini_requests: m.counters.gemini_requests,
+            gemini_errors: m.counters.gemini_errors,
+            gemini_error_rate: if m.counters.gemini_requests > 0 {
+                m.counters.gemini_errors as f64 / m.counters.gemini_requests as f64
+            } else {
+                0.0
+            },
+            cache_hits: m.counters.cache_hits,
+            cache_misses: m.counters.cache_misses,
+        })
+    }
+}
+
+fn current_metrics(app: &AppHandle) -> PipelineMetrics {
+    let retry_depth = app.state::<RetryQueueState>().pending().len();
+    let audio = app.state::<AudioState>().queue_stats();
+    app.state::<MetricsState>().snapshot(retry_depth, audio)
+}
+
+/// Periodic cognivox:metrics event for the diagnostics panel
+pub fn spawn_metrics_emitter(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = tokio::time::interval(Duration::from_secs(METRICS_EVENT_INTERVAL_SECS));
+        loop {
+            tick.tick().await;
+            events::emit(&app, &current_metrics(&app));
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_pipeline_metrics(app: AppHandle) -> PipelineMetrics {
+    current_metrics(&app)
+}
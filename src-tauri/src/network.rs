@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use tracing::{error, info, warn};
+use crate::settings::{redact, SettingsState, REDACTED};
+
+// ============================================================================
+// NETWORK - Shared HTTP Client (Proxy / Custom CA)
+// ============================================================================
+
+// Environment fallbacks when the settings file leaves a field empty.
+// Standard HTTP_PROXY / HTTPS_PROXY / NO_PROXY are still honoured by reqwest.
+const ENV_PROXY_URL: &str = "COGNIVOX_PROXY_URL";
+const ENV_PROXY_USERNAME: &str = "COGNIVOX_PROXY_USERNAME";
+const ENV_PROXY_PASSWORD: &str = "COGNIVOX_PROXY_PASSWORD";
+const ENV_CA_CERT: &str = "COGNIVOX_CA_CERT";
+
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+pub const LOCAL_ONLY_ERROR: &str = "Privacy mode is local-only: network access is disabled";
+
+/// `Local` hard-disables every outbound request; only Whisper and other
+/// on-device processing keep working.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyMode {
+    #[default]
+    Cloud,
+    Local,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    pub proxy_password: Option<String>,
+    pub no_proxy: Option<String>,
+    // PEM file; may contain a bundle of several certificates
+    pub ca_cert_path: Option<String>,
+}
+
+impl NetworkConfig {
+    fn resolved(&self) -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Self {
+            proxy_url: self.proxy_url.clone().or_else(|| env(ENV_PROXY_URL)),
+            proxy_username: self.proxy_username.clone().or_else(|| env(ENV_PROXY_USERNAME)),
+            proxy_password: self.proxy_password.clone().or_else(|| env(ENV_PROXY_PASSWORD)),
+            no_proxy: self.no_proxy.clone(),
+            ca_cert_path: self.ca_cert_path.clone().or_else(|| env(ENV_CA_CERT)),
+        }
+    }
+
+    /// Copy safe to hand back to the frontend
+    fn redacted(&self) -> Self {
+        Self {
+            proxy_password: redact(self.proxy_password.as_deref()),
+            ..self.clone()
+        }
+    }
+}
+
+pub fn build_client(config: &NetworkConfig) -> Result<reqwest::Client, String> {
+    let config = config.resolved();
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(CONNECT_TIMEOUT_SECS));
+
+    if let Some(url) = &config.proxy_url {
+        let mut proxy = reqwest::Proxy::all(url.as_str())
+            .map_err(|e| format!("Invalid proxy URL: {}", e))?;
+        if let Some(username) = &config.proxy_username {
+            proxy = proxy.basic_auth(username, config.proxy_password.as_deref().unwrap_or_default());
+        }
+        if let Some(no_proxy) = &config.no_proxy {
+            proxy = proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy));
+        }
+        builder = builder.proxy(proxy);
+        info!("[NETWORK] Using proxy {}", url);
+    }
+
+    if let Some(path) = &config.ca_cert_path {
+        let pem = fs::read(path)
+            .map_err(|e| format!("Failed to read CA certificate {}: {}", path, e))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("Invalid CA certificate {}: {}", path, e))?;
+        if certs.is_empty() {
+            return Err(format!("No certificates found in {}", path));
+        }
+        info!("[NETWORK] Trusting {} extra root certificate(s) from {}", certs.len(), path);
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    builder.build().map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+pub struct NetworkState {
+    client: StdMutex<reqwest::Client>,
+    local_only: AtomicBool,
+}
+
+impl NetworkState {
+    pub fn new(config: &NetworkConfig, mode: PrivacyMode) -> Self {
+        let client = build_client(config).unwrap_or_else(|e| {
+            error!("[NETWORK] ✗ {} - falling back to default client", e);
+            reqwest::Client::new()
+        });
+        if mode == PrivacyMode::Local {
+            info!("[NETWORK] Privacy mode: local-only, outbound requests disabled");
+        }
+        Self { client: StdMutex::new(client), local_only: AtomicBool::new(mode == PrivacyMode::Local) }
+    }
+
+    /// Shared client; cloning is cheap and keeps the connection pool.
+    /// Every outbound request goes through here, so local-only mode fails them all.
+    pub fn client(&self) -> Result<reqwest::Client, String> {
+        self.ensure_online()?;
+        Ok(self.client.lock().unwrap().clone())
+    }
+
+    /// For network access that doesn't use the shared client
+    pub fn ensure_online(&self) -> Result<(), String> {
+        if self.is_local_only() {
+            return Err(LOCAL_ONLY_ERROR.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn is_local_only(&self) -> bool {
+        self.local_only.load(Ordering::SeqCst)
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_network_config(settings: tauri::State<'_, SettingsState>) -> NetworkConfig {
+    settings.get().network.redacted()
+}
+
+#[tauri::command]
+pub fn set_network_config(
+    settings: tauri::State<'_, SettingsState>,
+    network: tauri::State<'_, NetworkState>,
+    config: NetworkConfig,
+) -> Result<String, String> {
+    let empty_to_none = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+    let mut config = NetworkConfig {
+        proxy_url: empty_to_none(config.proxy_url),
+        proxy_username: empty_to_none(config.proxy_username),
+        proxy_password: empty_to_none(config.proxy_password),
+        no_proxy: empty_to_none(config.no_proxy),
+        ca_cert_path: empty_to_none(config.ca_cert_path),
+    };
+    // The frontend only ever sees the redacted password; keep the stored one
+    if config.proxy_password.as_deref() == Some(REDACTED) {
+        config.proxy_password = settings.get().network.proxy_password;
+    }
+
+    // Validate before persisting so a bad proxy/CA never replaces a working client
+    let client = build_client(&config)?;
+    settings.update(|s| s.network = config)?;
+    *network.client.lock().unwrap() = client;
+
+    info!("[NETWORK] ✓ HTTP client rebuilt");
+    Ok("Network settings applied".to_string())
+}
+
+#[tauri::command]
+pub fn get_privacy_mode(network: tauri::State<'_, NetworkState>) -> PrivacyMode {
+    if network.is_local_only() { PrivacyMode::Local } else { PrivacyMode::Cloud }
+}
+
+/// "local" blocks Gemini, webhooks, Slack, calendar and model downloads; "cloud" allows them
+#[tauri::command]
+pub fn set_privacy_mode(
+    settings: tauri::State<'_, SettingsState>,
+    network: tauri::State<'_, NetworkState>,
+    mode: PrivacyMode,
+) -> Result<PrivacyMode, String> {
+    settings.update(|s| s.privacy_mode = mode)?;
+    network.local_only.store(mode == PrivacyMode::Local, Ordering::SeqCst);
+    match mode {
+        PrivacyMode::Local => warn!("[NETWORK] Privacy mode: local-only, outbound requests disabled"),
+        PrivacyMode::Cloud => info!("[NETWORK] Privacy mode: cloud, outbound requests allowed"),
+    }
+    Ok(mode)
+}
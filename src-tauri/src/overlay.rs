@@ -0,0 +1,97 @@
+use tauri::{AppHandle, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+use tracing::info;
+
+// ============================================================================
+// OVERLAY - Always-on-Top Live Caption Window
+// ============================================================================
+//
+// The window loads the /overlay route, which listens to the same
+// cognivox:whisper_transcription / cognivox:gemini_intelligence events as
+// the main window. The backend only owns its lifecycle and placement.
+
+const OVERLAY_LABEL: &str = "overlay";
+const OVERLAY_WIDTH: f64 = 900.0;
+const OVERLAY_HEIGHT: f64 = 150.0;
+const SCREEN_MARGIN: f64 = 48.0;
+
+fn overlay_window(app: &AppHandle) -> Option<WebviewWindow> {
+    app.get_webview_window(OVERLAY_LABEL)
+}
+
+/// Place the overlay at "top", "bottom" (default) or explicit physical x/y
+fn place(window: &WebviewWindow, position: Option<&str>, x: Option<i32>, y: Option<i32>) -> Result<(), String> {
+    if let (Some(x), Some(y)) = (x, y) {
+        return window.set_position(PhysicalPosition::new(x, y)).map_err(|e| e.to_string());
+    }
+
+    let monitor = window.current_monitor().ok().flatten()
+        .or_else(|| window.primary_monitor().ok().flatten())
+        .ok_or("No monitor found")?;
+    let scale = monitor.scale_factor();
+    let (screen_x, screen_y) = (monitor.position().x as f64, monitor.position().y as f64);
+    let (screen_w, screen_h) = (monitor.size().width as f64, monitor.size().height as f64);
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let (win_w, win_h) = (size.width as f64, size.height as f64);
+
+    let left = screen_x + (screen_w - win_w) / 2.0;
+    let top = match position.unwrap_or("bottom") {
+        "top" => screen_y + SCREEN_MARGIN * scale,
+        "bottom" => screen_y + screen_h - win_h - SCREEN_MARGIN * scale,
+        other => return Err(format!("Unknown overlay position '{}' (use top/bottom)", other)),
+    };
+    window.set_position(PhysicalPosition::new(left as i32, top as i32)).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn open_caption_overlay(app: AppHandle, position: Option<String>) -> Result<String, String> {
+    if let Some(window) = overlay_window(&app) {
+        place(&window, position.as_deref(), None, None)?;
+        let _ = window.show();
+        return Ok("Overlay already open".to_string());
+    }
+
+    let builder = WebviewWindowBuilder::new(&app, OVERLAY_LABEL, WebviewUrl::App("overlay".into()))
+        .title("Cognivox Captions")
+        .inner_size(OVERLAY_WIDTH, OVERLAY_HEIGHT)
+        .min_inner_size(320.0, 80.0)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .decorations(false)
+        .skip_taskbar(true)
+        .shadow(false)
+        .focused(false)
+        .resizable(true);
+    // Transparency on macOS needs the private API; there the page paints its own background
+    #[cfg(not(target_os = "macos"))]
+    let builder = builder.transparent(true);
+
+    let window = builder.build().map_err(|e| format!("Failed to create overlay: {}", e))?;
+    place(&window, position.as_deref(), None, None)?;
+
+    info!("[OVERLAY] ✓ Caption overlay opened");
+    Ok("Overlay opened".to_string())
+}
+
+#[tauri::command]
+pub fn position_caption_overlay(
+    app: AppHandle,
+    position: Option<String>,
+    x: Option<i32>,
+    y: Option<i32>,
+) -> Result<(), String> {
+    let window = overlay_window(&app).ok_or("Overlay is not open")?;
+    place(&window, position.as_deref(), x, y)
+}
+
+#[tauri::command]
+pub fn close_caption_overlay(app: AppHandle) -> Result<(), String> {
+    if let Some(window) = overlay_window(&app) {
+        window.close().map_err(|e| e.to_string())?;
+        info!("[OVERLAY] Caption overlay closed");
+    }
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex as StdMutex;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::alerts;
+use crate::audio_capture::{to_mono, AudioState, StreamResampler, MAX_PUSH_SAMPLE_RATE, MIN_PUSH_SAMPLE_RATE, TARGET_SAMPLE_RATE};
+use crate::batching::LiveSegment;
+use crate::dedupe;
+use crate::events::{self, TranscriptionEvent, TranscriptionSource};
+use crate::gemini_client::{
+    self, rms, MAX_BATCH_SECS, MIN_SPEECH_SECS, SILENCE_THRESHOLD, SILENCE_TIMEOUT_SECS, SPEECH_THRESHOLD,
+};
+use crate::hallucination::discard_if_hallucinated;
+use crate::levels::normalize_segment;
+use crate::live_session::{LiveSessionState, RecentSegment};
+use crate::metrics::MetricsState;
+use crate::translation::{self, CaptionSegment};
+use crate::voice_commands;
+use crate::whisper_client::{transcribe_audio, WhisperState};
+
+// ============================================================================
+// PARTICIPANTS - One Audio Track per Speaker from a Conference Bridge
+// ============================================================================
+//
+// Mixers and conferencing SDKs can hand over each participant's audio on
+// its own track. Every track gets its own VAD here and its segments go
+// straight to Whisper and Gemini tagged with the participant's name, so no
+// diarization (energy split or voice matching) is involved. Runs beside the
+// main audio loop rather than through it: that loop has a single buffer and
+// would mix overlapping speakers back together.
+
+// 10 ms VAD frames
+const VAD_FRAME_SAMPLES: usize = 160;
+const MAX_PARTICIPANTS: usize = 32;
+const MAX_NAME_CHARS: usize = 64;
+
+/// One participant's audio in a push_participant_audio call
+#[derive(Deserialize, Debug)]
+pub struct ParticipantTrack {
+    pub participant: String,
+    // Interleaved f32 at the call's sample rate
+    pub samples: Vec<f32>,
+    pub channels: Option<u16>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ParticipantStatus {
+    pub participant: String,
+    pub speaking: bool,
+    pub received_secs: f32,
+    pub segments: u64,
+}
+
+/// Per-track resampler and VAD; positions count 16 kHz samples on this track
+struct Channel {
+    sample_rate: u32,
+    resampler: StreamResampler,
+    // Session offset of the track's first sample
+    origin_ms: u64,
+    received: u64,
+    buffer: Vec<f32>,
+    speech_start: Option<u64>,
+    last_speech: u64,
+    segments: u64,
+}
+
+/// A finished stretch of speech on one track
+struct SpeechSegment {
+    speaker: String,
+    // Session live when the segment was cut; a flush at session end still
+    // files its segments under the session that is closing
+    session_id: Option<String>,
+    start_ms: u64,
+    audio: Vec<f32>,
+}
+
+fn secs(samples: u64) -> f32 {
+    samples as f32 / TARGET_SAMPLE_RATE as f32
+}
+
+impl Channel {
+    fn new(sample_rate: u32, origin_ms: u64) -> Self {
+        Self {
+            sample_rate,
+            resampler: StreamResampler::new(sample_rate),
+            origin_ms,
+            received: 0,
+            buffer: Vec::new(),
+            speech_start: None,
+            last_speech: 0,
+            segments: 0,
+        }
+    }
+
+    /// Run VAD over newly received audio; returns (start sample, audio) of
+    /// each segment it completes
+    fn feed(&mut self, samples: &[f32]) -> Vec<(u64, Vec<f32>)> {
+        let mut finished = Vec::new();
+        for frame in samples.chunks(VAD_FRAME_SAMPLES) {
+            let level = rms(frame);
+            let at = self.received;
+            self.received += frame.len() as u64;
+            if self.speech_start.is_none() {
+                if level > SPEECH_THRESHOLD {
+                    self.speech_start = Some(at);
+                    self.last_speech = self.received;
+                    self.buffer.extend_from_slice(frame);
+                }
+                continue;
+            }
+
+            self.buffer.extend_from_slice(frame);
+            if level > SILENCE_THRESHOLD {
+                self.last_speech = self.received;
+            }
+            let silence = secs(self.received - self.last_speech);
+            if silence >= SILENCE_TIMEOUT_SECS || secs(self.buffer.len() as u64) >= MAX_BATCH_SECS {
+                finished.extend(self.cut());
+            }
+        }
+        finished
+    }
+
+    /// End the current segment; too-short blips are dropped
+    fn cut(&mut self) -> Option<(u64, Vec<f32>)> {
+        let start = self.speech_start.take()?;
+        let audio = std::mem::take(&mut self.buffer);
+        if secs(audio.len() as u64) < MIN_SPEECH_SECS {
+            return None;
+        }
+        self.segments += 1;
+        Some((start, audio))
+    }
+
+    fn start_ms(&self, start: u64) -> u64 {
+        self.origin_ms + start * 1000 / TARGET_SAMPLE_RATE as u64
+    }
+}
+
+#[derive(Default)]
+pub struct ParticipantState {
+    channels: StdMutex<HashMap<String, Channel>>,
+    // Offsets when no session is active: time since the first track opened
+    clock: StdMutex<Option<Instant>>,
+    // Whisper runs one segment at a time, whichever track it came from
+    whisper_turn: tokio::sync::Mutex<()>,
+}
+
+impl ParticipantState {
+    fn now_ms(&self, app: &AppHandle) -> u64 {
+        app.state::<LiveSessionState>().elapsed_ms().unwrap_or_else(|| {
+            self.clock.lock().unwrap().get_or_insert_with(Instant::now).elapsed().as_millis() as u64
+        })
+    }
+}
+
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Participant name is empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(format!("Participant name is longer than {} characters", MAX_NAME_CHARS));
+    }
+    Ok(name.to_string())
+}
+
+/// Transcribe one participant segment and send it on for intelligence
+async fn process_segment(app: AppHandle, segment: SpeechSegment) {
+    let SpeechSegment { speaker, session_id, start_ms, mut audio } = segment;
+    let duration = secs(audio.len() as u64);
+    let end_ms = start_ms + (duration * 1000.0) as u64;
+    let speech_end = Instant::now();
+    let segment_id = uuid::Uuid::new_v4().to_string();
+
+    let whisper = app.state::<WhisperState>();
+    if !*whisper.is_initialized.lock().unwrap() {
+        warn!("[PARTICIPANTS] ✗ Whisper not initialized - dropping {:.1}s from {}", duration, speaker);
+        app.state::<MetricsState>().record_dropped();
+        return;
+    }
+    let Some(model_path) = whisper.model_path.lock().unwrap().clone() else {
+        app.state::<MetricsState>().record_dropped();
+        return;
+    };
+    let language = whisper.language.lock().unwrap().clone();
+    let decoding = whisper.decoding.lock().unwrap().clone();
+
+    let gain = normalize_segment(&mut audio);
+    let participants = app.state::<ParticipantState>();
+    let result = {
+        let _turn = participants.whisper_turn.lock().await;
+        let started = Instant::now();
+        let result = transcribe_audio(&model_path, &language, &decoding, &audio).await;
+        if result.is_ok() {
+            app.state::<MetricsState>().record_transcription(speech_end, duration, started.elapsed());
+        }
+        result
+    };
+    let result = match result {
+        Ok(result) if !result.text.trim().is_empty() => result,
+        Ok(_) => {
+            app.state::<MetricsState>().record_dropped();
+            return;
+        }
+        Err(e) => {
+            warn!("[PARTICIPANTS] ✗ {} not transcribed: {}", speaker, e);
+            app.state::<MetricsState>().record_dropped();
+            return;
+        }
+    };
+    if discard_if_hallucinated(&app, &result.text, result.no_speech_prob, Some(&segment_id), session_id.as_deref()) {
+        app.state::<MetricsState>().record_dropped();
+        return;
+    }
+    let Some(text) = dedupe::suppress_duplicate(&app, result.text.trim(), &speaker, result.no_speech_prob, Some(&segment_id), session_id.as_deref()) else {
+        app.state::<MetricsState>().record_dropped();
+        return;
+    };
+    info!("[PARTICIPANTS] {} ({:.1}s): {}", speaker, duration, text);
+
+    translation::caption_segment(&app, CaptionSegment {
+        segment_id: segment_id.clone(),
+        session_id: session_id.clone(),
+        speaker: speaker.clone(),
+        text: text.clone(),
+        start_ms,
+        end_ms,
+    }, &result.language, &audio);
+    events::emit(&app, &TranscriptionEvent {
+        segment_id: Some(segment_id.clone()),
+        session_id: session_id.clone(),
+        text: text.clone(),
+        language: result.language.clone(),
+        language_probability: result.language_probability,
+        confidence: result.confidence,
+        no_speech_prob: result.no_speech_prob,
+        source: TranscriptionSource::Whisper,
+        speaker: Some(speaker.clone()),
+        start_ms: Some(start_ms),
+        end_ms: Some(end_ms),
+        gain: Some(gain),
+    });
+    alerts::watch_keywords(&app, &text, &speaker, Some(&segment_id), session_id.as_deref(), Some(start_ms));
+
+    let mut recent = RecentSegment {
+        segment_id: segment_id.clone(),
+        session_id: session_id.clone(),
+        speaker: speaker.clone(),
+        text,
+        start_ms,
+        end_ms,
+    };
+    let Some(transcript) = voice_commands::intercept(&app, &recent) else { return; };
+    recent.text = transcript.clone();
+    app.state::<LiveSessionState>().remember_segment(recent);
+
+    gemini_client::analyze_segment(&app, LiveSegment {
+        segment_id,
+        session_id,
+        transcript,
+        speaker,
+        start_ms,
+        end_ms,
+        stt_confidence: result.confidence,
+        language: result.segment_language(),
+        speech_end,
+        transcribed_at: Instant::now(),
+    }).await;
+}
+
+fn spawn_segments(app: &AppHandle, segments: Vec<SpeechSegment>) -> Vec<tauri::async_runtime::JoinHandle<()>> {
+    segments.into_iter()
+        .map(|segment| {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move { process_segment(app, segment).await })
+        })
+        .collect()
+}
+
+/// Close every track and start transcribing the speech still buffered on it,
+/// filed under `session_id`. Resets the clock so the next session's offsets
+/// start from zero. Called when a session ends or rolls over and on quit.
+pub fn close_all_tracks(app: &AppHandle, session_id: Option<String>) -> Vec<tauri::async_runtime::JoinHandle<()>> {
+    let state = app.state::<ParticipantState>();
+    let pending: Vec<SpeechSegment> = {
+        let mut channels = state.channels.lock().unwrap();
+        channels.drain()
+            .filter_map(|(name, mut channel)| {
+                let (start, audio) = channel.cut()?;
+                Some(SpeechSegment { speaker: name, session_id: session_id.clone(), start_ms: channel.start_ms(start), audio })
+            })
+            .collect()
+    };
+    *state.clock.lock().unwrap() = None;
+    if !pending.is_empty() {
+        info!("[PARTICIPANTS] Flushing {} buffered segment(s)", pending.len());
+    }
+    spawn_segments(app, pending)
+}
+
+/// close_all_tracks, waiting until the flushed segments are processed
+pub async fn flush_all(app: &AppHandle, session_id: Option<String>) {
+    for handle in close_all_tracks(app, session_id) {
+        let _ = handle.await;
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Audio for one or more participants, all at `sample_rate`. Tracks are
+/// created on first use and keyed by participant name, which becomes the
+/// speaker of every segment. Returns the number of segments completed.
+#[tauri::command]
+pub fn push_participant_audio(app: AppHandle, sample_rate: u32, tracks: Vec<ParticipantTrack>) -> Result<usize, String> {
+    if !(MIN_PUSH_SAMPLE_RATE..=MAX_PUSH_SAMPLE_RATE).contains(&sample_rate) {
+        return Err(format!(
+            "Unsupported sample rate {} Hz (expected {}-{})",
+            sample_rate, MIN_PUSH_SAMPLE_RATE, MAX_PUSH_SAMPLE_RATE,
+        ));
+    }
+    // The mic would be transcribed a second time, unattributed
+    if *app.state::<AudioState>().is_recording.lock().map_err(|e| e.to_string())? {
+        return Err("Native audio capture is running - stop it before pushing participant tracks".to_string());
+    }
+
+    let state = app.state::<ParticipantState>();
+    let now_ms = state.now_ms(&app);
+    let session_id = app.state::<LiveSessionState>().active_id();
+    let mut finished = Vec::new();
+    {
+        let mut channels = state.channels.lock().unwrap();
+        // Check the whole call first: an error after some tracks were fed
+        // would drop the segments they completed
+        let names = tracks.iter()
+            .map(|track| validate_name(&track.participant))
+            .collect::<Result<Vec<_>, _>>()?;
+        let new_tracks: HashSet<&String> = names.iter().filter(|name| !channels.contains_key(*name)).collect();
+        if channels.len() + new_tracks.len() > MAX_PARTICIPANTS {
+            return Err(format!("At most {} participant tracks at once", MAX_PARTICIPANTS));
+        }
+
+        for (track, name) in tracks.into_iter().zip(names) {
+            let channel = channels.entry(name.clone()).or_insert_with(|| {
+                info!("[PARTICIPANTS] Track opened: {} ({} Hz)", name, sample_rate);
+                Channel::new(sample_rate, now_ms)
+            });
+            if channel.sample_rate != sample_rate {
+                channel.sample_rate = sample_rate;
+                channel.resampler = StreamResampler::new(sample_rate);
+            }
+            let mono = to_mono(&track.samples, track.channels.unwrap_or(1).max(1));
+            let resampled = channel.resampler.process(mono);
+            for (start, audio) in channel.feed(&resampled) {
+                finished.push(SpeechSegment {
+                    speaker: name.clone(),
+                    session_id: session_id.clone(),
+                    start_ms: channel.start_ms(start),
+                    audio,
+                });
+            }
+        }
+    }
+
+    let count = finished.len();
+    spawn_segments(&app, finished);
+    Ok(count)
+}
+
+/// Close a participant's track, transcribing any speech still buffered.
+/// Returns false if there was no such track.
+#[tauri::command]
+pub fn close_participant_track(app: AppHandle, participant: String) -> Result<bool, String> {
+    let name = validate_name(&participant)?;
+    let state = app.state::<ParticipantState>();
+    let Some(mut channel) = state.channels.lock().unwrap().remove(&name) else {
+        return Ok(false);
+    };
+    let session_id = app.state::<LiveSessionState>().active_id();
+    let pending: Vec<SpeechSegment> = channel.cut()
+        .map(|(start, audio)| SpeechSegment { speaker: name.clone(), session_id, start_ms: channel.start_ms(start), audio })
+        .into_iter()
+        .collect();
+    info!("[PARTICIPANTS] Track closed: {} ({} segment(s))", name, channel.segments);
+    spawn_segments(&app, pending);
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn list_participant_tracks(state: tauri::State<'_, ParticipantState>) -> Vec<ParticipantStatus> {
+    let mut tracks: Vec<ParticipantStatus> = state.channels.lock().unwrap().iter()
+        .map(|(name, channel)| ParticipantStatus {
+            participant: name.clone(),
+            speaking: channel.speech_start.is_some(),
+            received_secs: secs(channel.received),
+            segments: channel.segments,
+        })
+        .collect();
+    tracks.sort_by(|a, b| a.participant.cmp(&b.participant));
+    tracks
+}
@@ -0,0 +1,152 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use tauri::{AppHandle, Manager};
+use crate::audio_capture::{AudioState, CaptureMode};
+use crate::events::{self, PipelineState};
+use crate::gemini_client::GeminiState;
+use crate::levels::InputLevel;
+use crate::live_session::LiveSessionState;
+use crate::network::NetworkState;
+use crate::session_manager::SessionManager;
+use crate::whisper_client::WhisperState;
+
+// ============================================================================
+// PIPELINE STATUS - One Snapshot of Capture, Whisper, Provider and Session
+// ============================================================================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AudioStatus {
+    pub running: bool,
+    pub capture_mode: CaptureMode,
+    // Default input device, when the mode includes the microphone
+    pub device: Option<String>,
+    pub level: InputLevel,
+    pub noise_suppression: bool,
+    pub echo_cancellation: bool,
+    // The segmenting/analysis loop, which outlives a paused capture
+    pub loop_running: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WhisperStatus {
+    pub initialized: bool,
+    // Model file name
+    pub model: Option<String>,
+    pub language: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProviderStatus {
+    pub model: String,
+    pub connected: bool,
+    pub has_api_key: bool,
+    pub local_only: bool,
+    pub paused: bool,
+    pub backoff_remaining_ms: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SessionStatus {
+    pub id: String,
+    pub title: String,
+    pub started_at: String,
+    pub segment_count: usize,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PipelineStatus {
+    // Latest cognivox:status
+    pub state: PipelineState,
+    pub message: String,
+    pub audio: AudioStatus,
+    pub whisper: WhisperStatus,
+    pub provider: ProviderStatus,
+    pub session: Option<SessionStatus>,
+    pub timestamp: u64,
+}
+
+fn audio_status(app: &AppHandle) -> AudioStatus {
+    let audio = app.state::<AudioState>();
+    let capture_mode = *audio.capture_mode.lock().unwrap();
+    let device = match capture_mode {
+        CaptureMode::MicOnly | CaptureMode::Both => cpal::default_host()
+            .default_input_device()
+            .and_then(|d| d.name().ok()),
+        CaptureMode::SystemOnly => None,
+    };
+    let running = *audio.is_recording.lock().unwrap();
+    let level = *audio.input_level.lock().unwrap();
+    AudioStatus {
+        running,
+        capture_mode,
+        device,
+        level,
+        noise_suppression: audio.noise_suppression_enabled(),
+        echo_cancellation: audio.echo_cancellation_enabled(),
+        loop_running: app.state::<GeminiState>().audio_loop_running(),
+    }
+}
+
+fn whisper_status(app: &AppHandle) -> WhisperStatus {
+    let whisper = app.state::<WhisperState>();
+    let model = whisper.model_path.lock().unwrap().as_ref()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned());
+    let initialized = *whisper.is_initialized.lock().unwrap();
+    let language = whisper.language.lock().unwrap().clone();
+    WhisperStatus { initialized, model, language }
+}
+
+fn provider_status(app: &AppHandle) -> ProviderStatus {
+    let gemini = app.state::<GeminiState>();
+    let next_allowed_at = gemini.next_allowed_at.load(Ordering::SeqCst);
+    let model = gemini.selected_model.lock().unwrap().clone();
+    let connected = *gemini.is_connected.lock().unwrap();
+    let has_api_key = gemini.api_key.lock().unwrap().as_deref().is_some_and(|k| !k.is_empty());
+    let paused = *gemini.is_paused.lock().unwrap();
+    ProviderStatus {
+        model,
+        connected,
+        has_api_key,
+        local_only: app.state::<NetworkState>().is_local_only(),
+        paused,
+        backoff_remaining_ms: next_allowed_at.saturating_sub(events::now_ms()),
+    }
+}
+
+fn session_status(app: &AppHandle) -> Option<SessionStatus> {
+    let live = app.state::<LiveSessionState>();
+    let active = live.active()?;
+    // Unreadable (e.g. locked store) still reports the session, just without a count
+    let segment_count = SessionManager::new()
+        .and_then(|m| m.load_session(&active.id))
+        .map(|s| s.transcripts.len())
+        .unwrap_or(0);
+    Some(SessionStatus {
+        segment_count,
+        duration_ms: live.elapsed_ms().unwrap_or(0),
+        id: active.id,
+        title: active.title,
+        started_at: active.started_at,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_pipeline_status(app: AppHandle) -> PipelineStatus {
+    let status = events::last_status();
+    PipelineStatus {
+        state: status.state,
+        message: status.message,
+        audio: audio_status(&app),
+        whisper: whisper_status(&app),
+        provider: provider_status(&app),
+        session: session_status(&app),
+        timestamp: events::now_ms(),
+    }
+}
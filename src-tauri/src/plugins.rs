@@ -0,0 +1,374 @@
+use rhai::{CallFnOptions, Dynamic, Engine, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::events::{self, IntelligenceEvent};
+use crate::gemini_client::extract_json;
+use crate::live_session::record_segment;
+use crate::schema;
+use crate::settings::{app_data_dir, SettingsState};
+use crate::whisper_client::SegmentLanguage;
+
+// ============================================================================
+// PLUGINS - Rhai Scripts Run on Every Intelligence Result
+// ============================================================================
+//
+// Each *.rhai file in <app data>/plugins may define
+//
+//   fn on_intelligence(result) { ... }
+//
+// `result` carries segment_id, session_id, transcript, speaker, start_ms,
+// end_ms, retried and the parsed `intelligence` object. Return nothing to
+// keep the result as is, a map to replace the intelligence, or `false` to
+// drop it (the transcript is still stored, without intelligence). Scripts
+// run in file-name order, each seeing the previous one's output; with an
+// intelligence schema set, a replacement that doesn't match it is ignored.
+// Scripts run on copies of the compiled ASTs, outside the plugin list lock.
+//
+// Besides print (logged), scripts get append_line(file, text) and
+// append_csv(file, [values]) for routing; files land in plugins/output.
+//
+//   fn on_intelligence(result) {
+//       if result.intelligence.category.contains("RISK") {
+//           append_csv("risks.csv", [result.start_ms, result.speaker, result.transcript]);
+//       }
+//   }
+
+const HOOK_FN: &str = "on_intelligence";
+// Budget per call, so a runaway loop can't stall the pipeline
+const MAX_OPERATIONS: u64 = 500_000;
+const MAX_STRING_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PluginConfig {
+    pub enabled: bool,
+    // Script file names (e.g. "risks.rhai") skipped while loaded
+    pub disabled: Vec<String>,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self { enabled: true, disabled: Vec::new() }
+    }
+}
+
+/// What a script sees besides the intelligence itself
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct PluginInput<'a> {
+    pub segment_id: Option<&'a str>,
+    pub session_id: Option<&'a str>,
+    pub transcript: &'a str,
+    pub speaker: &'a str,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    pub language: Option<&'a SegmentLanguage>,
+    pub retried: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PluginInfo {
+    pub name: String,
+    pub path: String,
+    pub enabled: bool,
+    // Compiled and defines on_intelligence
+    pub loaded: bool,
+    // Compile error, or the most recent runtime error
+    pub error: Option<String>,
+    pub runs: u64,
+    pub dropped: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PluginList {
+    pub dir: String,
+    pub config: PluginConfig,
+    pub plugins: Vec<PluginInfo>,
+}
+
+struct Plugin {
+    name: String,
+    path: PathBuf,
+    ast: Option<AST>,
+    error: Option<String>,
+    runs: u64,
+    dropped: u64,
+}
+
+/// What a script asked for
+enum Verdict {
+    Keep,
+    Replace(serde_json::Value),
+    Drop,
+}
+
+pub struct PluginState {
+    engine: Engine,
+    plugins: StdMutex<Vec<Plugin>>,
+}
+
+fn plugins_dir() -> Result<PathBuf, String> {
+    let dir = app_data_dir()?.join("plugins");
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
+    Ok(dir)
+}
+
+/// Scripts may only name a file inside plugins/output
+fn output_path(dir: &Path, file: &str) -> Result<PathBuf, String> {
+    let valid = !file.is_empty()
+        && !file.starts_with('.')
+        && file.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if !valid {
+        return Err(format!("Invalid output file name '{}' (letters, digits, '.', '-' and '_' only)", file));
+    }
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create plugin output directory: {}", e))?;
+    Ok(dir.join(file))
+}
+
+fn append(dir: &Path, file: &str, line: &str) -> Result<(), String> {
+    let path = output_path(dir, file)?;
+    let mut handle = OpenOptions::new().create(true).append(true).open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(handle, "{}", line)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn csv_field(value: &Dynamic) -> String {
+    let text = value.to_string();
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn build_engine(output_dir: PathBuf) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.on_print(|text| info!("[PLUGIN] {}", text));
+    engine.on_debug(|text, source, _| info!("[PLUGIN] {} {}", source.unwrap_or(""), text));
+
+    let dir = output_dir.clone();
+    engine.register_fn("append_line", move |file: &str, text: &str| -> Result<(), Box<rhai::EvalAltResult>> {
+        append(&dir, file, text).map_err(Into::into)
+    });
+    let dir = output_dir;
+    engine.register_fn("append_csv", move |file: &str, row: rhai::Array| -> Result<(), Box<rhai::EvalAltResult>> {
+        let line = row.iter().map(csv_field).collect::<Vec<_>>().join(",");
+        append(&dir, file, &line).map_err(Into::into)
+    });
+    engine
+}
+
+impl PluginState {
+    pub fn load() -> Self {
+        let output_dir = plugins_dir()
+            .map(|d| d.join("output"))
+            .unwrap_or_else(|_| PathBuf::from("plugins/output"));
+        let state = Self { engine: build_engine(output_dir), plugins: StdMutex::new(Vec::new()) };
+        if let Err(e) = state.reload() {
+            warn!("[PLUGIN] {}", e);
+        }
+        state
+    }
+
+    /// Recompile every script in the plugins directory
+    pub fn reload(&self) -> Result<usize, String> {
+        let dir = plugins_dir()?;
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read plugins directory: {}", e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "rhai"))
+            .collect();
+        paths.sort();
+
+        let plugins: Vec<Plugin> = paths.into_iter().map(|path| self.compile(path)).collect();
+        let loaded = plugins.iter().filter(|p| p.ast.is_some()).count();
+        if !plugins.is_empty() {
+            info!("[PLUGIN] Loaded {}/{} script(s) from {}", loaded, plugins.len(), dir.display());
+        }
+        *self.plugins.lock().unwrap() = plugins;
+        Ok(loaded)
+    }
+
+    fn compile(&self, path: PathBuf) -> Plugin {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let compiled = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read: {}", e))
+            .and_then(|source| self.engine.compile(&source).map_err(|e| e.to_string()))
+            .and_then(|ast| {
+                let has_hook = ast.iter_functions().any(|f| f.name == HOOK_FN && f.params.len() == 1);
+                if has_hook { Ok(ast) } else { Err(format!("No {}(result) function", HOOK_FN)) }
+            });
+        if let Err(e) = &compiled {
+            warn!("[PLUGIN] ✗ {}: {}", name, e);
+        }
+        let (ast, error) = match compiled {
+            Ok(ast) => (Some(ast), None),
+            Err(e) => (None, Some(e)),
+        };
+        Plugin { name, path, ast, error, runs: 0, dropped: 0 }
+    }
+
+    fn call(&self, ast: &AST, result: &serde_json::Value) -> Result<Verdict, String> {
+        let input = rhai::serde::to_dynamic(result).map_err(|e| e.to_string())?;
+        let options = CallFnOptions::new().eval_ast(false);
+        let output: Dynamic = self.engine
+            .call_fn_with_options(options, &mut Scope::new(), ast, HOOK_FN, (input,))
+            .map_err(|e| e.to_string())?;
+
+        if output.is_unit() {
+            Ok(Verdict::Keep)
+        } else if output.as_bool() == Ok(false) {
+            Ok(Verdict::Drop)
+        } else if output.is_map() {
+            rhai::serde::from_dynamic(&output)
+                .map(Verdict::Replace)
+                .map_err(|e| e.to_string())
+        } else {
+            Err(format!("{} returned a {}; expected nothing, a map or false", HOOK_FN, output.type_name()))
+        }
+    }
+
+    /// Count a run (and a drop) and keep its error, if the script is still loaded
+    fn record_run(&self, name: &str, dropped: bool, error: Option<String>) {
+        let mut plugins = self.plugins.lock().unwrap();
+        let Some(plugin) = plugins.iter_mut().find(|p| p.name == name) else { return };
+        plugin.runs += 1;
+        if dropped {
+            plugin.dropped += 1;
+        }
+        if error.is_some() {
+            plugin.error = error;
+        }
+    }
+
+    fn list(&self, config: PluginConfig) -> PluginList {
+        let plugins = self.plugins.lock().unwrap().iter()
+            .map(|p| PluginInfo {
+                name: p.name.clone(),
+                path: p.path.to_string_lossy().into_owned(),
+                enabled: !config.disabled.contains(&p.name),
+                loaded: p.ast.is_some(),
+                error: p.error.clone(),
+                runs: p.runs,
+                dropped: p.dropped,
+            })
+            .collect();
+        PluginList {
+            dir: plugins_dir().map(|d| d.to_string_lossy().into_owned()).unwrap_or_default(),
+            config,
+            plugins,
+        }
+    }
+}
+
+/// Run the enabled scripts over one result. Returns the (possibly rewritten)
+/// intelligence JSON, or None if a script dropped it.
+pub(crate) fn apply(app: &AppHandle, input: &PluginInput<'_>, intelligence: String) -> Option<String> {
+    let settings = app.state::<SettingsState>().get();
+    let config = settings.plugins;
+    let state = app.state::<PluginState>();
+    let scripts: Vec<(String, AST)> = state.plugins.lock().unwrap().iter()
+        .filter(|p| !config.disabled.contains(&p.name))
+        .filter_map(|p| p.ast.clone().map(|ast| (p.name.clone(), ast)))
+        .collect();
+    if !config.enabled || scripts.is_empty() {
+        return Some(intelligence);
+    }
+    // Unparseable model output goes through untouched
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(extract_json(&intelligence)) else {
+        return Some(intelligence);
+    };
+    let mut result = match serde_json::to_value(input) {
+        Ok(value) => value,
+        Err(_) => return Some(intelligence),
+    };
+    result["intelligence"] = parsed;
+
+    let mut changed = false;
+    for (name, ast) in &scripts {
+        let verdict = state.call(ast, &result).and_then(|verdict| match (verdict, &settings.intelligence_schema) {
+            (Verdict::Replace(value), Some(output_schema)) => schema::validate(output_schema, &value)
+                .map(|_| Verdict::Replace(value))
+                .map_err(|e| format!("Rewritten intelligence doesn't match the schema: {}", e)),
+            (verdict, _) => Ok(verdict),
+        });
+        match verdict {
+            Ok(Verdict::Keep) => state.record_run(name, false, None),
+            Ok(Verdict::Replace(value)) => {
+                state.record_run(name, false, None);
+                result["intelligence"] = value;
+                changed = true;
+            }
+            Ok(Verdict::Drop) => {
+                state.record_run(name, true, None);
+                info!("[PLUGIN] {} dropped the result for segment {}", name, input.segment_id.unwrap_or("-"));
+                return None;
+            }
+            Err(e) => {
+                warn!("[PLUGIN] ✗ {}: {}", name, e);
+                state.record_run(name, false, Some(e));
+            }
+        }
+    }
+
+    if changed {
+        Some(result["intelligence"].to_string())
+    } else {
+        Some(intelligence)
+    }
+}
+
+/// A dropped result still leaves its transcript behind, minus intelligence
+pub(crate) fn emit_dropped(app: &AppHandle, input: &PluginInput<'_>) {
+    if let (Some(session_id), Some(segment_id)) = (input.session_id, input.segment_id) {
+        record_segment(session_id, segment_id, input.transcript, input.speaker, None, (input.start_ms, input.end_ms), input.language);
+    }
+    events::emit(app, &IntelligenceEvent {
+        segment_id: input.segment_id.map(str::to_string),
+        session_id: input.session_id.map(str::to_string),
+        transcript: input.transcript.to_string(),
+        speaker: Some(input.speaker.to_string()),
+        intelligence: None,
+        pending: false,
+        retried: input.retried,
+        start_ms: input.start_ms,
+        end_ms: input.end_ms,
+        timestamp: events::now_ms(),
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_plugins(state: tauri::State<'_, PluginState>, settings: tauri::State<'_, SettingsState>) -> PluginList {
+    state.list(settings.get().plugins)
+}
+
+/// Pick up added, edited or removed scripts
+#[tauri::command]
+pub fn reload_plugins(state: tauri::State<'_, PluginState>, settings: tauri::State<'_, SettingsState>) -> Result<PluginList, String> {
+    state.reload()?;
+    Ok(state.list(settings.get().plugins))
+}
+
+#[tauri::command]
+pub fn set_plugin_config(
+    state: tauri::State<'_, PluginState>,
+    settings: tauri::State<'_, SettingsState>,
+    config: PluginConfig,
+) -> Result<PluginList, String> {
+    settings.update(|s| s.plugins = config.clone())?;
+    info!("[PLUGIN] Plugins {}, {} disabled", if config.enabled { "enabled" } else { "off" }, config.disabled.len());
+    Ok(state.list(config))
+}
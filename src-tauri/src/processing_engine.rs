@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::settings::SettingsState;
 
 // ============================================================================
 // STATION 3: OMNIPOTENT PROCESSING ENGINE
@@ -72,13 +73,17 @@ const VALID_TONES: &[&str] = &[
     "HESITANT", "DOMINANT", "EMPATHETIC", "NEUTRAL"
 ];
 
+pub fn default_categories() -> Vec<String> {
+    VALID_CATEGORIES.iter().map(|s| s.to_string()).collect()
+}
+
 pub fn validate_intelligence_output(json: &str) -> Result<IntelligenceOutput, String> {
     serde_json::from_str::<IntelligenceOutput>(json)
         .map_err(|e| format!("Schema validation failed: {}", e))
 }
 
-pub fn validate_category(category: &[String]) -> bool {
-    category.iter().all(|c| VALID_CATEGORIES.contains(&c.as_str()))
+pub fn validate_category(category: &[String], allowed: &[String]) -> bool {
+    category.iter().all(|c| allowed.contains(c))
 }
 
 pub fn validate_tone(tone: &Option<String>) -> bool {
@@ -216,7 +221,7 @@ impl Default for ProcessingSettings {
             prediction_aggression: 0.5,
             max_error_streak: 5,
             enable_optimistic: true,
-            categories_filter: VALID_CATEGORIES.iter().map(|s| s.to_string()).collect(),
+            categories_filter: default_categories(),
         }
     }
 }
@@ -348,7 +353,7 @@ pub fn process_intelligence(
             *state.error_streak.lock().unwrap() = 0;
             
             // Validate categories and tones
-            if !validate_category(&output.intelligence.category) {
+            if !validate_category(&output.intelligence.category, &default_categories()) {
                 return Err(ProcessingError::InvalidCategory);
             }
             if !validate_tone(&output.intelligence.tone) {
@@ -497,10 +502,13 @@ fn now_ms() -> u64 {
 // ============================================================================
 
 #[tauri::command]
-pub fn validate_json_schema(json_str: String) -> Result<bool, String> {
+pub fn validate_json_schema(
+    settings: tauri::State<'_, SettingsState>,
+    json_str: String,
+) -> Result<bool, String> {
     match validate_intelligence_output(&json_str) {
         Ok(output) => {
-            if !validate_category(&output.intelligence.category) {
+            if !validate_category(&output.intelligence.category, settings.get().active_categories()) {
                 return Err("Invalid category".to_string());
             }
             if !validate_tone(&output.intelligence.tone) {
@@ -529,11 +537,6 @@ pub fn get_recent_intelligence(count: u32) -> Result<Vec<String>, String> {
     Ok(vec![])
 }
 
-#[tauri::command]
-pub fn clear_intelligence_cache() -> Result<String, String> {
-    Ok("Cache cleared".to_string())
-}
-
 #[tauri::command]
 pub fn inject_manual_intelligence(
     text: String,
@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::events::{self, PipelineState, ReanalysisProgressEvent};
+use crate::gemini_client::{annotate_segment, build_intelligence_prompt, call_gemini_with_text, extract_json, GeminiState};
+use crate::session_manager::{SessionData, SessionManager, TranscriptEntry};
+use crate::settings::SettingsState;
+use crate::simulation::MockProvider;
+
+// ============================================================================
+// REANALYSIS - Re-run Intelligence Extraction over a Stored Session
+// ============================================================================
+//
+// Segments go back through the intelligence layer one at a time, in order,
+// with the same rolling context the live loop uses and the shared rate
+// limiter. Each run is stored as a numbered version next to the transcripts,
+// which stay as they are; the first run also stores the original (live)
+// analysis as version 1 so there's always something to compare against.
+
+// A rate-limited segment is retried after the backoff; other errors aren't
+const MAX_ATTEMPTS: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisProvider {
+    // Analysis recorded while the session was captured
+    Live,
+    Gemini,
+    // Offline keyword rules (see simulation)
+    Mock,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SegmentAnalysis {
+    // Index into the session's transcripts
+    pub segment: usize,
+    pub segment_id: Option<String>,
+    pub tone: Option<String>,
+    pub category: Vec<String>,
+    pub confidence: f32,
+    // Raw model JSON; not kept for the live version
+    pub intelligence: Option<Value>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AnalysisRun {
+    pub version: u32,
+    pub provider: AnalysisProvider,
+    pub model: Option<String>,
+    pub prompt: Option<String>,
+    pub created_at: String,
+    pub segments: Vec<SegmentAnalysis>,
+    pub failed: usize,
+}
+
+/// A segment whose tone or categories differ between two versions
+#[derive(Serialize, Clone, Debug)]
+pub struct AnalysisChange {
+    pub segment: usize,
+    pub speaker: String,
+    pub text: String,
+    pub before: Option<SegmentAnalysis>,
+    pub after: Option<SegmentAnalysis>,
+}
+
+#[derive(Default)]
+pub struct ReanalysisState {
+    // Session being reanalyzed
+    running: StdMutex<Option<String>>,
+    cancel: AtomicBool,
+}
+
+fn live_run(session: &SessionData) -> AnalysisRun {
+    AnalysisRun {
+        version: 1,
+        provider: AnalysisProvider::Live,
+        model: None,
+        prompt: None,
+        created_at: session.created_at.clone(),
+        segments: session.transcripts.iter()
+            .enumerate()
+            .map(|(segment, t)| SegmentAnalysis {
+                segment,
+                segment_id: t.segment_id.clone(),
+                tone: t.tone.clone(),
+                category: t.category.clone().unwrap_or_default(),
+                confidence: t.confidence,
+                intelligence: None,
+                error: None,
+            })
+            .collect(),
+        failed: 0,
+    }
+}
+
+fn parse(segment: usize, entry: &TranscriptEntry, response: Result<String, String>) -> SegmentAnalysis {
+    let mut analysis = SegmentAnalysis {
+        segment,
+        segment_id: entry.segment_id.clone(),
+        tone: None,
+        category: Vec::new(),
+        confidence: 0.0,
+        intelligence: None,
+        error: None,
+    };
+    match response.and_then(|r| serde_json::from_str::<Value>(extract_json(&r)).map_err(|e| format!("Invalid JSON: {}", e))) {
+        Ok(parsed) => {
+            analysis.tone = parsed["tone"].as_str().map(str::to_string);
+            analysis.category = parsed["category"].as_array()
+                .map(|c| c.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                .unwrap_or_default();
+            analysis.confidence = parsed["confidence"].as_f64().unwrap_or(0.0) as f32;
+            analysis.intelligence = Some(parsed);
+        }
+        Err(e) => analysis.error = Some(e),
+    }
+    analysis
+}
+
+async fn run(
+    app: &AppHandle,
+    session: &SessionData,
+    provider: AnalysisProvider,
+    model: Option<String>,
+) -> Result<AnalysisRun, String> {
+    let settings = app.state::<SettingsState>().get();
+    let gemini = app.state::<GeminiState>();
+    let config = match provider {
+        AnalysisProvider::Gemini => {
+            let mut config = gemini.request_config(app)?;
+            if let Some(model) = &model {
+                config.model = model.clone();
+            }
+            Some(config)
+        }
+        _ => None,
+    };
+    let system_prompt = build_intelligence_prompt(&settings);
+    let context_size = *gemini.context_size.lock().unwrap();
+    let state = app.state::<ReanalysisState>();
+
+    let total = session.transcripts.len();
+    let mut segments = Vec::with_capacity(total);
+    let mut context: Vec<String> = Vec::new();
+    for (index, entry) in session.transcripts.iter().enumerate() {
+        if state.cancel.load(Ordering::SeqCst) {
+            return Err("Reanalysis cancelled".to_string());
+        }
+        let annotated = annotate_segment(&entry.speaker_id, &entry.text, 1.0);
+        let response = match &config {
+            Some(config) => {
+                let mut attempt = 1;
+                loop {
+                    match call_gemini_with_text(config, &system_prompt, &annotated, &context).await {
+                        Err(e) if e.starts_with("Rate limited") && attempt < MAX_ATTEMPTS => attempt += 1,
+                        result => break result,
+                    }
+                }
+            }
+            None => Ok(MockProvider::analyze(app, &entry.text, &entry.speaker_id).await),
+        };
+        let analysis = parse(index, entry, response);
+        if let Some(e) = &analysis.error {
+            warn!("[REANALYZE] Segment {}/{} failed: {}", index + 1, total, e);
+        }
+        events::emit(app, &ReanalysisProgressEvent {
+            session_id: session.id.clone(),
+            done: index + 1,
+            total,
+            analysis: analysis.clone(),
+        });
+        segments.push(analysis);
+
+        context.push(annotated);
+        if context.len() > context_size {
+            context.remove(0);
+        }
+    }
+
+    Ok(AnalysisRun {
+        version: 0,
+        provider,
+        model: config.map(|c| c.model),
+        prompt: Some(system_prompt),
+        created_at: Utc::now().to_rfc3339(),
+        failed: segments.iter().filter(|s| s.error.is_some()).count(),
+        segments,
+    })
+}
+
+/// Reanalyze every segment of a stored session and store the result as a new version
+pub async fn reanalyze(
+    app: &AppHandle,
+    session_id: &str,
+    provider: AnalysisProvider,
+    model: Option<String>,
+) -> Result<AnalysisRun, String> {
+    if provider == AnalysisProvider::Live {
+        return Err("The live analysis can't be re-run; use gemini or mock".to_string());
+    }
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(session_id)?;
+    if session.transcripts.is_empty() {
+        return Err("Session has no transcripts to reanalyze".to_string());
+    }
+
+    let state = app.state::<ReanalysisState>();
+    {
+        let mut running = state.running.lock().unwrap();
+        if let Some(other) = running.as_deref() {
+            return Err(format!("Session {} is already being reanalyzed", other));
+        }
+        *running = Some(session_id.to_string());
+    }
+    state.cancel.store(false, Ordering::SeqCst);
+
+    events::emit_status(app, PipelineState::Analyzing, format!("Reanalyzing {} segment(s)...", session.transcripts.len()));
+    let result = run(app, &session, provider, model).await;
+    *state.running.lock().unwrap() = None;
+    let mut analysis = match result {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            events::emit_status(app, PipelineState::Ready, "Ready");
+            return Err(e);
+        }
+    };
+
+    // Reload: bookmarks or chapters may have been saved meanwhile
+    manager.update_session(session_id, |session| {
+        if session.analyses.is_empty() {
+            session.analyses.push(live_run(session));
+        }
+        analysis.version = session.analyses.iter().map(|a| a.version).max().unwrap_or(0) + 1;
+        session.analyses.push(analysis.clone());
+        Ok(())
+    })?;
+
+    info!(
+        "[REANALYZE] ✓ Session {} version {} ({} segment(s), {} failed)",
+        session_id, analysis.version, analysis.segments.len(), analysis.failed
+    );
+    events::emit_status(app, PipelineState::Ready, format!("Reanalysis v{} ready ✓", analysis.version));
+    Ok(analysis)
+}
+
+/// Segments whose tone or categories differ between versions `from` and `to`
+pub fn compare(session: &SessionData, from: u32, to: u32) -> Result<Vec<AnalysisChange>, String> {
+    let find = |version: u32| {
+        session.analyses.iter()
+            .find(|a| a.version == version)
+            .ok_or_else(|| format!("Analysis version {} not found", version))
+    };
+    let (before, after) = (find(from)?, find(to)?);
+    let at = |run: &AnalysisRun, segment: usize| run.segments.iter().find(|s| s.segment == segment).cloned();
+
+    Ok(session.transcripts.iter()
+        .enumerate()
+        .filter_map(|(segment, t)| {
+            let (before, after) = (at(before, segment), at(after, segment));
+            let same = match (&before, &after) {
+                (Some(b), Some(a)) => b.tone == a.tone && b.category == a.category,
+                (None, None) => true,
+                _ => false,
+            };
+            (!same).then(|| AnalysisChange {
+                segment,
+                speaker: t.speaker_id.clone(),
+                text: t.text.clone(),
+                before,
+                after,
+            })
+        })
+        .collect())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Reanalyze with `provider` (default gemini) and optionally another model than the selected one
+#[tauri::command]
+pub async fn reanalyze_session(
+    app: AppHandle,
+    session_id: String,
+    provider: Option<AnalysisProvider>,
+    model: Option<String>,
+) -> Result<AnalysisRun, String> {
+    let model = model.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+    reanalyze(&app, &session_id, provider.unwrap_or(AnalysisProvider::Gemini), model).await
+}
+
+#[tauri::command]
+pub fn cancel_reanalysis(state: tauri::State<'_, ReanalysisState>) -> bool {
+    let running = state.running.lock().unwrap().is_some();
+    if running {
+        state.cancel.store(true, Ordering::SeqCst);
+    }
+    running
+}
+
+/// Every stored analysis version, oldest first
+#[tauri::command]
+pub fn get_session_analyses(session_id: String) -> Result<Vec<AnalysisRun>, String> {
+    Ok(SessionManager::new()?.load_session(&session_id)?.analyses)
+}
+
+#[tauri::command]
+pub fn compare_session_analyses(session_id: String, from: u32, to: u32) -> Result<Vec<AnalysisChange>, String> {
+    compare(&SessionManager::new()?.load_session(&session_id)?, from, to)
+}
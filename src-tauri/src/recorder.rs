@@ -0,0 +1,177 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use tracing::{error, info};
+use crate::live_session::LiveSessionState;
+use crate::session_manager::{valid_session_id, SessionManager};
+use crate::settings::app_data_dir;
+
+// ============================================================================
+// RECORDER - Per-Session WAV Recording of the Captured Audio
+// ============================================================================
+
+const RECORDING_SAMPLE_RATE: u32 = 16000;
+const FLUSH_EVERY_SAMPLES: u64 = RECORDING_SAMPLE_RATE as u64 * 5;  // Rewrite header every ~5s
+const WAV_HEADER_BYTES: u64 = 44;
+
+struct ActiveRecording {
+    writer: WavWriter<BufWriter<File>>,
+    path: PathBuf,
+    session_id: String,
+    samples_written: u64,
+    samples_since_flush: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RecordingInfo {
+    pub session_id: String,
+    pub path: String,
+    pub duration_secs: f32,
+}
+
+/// Where a session's WAV lives; creates the recordings directory
+pub fn recording_path(session_id: &str) -> Result<PathBuf, String> {
+    if !valid_session_id(session_id) {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+    let dir = app_data_dir()?.join("recordings");
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+    Ok(dir.join(format!("{}.wav", session_id)))
+}
+
+#[derive(Default)]
+pub struct RecorderState {
+    active: StdMutex<Option<ActiveRecording>>,
+    // Why the last recording stopped on its own, until the resource monitor reports it
+    failure: StdMutex<Option<String>>,
+}
+
+impl RecorderState {
+    /// Append 16 kHz mono samples; a no-op unless recording is on
+    pub fn append(&self, samples: &[f32]) {
+        let mut active = self.active.lock().unwrap();
+        let Some(rec) = active.as_mut() else { return; };
+
+        for &s in samples {
+            let value = (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            if let Err(e) = rec.writer.write_sample(value) {
+                error!("[RECORDER] ✗ Write failed, stopping recording: {}", e);
+                *self.failure.lock().unwrap() = Some(format!("Recording of {} stopped: {}", rec.session_id, e));
+                *active = None;
+                return;
+            }
+        }
+        rec.samples_written += samples.len() as u64;
+        rec.samples_since_flush += samples.len() as u64;
+
+        // Keep the header valid so a crash still leaves a playable file
+        if rec.samples_since_flush >= FLUSH_EVERY_SAMPLES {
+            rec.samples_since_flush = 0;
+            if let Err(e) = rec.writer.flush() {
+                error!("[RECORDER] ✗ Flush failed: {}", e);
+            }
+        }
+    }
+
+    pub(crate) fn start(&self, session_id: String) -> Result<RecordingInfo, String> {
+        let mut active = self.active.lock().unwrap();
+        if let Some(rec) = active.as_ref() {
+            return Err(format!("Already recording session {}", rec.session_id));
+        }
+
+        let path = recording_path(&session_id)?;
+
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: RECORDING_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let writer = WavWriter::create(&path, spec)
+            .map_err(|e| format!("Failed to create WAV file: {}", e))?;
+
+        info!("[RECORDER] ● Recording session {} to {:?}", session_id, path);
+        let info = RecordingInfo {
+            session_id: session_id.clone(),
+            path: path.to_string_lossy().to_string(),
+            duration_secs: 0.0,
+        };
+        *active = Some(ActiveRecording {
+            writer,
+            path,
+            session_id,
+            samples_written: 0,
+            samples_since_flush: 0,
+        });
+        Ok(info)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.lock().unwrap().is_some()
+    }
+
+    /// Size of the WAV being written, header included
+    pub fn bytes_written(&self) -> Option<u64> {
+        self.active.lock().unwrap().as_ref().map(|rec| WAV_HEADER_BYTES + rec.samples_written * 2)
+    }
+
+    pub fn take_failure(&self) -> Option<String> {
+        self.failure.lock().unwrap().take()
+    }
+
+    fn stop(&self) -> Result<RecordingInfo, String> {
+        let rec = self.active.lock().unwrap().take()
+            .ok_or("Not recording")?;
+
+        let info = RecordingInfo {
+            session_id: rec.session_id,
+            path: rec.path.to_string_lossy().to_string(),
+            duration_secs: rec.samples_written as f32 / RECORDING_SAMPLE_RATE as f32,
+        };
+        rec.writer.finalize()
+            .map_err(|e| format!("Failed to finalize WAV file: {}", e))?;
+
+        info!("[RECORDER] ■ Stopped: {:.1}s written to {}", info.duration_secs, info.path);
+        Ok(info)
+    }
+}
+
+/// Finalize the WAV and attach it to the stored session if the frontend already saved one
+pub fn finish_recording(state: &RecorderState) -> Result<RecordingInfo, String> {
+    let info = state.stop()?;
+
+    let manager = SessionManager::new()?;
+    if manager.session_exists(&info.session_id) {
+        manager.update_session(&info.session_id, |session| {
+            session.recording_path = Some(info.path.clone());
+            Ok(())
+        })?;
+    }
+
+    Ok(info)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn start_recording(
+    state: tauri::State<'_, RecorderState>,
+    live: tauri::State<'_, LiveSessionState>,
+    session_id: Option<String>,
+) -> Result<RecordingInfo, String> {
+    let session_id = session_id
+        .or_else(|| live.active_id())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    state.start(session_id)
+}
+
+#[tauri::command]
+pub fn stop_recording(state: tauri::State<'_, RecorderState>) -> Result<RecordingInfo, String> {
+    finish_recording(&state)
+}
@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::encryption;
+use crate::events::{self, SessionEvent, SessionPhase};
+use crate::gemini_client::GeminiState;
+use crate::live_session::{ActiveSession, LiveSessionState};
+use crate::session_manager::{SessionData, SessionManager, TranscriptEntry};
+use crate::settings::app_data_dir;
+use crate::summarizer;
+
+// ============================================================================
+// RECOVERY - Write-Ahead Journal for Live Sessions
+// ============================================================================
+//
+// While a session runs, a marker file lists it and every segment is appended
+// (and synced) to <id>.wal next to the session JSON before the JSON itself
+// is rewritten. Ending the session removes both. Entries left in the marker
+// mean the app died mid-meeting; recover_last_session replays the newest
+// one's journal, and older crashes stay listed until they're recovered too.
+
+const ACTIVE_MARKER: &str = "active_session.json";
+
+fn marker_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join(ACTIVE_MARKER))
+}
+
+/// Sessions with an open journal, oldest first. Older versions wrote a
+/// single session rather than a list.
+fn unrecovered() -> Vec<ActiveSession> {
+    let Some(json) = marker_path().ok().and_then(|p| fs::read_to_string(p).ok()) else { return Vec::new() };
+    serde_json::from_str::<Vec<ActiveSession>>(&json)
+        .or_else(|_| serde_json::from_str::<ActiveSession>(&json).map(|s| vec![s]))
+        .unwrap_or_default()
+}
+
+fn write_marker(sessions: &[ActiveSession]) -> Result<(), String> {
+    let path = marker_path()?;
+    if sessions.is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove session marker: {}", e)),
+            _ => Ok(()),
+        };
+    }
+    let json = serde_json::to_string(sessions).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write session marker: {}", e))
+}
+
+/// Start journaling a session that just began
+pub fn begin(session: &ActiveSession) -> Result<(), String> {
+    let mut sessions = unrecovered();
+    if sessions.iter().any(|s| s.id == session.id) {
+        return Err(format!("Session {} already has a journal", session.id));
+    }
+    fs::write(SessionManager::new()?.journal_path(&session.id)?, "")
+        .map_err(|e| format!("Failed to create session journal: {}", e))?;
+    sessions.push(session.clone());
+    write_marker(&sessions)
+}
+
+/// The session ended cleanly (or was recovered) and its JSON is complete
+pub fn end(session_id: &str) {
+    if let Ok(path) = SessionManager::new().and_then(|m| m.journal_path(session_id)) {
+        let _ = fs::remove_file(path);
+    }
+    let mut sessions = unrecovered();
+    sessions.retain(|s| s.id != session_id);
+    if let Err(e) = write_marker(&sessions) {
+        warn!("[RECOVERY] {}", e);
+    }
+}
+
+/// Durably record a segment. Only sessions with an open journal are journaled.
+pub fn append(session_id: &str, entry: &TranscriptEntry) {
+    let Ok(path) = SessionManager::new().and_then(|m| m.journal_path(session_id)) else { return };
+    let Ok(mut file) = OpenOptions::new().append(true).open(&path) else { return };
+    let result = serde_json::to_string(entry)
+        .map_err(|e| e.to_string())
+        .and_then(|line| encryption::seal_line(&line))
+        .and_then(|line| writeln!(file, "{}", line).map_err(|e| e.to_string()))
+        .and_then(|_| file.sync_data().map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!("[RECOVERY] Segment not journaled for {}: {}", session_id, e);
+    }
+}
+
+/// The newest session that was still running when the app exited, other
+/// than the one live now
+pub fn interrupted_session(live: Option<&str>) -> Option<ActiveSession> {
+    unrecovered().into_iter().rev().find(|s| Some(s.id.as_str()) != live)
+}
+
+/// Rebuild the interrupted session from its JSON plus the journal
+fn replay(interrupted: &ActiveSession) -> Result<SessionData, String> {
+    if encryption::is_locked() {
+        return Err("Unlock encrypted sessions before recovering".to_string());
+    }
+    let manager = SessionManager::new()?;
+    let mut session = manager.load_session(&interrupted.id).unwrap_or_else(|_| {
+        let mut session = SessionData::new(interrupted.title.clone());
+        session.id = interrupted.id.clone();
+        session.created_at = interrupted.started_at.clone();
+        session
+    });
+
+    let journal = fs::read_to_string(manager.journal_path(&interrupted.id)?).unwrap_or_default();
+    let mut replayed = 0;
+    // A torn last line from the crash is skipped
+    let entries = journal.lines()
+        .filter_map(|l| encryption::open_line(l).ok())
+        .filter_map(|l| serde_json::from_str::<TranscriptEntry>(&l).ok());
+    for entry in entries {
+        let existing = session.transcripts.iter_mut()
+            .find(|t| t.segment_id.is_some() && t.segment_id == entry.segment_id);
+        match existing {
+            Some(t) => *t = entry,
+            None => {
+                session.add_transcript(entry);
+                replayed += 1;
+            }
+        }
+    }
+    session.transcripts.sort_by_key(|t| t.start_ms.unwrap_or(u64::MAX));
+
+    let speakers: HashSet<&str> = session.transcripts.iter().map(|t| t.speaker_id.as_str()).collect();
+    session.metadata.total_speakers = speakers.len();
+    session.metadata.total_transcripts = session.transcripts.len();
+    session.metadata.duration_seconds = session.transcripts.iter()
+        .filter_map(|t| t.end_ms)
+        .max()
+        .map(|ms| ms / 1000)
+        .unwrap_or(session.metadata.duration_seconds);
+
+    // The recorder keeps its WAV header current, so a partial recording still plays
+    if session.recording_path.is_none() {
+        let wav = app_data_dir()?.join("recordings").join(format!("{}.wav", session.id));
+        if wav.exists() {
+            session.recording_path = Some(wav.to_string_lossy().to_string());
+        }
+    }
+    session.updated_at = Utc::now().to_rfc3339();
+    manager.save_session(&session)?;
+
+    info!("[RECOVERY] ✓ Recovered {} ({} segment(s), {} from the journal)",
+          session.id, session.transcripts.len(), replayed);
+    Ok(session)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// The session to offer for recovery at startup, if the last run crashed
+#[tauri::command]
+pub fn get_interrupted_session(state: tauri::State<'_, LiveSessionState>) -> Option<ActiveSession> {
+    interrupted_session(state.active_id().as_deref())
+}
+
+/// Reload the interrupted session, optionally generating its missing summary
+#[tauri::command]
+pub async fn recover_last_session(app: AppHandle, summarize: Option<bool>) -> Result<SessionData, String> {
+    let live = app.state::<LiveSessionState>().active_id();
+    let interrupted = interrupted_session(live.as_deref()).ok_or("No interrupted session to recover")?;
+
+    let session = replay(&interrupted)?;
+    end(&session.id);
+    events::emit(&app, &SessionEvent {
+        phase: SessionPhase::Recovered,
+        session_id: session.id.clone(),
+        title: session.metadata.title.clone(),
+    });
+
+    let has_key = app.state::<GeminiState>().api_key.lock().unwrap().is_some();
+    if summarize.unwrap_or(true) && has_key && session.summary.is_none() && !session.transcripts.is_empty() {
+        let app = app.clone();
+        let session_id = session.id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = summarizer::summarize_session(app, session_id).await {
+                warn!("[RECOVERY] Summary failed: {}", e);
+            }
+        });
+    }
+    Ok(session)
+}
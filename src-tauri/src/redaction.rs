@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use regex::{Regex, RegexBuilder};
+use tauri::Manager;
+use tracing::info;
+use crate::gemini_client::GeminiState;
+use crate::settings::SettingsState;
+
+// ============================================================================
+// REDACTION - Mask PII before Text leaves the Machine
+// ============================================================================
+//
+// Applied to every request body sent to Gemini (analysis, summaries,
+// embeddings). Transcripts, events and stored sessions keep the original
+// text. Only counts are logged, never the masked values.
+
+const EMAIL_PATTERN: &str = r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}\b";
+const CARD_PATTERN: &str = r"\b(?:\d[ -]?){12,18}\d\b";
+const PHONE_PATTERN: &str = r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?)?\d{2,4}(?:[\s.-]?\d{2,4}){1,3}\b";
+// Dates have phone-like digit runs; deadlines must survive redaction
+const DATE_PATTERN: &str = r"^(?:\d{4}[-./]\d{1,2}[-./]\d{1,2}|\d{1,2}[-./]\d{1,2}[-./]\d{2,4})$";
+const MIN_PHONE_DIGITS: usize = 7;
+const MAX_PHONE_DIGITS: usize = 15;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RedactionRules {
+    pub enabled: bool,
+    pub emails: bool,
+    pub phone_numbers: bool,
+    pub credit_cards: bool,
+    // Meeting participants from the calendar invite (a lightweight stand-in for NER)
+    pub names: bool,
+    // Extra words/phrases to mask, matched case-insensitively on word boundaries
+    pub custom_terms: Vec<String>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            emails: true,
+            phone_numbers: true,
+            credit_cards: true,
+            names: false,
+            custom_terms: Vec::new(),
+        }
+    }
+}
+
+/// Compiled form of RedactionRules
+pub struct Redactor {
+    rules: RedactionRules,
+    email: Regex,
+    card: Regex,
+    phone: Regex,
+    date: Regex,
+    custom: Option<Regex>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new(RedactionRules::default()).expect("built-in redaction patterns compile")
+    }
+}
+
+/// Alternation of literal terms, longest first so phrases win over their words
+fn terms_regex(terms: &[String]) -> Result<Option<Regex>, String> {
+    let mut terms: Vec<&str> = terms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+    if terms.is_empty() {
+        return Ok(None);
+    }
+    terms.sort_by_key(|t| std::cmp::Reverse(t.len()));
+    let pattern = terms.iter().map(|t| regex::escape(t)).collect::<Vec<_>>().join("|");
+    RegexBuilder::new(&format!(r"\b(?:{})\b", pattern))
+        .case_insensitive(true)
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid redaction term: {}", e))
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits.iter().rev().enumerate()
+        .map(|(i, &d)| if i % 2 == 1 { let d2 = d * 2; if d2 > 9 { d2 - 9 } else { d2 } } else { d })
+        .sum();
+    sum % 10 == 0
+}
+
+impl Redactor {
+    pub fn new(rules: RedactionRules) -> Result<Self, String> {
+        let compile = |p: &str| Regex::new(p).map_err(|e| e.to_string());
+        Ok(Self {
+            email: compile(EMAIL_PATTERN)?,
+            card: compile(CARD_PATTERN)?,
+            phone: compile(PHONE_PATTERN)?,
+            date: compile(DATE_PATTERN)?,
+            custom: terms_regex(&rules.custom_terms)?,
+            rules,
+        })
+    }
+
+    /// Masked copy of `text`. `names` are masked too when the names rule is on.
+    pub fn redact(&self, text: &str, names: &[String]) -> String {
+        if !self.rules.enabled {
+            return text.to_string();
+        }
+        let mut out = text.to_string();
+        let mut masked: Vec<(usize, &str)> = Vec::new();
+
+        if self.rules.emails {
+            let mut n = 0;
+            out = self.email.replace_all(&out, |_: &regex::Captures| { n += 1; "[EMAIL]" }).into_owned();
+            masked.push((n, "email(s)"));
+        }
+        // Cards before phones: a card number would otherwise match as a phone
+        if self.rules.credit_cards {
+            let mut n = 0;
+            out = self.card.replace_all(&out, |c: &regex::Captures| {
+                let digits: Vec<u32> = c[0].chars().filter_map(|ch| ch.to_digit(10)).collect();
+                if luhn_valid(&digits) { n += 1; "[CARD]".to_string() } else { c[0].to_string() }
+            }).into_owned();
+            masked.push((n, "card number(s)"));
+        }
+        if self.rules.phone_numbers {
+            let mut n = 0;
+            out = self.phone.replace_all(&out, |c: &regex::Captures| {
+                let digits = c[0].chars().filter(|ch| ch.is_ascii_digit()).count();
+                if (MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits) && !self.date.is_match(&c[0]) { n += 1; "[PHONE]".to_string() } else { c[0].to_string() }
+            }).into_owned();
+            masked.push((n, "phone number(s)"));
+        }
+        if self.rules.names {
+            if let Ok(Some(re)) = terms_regex(names) {
+                let mut n = 0;
+                out = re.replace_all(&out, |_: &regex::Captures| { n += 1; "[NAME]" }).into_owned();
+                masked.push((n, "name(s)"));
+            }
+        }
+        if let Some(re) = &self.custom {
+            let mut n = 0;
+            out = re.replace_all(&out, |_: &regex::Captures| { n += 1; "[REDACTED]" }).into_owned();
+            masked.push((n, "custom term(s)"));
+        }
+
+        let summary: Vec<String> = masked.iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, what)| format!("{} {}", n, what))
+            .collect();
+        if !summary.is_empty() {
+            info!("[REDACT] Masked before upload: {}", summary.join(", "));
+        }
+        out
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_redaction_rules(settings: tauri::State<'_, SettingsState>) -> RedactionRules {
+    settings.get().redaction
+}
+
+#[tauri::command]
+pub fn set_redaction_rules(app: tauri::AppHandle, rules: RedactionRules) -> Result<RedactionRules, String> {
+    let rules = RedactionRules {
+        custom_terms: rules.custom_terms.into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        ..rules
+    };
+    let redactor = Redactor::new(rules.clone())?;
+
+    app.state::<SettingsState>().update(|s| s.redaction = rules.clone())?;
+    app.state::<GeminiState>().set_redactor(redactor);
+    info!("[REDACT] Rules updated (enabled: {}, {} custom term(s))", rules.enabled, rules.custom_terms.len());
+    Ok(rules)
+}
+
+/// What a piece of text would look like after redaction, for checking rules
+#[tauri::command]
+pub fn preview_redaction(state: tauri::State<'_, GeminiState>, text: String) -> String {
+    state.redact(&text)
+}
@@ -0,0 +1,548 @@
+use std::collections::BTreeSet;
+use std::io::{Cursor, Write};
+use chrono::DateTime;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point};
+use tracing::info;
+use zip::write::SimpleFileOptions;
+use crate::action_items::items_from_session;
+use crate::analytics::{self, SPEAKER_COLUMNS};
+use crate::bookmarks::format_offset;
+use crate::session_manager::{ActionItem, SessionData, SessionManager};
+
+// ============================================================================
+// MEETING REPORT - PDF / DOCX Export for Stakeholders
+// ============================================================================
+//
+// Both formats render the same ReportData: header, attendees, summary,
+// decisions, action items with owners, and the full transcript as an
+// appendix. DOCX is written directly as WordprocessingML inside a zip.
+//
+// PDFs embed the first Unicode TrueType font found on the system (DejaVu,
+// Noto, Arial). Without one they fall back to the builtin Helvetica, which
+// only covers WinAnsi, and characters outside it print as '?'.
+
+// A4 in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const PT_TO_MM: f32 = 0.3528;
+const AVG_CHAR_WIDTH_EM: f32 = 0.5;            // Helvetica, close enough for wrapping
+const LINE_SPACING: f32 = 1.35;
+
+// (regular, bold) TrueType pairs, tried in order
+const UNICODE_FONTS: &[(&str, &str)] = &[
+    ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
+    ("/usr/share/fonts/TTF/DejaVuSans.ttf", "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
+    ("/usr/share/fonts/dejavu-sans-fonts/DejaVuSans.ttf", "/usr/share/fonts/dejavu-sans-fonts/DejaVuSans-Bold.ttf"),
+    ("/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf", "/usr/share/fonts/truetype/noto/NotoSans-Bold.ttf"),
+    ("C:\\Windows\\Fonts\\arial.ttf", "C:\\Windows\\Fonts\\arialbd.ttf"),
+    ("/System/Library/Fonts/Supplemental/Arial.ttf", "/System/Library/Fonts/Supplemental/Arial Bold.ttf"),
+    ("/Library/Fonts/Arial.ttf", "/Library/Fonts/Arial Bold.ttf"),
+];
+
+// Windows-1252 characters at 0x80-0x9F, where Latin-1 has control codes
+const WIN_ANSI_EXTRAS: &str = "€‚ƒ„…†‡ˆ‰Š‹ŒŽ‘’“”•–—˜™š›œžŸ";
+
+struct ReportData {
+    title: String,
+    date: String,
+    duration: String,
+    attendees: Vec<String>,
+    summary: Option<String>,
+    decisions: Vec<String>,
+    action_items: Vec<ActionItem>,
+    risks: Vec<String>,
+    next_steps: Vec<String>,
+    bookmarks: Vec<String>,
+    // Talk time etc.: overview lines, then one row per speaker
+    analytics: Vec<String>,
+    speaker_stats: Vec<Vec<String>>,
+    // (time, speaker, text)
+    transcript: Vec<(String, String, String)>,
+}
+
+impl ReportData {
+    fn from_session(session: &SessionData) -> Self {
+        let attendees = match &session.calendar_event {
+            Some(event) if !event.attendees.is_empty() => event.attendees.clone(),
+            _ => session.transcripts.iter()
+                .map(|t| t.speaker_id.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        };
+
+        // Without a generated summary, fall back to what the tracker picked up live
+        let action_items = match &session.summary {
+            Some(s) if !s.action_items.is_empty() => s.action_items.clone(),
+            _ => items_from_session(session).into_iter()
+                .map(|item| ActionItem {
+                    description: item.description,
+                    assignee: item.assignee,
+                    deadline: item.due_date,
+                    priority: "MEDIUM".to_string(),
+                })
+                .collect(),
+        };
+
+        let date = DateTime::parse_from_rfc3339(&session.created_at)
+            .map(|d| d.format("%A, %d %B %Y %H:%M").to_string())
+            .unwrap_or_else(|_| session.created_at.clone());
+        let secs = session.metadata.duration_seconds;
+
+        let mut bookmarks: Vec<_> = session.bookmarks.iter().collect();
+        bookmarks.sort_by_key(|b| b.at_ms);
+        let bookmarks = bookmarks.into_iter()
+            .map(|b| {
+                let mut line = format_offset(b.at_ms);
+                if let Some(note) = &b.note {
+                    line.push_str(&format!("  {}", note));
+                }
+                if let (Some(speaker), Some(text)) = (&b.speaker, &b.text) {
+                    line.push_str(&format!("  ({}: \"{}\")", speaker, text.trim()));
+                }
+                line
+            })
+            .collect();
+
+        let stats = analytics::compute(session);
+
+        Self {
+            title: session.metadata.title.clone(),
+            date,
+            duration: format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60),
+            attendees,
+            summary: session.summary.as_ref().map(|s| s.executive_summary.clone()),
+            decisions: session.summary.as_ref().map(|s| s.key_decisions.clone()).unwrap_or_default(),
+            action_items,
+            risks: session.summary.as_ref().map(|s| s.risks_identified.clone()).unwrap_or_default(),
+            next_steps: session.summary.as_ref().map(|s| s.next_steps.clone()).unwrap_or_default(),
+            bookmarks,
+            analytics: analytics::overview_lines(&stats),
+            speaker_stats: analytics::speaker_rows(&stats),
+            transcript: session.transcripts.iter()
+                .map(|t| (transcript_time(t.start_ms, &t.timestamp), t.speaker_id.clone(), t.text.trim().to_string()))
+                .collect(),
+        }
+    }
+}
+
+fn transcript_time(start_ms: Option<u64>, timestamp: &str) -> String {
+    match start_ms {
+        Some(ms) => format!("{:02}:{:02}:{:02}", ms / 3_600_000, (ms / 60_000) % 60, (ms / 1000) % 60),
+        None => DateTime::parse_from_rfc3339(timestamp)
+            .map(|d| d.format("%H:%M:%S").to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn owner(item: &ActionItem) -> &str {
+    item.assignee.as_deref().unwrap_or("Unassigned")
+}
+
+// ============================================================================
+// PDF
+// ============================================================================
+
+/// Greedy word wrap to at most `max_chars` per line
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    // Embedded TrueType rather than builtin Helvetica
+    unicode: bool,
+    // Baseline of the next line, in mm from the bottom of the page
+    y: f32,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+        let (regular, bold, unicode) = match unicode_fonts(&doc) {
+            Some((regular, bold)) => (regular, bold, true),
+            None => {
+                info!("[REPORT] No Unicode font found, using Helvetica (WinAnsi only)");
+                let regular = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| e.to_string())?;
+                let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| e.to_string())?;
+                (regular, bold, false)
+            }
+        };
+        let layer = doc.get_page(page).get_layer(layer);
+        Ok(Self { doc, layer, regular, bold, unicode, y: PAGE_HEIGHT - MARGIN })
+    }
+
+    /// Text the current font can show
+    fn printable(&self, text: &str) -> String {
+        if self.unicode {
+            return text.to_string();
+        }
+        text.chars()
+            .map(|c| if is_win_ansi(c) { c } else { '?' })
+            .collect()
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn line_height(size: f32) -> f32 {
+        size * PT_TO_MM * LINE_SPACING
+    }
+
+    fn max_chars(width: f32, size: f32) -> usize {
+        ((width / (size * PT_TO_MM * AVG_CHAR_WIDTH_EM)) as usize).max(1)
+    }
+
+    fn text(&mut self, text: &str, size: f32, bold: bool, indent: f32) {
+        let font = if bold { self.bold.clone() } else { self.regular.clone() };
+        let width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+        for line in wrap(text, Self::max_chars(width, size)) {
+            self.ensure_space(Self::line_height(size));
+            self.y -= Self::line_height(size);
+            self.layer.use_text(self.printable(&line), size, Mm(MARGIN + indent), Mm(self.y), &font);
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn rule(&mut self) {
+        self.ensure_space(3.0);
+        self.y -= 1.5;
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(self.y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+        self.y -= 1.5;
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.gap(4.0);
+        // Keep a heading with at least a couple of lines of its section
+        self.ensure_space(Self::line_height(14.0) + 12.0);
+        self.text(text, 14.0, true, 0.0);
+        self.rule();
+    }
+
+    fn bullets(&mut self, items: &[String]) {
+        for item in items {
+            self.text(&format!("- {}", item), 10.0, false, 2.0);
+        }
+    }
+
+    /// One table row; `columns` are (text, width in mm). Cells wrap independently.
+    fn row(&mut self, columns: &[(&str, f32)], bold: bool) {
+        let size = 10.0;
+        let font = if bold { self.bold.clone() } else { self.regular.clone() };
+        let cells: Vec<Vec<String>> = columns.iter()
+            .map(|(text, width)| wrap(text, Self::max_chars(width - 2.0, size)))
+            .collect();
+        let lines = cells.iter().map(|c| c.len()).max().unwrap_or(1);
+        self.ensure_space(lines as f32 * Self::line_height(size) + 2.0);
+
+        let top = self.y;
+        let mut x = MARGIN;
+        for (cell, (_, width)) in cells.iter().zip(columns) {
+            let mut y = top;
+            for line in cell {
+                y -= Self::line_height(size);
+                self.layer.use_text(self.printable(line), size, Mm(x), Mm(y), &font);
+            }
+            x += width;
+        }
+        self.y = top - lines as f32 * Self::line_height(size);
+        self.rule();
+    }
+
+    fn finish(self) -> Result<Vec<u8>, String> {
+        self.doc.save_to_bytes().map_err(|e| format!("Failed to write PDF: {}", e))
+    }
+}
+
+fn unicode_fonts(doc: &PdfDocumentReference) -> Option<(IndirectFontRef, IndirectFontRef)> {
+    UNICODE_FONTS.iter().find_map(|(regular, bold)| {
+        let (regular, bold) = (std::fs::File::open(regular).ok()?, std::fs::File::open(bold).ok()?);
+        Some((doc.add_external_font(regular).ok()?, doc.add_external_font(bold).ok()?))
+    })
+}
+
+fn is_win_ansi(c: char) -> bool {
+    matches!(c as u32, 0x20..=0x7E | 0xA0..=0xFF) || WIN_ANSI_EXTRAS.contains(c)
+}
+
+fn render_pdf(report: &ReportData) -> Result<Vec<u8>, String> {
+    let mut pdf = PdfWriter::new(&report.title)?;
+    let content_width = PAGE_WIDTH - 2.0 * MARGIN;
+
+    pdf.text(&report.title, 20.0, true, 0.0);
+    pdf.gap(2.0);
+    pdf.text(&format!("{}  |  Duration {}", report.date, report.duration), 10.0, false, 0.0);
+    if !report.attendees.is_empty() {
+        pdf.text(&format!("Attendees: {}", report.attendees.join(", ")), 10.0, false, 0.0);
+    }
+
+    pdf.heading("Summary");
+    pdf.text(report.summary.as_deref().unwrap_or("No summary has been generated for this meeting."), 10.0, false, 0.0);
+
+    if !report.decisions.is_empty() {
+        pdf.heading("Decisions");
+        let widths = [10.0, content_width - 10.0];
+        pdf.row(&[("#", widths[0]), ("Decision", widths[1])], true);
+        for (i, decision) in report.decisions.iter().enumerate() {
+            pdf.row(&[(&(i + 1).to_string(), widths[0]), (decision, widths[1])], false);
+        }
+    }
+
+    if !report.action_items.is_empty() {
+        pdf.heading("Action Items");
+        let widths = [content_width - 95.0, 40.0, 35.0, 20.0];
+        pdf.row(&[("Action", widths[0]), ("Owner", widths[1]), ("Due", widths[2]), ("Priority", widths[3])], true);
+        for item in &report.action_items {
+            pdf.row(&[
+                (&item.description, widths[0]),
+                (owner(item), widths[1]),
+                (item.deadline.as_deref().unwrap_or("-"), widths[2]),
+                (&item.priority, widths[3]),
+            ], false);
+        }
+    }
+
+    if !report.risks.is_empty() {
+        pdf.heading("Risks");
+        pdf.bullets(&report.risks);
+    }
+    if !report.next_steps.is_empty() {
+        pdf.heading("Next Steps");
+        pdf.bullets(&report.next_steps);
+    }
+    if !report.bookmarks.is_empty() {
+        pdf.heading("Bookmarks");
+        pdf.bullets(&report.bookmarks);
+    }
+    if !report.speaker_stats.is_empty() {
+        pdf.heading("Meeting Analytics");
+        pdf.bullets(&report.analytics);
+        pdf.gap(2.0);
+        let widths = [content_width - 120.0, 25.0, 17.0, 23.0, 25.0, 30.0];
+        let header: Vec<(&str, f32)> = SPEAKER_COLUMNS.iter().copied().zip(widths).collect();
+        pdf.row(&header, true);
+        for row in &report.speaker_stats {
+            let cells: Vec<(&str, f32)> = row.iter().map(String::as_str).zip(widths).collect();
+            pdf.row(&cells, false);
+        }
+    }
+
+    pdf.heading("Appendix: Full Transcript");
+    for (time, speaker, text) in &report.transcript {
+        pdf.gap(1.0);
+        pdf.text(&format!("{}  {}", time, speaker), 9.0, true, 0.0);
+        pdf.text(text, 9.0, false, 4.0);
+    }
+
+    pdf.finish()
+}
+
+// ============================================================================
+// DOCX
+// ============================================================================
+
+const CONTENT_TYPES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#;
+
+const RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// WordprocessingML body with direct formatting (no styles part needed)
+#[derive(Default)]
+struct DocxBody {
+    xml: String,
+}
+
+impl DocxBody {
+    fn run(text: &str, half_points: u32, bold: bool) -> String {
+        format!(
+            r#"<w:r><w:rPr>{}<w:sz w:val="{}"/></w:rPr><w:t xml:space="preserve">{}</w:t></w:r>"#,
+            if bold { "<w:b/>" } else { "" }, half_points, xml_escape(text),
+        )
+    }
+
+    fn paragraph(&mut self, text: &str, half_points: u32, bold: bool) {
+        self.xml.push_str(&format!("<w:p>{}</w:p>", Self::run(text, half_points, bold)));
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.xml.push_str(&format!(
+            r#"<w:p><w:pPr><w:spacing w:before="240" w:after="120"/><w:keepNext/></w:pPr>{}</w:p>"#,
+            Self::run(text, 28, true),
+        ));
+    }
+
+    fn bullets(&mut self, items: &[String]) {
+        for item in items {
+            self.paragraph(&format!("• {}", item), 20, false);
+        }
+    }
+
+    fn table(&mut self, header: &[&str], rows: &[Vec<String>]) {
+        let cell = |text: &str, bold: bool| format!("<w:tc><w:p>{}</w:p></w:tc>", Self::run(text, 20, bold));
+        self.xml.push_str(concat!(
+            r#"<w:tbl><w:tblPr><w:tblW w:w="5000" w:type="pct"/><w:tblBorders>"#,
+            r#"<w:top w:val="single" w:sz="4"/><w:bottom w:val="single" w:sz="4"/>"#,
+            r#"<w:insideH w:val="single" w:sz="4"/><w:insideV w:val="single" w:sz="4"/>"#,
+            r#"<w:left w:val="single" w:sz="4"/><w:right w:val="single" w:sz="4"/>"#,
+            r#"</w:tblBorders></w:tblPr>"#,
+        ));
+        self.xml.push_str(r#"<w:tr><w:trPr><w:tblHeader/></w:trPr>"#);
+        for h in header {
+            self.xml.push_str(&cell(h, true));
+        }
+        self.xml.push_str("</w:tr>");
+        for row in rows {
+            self.xml.push_str("<w:tr>");
+            for c in row {
+                self.xml.push_str(&cell(c, false));
+            }
+            self.xml.push_str("</w:tr>");
+        }
+        // Word requires a paragraph between a table and what follows
+        self.xml.push_str("</w:tbl><w:p/>");
+    }
+
+    fn into_document(self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1134" w:right="1134" w:bottom="1134" w:left="1134" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+            self.xml,
+        )
+    }
+}
+
+fn render_docx(report: &ReportData) -> Result<Vec<u8>, String> {
+    let mut body = DocxBody::default();
+
+    body.paragraph(&report.title, 40, true);
+    body.paragraph(&format!("{}  |  Duration {}", report.date, report.duration), 20, false);
+    if !report.attendees.is_empty() {
+        body.paragraph(&format!("Attendees: {}", report.attendees.join(", ")), 20, false);
+    }
+
+    body.heading("Summary");
+    body.paragraph(report.summary.as_deref().unwrap_or("No summary has been generated for this meeting."), 22, false);
+
+    if !report.decisions.is_empty() {
+        body.heading("Decisions");
+        let rows: Vec<Vec<String>> = report.decisions.iter().enumerate()
+            .map(|(i, d)| vec![(i + 1).to_string(), d.clone()])
+            .collect();
+        body.table(&["#", "Decision"], &rows);
+    }
+
+    if !report.action_items.is_empty() {
+        body.heading("Action Items");
+        let rows: Vec<Vec<String>> = report.action_items.iter()
+            .map(|item| vec![
+                item.description.clone(),
+                owner(item).to_string(),
+                item.deadline.clone().unwrap_or_else(|| "-".to_string()),
+                item.priority.clone(),
+            ])
+            .collect();
+        body.table(&["Action", "Owner", "Due", "Priority"], &rows);
+    }
+
+    if !report.risks.is_empty() {
+        body.heading("Risks");
+        body.bullets(&report.risks);
+    }
+    if !report.next_steps.is_empty() {
+        body.heading("Next Steps");
+        body.bullets(&report.next_steps);
+    }
+    if !report.bookmarks.is_empty() {
+        body.heading("Bookmarks");
+        body.bullets(&report.bookmarks);
+    }
+    if !report.speaker_stats.is_empty() {
+        body.heading("Meeting Analytics");
+        body.bullets(&report.analytics);
+        body.table(&SPEAKER_COLUMNS, &report.speaker_stats);
+    }
+
+    body.heading("Appendix: Full Transcript");
+    for (time, speaker, text) in &report.transcript {
+        body.xml.push_str(&format!(
+            "<w:p>{}{}</w:p>",
+            DocxBody::run(&format!("{}  {}: ", time, speaker), 18, true),
+            DocxBody::run(text, 18, false),
+        ));
+    }
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, content) in [
+        ("[Content_Types].xml", CONTENT_TYPES_XML.to_string()),
+        ("_rels/.rels", RELS_XML.to_string()),
+        ("word/document.xml", body.into_document()),
+    ] {
+        zip.start_file(name, options).map_err(|e| format!("Failed to write DOCX: {}", e))?;
+        zip.write_all(content.as_bytes()).map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    }
+    let cursor = zip.finish().map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Render a stored session as a PDF or DOCX report; returns the file path
+#[tauri::command]
+pub fn export_report(session_id: String, format: String) -> Result<String, String> {
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(&session_id)?;
+    let report = ReportData::from_session(&session);
+
+    let bytes = match format.as_str() {
+        "pdf" => render_pdf(&report)?,
+        "docx" => render_docx(&report)?,
+        _ => return Err(format!("Unsupported report format: {}", format)),
+    };
+
+    let path = manager.write_export(&session.id, &format, &bytes)?;
+    info!("[EXPORT] {} report written to {}", format.to_uppercase(), path);
+    Ok(path)
+}
@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+use tokio::time::{interval, Duration};
+use tracing::{info, warn};
+use crate::events::{self, ResourceAction, ResourceKind, ResourceWarningEvent};
+use crate::live_session::{self, LiveSessionState};
+use crate::recorder::{self, RecorderState};
+use crate::settings::{app_data_dir, SettingsState};
+
+// ============================================================================
+// RESOURCES - Disk, Memory and Length Guardrails for Long Sessions
+// ============================================================================
+//
+// A background check every CHECK_INTERVAL_SECS while a session runs:
+// - Session length / WAV size at the limit: roll over into a new session
+//   ("Title (part 2)") so capture never stops; a warning goes out first at
+//   WARN_FRACTION of the limit. A recording without a session has nothing
+//   to roll over, so it is finished instead
+// - Free disk space under the minimum: stop the WAV recording (transcripts
+//   are small and keep being stored)
+// - Process memory over the limit: warning only
+// - A recording that stopped on a write error is reported instead of
+//   disappearing silently
+// Each warning is sent once per session and limit.
+
+const CHECK_INTERVAL_SECS: u64 = 15;
+const WARN_FRACTION: f64 = 0.9;
+const MB: u64 = 1024 * 1024;
+
+/// 0 turns a limit off
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ResourceLimits {
+    pub max_session_minutes: u64,
+    // A 16 kHz WAV grows ~110 MB an hour; the format itself stops at 4 GB
+    pub max_recording_mb: u64,
+    pub min_free_disk_mb: u64,
+    pub max_memory_mb: u64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self { max_session_minutes: 480, max_recording_mb: 2048, min_free_disk_mb: 1024, max_memory_mb: 4096 }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ResourceUsage {
+    pub session_minutes: Option<u64>,
+    pub recording_mb: Option<u64>,
+    pub free_disk_mb: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub limits: ResourceLimits,
+}
+
+#[derive(Default)]
+pub struct ResourceState {
+    // Session the warnings below were sent for
+    warned: StdMutex<(Option<String>, Vec<ResourceKind>)>,
+}
+
+impl ResourceState {
+    /// True the first time `kind` comes up for this session
+    fn first_warning(&self, session_id: Option<&str>, kind: ResourceKind) -> bool {
+        let mut warned = self.warned.lock().unwrap();
+        if warned.0.as_deref() != session_id {
+            *warned = (session_id.map(str::to_string), Vec::new());
+        }
+        if warned.1.contains(&kind) {
+            return false;
+        }
+        warned.1.push(kind);
+        true
+    }
+}
+
+#[cfg(unix)]
+// statvfs field types differ between platforms (u32 on macOS)
+#[allow(clippy::useless_conversion)]
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statvfs only writes into the zeroed struct we own
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(u64::from(stat.f_bavail) * u64::from(stat.f_frsize))
+}
+
+#[cfg(windows)]
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(directory: *const u16, free_to_caller: *mut u64, total: *mut u64, total_free: *mut u64) -> i32;
+    }
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut free = 0u64;
+    // SAFETY: a NUL-terminated path and a valid out pointer; the others may be null
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut free, std::ptr::null_mut(), std::ptr::null_mut()) };
+    (ok != 0).then_some(free)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+pub fn usage(app: &AppHandle) -> ResourceUsage {
+    ResourceUsage {
+        session_minutes: app.state::<LiveSessionState>().elapsed_ms().map(|ms| ms / 60_000),
+        recording_mb: app.state::<RecorderState>().bytes_written().map(|b| b / MB),
+        free_disk_mb: app_data_dir().ok().and_then(|dir| free_bytes(&dir)).map(|b| b / MB),
+        memory_mb: memory_stats::memory_stats().map(|m| m.physical_mem as u64 / MB),
+        limits: app.state::<SettingsState>().get().resources,
+    }
+}
+
+fn warn_once(app: &AppHandle, session_id: Option<&str>, event: ResourceWarningEvent) {
+    if app.state::<ResourceState>().first_warning(session_id, event.kind) {
+        warn!("[RESOURCES] {}", event.message);
+        events::emit(app, &event);
+    }
+}
+
+/// Roll the live session over; the warning names the session that continues it
+fn roll_over(app: &AppHandle, session_id: &str, kind: ResourceKind, value: u64, limit: u64, what: &str) {
+    let (message, new_session_id) = match live_session::rollover(app) {
+        Ok(next) => (format!("{} reached, continuing in '{}'", what, next.title), Some(next.id)),
+        Err(e) => (format!("{} reached, rollover failed: {}", what, e), None),
+    };
+    warn!("[RESOURCES] {}", message);
+    events::emit(app, &ResourceWarningEvent {
+        kind,
+        action: ResourceAction::RolledOver,
+        session_id: Some(session_id.to_string()),
+        new_session_id,
+        value,
+        limit,
+        message,
+    });
+}
+
+/// Finish a recording that has no session to roll over into
+fn stop_recording(app: &AppHandle, recorder: &RecorderState, value: u64, limit: u64, what: &str) {
+    let (message, action) = match recorder::finish_recording(recorder) {
+        Ok(info) => (format!("{} reached - recording stopped, saved to {}", what, info.path), ResourceAction::RecordingStopped),
+        Err(e) => (format!("{} reached, stopping the recording failed: {}", what, e), ResourceAction::Warned),
+    };
+    warn!("[RESOURCES] {}", message);
+    events::emit(app, &ResourceWarningEvent {
+        kind: ResourceKind::RecordingSize,
+        action,
+        session_id: None,
+        new_session_id: None,
+        value,
+        limit,
+        message,
+    });
+}
+
+fn check(app: &AppHandle) {
+    let usage = usage(app);
+    let limits = &usage.limits;
+    let session_id = app.state::<LiveSessionState>().active_id();
+    let warning = |kind, action, value, limit, message: String| ResourceWarningEvent {
+        kind,
+        action,
+        session_id: session_id.clone(),
+        new_session_id: None,
+        value,
+        limit,
+        message,
+    };
+
+    let recorder = app.state::<RecorderState>();
+    if let Some(failure) = recorder.take_failure() {
+        events::emit(app, &warning(ResourceKind::RecordingSize, ResourceAction::RecordingStopped, 0, 0, failure));
+    }
+
+    if let (Some(free), true) = (usage.free_disk_mb, limits.min_free_disk_mb > 0) {
+        if free < limits.min_free_disk_mb && recorder.is_active() {
+            let stopped = recorder::finish_recording(&recorder).is_ok();
+            let message = format!("Only {} MB of disk left - recording stopped, transcripts are still saved", free);
+            let action = if stopped { ResourceAction::RecordingStopped } else { ResourceAction::Warned };
+            warn!("[RESOURCES] {}", message);
+            events::emit(app, &warning(ResourceKind::DiskSpace, action, free, limits.min_free_disk_mb, message));
+        } else if (free as f64) < limits.min_free_disk_mb as f64 / WARN_FRACTION {
+            let message = format!("Disk space is running low ({} MB free)", free);
+            warn_once(app, session_id.as_deref(), warning(ResourceKind::DiskSpace, ResourceAction::Warned, free, limits.min_free_disk_mb, message));
+        }
+    }
+
+    if let (Some(memory), true) = (usage.memory_mb, limits.max_memory_mb > 0) {
+        if memory >= limits.max_memory_mb {
+            let message = format!("Cognivox is using {} MB of memory (limit {} MB)", memory, limits.max_memory_mb);
+            warn_once(app, session_id.as_deref(), warning(ResourceKind::Memory, ResourceAction::Warned, memory, limits.max_memory_mb, message));
+        }
+    }
+
+    // Recording size is checked with or without a session; sessionless WAVs
+    // would otherwise grow until the format's 4 GB limit breaks them
+    let measured = [
+        (ResourceKind::SessionDuration, usage.session_minutes, limits.max_session_minutes, "minutes", "Session length limit"),
+        (ResourceKind::RecordingSize, usage.recording_mb, limits.max_recording_mb, "MB", "Recording size limit"),
+    ];
+    for (kind, value, limit, unit, what) in measured {
+        let Some(value) = value.filter(|_| limit > 0) else { continue };
+        if value >= limit {
+            match session_id.as_deref() {
+                Some(session_id) => roll_over(app, session_id, kind, value, limit, what),
+                None => stop_recording(app, &recorder, value, limit, what),
+            }
+            return;
+        }
+        if value as f64 >= limit as f64 * WARN_FRACTION {
+            let message = format!("{}: {} of {} {} used", what, value, limit, unit);
+            warn_once(app, session_id.as_deref(), warning(kind, ResourceAction::Warned, value, limit, message));
+        }
+    }
+}
+
+pub fn spawn_resource_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+        loop {
+            tick.tick().await;
+            if app.state::<LiveSessionState>().active_id().is_some() || app.state::<RecorderState>().is_active() {
+                check(&app);
+            }
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_resource_usage(app: AppHandle) -> ResourceUsage {
+    usage(&app)
+}
+
+#[tauri::command]
+pub fn get_resource_limits(settings: tauri::State<'_, SettingsState>) -> ResourceLimits {
+    settings.get().resources
+}
+
+#[tauri::command]
+pub fn set_resource_limits(
+    settings: tauri::State<'_, SettingsState>,
+    limits: ResourceLimits,
+) -> Result<ResourceLimits, String> {
+    if limits.max_session_minutes > 0 && limits.max_session_minutes < 10 {
+        return Err("max_session_minutes must be at least 10 (or 0 for no limit)".to_string());
+    }
+    if limits.max_recording_mb >= 4096 {
+        return Err("max_recording_mb must stay under 4096, the WAV size limit".to_string());
+    }
+    settings.update(|s| s.resources = limits.clone())?;
+    info!(
+        "[RESOURCES] Limits: {} min, {} MB WAV, {} MB free disk, {} MB memory",
+        limits.max_session_minutes, limits.max_recording_mb, limits.min_free_disk_mb, limits.max_memory_mb
+    );
+    Ok(limits)
+}
@@ -0,0 +1,112 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::{debug, info};
+use crate::encryption;
+use crate::gemini_client::GenerationSettings;
+use crate::settings::app_data_dir;
+
+// ============================================================================
+// RESPONSE CACHE - Skip Re-billing Identical Intelligence Requests
+// ============================================================================
+//
+// One file per request, keyed by a hash of model + generation config +
+// output schema + system prompt + user text, so changing any of them (e.g.
+// a higher token cap) gets fresh responses instead of stale, truncated ones.
+// Imported files re-analyzed and retried segments hit the cache instead of
+// the API. Entries are sealed like sessions when encryption is on.
+
+// Oldest entries (by write time) are dropped past this
+const MAX_ENTRIES: usize = 2000;
+
+fn cache_dir() -> Result<PathBuf, String> {
+    let dir = app_data_dir()?.join("cache").join("intelligence");
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    Ok(dir)
+}
+
+pub fn key(
+    model: &str,
+    generation: &GenerationSettings,
+    output_schema: Option<&serde_json::Value>,
+    system_prompt: &str,
+    user_text: &str,
+) -> String {
+    let generation = serde_json::to_string(generation).unwrap_or_default();
+    let output_schema = output_schema.map(|s| s.to_string()).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    for part in [model, &generation, &output_schema, system_prompt, user_text] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Cached response text; any read or decrypt failure counts as a miss
+pub fn get(key: &str) -> Option<String> {
+    let path = cache_dir().ok()?.join(format!("{}.txt", key));
+    let bytes = encryption::open(fs::read(path).ok()?).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+pub fn put(key: &str, response: &str) {
+    let result = cache_dir().and_then(|dir| {
+        let sealed = encryption::seal(response.as_bytes().to_vec())?;
+        let tmp_path = dir.join(format!("{}.tmp", key));
+        fs::write(&tmp_path, sealed).map_err(|e| e.to_string())?;
+        fs::rename(&tmp_path, dir.join(format!("{}.txt", key))).map_err(|e| e.to_string())?;
+        prune(&dir);
+        Ok(())
+    });
+    if let Err(e) = result {
+        debug!("[CACHE] Response not cached: {}", e);
+    }
+}
+
+fn entries(dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+    let Ok(read_dir) = fs::read_dir(dir) else { return Vec::new(); };
+    read_dir
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("txt"))
+        .map(|e| {
+            let modified = e.metadata().and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+            (e.path(), modified)
+        })
+        .collect()
+}
+
+fn prune(dir: &Path) {
+    let mut entries = entries(dir);
+    if entries.len() <= MAX_ENTRIES {
+        return;
+    }
+    entries.sort_by_key(|(_, modified)| *modified);
+    let excess = entries.len() - MAX_ENTRIES;
+    for (path, _) in entries.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Remove every cached response; returns how many were removed
+pub fn clear() -> Result<usize, String> {
+    let dir = cache_dir()?;
+    let mut removed = 0;
+    for (path, _) in entries(&dir) {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn clear_intelligence_cache() -> Result<usize, String> {
+    let removed = clear()?;
+    info!("[CACHE] Cleared {} cached response(s)", removed);
+    Ok(removed)
+}
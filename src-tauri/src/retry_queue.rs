@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tokio::time::{Duration, interval};
+use tracing::{error, info, warn};
+use crate::action_items;
+use crate::alerts;
+use crate::encryption;
+use crate::events::{self, IntelligenceEvent};
+use crate::gemini_client::{GeminiState, build_intelligence_prompt, call_gemini_with_text};
+use crate::live_session::record_segment;
+use crate::metrics::MetricsState;
+use crate::plugins::{self, PluginInput};
+use crate::session_manager::dispatch_webhook;
+use crate::settings::{SettingsState, app_data_dir};
+
+// ============================================================================
+// RETRY QUEUE - Disk-Backed Queue for Failed Intelligence Requests
+// ============================================================================
+//
+// retry_queue.json holds one segment per line, sealed like the crash journal
+// when encryption is on. Lines that can't be opened while the store is locked
+// are kept as they are and read again on unlock.
+
+const RETRY_POLL_SECS: u64 = 10;
+const RETRY_BASE_DELAY_SECS: i64 = 15;
+const RETRY_MAX_DELAY_SECS: i64 = 30 * 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingSegment {
+    pub segment_id: String,
+    // Live session the segment belongs to; its stored entry is updated on success
+    #[serde(default)]
+    pub session_id: Option<String>,
+    pub transcript: String,
+    pub speaker: String,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    pub attempts: u32,
+    pub last_error: String,
+    pub queued_at: String,
+    pub next_attempt_ms: i64,
+}
+
+impl PendingSegment {
+    pub fn new(
+        segment_id: String,
+        session_id: Option<String>,
+        transcript: String,
+        speaker: String,
+        start_ms: Option<u64>,
+        end_ms: Option<u64>,
+        error: String,
+    ) -> Self {
+        Self {
+            segment_id,
+            session_id,
+            transcript,
+            speaker,
+            start_ms,
+            end_ms,
+            attempts: 0,
+            last_error: error,
+            queued_at: Utc::now().to_rfc3339(),
+            next_attempt_ms: Utc::now().timestamp_millis() + RETRY_BASE_DELAY_SECS * 1000,
+        }
+    }
+}
+
+pub struct RetryQueueState {
+    queue: StdMutex<Vec<PendingSegment>>,
+    // Sealed lines waiting for the passphrase
+    locked: StdMutex<Vec<String>>,
+}
+
+/// Segments that could be read, and the sealed lines that couldn't (yet)
+fn parse_lines(lines: Vec<String>) -> (Vec<PendingSegment>, Vec<String>) {
+    let mut queue = Vec::new();
+    let mut locked = Vec::new();
+    for line in lines {
+        match encryption::open_line(&line).map(|l| serde_json::from_str::<PendingSegment>(&l)) {
+            Ok(Ok(segment)) => queue.push(segment),
+            Ok(Err(e)) => warn!("[RETRY] Dropped unreadable queue line: {}", e),
+            Err(_) => locked.push(line),
+        }
+    }
+    (queue, locked)
+}
+
+impl RetryQueueState {
+    pub fn load() -> Self {
+        let contents = queue_path()
+            .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+            .unwrap_or_default();
+        // Older versions wrote one plaintext JSON array
+        let (queue, locked) = match serde_json::from_str::<Vec<PendingSegment>>(&contents) {
+            Ok(queue) => (queue, Vec::new()),
+            Err(_) => parse_lines(contents.lines().filter(|l| !l.trim().is_empty()).map(str::to_string).collect()),
+        };
+
+        if !queue.is_empty() {
+            info!("[RETRY] Restored {} pending segment(s) from disk", queue.len());
+        }
+        if !locked.is_empty() {
+            info!("[RETRY] {} pending segment(s) are encrypted until unlock", locked.len());
+        }
+        Self { queue: StdMutex::new(queue), locked: StdMutex::new(locked) }
+    }
+
+    /// The passphrase was entered: read the segments that were sealed
+    pub fn unlock(&self) {
+        let lines = std::mem::take(&mut *self.locked.lock().unwrap());
+        if lines.is_empty() {
+            return;
+        }
+        let (restored, still_locked) = parse_lines(lines);
+        info!("[RETRY] Restored {} encrypted pending segment(s)", restored.len());
+        *self.locked.lock().unwrap() = still_locked;
+        let mut queue = self.queue.lock().unwrap();
+        queue.extend(restored);
+        self.persist(&queue);
+    }
+
+    /// Rewrite the file, e.g. after the key changed
+    pub fn reseal(&self) {
+        self.persist(&self.queue.lock().unwrap());
+    }
+
+    pub fn enqueue(&self, segment: PendingSegment) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.push(segment);
+        self.persist(&queue);
+    }
+
+    pub fn pending(&self) -> Vec<PendingSegment> {
+        self.queue.lock().unwrap().clone()
+    }
+
+    /// Connectivity is back: make every pending segment due now
+    pub fn mark_online(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.is_empty() { return; }
+        let now = Utc::now().timestamp_millis();
+        for s in queue.iter_mut() {
+            s.next_attempt_ms = s.next_attempt_ms.min(now);
+        }
+        self.persist(&queue);
+    }
+
+    fn next_due(&self) -> Option<PendingSegment> {
+        let now = Utc::now().timestamp_millis();
+        self.queue.lock().unwrap().iter()
+            .filter(|s| s.next_attempt_ms <= now)
+            .min_by_key(|s| s.next_attempt_ms)
+            .cloned()
+    }
+
+    fn complete(&self, segment_id: &str) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.retain(|s| s.segment_id != segment_id);
+        self.persist(&queue);
+    }
+
+    fn reschedule(&self, segment_id: &str, error: String) {
+        let mut queue = self.queue.lock().unwrap();
+        if let Some(s) = queue.iter_mut().find(|s| s.segment_id == segment_id) {
+            s.attempts += 1;
+            s.last_error = error;
+            let delay = (RETRY_BASE_DELAY_SECS << s.attempts.min(10)).min(RETRY_MAX_DELAY_SECS);
+            s.next_attempt_ms = Utc::now().timestamp_millis() + delay * 1000;
+        }
+        self.persist(&queue);
+    }
+
+    fn clear(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.clear();
+        self.locked.lock().unwrap().clear();
+        self.persist(&queue);
+    }
+
+    fn persist(&self, queue: &[PendingSegment]) {
+        let result = queue_path().and_then(|path| {
+            let mut lines = self.locked.lock().unwrap().clone();
+            for segment in queue {
+                let json = serde_json::to_string(segment).map_err(|e| e.to_string())?;
+                lines.push(encryption::seal_line(&json)?);
+            }
+            let tmp_path = path.with_extension("tmp");
+            fs::write(&tmp_path, lines.join("\n")).map_err(|e| e.to_string())?;
+            fs::rename(&tmp_path, &path).map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            error!("[RETRY] ✗ Failed to persist retry queue: {}", e);
+        }
+    }
+}
+
+fn queue_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join("retry_queue.json"))
+}
+
+// ============================================================================
+// Retry Worker
+// ============================================================================
+
+pub fn spawn_retry_worker(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = interval(Duration::from_secs(RETRY_POLL_SECS));
+
+        loop {
+            tick.tick().await;
+
+            let queue = app.state::<RetryQueueState>();
+            let Some(segment) = queue.next_due() else { continue; };
+
+            let config = match app.state::<GeminiState>().request_config(&app) {
+                Ok(c) => c,
+                Err(e) => {
+                    queue.reschedule(&segment.segment_id, e);
+                    continue;
+                }
+            };
+            let system_prompt = build_intelligence_prompt(&app.state::<SettingsState>().get());
+            let annotated = format!("[{}]: {}", segment.speaker, segment.transcript);
+
+            info!("[RETRY] Retrying segment {} (attempt {})", segment.segment_id, segment.attempts + 1);
+            let result = call_gemini_with_text(&config, &system_prompt, &annotated, &[]).await;
+            app.state::<MetricsState>().record_gemini_result(result.is_ok());
+            match result {
+                Ok(response) => {
+                    info!("[RETRY] ✓ Segment {} analyzed", segment.segment_id);
+                    queue.complete(&segment.segment_id);
+
+                    let input = PluginInput {
+                        segment_id: Some(&segment.segment_id),
+                        session_id: segment.session_id.as_deref(),
+                        transcript: &segment.transcript,
+                        speaker: &segment.speaker,
+                        start_ms: segment.start_ms,
+                        end_ms: segment.end_ms,
+                        language: None,
+                        retried: true,
+                    };
+                    let Some(response) = plugins::apply(&app, &input, response) else {
+                        plugins::emit_dropped(&app, &input);
+                        queue.mark_online();
+                        continue;
+                    };
+
+                    if let Some(session_id) = &segment.session_id {
+                        record_segment(session_id, &segment.segment_id, &segment.transcript, &segment.speaker,
+                                       Some(&response), (segment.start_ms, segment.end_ms), None);
+                    }
+                    let event = IntelligenceEvent {
+                        segment_id: Some(segment.segment_id.clone()),
+                        session_id: segment.session_id.clone(),
+                        transcript: segment.transcript.clone(),
+                        speaker: Some(segment.speaker.clone()),
+                        intelligence: Some(response.clone()),
+                        pending: false,
+                        retried: true,
+                        start_ms: segment.start_ms,
+                        end_ms: segment.end_ms,
+                        timestamp: events::now_ms(),
+                    };
+                    events::emit(&app, &event);
+                    dispatch_webhook(&app, "gemini_intelligence", &events::to_payload(&event));
+                    action_items::ingest_intelligence(&app, segment.session_id.as_deref(), &segment.transcript, &segment.speaker, &response, segment.start_ms);
+                    alerts::notify_if_urgent(&app, &segment.transcript, &segment.speaker, &response);
+
+                    // The API is reachable again - don't make the rest wait out their backoff
+                    queue.mark_online();
+                }
+                Err(e) => {
+                    warn!("[RETRY] ✗ Segment {} failed again: {}", segment.segment_id, e);
+                    queue.reschedule(&segment.segment_id, e);
+                }
+            }
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_retry_queue(state: tauri::State<'_, RetryQueueState>) -> Vec<PendingSegment> {
+    state.pending()
+}
+
+#[tauri::command]
+pub fn retry_pending_now(state: tauri::State<'_, RetryQueueState>) -> usize {
+    state.mark_online();
+    state.pending().len()
+}
+
+#[tauri::command]
+pub fn clear_retry_queue(state: tauri::State<'_, RetryQueueState>) -> Result<(), String> {
+    state.clear();
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use serde_json::{Map, Value};
+use tracing::info;
+use crate::settings::SettingsState;
+
+// ============================================================================
+// SCHEMA - User-Defined Output Schema for Intelligence Extraction
+// ============================================================================
+//
+// Written in Gemini's responseSchema dialect (an OpenAPI subset):
+//
+//   {"type":"OBJECT","properties":{"topic":{"type":"STRING"},
+//    "priority":{"type":"STRING","enum":["LOW","HIGH"]}},"required":["topic"]}
+//
+// The schema is appended to the intelligence prompt, sent as responseSchema,
+// and every intelligence response is checked against it before it's used.
+
+const TYPES: &[&str] = &["STRING", "NUMBER", "INTEGER", "BOOLEAN", "ARRAY", "OBJECT"];
+// Anything else makes Gemini reject the whole request
+const KEYS: &[&str] = &["type", "format", "description", "nullable", "enum", "properties", "required", "items", "propertyOrdering"];
+const MAX_DEPTH: usize = 8;
+
+/// Check a user schema and return it with type names uppercased
+pub fn normalize(schema: &Value) -> Result<Value, String> {
+    let schema = normalize_at(schema, "$", 0)?;
+    if schema["type"] != "OBJECT" {
+        return Err("The top-level schema must be an OBJECT".to_string());
+    }
+    Ok(schema)
+}
+
+fn normalize_at(schema: &Value, path: &str, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("{}: nested deeper than {} levels", path, MAX_DEPTH));
+    }
+    let object = schema.as_object().ok_or_else(|| format!("{}: expected a schema object", path))?;
+    if let Some(key) = object.keys().find(|k| !KEYS.contains(&k.as_str())) {
+        return Err(format!("{}: unsupported key '{}'", path, key));
+    }
+
+    let kind = object.get("type")
+        .and_then(Value::as_str)
+        .map(str::to_uppercase)
+        .filter(|t| TYPES.contains(&t.as_str()))
+        .ok_or_else(|| format!("{}: type must be one of {}", path, TYPES.join(", ")))?;
+    let mut normalized = object.clone();
+    normalized.insert("type".to_string(), Value::String(kind.clone()));
+
+    if let Some(values) = object.get("enum") {
+        let values = values.as_array().filter(|v| !v.is_empty() && v.iter().all(Value::is_string));
+        if kind != "STRING" || values.is_none() {
+            return Err(format!("{}: enum must be a non-empty list of strings on a STRING", path));
+        }
+    }
+
+    match kind.as_str() {
+        "OBJECT" => {
+            let properties = object.get("properties")
+                .and_then(Value::as_object)
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| format!("{}: OBJECT needs non-empty properties", path))?;
+            let mut checked = Map::new();
+            for (name, property) in properties {
+                checked.insert(name.clone(), normalize_at(property, &format!("{}.{}", path, name), depth + 1)?);
+            }
+            normalized.insert("properties".to_string(), Value::Object(checked));
+
+            if let Some(required) = object.get("required") {
+                let required = required.as_array().ok_or_else(|| format!("{}: required must be a list", path))?;
+                for key in required {
+                    let key = key.as_str().ok_or_else(|| format!("{}: required keys must be strings", path))?;
+                    if !properties.contains_key(key) {
+                        return Err(format!("{}: required key '{}' is not a property", path, key));
+                    }
+                }
+            }
+        }
+        "ARRAY" => {
+            let items = object.get("items").ok_or_else(|| format!("{}: ARRAY needs items", path))?;
+            normalized.insert("items".to_string(), normalize_at(items, &format!("{}[]", path), depth + 1)?);
+        }
+        _ => {}
+    }
+    Ok(Value::Object(normalized))
+}
+
+/// Check a response against a normalized schema; the error names the first mismatch
+pub fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    validate_at(schema, value, "$")
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    if value.is_null() && schema["nullable"].as_bool().unwrap_or(false) {
+        return Ok(());
+    }
+    let kind = schema["type"].as_str().unwrap_or_default();
+    let matches = match kind {
+        "STRING" => value.is_string(),
+        "NUMBER" => value.is_number(),
+        "INTEGER" => value.is_i64() || value.is_u64(),
+        "BOOLEAN" => value.is_boolean(),
+        "ARRAY" => value.is_array(),
+        "OBJECT" => value.is_object(),
+        _ => true,
+    };
+    if !matches {
+        return Err(format!("{}: expected {}", path, kind));
+    }
+
+    if let Some(allowed) = schema["enum"].as_array() {
+        if !allowed.contains(value) {
+            return Err(format!("{}: {} is not one of the allowed values", path, value));
+        }
+    }
+    if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in values.iter().enumerate() {
+            validate_at(items, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for key in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+            if !object.contains_key(key) {
+                return Err(format!("{}: missing required key '{}'", path, key));
+            }
+        }
+        if let Some(properties) = schema["properties"].as_object() {
+            for (name, property) in properties {
+                if let Some(field) = object.get(name) {
+                    validate_at(property, field, &format!("{}.{}", path, name))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Appended to the intelligence prompt so the model knows what the fields mean
+pub fn prompt_section(schema: &Value) -> String {
+    format!(
+        "OUTPUT SCHEMA (replaces FORMAT above; respond with JSON matching exactly this schema):\n{}",
+        serde_json::to_string_pretty(schema).unwrap_or_default()
+    )
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_intelligence_schema(settings: tauri::State<'_, SettingsState>) -> Option<Value> {
+    settings.get().intelligence_schema
+}
+
+/// Replace the intelligence output schema; None restores the built-in format
+#[tauri::command]
+pub fn set_intelligence_schema(
+    settings: tauri::State<'_, SettingsState>,
+    schema: Option<Value>,
+) -> Result<Option<Value>, String> {
+    let schema = schema.filter(|s| !s.is_null()).map(|s| normalize(&s)).transpose()?;
+    let settings = settings.update(|s| s.intelligence_schema = schema)?;
+    info!("[SCHEMA] Intelligence schema: {}", if settings.intelligence_schema.is_some() { "custom" } else { "default" });
+    Ok(settings.intelligence_schema)
+}
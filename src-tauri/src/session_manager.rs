@@ -1,8 +1,24 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use ring::hmac;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, error, info, warn};
+use crate::analytics;
+use crate::bookmarks::{format_offset, Bookmark};
+use crate::calendar::CalendarEvent;
+use crate::chapters::{self, Chapter};
+use crate::embeddings;
+use crate::event_journal;
+use crate::encryption;
+use crate::gemini_client::GeminiState;
+use crate::live_session::LiveSessionState;
+use crate::network::NetworkState;
+use crate::reanalysis::AnalysisRun;
+use crate::settings::{redact, SettingsState, REDACTED};
 
 // ============================================================================
 // STATION 5: COSMIC POST-PROCESSING & EMPIRE
@@ -23,6 +39,24 @@ pub struct SessionData {
     pub psychosomatic: Option<PsychosomaticState>,
     #[serde(default)]
     pub insights: Option<ExtractedInsights>,
+    #[serde(default)]
+    pub recording_path: Option<String>,
+    // Where the vault export last wrote this session's note
+    #[serde(default)]
+    pub vault_note: Option<String>,
+    #[serde(default)]
+    pub calendar_event: Option<CalendarEvent>,
+    #[serde(default)]
+    pub issue_links: Vec<IssueLink>,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    // Versioned re-runs of intelligence extraction (see reanalysis)
+    #[serde(default)]
+    pub analyses: Vec<AnalysisRun>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_checkpoint: Option<SummaryCheckpoint>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -49,6 +83,19 @@ pub struct TranscriptEntry {
     pub tone: Option<String>,
     pub category: Option<Vec<String>>,
     pub confidence: f32,
+    // Segment offsets from the start of the session (set by the audio pipeline)
+    #[serde(default)]
+    pub start_ms: Option<u64>,
+    #[serde(default)]
+    pub end_ms: Option<u64>,
+    // Pipeline segment the entry came from, so a retried analysis can replace it
+    #[serde(default)]
+    pub segment_id: Option<String>,
+    // Spoken language (detected per segment with Whisper's "auto")
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub language_probability: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -73,6 +120,12 @@ pub struct SessionMetadata {
     pub total_transcripts: usize,
     pub total_speakers: usize,
     pub tags: Vec<String>,
+    // Title came from titling, so a later run may replace it
+    #[serde(default)]
+    pub auto_titled: bool,
+    // Placeholder title nobody chose ("Session 2025-01-31 14:00"); titling replaces it
+    #[serde(default)]
+    pub default_title: bool,
 }
 
 // Station 5: Auto-generated summary
@@ -83,9 +136,19 @@ pub struct SessionSummary {
     pub action_items: Vec<ActionItem>,
     pub risks_identified: Vec<String>,
     pub next_steps: Vec<String>,
+    #[serde(default)]
+    pub open_questions: Vec<String>,
     pub generated_at: String,
 }
 
+/// Partial summaries of an interrupted run, keyed by a hash of their input,
+/// so the next run only requests the chunks that are still missing
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SummaryCheckpoint {
+    pub partials: HashMap<String, String>,
+    pub updated_at: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ActionItem {
     pub description: String,
@@ -94,6 +157,20 @@ pub struct ActionItem {
     pub priority: String,
 }
 
+/// Jira/GitHub issue created from one of the session's action items
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueLink {
+    // Id of the action item it was created from; None in older sessions
+    #[serde(default)]
+    pub item_id: Option<String>,
+    pub description: String,
+    // "jira" or "github"
+    pub tracker: String,
+    pub key: String,
+    pub url: String,
+    pub created_at: String,
+}
+
 impl SessionData {
     pub fn new(title: String) -> Self {
         let now = Utc::now().to_rfc3339();
@@ -110,19 +187,63 @@ impl SessionData {
                 total_transcripts: 0,
                 total_speakers: 0,
                 tags: Vec::new(),
+                auto_titled: false,
+                default_title: false,
             },
             summary: None,
             psychosomatic: None,
             insights: None,
+            recording_path: None,
+            vault_note: None,
+            calendar_event: None,
+            issue_links: Vec::new(),
+            bookmarks: Vec::new(),
+            chapters: Vec::new(),
+            analyses: Vec::new(),
+            summary_checkpoint: None,
         }
     }
 
+    /// Take the fields only the backend writes from the stored copy. The
+    /// frontend saves the whole session it loaded earlier, so its copies of
+    /// these are stale or missing.
+    pub fn keep_backend_fields(&mut self, stored: SessionData) {
+        // Segments the live pipeline stored (with ids, offsets and retries
+        // applied) are only ever written by it
+        if stored.transcripts.iter().any(|t| t.segment_id.is_some()) {
+            self.transcripts = stored.transcripts;
+            self.metadata.total_transcripts = self.transcripts.len();
+        }
+        if self.summary.is_none() {
+            self.summary = stored.summary;
+        }
+        // Titling and rename_session change these; an autosave never does
+        self.metadata.title = stored.metadata.title;
+        self.metadata.tags = stored.metadata.tags;
+        self.metadata.auto_titled = stored.metadata.auto_titled;
+        self.metadata.default_title = stored.metadata.default_title;
+        self.recording_path = stored.recording_path;
+        self.vault_note = stored.vault_note;
+        self.calendar_event = stored.calendar_event;
+        self.issue_links = stored.issue_links;
+        self.bookmarks = stored.bookmarks;
+        self.chapters = stored.chapters;
+        self.analyses = stored.analyses;
+        self.summary_checkpoint = stored.summary_checkpoint;
+    }
+
     pub fn add_transcript(&mut self, entry: TranscriptEntry) {
         self.transcripts.push(entry);
         self.metadata.total_transcripts = self.transcripts.len();
         self.updated_at = Utc::now().to_rfc3339();
     }
 
+    /// Keep only segments spoken in `language` (e.g. "de"), for filtered exports
+    pub fn retain_language(&mut self, language: &str) {
+        self.transcripts.retain(|t| t.language.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(language)));
+        self.metadata.total_transcripts = self.transcripts.len();
+    }
+
     pub fn add_graph_node(&mut self, node: GraphNode) {
         self.graph_nodes.push(node);
         self.updated_at = Utc::now().to_rfc3339();
@@ -138,6 +259,7 @@ impl SessionData {
         let mut decisions = Vec::new();
         let mut tasks = Vec::new();
         let mut risks = Vec::new();
+        let mut questions = Vec::new();
         
         for t in &self.transcripts {
             if let Some(cats) = &t.category {
@@ -151,6 +273,7 @@ impl SessionData {
                             priority: "MEDIUM".to_string(),
                         }),
                         "RISK" => risks.push(t.text.clone()),
+                        "QUERY" => questions.push(t.text.clone()),
                         _ => {}
                     }
                 }
@@ -167,11 +290,30 @@ impl SessionData {
             action_items: tasks.into_iter().take(10).collect(),
             risks_identified: risks.into_iter().take(5).collect(),
             next_steps: vec!["Review action items".to_string(), "Schedule follow-up".to_string()],
+            open_questions: questions.into_iter().take(5).collect(),
             generated_at: Utc::now().to_rfc3339(),
         });
     }
 }
 
+/// Ids become file names; anything else could point outside the data directory
+pub fn valid_session_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+static SESSION_LOCKS: OnceLock<StdMutex<HashMap<String, Arc<StdMutex<()>>>>> = OnceLock::new();
+
+/// Held for every load-modify-save of a session file; the live pipeline,
+/// frontend autosaves and background jobs all write the same file
+pub fn session_lock(session_id: &str) -> Arc<StdMutex<()>> {
+    SESSION_LOCKS.get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(session_id.to_string())
+        .or_default()
+        .clone()
+}
+
 // Session Manager
 pub struct SessionManager {
     sessions_dir: PathBuf,
@@ -190,51 +332,121 @@ impl SessionManager {
         Ok(Self { sessions_dir })
     }
 
+    /// `<sessions>/<id><suffix>`. Every session file path goes through here,
+    /// so ids from commands, MCP or HTTP clients can't reach other files.
+    fn session_file(&self, session_id: &str, suffix: &str) -> Result<PathBuf, String> {
+        if !valid_session_id(session_id) {
+            return Err(format!("Invalid session id: {}", session_id));
+        }
+        Ok(self.sessions_dir.join(format!("{}{}", session_id, suffix)))
+    }
+
     pub fn save_session(&self, session: &SessionData) -> Result<String, String> {
-        let filename = format!("{}.json", session.id);
-        let filepath = self.sessions_dir.join(&filename);
+        let filepath = self.session_file(&session.id, ".json")?;
 
         let json = serde_json::to_string_pretty(session)
             .map_err(|e| format!("Failed to serialize session: {}", e))?;
 
+        self.write_sealed(&filepath, json.into_bytes())?;
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    /// Load, change and save a session under its write lock
+    pub fn update_session<T>(
+        &self,
+        session_id: &str,
+        f: impl FnOnce(&mut SessionData) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if !valid_session_id(session_id) {
+            return Err(format!("Invalid session id: {}", session_id));
+        }
+        let lock = session_lock(session_id);
+        let _guard = lock.lock().unwrap();
+        let mut session = self.load_session(session_id)?;
+        let result = f(&mut session)?;
+        session.updated_at = Utc::now().to_rfc3339();
+        self.save_session(&session)?;
+        Ok(result)
+    }
+
+    /// Atomically write a session file, encrypted if encryption is enabled
+    pub fn write_sealed(&self, filepath: &Path, plaintext: Vec<u8>) -> Result<(), String> {
+        let bytes = encryption::seal(plaintext)?;
         let tmp_filepath = filepath.with_extension("tmp");
 
-        fs::write(&tmp_filepath, json)
+        fs::write(&tmp_filepath, bytes)
             .map_err(|e| format!("Failed to write temp session file: {}", e))?;
 
-        fs::rename(&tmp_filepath, &filepath)
-            .map_err(|e| format!("Failed to commit session file (atomic rename): {}", e))?;
+        fs::rename(&tmp_filepath, filepath)
+            .map_err(|e| format!("Failed to commit session file (atomic rename): {}", e))
+    }
 
-        Ok(filepath.to_string_lossy().to_string())
+    fn read_session_file(filepath: &Path) -> Result<String, String> {
+        let bytes = fs::read(filepath)
+            .map_err(|e| format!("Failed to read session file: {}", e))?;
+        String::from_utf8(encryption::open(bytes)?)
+            .map_err(|e| format!("Session file is not valid UTF-8: {}", e))
+    }
+
+    /// Paths of every stored session JSON
+    pub fn session_files(&self) -> Result<Vec<PathBuf>, String> {
+        let entries = fs::read_dir(&self.sessions_dir)
+            .map_err(|e| format!("Failed to read sessions directory: {}", e))?;
+        Ok(entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect())
+    }
+
+    /// Crash and event journals; sealed line by line
+    pub fn journal_files(&self) -> Result<Vec<PathBuf>, String> {
+        let entries = fs::read_dir(&self.sessions_dir)
+            .map_err(|e| format!("Failed to read sessions directory: {}", e))?;
+        Ok(entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+                name.ends_with(".wal") || name.ends_with(".events.jsonl")
+            })
+            .collect())
+    }
+
+    /// Append-only segment journal kept while a session is live (see recovery)
+    pub fn journal_path(&self, session_id: &str) -> Result<PathBuf, String> {
+        self.session_file(session_id, ".wal")
+    }
+
+    /// Every event emitted for the session (see event_journal)
+    pub fn events_path(&self, session_id: &str) -> Result<PathBuf, String> {
+        self.session_file(session_id, ".events.jsonl")
+    }
+
+    /// Whether the session is stored, even if it can't be read right now (locked)
+    pub fn session_exists(&self, session_id: &str) -> bool {
+        self.session_file(session_id, ".json").is_ok_and(|path| path.exists())
     }
 
     pub fn load_session(&self, session_id: &str) -> Result<SessionData, String> {
-        let filename = format!("{}.json", session_id);
-        let filepath = self.sessions_dir.join(&filename);
+        let filepath = self.session_file(session_id, ".json")?;
 
-        let json = fs::read_to_string(&filepath)
-            .map_err(|e| format!("Failed to read session file: {}", e))?;
+        let json = Self::read_session_file(&filepath)?;
 
         serde_json::from_str(&json)
             .map_err(|e| format!("Failed to deserialize session: {}", e))
     }
 
     pub fn list_sessions(&self) -> Result<Vec<SessionData>, String> {
-        let entries = fs::read_dir(&self.sessions_dir)
-            .map_err(|e| format!("Failed to read sessions directory: {}", e))?;
-
         let mut sessions = Vec::new();
-        for entry in entries {
-            if let Ok(entry) = entry {
-                if let Some(ext) = entry.path().extension() {
-                    if ext == "json" {
-                        if let Ok(json) = fs::read_to_string(entry.path()) {
-                            if let Ok(session) = serde_json::from_str::<SessionData>(&json) {
-                                sessions.push(session);
-                            }
-                        }
+        for path in self.session_files()? {
+            match Self::read_session_file(&path) {
+                Ok(json) => {
+                    if let Ok(session) = serde_json::from_str::<SessionData>(&json) {
+                        sessions.push(session);
                     }
                 }
+                // Locked store: an empty list would look like data loss
+                Err(e) if encryption::is_locked() => return Err(e),
+                Err(_) => {}
             }
         }
 
@@ -243,12 +455,38 @@ impl SessionManager {
     }
 
     pub fn delete_session(&self, session_id: &str) -> Result<(), String> {
-        let filename = format!("{}.json", session_id);
-        let filepath = self.sessions_dir.join(&filename);
+        let filepath = self.session_file(session_id, ".json")?;
 
+        let _ = fs::remove_file(self.journal_path(session_id)?);
+        event_journal::close(Some(session_id));
+        let _ = fs::remove_file(self.events_path(session_id)?);
         fs::remove_file(&filepath)
             .map_err(|e| format!("Failed to delete session: {}", e))
     }
+
+    /// `<exports>/<id>.<extension>`; creates the exports directory
+    pub fn export_path(&self, session_id: &str, extension: &str) -> Result<PathBuf, String> {
+        if !valid_session_id(session_id) {
+            return Err(format!("Invalid session id: {}", session_id));
+        }
+        let exports_dir = self.sessions_dir
+            .parent()
+            .ok_or("Invalid sessions directory")?
+            .join("exports");
+
+        fs::create_dir_all(&exports_dir)
+            .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+
+        Ok(exports_dir.join(format!("{}.{}", session_id, extension)))
+    }
+
+    pub fn write_export(&self, session_id: &str, extension: &str, content: impl AsRef<[u8]>) -> Result<String, String> {
+        let filepath = self.export_path(session_id, extension)?;
+        fs::write(&filepath, content)
+            .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+        Ok(filepath.to_string_lossy().to_string())
+    }
 }
 
 // ============================================================================
@@ -264,7 +502,7 @@ impl ExportManager {
     }
 
     pub fn export_to_csv(session: &SessionData) -> Result<String, String> {
-        let mut csv = String::from("Timestamp,Speaker,Text,Tone,Categories,Confidence\n");
+        let mut csv = String::from("Timestamp,Speaker,Text,Tone,Categories,Confidence,Language\n");
         
         for transcript in &session.transcripts {
             let categories = transcript.category.as_ref()
@@ -272,13 +510,14 @@ impl ExportManager {
                 .unwrap_or_default();
             
             csv.push_str(&format!(
-                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{}\n",
+                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{}\n",
                 transcript.timestamp,
                 transcript.speaker_id,
                 transcript.text.replace("\"", "\"\""),
                 transcript.tone.as_deref().unwrap_or(""),
                 categories,
-                transcript.confidence
+                transcript.confidence,
+                transcript.language.as_deref().unwrap_or("")
             ));
         }
         
@@ -322,6 +561,49 @@ impl ExportManager {
             }
         }
         
+        if !session.chapters.is_empty() {
+            md.push_str("## Chapters\n\n");
+            for (line, chapter) in chapters::chapter_lines(&session.chapters).iter().zip(&session.chapters) {
+                md.push_str(&format!("- **{}**", line));
+                if !chapter.summary.is_empty() {
+                    md.push_str(&format!(" — {}", chapter.summary));
+                }
+                md.push('\n');
+            }
+            md.push('\n');
+        }
+
+        if !session.bookmarks.is_empty() {
+            md.push_str("## Bookmarks\n\n");
+            let mut bookmarks: Vec<_> = session.bookmarks.iter().collect();
+            bookmarks.sort_by_key(|b| b.at_ms);
+            for bookmark in bookmarks {
+                md.push_str(&format!("- ★ **{}**", format_offset(bookmark.at_ms)));
+                if let Some(note) = &bookmark.note {
+                    md.push_str(&format!(" {}", note));
+                }
+                if let (Some(speaker), Some(text)) = (&bookmark.speaker, &bookmark.text) {
+                    md.push_str(&format!(" — {}: \"{}\"", speaker, text.trim()));
+                }
+                md.push('\n');
+            }
+            md.push_str("\n");
+        }
+
+        let stats = analytics::compute(session);
+        if !stats.speakers.is_empty() {
+            md.push_str("## Meeting Analytics\n\n");
+            for line in analytics::overview_lines(&stats) {
+                md.push_str(&format!("- {}\n", line));
+            }
+            md.push_str(&format!("\n| {} |\n", analytics::SPEAKER_COLUMNS.join(" | ")));
+            md.push_str(&format!("|{}\n", "---|".repeat(analytics::SPEAKER_COLUMNS.len())));
+            for row in analytics::speaker_rows(&stats) {
+                md.push_str(&format!("| {} |\n", row.join(" | ")));
+            }
+            md.push('\n');
+        }
+
         md.push_str("## Transcripts\n\n");
         for transcript in &session.transcripts {
             md.push_str(&format!("### {} - {}\n", transcript.timestamp, transcript.speaker_id));
@@ -341,6 +623,98 @@ impl ExportManager {
         Ok(md)
     }
     
+    // Subtitle cues: (start_ms, end_ms, speaker, text) with monotonic timing
+    fn subtitle_cues(session: &SessionData) -> Vec<(u64, u64, String, String)> {
+        let session_start = DateTime::parse_from_rfc3339(&session.created_at).ok();
+
+        // Offset of an entry from session start, from the pipeline or its wall-clock timestamp
+        let offset_of = |t: &TranscriptEntry| -> Option<u64> {
+            t.start_ms.or_else(|| {
+                let start = session_start?;
+                let ts = DateTime::parse_from_rfc3339(&t.timestamp).ok()?;
+                u64::try_from((ts - start).num_milliseconds()).ok()
+            })
+        };
+
+        let mut cues = Vec::new();
+        let mut cursor: u64 = 0;
+        for (i, t) in session.transcripts.iter().enumerate() {
+            if t.text.trim().is_empty() { continue; }
+
+            let start = offset_of(t).unwrap_or(cursor).max(cursor);
+            // ~400ms per word when the pipeline didn't record an end, min 1s
+            let estimated = (t.text.split_whitespace().count() as u64 * 400).max(1000);
+            let mut end = t.end_ms.unwrap_or(start + estimated);
+            if let Some(next_start) = session.transcripts.get(i + 1).and_then(offset_of) {
+                if t.end_ms.is_none() && next_start > start {
+                    end = end.min(next_start);
+                }
+            }
+            let end = end.max(start + 1);
+
+            cues.push((start, end, t.speaker_id.clone(), t.text.trim().to_string()));
+            cursor = end;
+        }
+        cues
+    }
+
+    fn format_timestamp(ms: u64, separator: char) -> String {
+        format!(
+            "{:02}:{:02}:{:02}{}{:03}",
+            ms / 3_600_000,
+            (ms / 60_000) % 60,
+            (ms / 1000) % 60,
+            separator,
+            ms % 1000
+        )
+    }
+
+    /// One line of cue text: a line break or blank line inside would end the cue early
+    fn single_line(text: &str) -> String {
+        text.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ")
+    }
+
+    /// WebVTT cue text is markup; this also turns a literal "-->" into "--&gt;"
+    fn vtt_escape(text: &str) -> String {
+        Self::single_line(text)
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    pub fn export_to_srt(session: &SessionData) -> Result<String, String> {
+        let mut srt = String::new();
+
+        for (i, (start, end, speaker, text)) in Self::subtitle_cues(session).iter().enumerate() {
+            srt.push_str(&format!(
+                "{}\n{} --> {}\n{}: {}\n\n",
+                i + 1,
+                Self::format_timestamp(*start, ','),
+                Self::format_timestamp(*end, ','),
+                Self::single_line(speaker),
+                Self::single_line(text)
+            ));
+        }
+
+        Ok(srt)
+    }
+
+    pub fn export_to_vtt(session: &SessionData) -> Result<String, String> {
+        let mut vtt = String::from("WEBVTT\n\n");
+
+        for (start, end, speaker, text) in Self::subtitle_cues(session) {
+            vtt.push_str(&format!(
+                "{} --> {}\n<v {}>{}\n\n",
+                Self::format_timestamp(start, '.'),
+                Self::format_timestamp(end, '.'),
+                Self::vtt_escape(&speaker),
+                Self::vtt_escape(&text)
+            ));
+        }
+
+        Ok(vtt)
+    }
+
     // Station 5: GraphML Export
     pub fn export_to_graphml(session: &SessionData) -> Result<String, String> {
         let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -410,37 +784,131 @@ impl ExportManager {
 }
 
 // ============================================================================
-// WEBHOOK MANAGER - Station 5
+// WEBHOOK MANAGER - Station 5 (signed POSTs of intelligence & summaries)
 // ============================================================================
 
+const WEBHOOK_MAX_ATTEMPTS: u32 = 4;
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WebhookConfig {
     pub url: String,
-    pub events: Vec<String>, // "session_end", "task_detected", "risk_detected"
+    #[serde(default)]
+    pub events: Vec<String>, // "gemini_intelligence", "meeting_summary"; empty = all
+    #[serde(default)]
     pub headers: HashMap<String, String>,
     pub enabled: bool,
+    // Signs each body as X-Cognivox-Signature: sha256=HMAC(secret, "{timestamp}.{body}")
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl WebhookConfig {
+    fn wants(&self, event: &str) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.iter().any(|e| e == event))
+    }
 }
 
 pub struct WebhookManager {
-    configs: Vec<WebhookConfig>,
+    configs: StdMutex<Vec<WebhookConfig>>,
 }
 
 impl WebhookManager {
-    pub fn new() -> Self {
-        Self { configs: Vec::new() }
+    pub fn new(configs: Vec<WebhookConfig>) -> Self {
+        Self { configs: StdMutex::new(configs) }
     }
     
-    pub fn add_webhook(&mut self, config: WebhookConfig) {
-        self.configs.push(config);
+    pub fn set_webhooks(&self, configs: Vec<WebhookConfig>) {
+        *self.configs.lock().unwrap() = configs;
     }
     
-    pub async fn trigger_webhook(&self, event: &str, payload: &serde_json::Value) {
-        for config in &self.configs {
-            if config.enabled && config.events.contains(&event.to_string()) {
-                // In production, use reqwest to POST
-                println!("[WEBHOOK] Would POST to {}: {:?}", config.url, payload);
+    /// Fire-and-forget delivery to every enabled webhook subscribed to `event`
+    pub fn trigger_webhook(&self, client: &reqwest::Client, event: &str, payload: &serde_json::Value) {
+        let targets: Vec<WebhookConfig> = self.configs.lock().unwrap().iter()
+            .filter(|c| c.wants(event))
+            .cloned()
+            .collect();
+        if targets.is_empty() { return; }
+        
+        let body = serde_json::json!({
+            "event": event,
+            "timestamp": Utc::now().to_rfc3339(),
+            "data": payload
+        }).to_string();
+        
+        for config in targets {
+            let client = client.clone();
+            let event = event.to_string();
+            let body = body.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = deliver_webhook(&client, &config, &event, &body).await {
+                    error!("[WEBHOOK] ✗ {} -> {}: {}", event, config.url, e);
+                }
+            });
+        }
+    }
+}
+
+/// POST with retries on network errors, 429 and 5xx
+async fn deliver_webhook(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    event: &str,
+    body: &str,
+) -> Result<u16, String> {
+    let mut last_error = String::new();
+    
+    for attempt in 0..WEBHOOK_MAX_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(std::time::Duration::from_secs(1 << attempt)).await;
+        }
+        
+        let timestamp = Utc::now().timestamp().to_string();
+        let mut request = client.post(&config.url)
+            .timeout(std::time::Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .header("Content-Type", "application/json")
+            .header("X-Cognivox-Event", event)
+            .header("X-Cognivox-Timestamp", &timestamp)
+            .body(body.to_string());
+        for (name, value) in &config.headers {
+            request = request.header(name, value);
+        }
+        if let Some(secret) = config.secret.as_deref().filter(|s| !s.is_empty()) {
+            request = request.header("X-Cognivox-Signature", webhook_signature(secret, &timestamp, body));
+        }
+        
+        match request.send().await {
+            Ok(resp) if resp.status().is_success() => {
+                info!("[WEBHOOK] ✓ {} -> {} ({})", event, config.url, resp.status());
+                return Ok(resp.status().as_u16());
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                last_error = format!("HTTP {}", status);
+                if !(status.is_server_error() || status.as_u16() == 429) {
+                    break;  // Client errors won't fix themselves
+                }
             }
+            Err(e) => last_error = e.to_string(),
         }
+        warn!("[WEBHOOK] Attempt {}/{} to {} failed: {}", attempt + 1, WEBHOOK_MAX_ATTEMPTS, config.url, last_error);
+    }
+    
+    Err(last_error)
+}
+
+/// X-Cognivox-Signature value: HMAC-SHA256 of "<timestamp>.<body>"
+fn webhook_signature(secret: &str, timestamp: &str, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={}", hex::encode(tag.as_ref()))
+}
+
+/// Forward a backend event to the configured webhooks
+pub fn dispatch_webhook(app: &AppHandle, event: &str, payload: &serde_json::Value) {
+    match app.state::<NetworkState>().client() {
+        Ok(client) => app.state::<WebhookManager>().trigger_webhook(&client, event, payload),
+        Err(e) => debug!("[WEBHOOK] Not sent: {}", e),
     }
 }
 
@@ -449,12 +917,47 @@ impl WebhookManager {
 // ============================================================================
 
 #[tauri::command]
-pub fn save_session(session_json: String) -> Result<String, String> {
-    let session: SessionData = serde_json::from_str(&session_json)
+pub fn save_session(app: AppHandle, session_json: String) -> Result<String, String> {
+    let mut session: SessionData = serde_json::from_str(&session_json)
         .map_err(|e| format!("Invalid session data: {}", e))?;
     
     let manager = SessionManager::new()?;
-    manager.save_session(&session)
+    let lock = session_lock(&session.id);
+    let guard = lock.lock().unwrap();
+
+    if let Ok(existing) = manager.load_session(&session.id) {
+        session.keep_backend_fields(existing);
+    }
+
+    let path = manager.save_session(&session)?;
+    drop(guard);
+    
+    // Keep the semantic search index current when Gemini is configured
+    if app.state::<GeminiState>().api_key.lock().unwrap().is_some() {
+        embeddings::index_in_background(&app, &session.id);
+    }
+    Ok(path)
+}
+
+/// Title chosen by the user; auto-titling leaves it alone from now on
+#[tauri::command]
+pub fn rename_session(
+    live: tauri::State<'_, LiveSessionState>,
+    session_id: String,
+    title: String,
+) -> Result<(), String> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Title can't be empty".to_string());
+    }
+    SessionManager::new()?.update_session(&session_id, |session| {
+        session.metadata.title = title.clone();
+        session.metadata.auto_titled = false;
+        session.metadata.default_title = false;
+        Ok(())
+    })?;
+    live.rename(&session_id, &title);
+    Ok(())
 }
 
 #[tauri::command]
@@ -476,13 +979,18 @@ pub fn list_sessions() -> Result<String, String> {
 #[tauri::command]
 pub fn delete_session(session_id: String) -> Result<(), String> {
     let manager = SessionManager::new()?;
-    manager.delete_session(&session_id)
+    manager.delete_session(&session_id)?;
+    embeddings::delete_index(&session_id);
+    Ok(())
 }
 
 #[tauri::command]
-pub fn export_session(session_json: String, format: String) -> Result<String, String> {
-    let session: SessionData = serde_json::from_str(&session_json)
+pub fn export_session(session_json: String, format: String, language: Option<String>) -> Result<String, String> {
+    let mut session: SessionData = serde_json::from_str(&session_json)
         .map_err(|e| format!("Invalid session data: {}", e))?;
+    if let Some(language) = language.as_deref().filter(|l| !l.is_empty()) {
+        session.retain_language(language);
+    }
     
     match format.as_str() {
         "json" => ExportManager::export_to_json(&session),
@@ -490,10 +998,30 @@ pub fn export_session(session_json: String, format: String) -> Result<String, St
         "markdown" | "md" => ExportManager::export_to_markdown(&session),
         "graphml" => ExportManager::export_to_graphml(&session),
         "entities" => ExportManager::export_entities_csv(&session),
+        "srt" => ExportManager::export_to_srt(&session),
+        "vtt" => ExportManager::export_to_vtt(&session),
+        "analytics" => Ok(analytics::to_csv(&analytics::compute(&session))),
         _ => Err(format!("Unsupported export format: {}", format)),
     }
 }
 
+#[tauri::command]
+pub fn export_subtitles(session_id: String, format: String, language: Option<String>) -> Result<String, String> {
+    let manager = SessionManager::new()?;
+    let mut session = manager.load_session(&session_id)?;
+    if let Some(language) = language.as_deref().filter(|l| !l.is_empty()) {
+        session.retain_language(language);
+    }
+
+    let content = match format.as_str() {
+        "srt" => ExportManager::export_to_srt(&session)?,
+        "vtt" => ExportManager::export_to_vtt(&session)?,
+        _ => return Err(format!("Unsupported subtitle format: {}", format)),
+    };
+
+    manager.write_export(&session.id, &format, &content)
+}
+
 #[tauri::command]
 pub fn generate_session_summary(session_json: String) -> Result<String, String> {
     let mut session: SessionData = serde_json::from_str(&session_json)
@@ -517,3 +1045,69 @@ pub fn get_session_summary(session_json: String) -> Result<String, String> {
         Ok("null".to_string())
     }
 }
+
+#[tauri::command]
+pub fn get_webhooks(settings: tauri::State<'_, SettingsState>) -> Vec<WebhookConfig> {
+    settings.get().webhooks.into_iter()
+        .map(|mut w| {
+            w.secret = redact(w.secret.as_deref());
+            // Custom headers usually carry auth tokens
+            for value in w.headers.values_mut() {
+                *value = REDACTED.to_string();
+            }
+            w
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn set_webhooks(
+    settings: tauri::State<'_, SettingsState>,
+    manager: tauri::State<'_, WebhookManager>,
+    webhooks: Vec<WebhookConfig>,
+) -> Result<usize, String> {
+    let stored = settings.get().webhooks;
+    let mut validated = Vec::new();
+    for mut hook in webhooks {
+        let url = url::Url::parse(hook.url.trim())
+            .map_err(|e| format!("Invalid webhook URL '{}': {}", hook.url, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("Webhook URL must be http(s): {}", hook.url));
+        }
+        hook.url = url.to_string();
+        // The frontend only ever sees the redacted secret and header values; keep the stored ones
+        let previous = stored.iter().find(|s| s.url == hook.url);
+        if hook.secret.as_deref() == Some(REDACTED) {
+            hook.secret = previous.and_then(|s| s.secret.clone());
+        }
+        hook.headers = hook.headers.into_iter()
+            .filter_map(|(name, value)| {
+                let value = if value == REDACTED { previous.and_then(|s| s.headers.get(&name).cloned())? } else { value };
+                Some((name, value))
+            })
+            .collect();
+        validated.push(hook);
+    }
+    
+    let settings = settings.update(|s| s.webhooks = validated)?;
+    manager.set_webhooks(settings.webhooks.clone());
+    info!("[WEBHOOK] {} webhook(s) configured", settings.webhooks.len());
+    Ok(settings.webhooks.len())
+}
+
+/// Send a signed "ping" to one configured webhook and report the HTTP status
+#[tauri::command]
+pub async fn test_webhook(
+    settings: tauri::State<'_, SettingsState>,
+    network: tauri::State<'_, NetworkState>,
+    index: usize,
+) -> Result<u16, String> {
+    let config = settings.get().webhooks.get(index).cloned()
+        .ok_or("No webhook at that index")?;
+    let body = serde_json::json!({
+        "event": "ping",
+        "timestamp": Utc::now().to_rfc3339(),
+        "data": {}
+    }).to_string();
+    deliver_webhook(&network.client()?, &config, "ping", &body).await
+}
@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use tracing::info;
+use crate::alerts::AlertRules;
+use crate::calendar::CalendarConfig;
+use crate::dedupe::DedupeRules;
+use crate::encryption::EncryptionConfig;
+use crate::hallucination::HallucinationRules;
+use crate::hotkeys::HotkeyConfig;
+use crate::http_api::HttpApiConfig;
+use crate::issues::IssueTrackerConfig;
+use crate::network::{NetworkConfig, PrivacyMode};
+use crate::plugins::PluginConfig;
+use crate::processing_engine::default_categories;
+use crate::redaction::RedactionRules;
+use crate::resources::ResourceLimits;
+use crate::session_manager::WebhookConfig;
+use crate::slack::SlackConfig;
+use crate::templates::{default_templates, MeetingTemplate};
+use crate::titling::AutoTitleConfig;
+use crate::translation::TranslationConfig;
+use crate::vault::VaultConfig;
+use crate::voice_commands::VoiceCommandConfig;
+use crate::whisper_client::WhisperDecodingConfig;
+use crate::whisper_models::ModelSource;
+
+// ============================================================================
+// SETTINGS - Persisted Backend Configuration
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AppSettings {
+    // None = built-in COGNIVOX_INTELLIGENCE_PROMPT
+    pub intelligence_prompt: Option<String>,
+    // Gemini responseSchema for intelligence results; None = built-in format
+    pub intelligence_schema: Option<serde_json::Value>,
+    pub categories: Vec<String>,
+    // Active MeetingTemplate id; None = general prompt and categories
+    pub meeting_type: Option<String>,
+    pub templates: Vec<MeetingTemplate>,
+    // Generated titles and tags for live sessions
+    pub auto_title: AutoTitleConfig,
+    pub network: NetworkConfig,
+    pub privacy_mode: PrivacyMode,
+    // RNNoise pass before VAD/Whisper
+    pub noise_suppression: bool,
+    // Cancel loopback audio picked up by the mic (capture mode "both")
+    pub echo_cancellation: bool,
+    // Audio carried into the next segment when MAX_BATCH_SECS cuts mid-speech
+    pub segment_overlap_ms: u64,
+    // Session length / WAV size before rollover, disk and memory floors
+    pub resources: ResourceLimits,
+    // Whisper model download directory; None = shared Hugging Face cache
+    pub whisper_model_dir: Option<String>,
+    // Mirror / local directory for model downloads
+    pub model_source: ModelSource,
+    pub whisper_decoding: WhisperDecodingConfig,
+    // Post-STT filter for Whisper's silence/noise hallucinations
+    pub hallucinations: HallucinationRules,
+    // Near-repeats of the last few segments
+    pub dedupe: DedupeRules,
+    // Second-language live captions
+    pub translation: TranslationConfig,
+    // Wake word + spoken commands, handled instead of analyzed
+    pub voice_commands: VoiceCommandConfig,
+    pub webhooks: Vec<WebhookConfig>,
+    pub slack: SlackConfig,
+    pub calendar: CalendarConfig,
+    pub issue_trackers: IssueTrackerConfig,
+    pub hotkeys: HotkeyConfig,
+    // Embedded LAN API; always on when started with --headless
+    pub http_api: HttpApiConfig,
+    pub alerts: AlertRules,
+    // Terms that raise cognivox:keyword_alert whenever they're transcribed
+    pub watch_keywords: Vec<String>,
+    // Rhai scripts run on every intelligence result
+    pub plugins: PluginConfig,
+    pub vault: VaultConfig,
+    // PII masking for text sent to Gemini
+    pub redaction: RedactionRules,
+    // AES-GCM for sessions at rest; the key itself is never stored
+    pub encryption: EncryptionConfig,
+    // error/warn/info/debug/trace
+    pub log_level: String,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            intelligence_prompt: None,
+            intelligence_schema: None,
+            categories: default_categories(),
+            meeting_type: None,
+            templates: default_templates(),
+            auto_title: AutoTitleConfig::default(),
+            network: NetworkConfig::default(),
+            privacy_mode: PrivacyMode::default(),
+            noise_suppression: false,
+            echo_cancellation: false,
+            segment_overlap_ms: 500,
+            resources: ResourceLimits::default(),
+            whisper_model_dir: None,
+            model_source: ModelSource::default(),
+            whisper_decoding: WhisperDecodingConfig::default(),
+            hallucinations: HallucinationRules::default(),
+            dedupe: DedupeRules::default(),
+            translation: TranslationConfig::default(),
+            voice_commands: VoiceCommandConfig::default(),
+            webhooks: Vec::new(),
+            slack: SlackConfig::default(),
+            calendar: CalendarConfig::default(),
+            issue_trackers: IssueTrackerConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            http_api: HttpApiConfig::default(),
+            alerts: AlertRules::default(),
+            watch_keywords: Vec::new(),
+            plugins: PluginConfig::default(),
+            vault: VaultConfig::default(),
+            redaction: RedactionRules::default(),
+            encryption: EncryptionConfig::default(),
+            log_level: "info".to_string(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Template for the selected meeting type, if any
+    pub fn active_template(&self) -> Option<&MeetingTemplate> {
+        let id = self.meeting_type.as_deref()?;
+        self.templates.iter().find(|t| t.id == id)
+    }
+
+    /// Categories the model may assign right now
+    pub fn active_categories(&self) -> &[String] {
+        self.active_template().map(|t| t.categories.as_slice()).unwrap_or(&self.categories)
+    }
+}
+
+pub struct SettingsState {
+    pub settings: StdMutex<AppSettings>,
+}
+
+impl SettingsState {
+    pub fn load() -> Self {
+        let settings = settings_path()
+            .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+            .and_then(|json| serde_json::from_str::<AppSettings>(&json).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                info!("[SETTINGS] Using defaults ({})", e);
+                AppSettings::default()
+            });
+
+        Self { settings: StdMutex::new(settings) }
+    }
+
+    pub fn get(&self) -> AppSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// Apply a change and write the result to disk
+    pub fn update<F: FnOnce(&mut AppSettings)>(&self, f: F) -> Result<AppSettings, String> {
+        let mut settings = self.settings.lock().unwrap();
+        f(&mut settings);
+        save_settings(&settings)?;
+        Ok(settings.clone())
+    }
+}
+
+/// Root of the backend's on-disk state (sessions, settings, queues)
+pub fn app_data_dir() -> Result<PathBuf, String> {
+    let dir = dirs::data_local_dir()
+        .ok_or("Could not find local data directory")?
+        .join("GOD-V8");
+
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create data directory: {}", e))?;
+
+    Ok(dir)
+}
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join("settings.json"))
+}
+
+fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write settings: {}", e))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Failed to commit settings file: {}", e))
+}
+
+// ============================================================================
+// Secrets
+// ============================================================================
+
+/// Stands in for a stored secret in everything sent to the frontend
+pub const REDACTED: &str = "********";
+
+/// What the frontend may see of a stored secret
+pub fn redact(secret: Option<&str>) -> Option<String> {
+    secret.map(|_| REDACTED.to_string())
+}
+
+/// A secret sent back by the frontend: the placeholder keeps `stored`,
+/// blank clears it, anything else replaces it
+pub fn unredact(incoming: Option<String>, stored: Option<String>) -> Option<String> {
+    match incoming {
+        Some(v) if v == REDACTED => stored,
+        Some(v) if v.trim().is_empty() => None,
+        other => other.map(|v| v.trim().to_string()),
+    }
+}
+
+/// Upper snake case, deduplicated
+pub fn normalize_categories(categories: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for c in categories {
+        let c = c.trim().to_uppercase().replace([' ', '-'], "_");
+        if !c.is_empty() && !normalized.contains(&c) {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct IntelligenceConfig {
+    pub prompt: String,
+    pub is_custom: bool,
+    pub categories: Vec<String>,
+    pub meeting_type: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_intelligence_config(state: tauri::State<'_, SettingsState>) -> Result<IntelligenceConfig, String> {
+    let settings = state.get();
+    Ok(IntelligenceConfig {
+        prompt: crate::gemini_client::build_intelligence_prompt(&settings),
+        is_custom: settings.intelligence_prompt.is_some(),
+        categories: settings.active_categories().to_vec(),
+        meeting_type: settings.meeting_type.clone(),
+    })
+}
+
+/// Replace the system prompt. `{categories}` is substituted with the category list;
+/// an empty prompt restores the built-in one.
+#[tauri::command]
+pub fn set_intelligence_prompt(
+    state: tauri::State<'_, SettingsState>,
+    prompt: Option<String>,
+) -> Result<String, String> {
+    let prompt = prompt.filter(|p| !p.trim().is_empty());
+    let is_custom = prompt.is_some();
+    state.update(|s| s.intelligence_prompt = prompt)?;
+
+    info!("[SETTINGS] Intelligence prompt: {}", if is_custom { "custom" } else { "default" });
+    Ok(if is_custom { "Custom prompt saved" } else { "Default prompt restored" }.to_string())
+}
+
+#[tauri::command]
+pub fn set_categories(
+    state: tauri::State<'_, SettingsState>,
+    categories: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let normalized = normalize_categories(categories);
+    if normalized.is_empty() {
+        return Err("At least one category is required".to_string());
+    }
+
+    let settings = state.update(|s| s.categories = normalized)?;
+    info!("[SETTINGS] Categories: {}", settings.categories.join("|"));
+    Ok(settings.categories)
+}
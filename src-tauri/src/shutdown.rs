@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout, Duration};
+use tracing::{info, warn};
+use crate::audio_capture::AudioState;
+use crate::events::{self, ShutdownEvent, ShutdownPhase};
+use crate::gemini_client::GeminiState;
+use crate::live_session;
+use crate::participants;
+use crate::recorder::{self, RecorderState};
+
+// ============================================================================
+// SHUTDOWN - Flush Buffered Speech Before Exit
+// ============================================================================
+//
+// Quitting goes through ExitRequested (tray Quit, Cmd+Q) or the main
+// window's CloseRequested. The first request is held back while we:
+//   1. stop capture and let the last callbacks land in the channel,
+//   2. have the audio loop force-process whatever speech is buffered
+//      (Gemini failures land in the persisted retry queue as usual),
+//   3. finalize an active WAV recording and close the live session record,
+//   4. ask the frontend to save the session and wait for its confirmation,
+// then exit for real. Every step is time-boxed so a hung request can't keep
+// the app alive.
+
+pub const MAIN_WINDOW: &str = "main";
+const CAPTURE_DRAIN_MS: u64 = 250;
+const FLUSH_TIMEOUT_SECS: u64 = 20;
+const SESSION_SAVE_TIMEOUT_SECS: u64 = 5;
+
+const RUNNING: u8 = 0;
+const FLUSHING: u8 = 1;
+const DONE: u8 = 2;
+
+#[derive(Default)]
+pub struct ShutdownState {
+    phase: AtomicU8,
+    session_saved: Notify,
+}
+
+/// Decide whether an exit request may proceed. The first one starts the
+/// flush and returns false; the exit we trigger afterwards returns true.
+pub fn should_exit(app: &AppHandle) -> bool {
+    let state = app.state::<ShutdownState>();
+    match state.phase.compare_exchange(RUNNING, FLUSHING, Ordering::SeqCst, Ordering::SeqCst) {
+        Ok(_) => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                flush_and_exit(app).await;
+            });
+            false
+        }
+        Err(FLUSHING) => false,
+        Err(_) => true,
+    }
+}
+
+async fn flush_and_exit(app: AppHandle) {
+    info!("[SHUTDOWN] Exit requested - flushing pipeline");
+    events::emit(&app, &ShutdownEvent { phase: ShutdownPhase::Flushing });
+
+    // A running session does the whole flush itself; the steps below then find nothing to do
+    match live_session::finish_active(&app).await {
+        Ok(Some(session)) => info!("[SHUTDOWN] ✓ Session {} closed", session.id),
+        Ok(None) => {}
+        Err(e) => warn!("[SHUTDOWN] Session not closed cleanly: {}", e),
+    }
+
+    let was_capturing = app.state::<AudioState>().stop_capture().unwrap_or(false);
+    if was_capturing {
+        sleep(Duration::from_millis(CAPTURE_DRAIN_MS)).await;
+    }
+
+    let gemini = app.state::<GeminiState>();
+    if gemini.audio_loop_running() {
+        if gemini.flush_audio_loop(true, Duration::from_secs(FLUSH_TIMEOUT_SECS)).await {
+            info!("[SHUTDOWN] ✓ Buffered speech processed");
+        } else {
+            warn!("[SHUTDOWN] Flush timed out after {}s", FLUSH_TIMEOUT_SECS);
+        }
+    }
+
+    // Tracks pushed without a session; a running session flushed its own above
+    if timeout(Duration::from_secs(FLUSH_TIMEOUT_SECS), participants::flush_all(&app, None)).await.is_err() {
+        warn!("[SHUTDOWN] Participant tracks not flushed after {}s", FLUSH_TIMEOUT_SECS);
+    }
+
+    let recorder = app.state::<RecorderState>();
+    if recorder.is_active() {
+        match recorder::finish_recording(&recorder) {
+            Ok(info) => info!("[SHUTDOWN] ✓ Recording finalized ({:.1}s)", info.duration_secs),
+            Err(e) => warn!("[SHUTDOWN] Recording not finalized: {}", e),
+        }
+    }
+
+    events::emit(&app, &ShutdownEvent { phase: ShutdownPhase::SaveSession });
+    let state = app.state::<ShutdownState>();
+    if timeout(Duration::from_secs(SESSION_SAVE_TIMEOUT_SECS), state.session_saved.notified()).await.is_err() {
+        warn!("[SHUTDOWN] Frontend did not confirm session save within {}s", SESSION_SAVE_TIMEOUT_SECS);
+    }
+
+    state.phase.store(DONE, Ordering::SeqCst);
+    info!("[SHUTDOWN] Exiting");
+    app.exit(0);
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// The frontend has persisted its session after a SaveSession shutdown event
+#[tauri::command]
+pub fn confirm_shutdown_saved(state: tauri::State<'_, ShutdownState>) {
+    state.session_saved.notify_one();
+}
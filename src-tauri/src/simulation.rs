@@ -0,0 +1,340 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+use crate::alerts;
+use crate::audio_capture::{AudioSource, AudioState, TaggedAudio, TARGET_SAMPLE_RATE};
+use crate::batching::LiveSegment;
+use crate::events::{self, PipelineState, TranscriptionEvent, TranscriptionSource};
+use crate::file_import::{is_audio_file, load_samples};
+use crate::gemini_client::{self, GeminiState};
+use crate::live_session::{LiveSessionState, RecentSegment};
+use crate::settings::SettingsState;
+use crate::whisper_client::WhisperState;
+
+// ============================================================================
+// SIMULATION - Realistic Event Streams without a Mic or API Key
+// ============================================================================
+//
+// An audio file is played into the audio loop in real time, so capture,
+// segmentation and Whisper all run for real. A script skips straight to
+// transcripts, one "Speaker: text" line at a time. Either way intelligence
+// comes from MockProvider instead of the API, so no quota is used.
+//
+// Script lines: "Speaker: text", optionally prefixed with a start offset
+// ("[01:15] Speaker: text"). Blank lines and lines starting with '#' are skipped.
+
+const CHUNK_MS: u64 = 100;
+const MOCK_LATENCY_MS: u64 = 400;
+// Pacing for scripts without offsets, roughly 150 words a minute
+const MS_PER_WORD: u64 = 400;
+const MIN_LINE_MS: u64 = 1200;
+const SIMULATION_FLUSH_TIMEOUT_SECS: u64 = 30;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// True while a simulation runs; intelligence requests go to MockProvider
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+// ============================================================================
+// MockProvider
+// ============================================================================
+
+/// Keyword-driven stand-in for the model. Produces the same JSON shape as
+/// the intelligence prompt, limited to the categories currently enabled.
+pub struct MockProvider;
+
+impl MockProvider {
+    const RULES: &'static [(&'static str, &'static [&'static str])] = &[
+        ("DECISION", &["decided", "agreed", "let's go with", "we'll go with", "final answer"]),
+        ("ACTION_ITEM", &["i'll", "i will", "can you", "please", "follow up", "action item", "todo"]),
+        ("DEADLINE", &["by friday", "by monday", "deadline", "due ", "end of week", "tomorrow", "next week"]),
+        ("RISK", &["risk", "concern", "blocker", "blocked", "worried", "might slip"]),
+        ("URGENT", &["urgent", "asap", "immediately", "right away"]),
+        ("QUESTION", &["?"]),
+    ];
+
+    /// Categories come from the active template or settings, like the real prompt
+    pub async fn analyze(app: &AppHandle, transcript: &str, speaker: &str) -> String {
+        sleep(Duration::from_millis(MOCK_LATENCY_MS)).await;
+        let settings = app.state::<SettingsState>().get();
+        Self::intelligence(transcript, speaker, settings.active_categories())
+    }
+
+    fn intelligence(transcript: &str, speaker: &str, categories: &[String]) -> String {
+        let lower = transcript.to_lowercase();
+        let mut category: Vec<String> = Self::RULES.iter()
+            .filter(|(_, keywords)| keywords.iter().any(|k| lower.contains(k)))
+            .map(|(c, _)| c.to_string())
+            .filter(|c| categories.contains(c))
+            .collect();
+        if category.is_empty() {
+            category.push(categories.first().cloned().unwrap_or_else(|| "INFO".to_string()));
+        }
+        let tone = if category.iter().any(|c| c == "URGENT") { "URGENT" } else { "NEUTRAL" };
+
+        // Capitalized words after the first stand in for named entities
+        let entities: Vec<String> = transcript.split_whitespace()
+            .skip(1)
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|w| w.len() > 1 && w.chars().next().is_some_and(char::is_uppercase))
+            .fold(Vec::new(), |mut seen, w| {
+                if !seen.iter().any(|s: &String| s == w) {
+                    seen.push(w.to_string());
+                }
+                seen
+            });
+        let graph_edges: Vec<serde_json::Value> = entities.first()
+            .map(|e| serde_json::json!({"from": speaker, "to": e, "relation": "mentioned"}))
+            .into_iter()
+            .collect();
+
+        serde_json::json!({
+            "transcript": transcript,
+            "speaker": speaker,
+            "tone": tone,
+            "category": category,
+            "confidence": 0.8,
+            "summary": format!("Simulated: {}", transcript.chars().take(60).collect::<String>()),
+            "entities": entities.iter().map(|e| serde_json::json!({"name": e, "type": "TOPIC"})).collect::<Vec<_>>(),
+            "graph_edges": graph_edges,
+        })
+        .to_string()
+    }
+}
+
+// ============================================================================
+// Scripts
+// ============================================================================
+
+#[derive(Debug)]
+struct ScriptLine {
+    at_ms: Option<u64>,
+    speaker: String,
+    text: String,
+}
+
+/// "[mm:ss]" or "[hh:mm:ss]"
+fn parse_offset(stamp: &str) -> Option<u64> {
+    let parts: Vec<u64> = stamp.split(':').map(|p| p.trim().parse().ok()).collect::<Option<_>>()?;
+    let secs = match parts.as_slice() {
+        [m, s] => m * 60 + s,
+        [h, m, s] => h * 3600 + m * 60 + s,
+        _ => return None,
+    };
+    Some(secs * 1000)
+}
+
+fn parse_script(content: &str) -> Result<Vec<ScriptLine>, String> {
+    let mut lines = Vec::new();
+    for (number, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (at_ms, rest) = match line.strip_prefix('[').and_then(|l| l.split_once(']')) {
+            Some((stamp, rest)) => (
+                Some(parse_offset(stamp).ok_or_else(|| format!("Line {}: bad offset '[{}]'", number + 1, stamp))?),
+                rest.trim(),
+            ),
+            None => (None, line),
+        };
+        let (speaker, text) = rest.split_once(':')
+            .map(|(s, t)| (s.trim(), t.trim()))
+            .filter(|(s, t)| !s.is_empty() && !t.is_empty())
+            .ok_or_else(|| format!("Line {}: expected 'Speaker: text'", number + 1))?;
+        lines.push(ScriptLine { at_ms, speaker: speaker.to_string(), text: text.to_string() });
+    }
+    if lines.is_empty() {
+        return Err("Script has no lines".to_string());
+    }
+    Ok(lines)
+}
+
+fn line_duration_ms(text: &str) -> u64 {
+    (text.split_whitespace().count() as u64 * MS_PER_WORD).max(MIN_LINE_MS)
+}
+
+/// Sleep in short steps so a cancel lands quickly. False if cancelled.
+async fn wait(ms: u64) -> bool {
+    let until = Instant::now() + Duration::from_millis(ms);
+    while Instant::now() < until {
+        if CANCEL.load(Ordering::SeqCst) {
+            return false;
+        }
+        sleep(Duration::from_millis(CHUNK_MS.min(ms))).await;
+    }
+    !CANCEL.load(Ordering::SeqCst)
+}
+
+async fn run_script(app: &AppHandle, lines: Vec<ScriptLine>) -> Result<(), String> {
+    let session_id = app.state::<LiveSessionState>().active_id();
+    let clock = Instant::now();
+    let mut next_ms = 0;
+
+    for line in lines {
+        let start_ms = line.at_ms.unwrap_or(next_ms).max(next_ms);
+        let end_ms = start_ms + line_duration_ms(&line.text);
+        let elapsed = clock.elapsed().as_millis() as u64;
+        // The line "is spoken" until end_ms, then transcribed
+        if !wait(end_ms.saturating_sub(elapsed)).await {
+            return Err("Simulation stopped".to_string());
+        }
+        next_ms = end_ms;
+
+        let segment_id = uuid::Uuid::new_v4().to_string();
+        events::emit(app, &TranscriptionEvent {
+            segment_id: Some(segment_id.clone()),
+            session_id: session_id.clone(),
+            text: line.text.clone(),
+            language: app.state::<WhisperState>().language.lock().unwrap().clone(),
+            language_probability: None,
+            confidence: 1.0,
+            no_speech_prob: 0.0,
+            source: TranscriptionSource::Simulated,
+            speaker: Some(line.speaker.clone()),
+            start_ms: Some(start_ms),
+            end_ms: Some(end_ms),
+            gain: None,
+        });
+        alerts::watch_keywords(app, &line.text, &line.speaker, Some(&segment_id), session_id.as_deref(), Some(start_ms));
+        app.state::<LiveSessionState>().remember_segment(RecentSegment {
+            segment_id: segment_id.clone(),
+            session_id: session_id.clone(),
+            speaker: line.speaker.clone(),
+            text: line.text.clone(),
+            start_ms,
+            end_ms,
+        });
+        gemini_client::analyze_segment(app, LiveSegment {
+            segment_id,
+            session_id: session_id.clone(),
+            transcript: line.text,
+            speaker: line.speaker,
+            start_ms,
+            end_ms,
+            stt_confidence: 1.0,
+            language: None,
+            speech_end: Instant::now(),
+            transcribed_at: Instant::now(),
+        }).await;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Audio files
+// ============================================================================
+
+async fn run_audio(app: &AppHandle, path: PathBuf) -> Result<(), String> {
+    if !*app.state::<WhisperState>().is_initialized.lock().unwrap() {
+        return Err("Whisper not initialized".to_string());
+    }
+    let samples = load_samples(app, path).await?;
+    let tx = app.state::<AudioState>().audio_tx.lock().unwrap().clone().ok_or("Audio channel unavailable")?;
+
+    let gemini = app.state::<GeminiState>();
+    let owns_loop = !gemini.audio_loop_running();
+    gemini_client::ensure_audio_loop(app);
+
+    let chunk = (TARGET_SAMPLE_RATE as u64 * CHUNK_MS / 1000) as usize;
+    let mut played = true;
+    for samples in samples.chunks(chunk) {
+        tx.send(TaggedAudio { samples: samples.to_vec(), source: AudioSource::Microphone });
+        if !wait(CHUNK_MS).await {
+            played = false;
+            break;
+        }
+    }
+
+    // Whatever is still buffered gets transcribed; a loop we started is stopped
+    if !gemini.flush_audio_loop(owns_loop, Duration::from_secs(SIMULATION_FLUSH_TIMEOUT_SECS)).await {
+        warn!("[SIM] Audio loop didn't finish flushing in time");
+    }
+    if played { Ok(()) } else { Err("Simulation stopped".to_string()) }
+}
+
+/// Clears the active flag however the run ends
+struct ActiveGuard;
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        ACTIVE.store(false, Ordering::SeqCst);
+        CANCEL.store(false, Ordering::SeqCst);
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SimulationInfo {
+    pub script_path: String,
+    // "audio" or "script"
+    pub mode: String,
+    pub lines: Option<usize>,
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Play an audio file or a transcript script through the pipeline with mock intelligence
+#[tauri::command]
+pub async fn start_simulation(app: AppHandle, script_path: String) -> Result<SimulationInfo, String> {
+    let path = PathBuf::from(&script_path);
+    if !path.is_file() {
+        return Err(format!("File not found: {}", script_path));
+    }
+    if *app.state::<AudioState>().is_recording.lock().unwrap() {
+        return Err("Stop audio capture before starting a simulation".to_string());
+    }
+    let script = if is_audio_file(&path) {
+        None
+    } else {
+        let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read script: {}", e))?;
+        Some(parse_script(&content)?)
+    };
+    if ACTIVE.swap(true, Ordering::SeqCst) {
+        return Err("A simulation is already running".to_string());
+    }
+
+    let info = SimulationInfo {
+        script_path: script_path.clone(),
+        mode: if script.is_some() { "script" } else { "audio" }.to_string(),
+        lines: script.as_ref().map(Vec::len),
+    };
+    info!("[SIM] ▶ Simulating {} ({})", script_path, info.mode);
+    events::emit_status(&app, PipelineState::Listening, "Simulation running...");
+
+    tauri::async_runtime::spawn(async move {
+        let _active = ActiveGuard;
+        let result = match script {
+            Some(lines) => run_script(&app, lines).await,
+            None => run_audio(&app, path).await,
+        };
+        match result {
+            Ok(()) => {
+                info!("[SIM] ✓ Simulation finished: {}", script_path);
+                events::emit_status(&app, PipelineState::Ready, "Simulation finished");
+            }
+            Err(e) => {
+                warn!("[SIM] Simulation ended: {}", e);
+                events::emit_status(&app, PipelineState::Ready, format!("Simulation ended: {}", e));
+            }
+        }
+    });
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn stop_simulation() -> bool {
+    if !is_active() {
+        return false;
+    }
+    CANCEL.store(true, Ordering::SeqCst);
+    true
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info};
+use crate::action_items::ActionItemState;
+use crate::network::NetworkState;
+use crate::session_manager::{SessionData, SessionManager};
+use crate::settings::{redact, unredact, SettingsState};
+
+// ============================================================================
+// SLACK - Post Meeting Summaries & Action Items
+// ============================================================================
+
+const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+const MAX_BLOCK_TEXT: usize = 2900;  // Slack rejects section text over 3000 chars
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SlackConfig {
+    // Incoming webhook (channel is fixed by the webhook) ...
+    pub webhook_url: Option<String>,
+    // ... or a bot token (xoxb-...) with chat:write, posting to `channel`
+    pub bot_token: Option<String>,
+    pub channel: Option<String>,
+    // Post automatically once the meeting summary is generated
+    pub post_on_summary: bool,
+}
+
+impl SlackConfig {
+    fn redacted(&self) -> Self {
+        Self {
+            webhook_url: redact(self.webhook_url.as_deref()),
+            bot_token: redact(self.bot_token.as_deref()),
+            ..self.clone()
+        }
+    }
+
+    fn is_configured(&self) -> bool {
+        self.webhook_url.is_some() || self.bot_token.is_some()
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_BLOCK_TEXT {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_BLOCK_TEXT).collect();
+    format!("{}…", cut)
+}
+
+fn section(text: String) -> serde_json::Value {
+    serde_json::json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": truncate(&text) }
+    })
+}
+
+fn bullet_section(title: &str, items: &[String]) -> Option<serde_json::Value> {
+    if items.is_empty() { return None; }
+    let lines: Vec<String> = items.iter().map(|i| format!("• {}", i)).collect();
+    Some(section(format!("*{}*\n{}", title, lines.join("\n"))))
+}
+
+/// Summary + action items as Block Kit blocks
+fn build_blocks(session: &SessionData, action_items: &[String]) -> Vec<serde_json::Value> {
+    let mut blocks = vec![serde_json::json!({
+        "type": "header",
+        "text": {
+            "type": "plain_text",
+            "text": format!("📝 {}", session.metadata.title.chars().take(140).collect::<String>())
+        }
+    })];
+
+    if let Some(summary) = &session.summary {
+        if !summary.executive_summary.is_empty() {
+            blocks.push(section(summary.executive_summary.clone()));
+        }
+        blocks.extend(bullet_section("Key decisions", &summary.key_decisions));
+    }
+    blocks.extend(bullet_section("Action items", action_items));
+    if let Some(summary) = &session.summary {
+        blocks.extend(bullet_section("Risks", &summary.risks_identified));
+        blocks.extend(bullet_section("Open questions", &summary.open_questions));
+    }
+
+    blocks.push(serde_json::json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!("{} min · {} segments · Cognivox",
+                            session.metadata.duration_seconds / 60, session.transcripts.len())
+        }]
+    }));
+    blocks
+}
+
+/// Live-tracked items carry assignee/due date; fall back to the summary's list
+fn collect_action_items(app: &AppHandle, session: &SessionData) -> Vec<String> {
+    let describe = |desc: &str, who: Option<&str>, due: Option<&str>| {
+        let mut line = desc.to_string();
+        if let Some(who) = who { line.push_str(&format!(" — _{}_", who)); }
+        if let Some(due) = due { line.push_str(&format!(" (due {})", due)); }
+        line
+    };
+
+    if let Some(items) = app.state::<ActionItemState>().get(&session.id).filter(|i| !i.is_empty()) {
+        return items.iter()
+            .map(|i| describe(&i.description, i.assignee.as_deref(), i.due_date.as_deref()))
+            .collect();
+    }
+    session.summary.as_ref()
+        .map(|s| s.action_items.iter()
+            .map(|i| describe(&i.description, i.assignee.as_deref(), i.deadline.as_deref()))
+            .collect())
+        .unwrap_or_default()
+}
+
+async fn post_blocks(
+    client: &reqwest::Client,
+    config: &SlackConfig,
+    channel: Option<String>,
+    text: String,
+    blocks: Vec<serde_json::Value>,
+) -> Result<(), String> {
+    if let Some(token) = &config.bot_token {
+        let channel = channel.or_else(|| config.channel.clone())
+            .ok_or("No Slack channel configured")?;
+        let resp: serde_json::Value = client.post(SLACK_POST_MESSAGE_URL)
+            .bearer_auth(token)
+            .json(&serde_json::json!({ "channel": channel, "text": text, "blocks": blocks }))
+            .send()
+            .await
+            .map_err(|e| format!("Slack request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Slack response: {}", e))?;
+
+        // The Web API answers 200 with ok=false on errors
+        if resp["ok"].as_bool() != Some(true) {
+            return Err(format!("Slack error: {}", resp["error"].as_str().unwrap_or("unknown")));
+        }
+        return Ok(());
+    }
+
+    let url = config.webhook_url.as_ref().ok_or("Slack is not configured")?;
+    let resp = client.post(url)
+        .json(&serde_json::json!({ "text": text, "blocks": blocks }))
+        .send()
+        .await
+        .map_err(|e| format!("Slack request failed: {}", e))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Slack webhook error {}: {}", status, body));
+    }
+    Ok(())
+}
+
+pub async fn post_session(app: &AppHandle, session_id: &str, channel: Option<String>) -> Result<String, String> {
+    let config = app.state::<SettingsState>().get().slack;
+    if !config.is_configured() {
+        return Err("Slack is not configured".to_string());
+    }
+
+    let session = SessionManager::new()?.load_session(session_id)?;
+    let action_items = collect_action_items(app, &session);
+    let blocks = build_blocks(&session, &action_items);
+    let text = format!("Meeting notes: {}", session.metadata.title);  // Notification fallback
+
+    let client = app.state::<NetworkState>().client()?;
+    post_blocks(&client, &config, channel, text, blocks).await?;
+
+    info!("[SLACK] ✓ Posted session {}", session_id);
+    Ok("Posted to Slack".to_string())
+}
+
+/// Called after a summary is stored; posts if auto-posting is on
+pub fn post_after_summary(app: &AppHandle, session_id: &str) {
+    if !app.state::<SettingsState>().get().slack.post_on_summary { return; }
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = post_session(&app, &session_id, None).await {
+            error!("[SLACK] ✗ Auto-post failed: {}", e);
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_slack_config(settings: tauri::State<'_, SettingsState>) -> SlackConfig {
+    settings.get().slack.redacted()
+}
+
+#[tauri::command]
+pub fn set_slack_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: SlackConfig,
+) -> Result<String, String> {
+    let stored = settings.get().slack;
+    let config = SlackConfig {
+        webhook_url: unredact(config.webhook_url, stored.webhook_url),
+        bot_token: unredact(config.bot_token, stored.bot_token),
+        channel: config.channel.filter(|c| !c.trim().is_empty()),
+        post_on_summary: config.post_on_summary,
+    };
+    if config.bot_token.is_some() && config.channel.is_none() {
+        return Err("A channel is required when using a bot token".to_string());
+    }
+
+    settings.update(|s| s.slack = config)?;
+    Ok("Slack settings saved".to_string())
+}
+
+#[tauri::command]
+pub async fn post_to_slack(
+    app: AppHandle,
+    session_id: String,
+    channel: Option<String>,
+) -> Result<String, String> {
+    post_session(&app, &session_id, channel).await
+}
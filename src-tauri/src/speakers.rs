@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use rustfft::{FftPlanner, num_complex::Complex};
+use tracing::info;
+use crate::audio_capture::TARGET_SAMPLE_RATE;
+use crate::settings::app_data_dir;
+
+// ============================================================================
+// SPEAKERS - Voice Enrollment & Recognition
+// ============================================================================
+//
+// A voiceprint is the mean and spread of MFCCs over the voiced frames of a
+// sample. It's lightweight (no model download) and good enough to tell a
+// handful of enrolled teammates apart; unknown voices keep their default label.
+
+const FRAME_LEN: usize = 400;          // 25 ms @ 16 kHz
+const FRAME_HOP: usize = 160;          // 10 ms
+const FFT_SIZE: usize = 512;
+const MEL_BANDS: usize = 26;
+const MFCC_COUNT: usize = 13;          // c0 (loudness) is dropped from the print
+const MIN_ENROLL_SECS: f32 = 3.0;
+const MIN_MATCH_SECS: f32 = 1.0;
+const MATCH_THRESHOLD: f32 = 0.82;     // Cosine similarity needed to name a speaker
+const VOICED_ENERGY_RATIO: f32 = 0.1;  // Frames quieter than 10% of the mean are skipped
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoiceProfile {
+    pub id: String,
+    pub name: String,
+    pub embedding: Vec<f32>,
+    pub sample_secs: f32,
+    pub enrolled_at: String,
+}
+
+pub struct SpeakerState {
+    profiles: StdMutex<Vec<VoiceProfile>>,
+    // Audio of the most recent pipeline segment, so "that was Anila" can enroll it
+    last_segment: StdMutex<Option<Vec<f32>>>,
+}
+
+impl SpeakerState {
+    pub fn load() -> Self {
+        let profiles = profiles_path()
+            .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+            .and_then(|json| serde_json::from_str::<Vec<VoiceProfile>>(&json).map_err(|e| e.to_string()))
+            .unwrap_or_default();
+
+        if !profiles.is_empty() {
+            info!("[SPEAKERS] Loaded {} voice profile(s)", profiles.len());
+        }
+        Self { profiles: StdMutex::new(profiles), last_segment: StdMutex::new(None) }
+    }
+
+    pub fn remember_segment(&self, samples: &[f32]) {
+        *self.last_segment.lock().unwrap() = Some(samples.to_vec());
+    }
+
+    /// Best enrolled match for a segment, if any is close enough
+    pub fn identify(&self, samples: &[f32]) -> Option<(String, f32)> {
+        let profiles = self.profiles.lock().unwrap();
+        if profiles.is_empty() || (samples.len() as f32 / TARGET_SAMPLE_RATE as f32) < MIN_MATCH_SECS {
+            return None;
+        }
+        let embedding = voice_embedding(samples)?;
+
+        profiles.iter()
+            .map(|p| (p.name.clone(), cosine(&embedding, &p.embedding)))
+            .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+fn profiles_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join("speakers.json"))
+}
+
+fn persist(profiles: &[VoiceProfile]) -> Result<(), String> {
+    let path = profiles_path()?;
+    let json = serde_json::to_string_pretty(profiles).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, json).map_err(|e| format!("Failed to write speaker profiles: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to commit speaker profiles: {}", e))
+}
+
+// ============================================================================
+// Feature Extraction
+// ============================================================================
+
+fn hz_to_mel(hz: f32) -> f32 { 2595.0 * (1.0 + hz / 700.0).log10() }
+fn mel_to_hz(mel: f32) -> f32 { 700.0 * (10f32.powf(mel / 2595.0) - 1.0) }
+
+/// Triangular mel filters over the FFT bins
+fn mel_filterbank() -> Vec<Vec<f32>> {
+    let bins = FFT_SIZE / 2 + 1;
+    let max_mel = hz_to_mel(TARGET_SAMPLE_RATE as f32 / 2.0);
+    let points: Vec<usize> = (0..MEL_BANDS + 2)
+        .map(|i| mel_to_hz(max_mel * i as f32 / (MEL_BANDS + 1) as f32))
+        .map(|hz| ((FFT_SIZE + 1) as f32 * hz / TARGET_SAMPLE_RATE as f32) as usize)
+        .collect();
+
+    (0..MEL_BANDS).map(|m| {
+        let (left, center, right) = (points[m], points[m + 1], points[m + 2]);
+        (0..bins).map(|k| {
+            if k >= left && k < center && center > left {
+                (k - left) as f32 / (center - left) as f32
+            } else if k >= center && k <= right && right > center {
+                (right - k) as f32 / (right - center) as f32
+            } else {
+                0.0
+            }
+        }).collect()
+    }).collect()
+}
+
+/// MFCC mean + standard deviation over voiced frames, L2-normalized
+pub fn voice_embedding(samples: &[f32]) -> Option<Vec<f32>> {
+    if samples.len() < FRAME_LEN { return None; }
+
+    let filters = mel_filterbank();
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos())
+        .collect();
+
+    let mut frames: Vec<(f32, Vec<f32>)> = Vec::new();
+    let mut buf = vec![Complex::new(0.0f32, 0.0); FFT_SIZE];
+    for start in (0..=samples.len() - FRAME_LEN).step_by(FRAME_HOP) {
+        let frame = &samples[start..start + FRAME_LEN];
+        let energy = frame.iter().map(|s| s * s).sum::<f32>() / FRAME_LEN as f32;
+
+        for (i, c) in buf.iter_mut().enumerate() {
+            *c = Complex::new(if i < FRAME_LEN { frame[i] * window[i] } else { 0.0 }, 0.0);
+        }
+        fft.process(&mut buf);
+        let power: Vec<f32> = buf[..FFT_SIZE / 2 + 1].iter().map(|c| c.norm_sqr()).collect();
+
+        let log_mel: Vec<f32> = filters.iter()
+            .map(|f| f.iter().zip(&power).map(|(w, p)| w * p).sum::<f32>().max(1e-10).ln())
+            .collect();
+
+        // DCT-II of the log mel energies
+        let mfcc: Vec<f32> = (1..MFCC_COUNT).map(|n| {
+            log_mel.iter().enumerate()
+                .map(|(m, v)| v * (std::f32::consts::PI * n as f32 * (m as f32 + 0.5) / MEL_BANDS as f32).cos())
+                .sum()
+        }).collect();
+        frames.push((energy, mfcc));
+    }
+
+    let mean_energy = frames.iter().map(|(e, _)| e).sum::<f32>() / frames.len().max(1) as f32;
+    let voiced: Vec<&Vec<f32>> = frames.iter()
+        .filter(|(e, _)| *e >= mean_energy * VOICED_ENERGY_RATIO)
+        .map(|(_, m)| m)
+        .collect();
+    if voiced.len() < 10 { return None; }
+
+    let dims = MFCC_COUNT - 1;
+    let n = voiced.len() as f32;
+    let mean: Vec<f32> = (0..dims).map(|d| voiced.iter().map(|m| m[d]).sum::<f32>() / n).collect();
+    let std: Vec<f32> = (0..dims).map(|d| {
+        (voiced.iter().map(|m| (m[d] - mean[d]).powi(2)).sum::<f32>() / n).sqrt()
+    }).collect();
+
+    let mut embedding: Vec<f32> = mean.into_iter().chain(std).collect();
+    let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|v| *v /= norm);
+    }
+    Some(embedding)
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() { return 0.0; }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()  // Both are unit vectors
+}
+
+/// Read a WAV file as 16 kHz mono
+fn read_wav(path: &str) -> Result<Vec<f32>, String> {
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV: {}", e))?;
+    let spec = reader.spec();
+    let raw: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(Result::ok).collect(),
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader.samples::<i32>().filter_map(Result::ok).map(|s| s as f32 / scale).collect()
+        }
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono: Vec<f32> = raw.chunks(channels).map(|c| c.iter().sum::<f32>() / channels as f32).collect();
+    if spec.sample_rate == TARGET_SAMPLE_RATE {
+        return Ok(mono);
+    }
+
+    let step = spec.sample_rate as f64 / TARGET_SAMPLE_RATE as f64;
+    let out_len = (mono.len() as f64 / step) as usize;
+    Ok((0..out_len).map(|i| {
+        let pos = i as f64 * step;
+        let idx = pos as usize;
+        let frac = (pos - idx as f64) as f32;
+        let next = mono.get(idx + 1).copied().unwrap_or(mono[idx]);
+        mono[idx] + (next - mono[idx]) * frac
+    }).collect())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_speakers(state: tauri::State<'_, SpeakerState>) -> Vec<VoiceProfile> {
+    state.profiles.lock().unwrap().clone()
+}
+
+/// Enroll from a WAV file, or from the last segment the pipeline heard when
+/// no path is given. Re-enrolling a name replaces its profile.
+#[tauri::command]
+pub fn enroll_speaker(
+    state: tauri::State<'_, SpeakerState>,
+    name: String,
+    wav_path: Option<String>,
+) -> Result<VoiceProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Speaker name is required".to_string());
+    }
+
+    let samples = match wav_path {
+        Some(path) => read_wav(&path)?,
+        None => state.last_segment.lock().unwrap().clone()
+            .ok_or("No recent speech to enroll from")?,
+    };
+    let sample_secs = samples.len() as f32 / TARGET_SAMPLE_RATE as f32;
+    if sample_secs < MIN_ENROLL_SECS {
+        return Err(format!("Need at least {:.0}s of speech, got {:.1}s", MIN_ENROLL_SECS, sample_secs));
+    }
+    let embedding = voice_embedding(&samples).ok_or("Sample has too little voiced audio")?;
+
+    let profile = VoiceProfile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.clone(),
+        embedding,
+        sample_secs,
+        enrolled_at: Utc::now().to_rfc3339(),
+    };
+
+    let mut profiles = state.profiles.lock().unwrap();
+    profiles.retain(|p| !p.name.eq_ignore_ascii_case(&name));
+    profiles.push(profile.clone());
+    persist(&profiles)?;
+
+    info!("[SPEAKERS] ✓ Enrolled '{}' ({:.1}s sample)", name, sample_secs);
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn remove_speaker(state: tauri::State<'_, SpeakerState>, id: String) -> Result<(), String> {
+    let mut profiles = state.profiles.lock().unwrap();
+    let before = profiles.len();
+    profiles.retain(|p| p.id != id);
+    if profiles.len() == before {
+        return Err("Speaker not found".to_string());
+    }
+    persist(&profiles)
+}
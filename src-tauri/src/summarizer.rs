@@ -0,0 +1,421 @@
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tauri::{AppHandle, Manager};
+use chrono::Utc;
+use tracing::{info, warn};
+use crate::events::{self, MeetingSummaryEvent, PipelineState, SummaryProgressEvent, SummaryStage};
+use crate::gemini_client::{GeminiState, RequestConfig, call_gemini, extract_json};
+use crate::slack;
+use crate::vault;
+use crate::session_manager::{SessionData, SessionManager, SessionSummary, SummaryCheckpoint, ActionItem, dispatch_webhook};
+
+// ============================================================================
+// MEETING SUMMARIZER - Map-Reduce Summary over a Whole Session
+// ============================================================================
+
+// Rough estimate, good enough for budgeting requests
+const CHARS_PER_TOKEN: usize = 4;
+// Per request, for map chunks and for each group of partials in a reduce
+// round; keeps every request well inside the context window
+const CHUNK_TOKEN_BUDGET: usize = 3_000;
+
+const MAP_PROMPT: &str = r#"You are summarizing ONE PART of a longer meeting transcript.
+
+INPUT: Transcript lines formatted as "[Speaker]: text".
+OUTPUT: JSON only, no markdown.
+
+FORMAT:
+{"summary":"3-5 sentence summary of this part","decisions":["..."],"action_items":[{"description":"...","assignee":"name or null","deadline":"date or null","priority":"HIGH|MEDIUM|LOW"}],"open_questions":["..."],"risks":["..."]}
+
+RULES:
+- Only include what is actually said in this part
+- Keep speaker names exactly as given
+- Use empty arrays when nothing applies"#;
+
+const REDUCE_PROMPT: &str = r#"You are combining partial summaries of ONE meeting into a final meeting summary.
+
+INPUT: JSON partial summaries, in chronological order.
+OUTPUT: JSON only, no markdown.
+
+FORMAT:
+{"executive_summary":"concise overview of the whole meeting","decisions":["..."],"action_items":[{"description":"...","assignee":"name or null","deadline":"date or null","priority":"HIGH|MEDIUM|LOW"}],"open_questions":["..."],"risks":["..."],"next_steps":["..."]}
+
+RULES:
+- Merge duplicates across parts; keep the latest wording
+- Drop open questions that a later part answered
+- Use empty arrays when nothing applies"#;
+
+#[derive(Deserialize, Default)]
+struct SummaryResponse {
+    #[serde(default, alias = "summary")]
+    executive_summary: String,
+    #[serde(default)]
+    decisions: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<SummaryActionItem>,
+    #[serde(default)]
+    open_questions: Vec<String>,
+    #[serde(default)]
+    risks: Vec<String>,
+    #[serde(default)]
+    next_steps: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SummaryActionItem {
+    description: String,
+    assignee: Option<String>,
+    deadline: Option<String>,
+    priority: Option<String>,
+}
+
+impl From<SummaryResponse> for SessionSummary {
+    fn from(r: SummaryResponse) -> Self {
+        SessionSummary {
+            executive_summary: r.executive_summary,
+            key_decisions: r.decisions,
+            action_items: r.action_items.into_iter().map(|a| ActionItem {
+                description: a.description,
+                assignee: a.assignee,
+                deadline: a.deadline,
+                priority: a.priority.unwrap_or_else(|| "MEDIUM".to_string()),
+            }).collect(),
+            risks_identified: r.risks,
+            next_steps: r.next_steps,
+            open_questions: r.open_questions,
+            generated_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+const SUMMARY_CANCELLED: &str = "Summary cancelled";
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+static CANCEL_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Counts a summary as running for as long as it's alive
+struct RunningGuard;
+
+impl RunningGuard {
+    fn start() -> Self {
+        RUNNING.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        if RUNNING.fetch_sub(1, Ordering::SeqCst) == 1 {
+            CANCEL_REQUESTED.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Stop a running summary before its next model request. False if none is running.
+pub fn request_cancel() -> bool {
+    if RUNNING.load(Ordering::SeqCst) == 0 {
+        return false;
+    }
+    CANCEL_REQUESTED.store(true, Ordering::SeqCst);
+    true
+}
+
+fn check_cancelled() -> Result<(), String> {
+    if CANCEL_REQUESTED.swap(false, Ordering::SeqCst) {
+        return Err(SUMMARY_CANCELLED.to_string());
+    }
+    Ok(())
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// "[Speaker]: text" lines, splitting a monologue that alone exceeds the budget
+fn speaker_lines(speaker: &str, text: &str) -> Vec<String> {
+    let max_chars = (CHUNK_TOKEN_BUDGET * CHARS_PER_TOKEN).saturating_sub(speaker.len() + 5).max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > max_chars {
+            lines.push(format!("[{}]: {}\n", speaker, std::mem::take(&mut current)));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(format!("[{}]: {}\n", speaker, current));
+    }
+    lines
+}
+
+/// Split the session transcript into chunks that fit the per-request token budget
+fn chunk_transcript(session: &SessionData) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for t in &session.transcripts {
+        for line in speaker_lines(&t.speaker_id, t.text.trim()) {
+            if !current.is_empty() && estimate_tokens(&current) + estimate_tokens(&line) > CHUNK_TOKEN_BUDGET {
+                chunks.push(std::mem::take(&mut current));
+            }
+            current.push_str(&line);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Group partial summaries for one reduce round. Every group but the last
+/// holds at least two, so each round shrinks the list.
+fn group_partials(partials: &[String]) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut tokens = 0;
+
+    for partial in partials {
+        let t = estimate_tokens(partial);
+        match groups.last_mut() {
+            Some(group) if group.len() < 2 || tokens + t <= CHUNK_TOKEN_BUDGET => {
+                group.push(partial.clone());
+                tokens += t;
+            }
+            _ => {
+                groups.push(vec![partial.clone()]);
+                tokens = t;
+            }
+        }
+    }
+    groups
+}
+
+fn reduce_input(group: &[String]) -> String {
+    group.iter()
+        .enumerate()
+        .map(|(i, p)| format!("PART {}:\n{}", i + 1, p))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn checkpoint_key(prompt: &str, input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prompt.as_bytes());
+    hasher.update([0]);
+    hasher.update(input.as_bytes());
+    hex::encode(&hasher.finalize()[..12])
+}
+
+async fn request_json(
+    config: &RequestConfig,
+    system_prompt: &str,
+    user_text: &str,
+) -> Result<String, String> {
+    let text = call_gemini(config, system_prompt, user_text)
+        .await?
+        .ok_or("Empty response from model")?;
+    Ok(extract_json(&text).to_string())
+}
+
+/// Model requests of one summary run. Each result is checkpointed into the
+/// session, so a run that fails part-way resumes where it stopped. Without an
+/// app (CLI) nothing is stored or emitted.
+struct SummaryRun<'a> {
+    app: Option<&'a AppHandle>,
+    config: RequestConfig,
+    manager: SessionManager,
+    session_id: String,
+    checkpoint: SummaryCheckpoint,
+    resumed: usize,
+}
+
+impl SummaryRun<'_> {
+    async fn request(&mut self, prompt: &str, input: &str) -> Result<String, String> {
+        let key = checkpoint_key(prompt, input);
+        if let Some(json) = self.checkpoint.partials.get(&key) {
+            self.resumed += 1;
+            return Ok(json.clone());
+        }
+
+        if self.app.is_some() {
+            check_cancelled()?;
+        }
+        let json = match request_json(&self.config, prompt, input).await {
+            Ok(json) => json,
+            Err(e) if self.checkpoint.partials.is_empty() || self.app.is_none() => return Err(e),
+            Err(e) => return Err(format!("{} (progress saved - summarize again to resume)", e)),
+        };
+        self.checkpoint.partials.insert(key, json.clone());
+        self.save_checkpoint();
+        Ok(json)
+    }
+
+    fn save_checkpoint(&mut self) {
+        if self.app.is_none() {
+            return;
+        }
+        self.checkpoint.updated_at = Utc::now().to_rfc3339();
+        let checkpoint = self.checkpoint.clone();
+        let result = self.manager.update_session(&self.session_id, |session| {
+            session.summary_checkpoint = Some(checkpoint);
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!("[SUMMARY] Checkpoint not saved: {}", e);
+        }
+    }
+
+    fn status(&self, state: PipelineState, message: impl Into<String>) {
+        if let Some(app) = self.app {
+            events::emit_status(app, state, message);
+        }
+    }
+
+    fn progress(&self, stage: SummaryStage, level: usize, completed: usize, total: usize) {
+        let Some(app) = self.app else { return; };
+        events::emit(app, &SummaryProgressEvent {
+            session_id: self.session_id.clone(),
+            stage,
+            level,
+            completed,
+            total,
+            resumed: self.resumed,
+        });
+    }
+
+    /// Map each chunk, then reduce partials in rounds until one summary is left
+    async fn map_reduce(&mut self, chunks: &[String]) -> Result<SessionSummary, String> {
+        // Map: summarize each chunk independently
+        let mut partials = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            info!("[SUMMARY] Map {}/{}", i + 1, chunks.len());
+            self.status(PipelineState::Summarizing, format!("Summarizing part {}/{}...", i + 1, chunks.len()));
+            let prompt = if chunks.len() == 1 { REDUCE_PROMPT } else { MAP_PROMPT };
+            partials.push(self.request(prompt, chunk).await?);
+            self.progress(SummaryStage::Map, 0, i + 1, chunks.len());
+        }
+
+        // Reduce: merge partial summaries in rounds until one is left
+        // (a single chunk was already reduced)
+        let mut level = 0;
+        while partials.len() > 1 {
+            level += 1;
+            let groups = group_partials(&partials);
+            info!("[SUMMARY] Reduce round {}: {} partial summaries into {}", level, partials.len(), groups.len());
+            self.status(PipelineState::Summarizing, if groups.len() == 1 {
+                "Combining summaries...".to_string()
+            } else {
+                format!("Combining summaries (round {})...", level)
+            });
+
+            let mut merged = Vec::with_capacity(groups.len());
+            for (i, group) in groups.iter().enumerate() {
+                merged.push(match group.as_slice() {
+                    [only] => only.clone(),
+                    _ => self.request(REDUCE_PROMPT, &reduce_input(group)).await?,
+                });
+                self.progress(SummaryStage::Reduce, level, i + 1, groups.len());
+            }
+            partials = merged;
+        }
+        let final_json = partials.remove(0);
+
+        let response: SummaryResponse = serde_json::from_str(&final_json)
+            .map_err(|e| format!("Invalid summary JSON: {}", e))?;
+        Ok(SessionSummary::from(response))
+    }
+
+    /// Map-reduce, then store the summary in place of the checkpoint
+    async fn summarize_and_store(&mut self, chunks: &[String]) -> Result<SessionSummary, String> {
+        let summary = self.map_reduce(chunks).await?;
+        self.manager.update_session(&self.session_id, |session| {
+            session.summary = Some(summary.clone());
+            session.summary_checkpoint = None;
+            Ok(())
+        })?;
+        Ok(summary)
+    }
+}
+
+/// Map-reduce summary of a stored session; persists it, notifies and returns the JSON
+pub async fn summarize_session(app: AppHandle, session_id: String) -> Result<String, String> {
+    let config = app.state::<GeminiState>().request_config(&app)?;
+
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(&session_id)?;
+
+    let chunks = chunk_transcript(&session);
+    if chunks.is_empty() {
+        return Err("Session has no transcripts to summarize".to_string());
+    }
+
+    let _running = RunningGuard::start();
+    let mut run = SummaryRun {
+        app: Some(&app),
+        config,
+        manager,
+        session_id: session_id.clone(),
+        checkpoint: session.summary_checkpoint.unwrap_or_default(),
+        resumed: 0,
+    };
+    if !run.checkpoint.partials.is_empty() {
+        info!("[SUMMARY] Resuming with {} saved partial summaries", run.checkpoint.partials.len());
+    }
+    info!("[SUMMARY] Summarizing session {} in {} chunk(s)", session_id, chunks.len());
+    events::emit_status(&app, PipelineState::Summarizing, "Generating meeting summary...");
+    // Whatever happens, don't leave the status stuck on Summarizing
+    let summary = match run.summarize_and_store(&chunks).await {
+        Ok(summary) => summary,
+        Err(e) if e == SUMMARY_CANCELLED => {
+            events::emit_status(&app, PipelineState::Ready, SUMMARY_CANCELLED);
+            return Err(e);
+        }
+        Err(e) => {
+            warn!("[SUMMARY] Failed: {}", e);
+            events::emit_status(&app, PipelineState::Error, format!("Summary failed: {}", e));
+            return Err(e);
+        }
+    };
+
+    info!("[SUMMARY] ✓ Summary stored for session {}", session_id);
+    let event = MeetingSummaryEvent { session_id: session_id.clone(), summary: summary.clone() };
+    events::emit(&app, &event);
+    dispatch_webhook(&app, "meeting_summary", &events::to_payload(&event));
+    slack::post_after_summary(&app, &session_id);
+    vault::export_after_summary(&app, &session_id);
+    events::emit_status(&app, PipelineState::Ready, "Summary ready ✓");
+
+    serde_json::to_string(&summary)
+        .map_err(|e| format!("Failed to serialize summary: {}", e))
+}
+
+/// Same map-reduce for a session that isn't stored (CLI); nothing is saved
+pub async fn summarize_unsaved(config: RequestConfig, session: &SessionData) -> Result<SessionSummary, String> {
+    let chunks = chunk_transcript(session);
+    if chunks.is_empty() {
+        return Err("Nothing was transcribed to summarize".to_string());
+    }
+    info!("[SUMMARY] Summarizing in {} chunk(s)", chunks.len());
+    let mut run = SummaryRun {
+        app: None,
+        config,
+        manager: SessionManager::new()?,
+        session_id: session.id.clone(),
+        checkpoint: SummaryCheckpoint::default(),
+        resumed: 0,
+    };
+    run.map_reduce(&chunks).await
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn generate_meeting_summary(app: AppHandle, session_id: String) -> Result<String, String> {
+    summarize_session(app, session_id).await
+}
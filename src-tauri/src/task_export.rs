@@ -0,0 +1,314 @@
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, Utc, Weekday};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+use crate::action_items::{items_from_session, ActionItemState};
+use crate::session_manager::{SessionData, SessionManager};
+
+// ============================================================================
+// TASK EXPORT - Action Items & Deadlines as ICS / todo.txt
+// ============================================================================
+//
+// Spoken due dates ("Friday", "March 3rd", "end of the month") are resolved
+// against the meeting date. Items whose date can't be resolved keep the
+// phrase in their description and are exported without a due date.
+
+const EXPORTED_CATEGORIES: &[&str] = &["DEADLINE", "ACTION_ITEM"];
+const ICS_LINE_OCTETS: usize = 75;
+
+struct ExportTask {
+    uid: String,
+    description: String,
+    assignee: Option<String>,
+    due_text: Option<String>,
+    due: Option<NaiveDate>,
+    deadline: bool,
+}
+
+// ============================================================================
+// Spoken Date Resolution
+// ============================================================================
+
+fn weekday(word: &str) -> Option<Weekday> {
+    Some(match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    if word.len() < 3 { return None; }
+    MONTHS.iter().position(|m| word.starts_with(m)).map(|i| i as u32 + 1)
+}
+
+/// "3rd" -> 3
+fn day_of_month(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+/// First `day` strictly after `from`
+fn next_weekday(from: NaiveDate, day: Weekday) -> NaiveDate {
+    let ahead = (day.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(if ahead == 0 { 7 } else { ahead as i64 })
+}
+
+fn end_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?
+        .checked_add_months(Months::new(1))?
+        .pred_opt()
+}
+
+/// Resolve a spoken/written due date relative to the meeting day
+fn resolve_date(text: &str, meeting: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim();
+    for format in ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return Some(date);
+        }
+    }
+
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !matches!(*w, "the" | "of" | "by" | "on" | "this" | "due" | "until"))
+        .collect();
+
+    match words.as_slice() {
+        ["today"] | ["tonight"] | ["eod"] => return Some(meeting),
+        ["tomorrow"] => return Some(meeting + Duration::days(1)),
+        ["end", "week"] | ["eow"] => {
+            let friday = next_weekday(meeting - Duration::days(1), Weekday::Fri);
+            return Some(friday);
+        }
+        ["next", "week"] => return Some(next_weekday(meeting, Weekday::Mon)),
+        ["end", "month"] | ["eom"] => return end_of_month(meeting),
+        ["in", n, unit] => {
+            let n: i64 = n.parse().ok()?;
+            return match unit.trim_end_matches('s') {
+                "day" => Some(meeting + Duration::days(n)),
+                "week" => Some(meeting + Duration::weeks(n)),
+                _ => None,
+            };
+        }
+        ["next", day] => {
+            // "next Friday" said on a Monday usually means the following week's
+            let day = weekday(day)?;
+            let upcoming = next_weekday(meeting, day);
+            let same_week = upcoming.iso_week() == meeting.iso_week();
+            return Some(if same_week { upcoming + Duration::weeks(1) } else { upcoming });
+        }
+        [day] if weekday(day).is_some() => return Some(next_weekday(meeting, weekday(day)?)),
+        _ => {}
+    }
+
+    // "March 3rd", "3 March", optionally with a year
+    let (m, d) = match words.as_slice() {
+        [a, b, ..] if month(a).is_some() => (month(a)?, day_of_month(b)?),
+        [a, b, ..] if month(b).is_some() => (month(b)?, day_of_month(a)?),
+        _ => return None,
+    };
+    let year = words.get(2).and_then(|y| y.parse::<i32>().ok()).filter(|y| *y > 1900);
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, m, d),
+        None => {
+            // A date already past in the meeting's year means next year
+            let date = NaiveDate::from_ymd_opt(meeting.year(), m, d)?;
+            if date < meeting { NaiveDate::from_ymd_opt(meeting.year() + 1, m, d) } else { Some(date) }
+        }
+    }
+}
+
+fn meeting_date(session: &SessionData) -> NaiveDate {
+    let start = session.calendar_event.as_ref().map(|e| e.start.as_str()).unwrap_or(&session.created_at);
+    DateTime::parse_from_rfc3339(start)
+        .map(|d| d.with_timezone(&Local).date_naive())
+        .unwrap_or_else(|_| Local::now().date_naive())
+}
+
+/// Tracked DEADLINE/ACTION_ITEM items, or the summary's action items if none were tracked
+fn collect_tasks(app: &AppHandle, session: &SessionData) -> Vec<ExportTask> {
+    let meeting = meeting_date(session);
+    let tracked = app.state::<ActionItemState>().get(&session.id)
+        .filter(|items| !items.is_empty())
+        .unwrap_or_else(|| items_from_session(session));
+
+    let mut tasks: Vec<ExportTask> = tracked.into_iter()
+        .filter(|i| i.categories.iter().any(|c| EXPORTED_CATEGORIES.contains(&c.as_str())))
+        .map(|i| ExportTask {
+            due: i.due_date.as_deref().and_then(|d| resolve_date(d, meeting)),
+            deadline: i.categories.iter().any(|c| c == "DEADLINE"),
+            uid: i.id,
+            description: i.description,
+            assignee: i.assignee,
+            due_text: i.due_date,
+        })
+        .collect();
+
+    if tasks.is_empty() {
+        if let Some(summary) = &session.summary {
+            tasks = summary.action_items.iter().enumerate()
+                .map(|(n, a)| ExportTask {
+                    uid: format!("{}-summary-{}", session.id, n),
+                    description: a.description.clone(),
+                    assignee: a.assignee.clone(),
+                    due_text: a.deadline.clone(),
+                    due: a.deadline.as_deref().and_then(|d| resolve_date(d, meeting)),
+                    deadline: a.deadline.is_some(),
+                })
+                .collect();
+        }
+    }
+    tasks
+}
+
+// ============================================================================
+// ICS
+// ============================================================================
+
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// Fold content lines at 75 octets without splitting UTF-8 characters
+fn ics_fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > ICS_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+fn task_notes(task: &ExportTask, session: &SessionData) -> String {
+    let mut notes = format!("From meeting \"{}\"", session.metadata.title);
+    if let Some(who) = &task.assignee { notes.push_str(&format!("\nAssignee: {}", who)); }
+    if let (Some(text), None) = (&task.due_text, task.due) { notes.push_str(&format!("\nDue: {}", text)); }
+    notes
+}
+
+/// VTODO per item, or with `events` an all-day VEVENT per dated item
+fn export_ics(tasks: &[ExportTask], session: &SessionData, events: bool) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Cognivox//Action Items//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for task in tasks {
+        if events && task.due.is_none() { continue; }
+        let component = if events { "VEVENT" } else { "VTODO" };
+        lines.push(format!("BEGIN:{}", component));
+        lines.push(format!("UID:{}@cognivox", task.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("SUMMARY:{}", ics_escape(&task.description)));
+        lines.push(format!("DESCRIPTION:{}", ics_escape(&task_notes(task, session))));
+        lines.push(format!("CATEGORIES:{}", if task.deadline { "DEADLINE" } else { "ACTION_ITEM" }));
+        match (events, task.due) {
+            (true, Some(due)) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", due.format("%Y%m%d")));
+                lines.push(format!("DTEND;VALUE=DATE:{}", (due + Duration::days(1)).format("%Y%m%d")));
+                lines.push("TRANSP:TRANSPARENT".to_string());
+            }
+            (false, due) => {
+                if let Some(due) = due {
+                    lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+                }
+                lines.push("STATUS:NEEDS-ACTION".to_string());
+                if task.deadline { lines.push("PRIORITY:1".to_string()); }
+            }
+            _ => {}
+        }
+        lines.push(format!("END:{}", component));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|l| ics_fold(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+// ============================================================================
+// todo.txt
+// ============================================================================
+
+/// Tags can't contain spaces: "Q3 Planning" -> "Q3_Planning"
+fn todo_tag(text: &str) -> String {
+    text.split_whitespace()
+        .map(|w| w.chars().filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_').collect::<String>())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn export_todo_txt(tasks: &[ExportTask], session: &SessionData) -> String {
+    let created = meeting_date(session).format("%Y-%m-%d").to_string();
+    let project = todo_tag(&session.metadata.title);
+    let mut out = String::new();
+
+    for task in tasks {
+        let mut line = String::new();
+        if task.deadline { line.push_str("(A) "); }
+        line.push_str(&created);
+        line.push(' ');
+        line.push_str(&task.description.replace('\n', " "));
+        if let (Some(text), None) = (&task.due_text, task.due) {
+            line.push_str(&format!(" (due {})", text));
+        }
+        if !project.is_empty() { line.push_str(&format!(" +{}", project)); }
+        if let Some(who) = task.assignee.as_deref().map(todo_tag).filter(|t| !t.is_empty()) {
+            line.push_str(&format!(" @{}", who));
+        }
+        if let Some(due) = task.due { line.push_str(&format!(" due:{}", due.format("%Y-%m-%d"))); }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Export a session's action items and deadlines. `format`: "ics" (VTODO
+/// tasks), "ics_events" (all-day calendar events for dated items) or "todo"
+/// (todo.txt). Returns the path of the written file.
+#[tauri::command]
+pub fn export_action_items(app: AppHandle, session_id: String, format: String) -> Result<String, String> {
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(&session_id)?;
+    let tasks = collect_tasks(&app, &session);
+    if tasks.is_empty() {
+        return Err("Session has no action items or deadlines".to_string());
+    }
+
+    let (extension, content) = match format.as_str() {
+        "ics" => ("tasks.ics", export_ics(&tasks, &session, false)),
+        "ics_events" => {
+            if tasks.iter().all(|t| t.due.is_none()) {
+                return Err("No action item has a due date that could be resolved".to_string());
+            }
+            ("deadlines.ics", export_ics(&tasks, &session, true))
+        }
+        "todo" | "todo.txt" => ("todo.txt", export_todo_txt(&tasks, &session)),
+        _ => return Err(format!("Unsupported task export format: {}", format)),
+    };
+
+    let path = manager.write_export(&session.id, extension, content)?;
+    info!("[EXPORT] ✓ {} action item(s) from {} exported as {}", tasks.len(), session.id, format);
+    Ok(path)
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::settings::{normalize_categories, SettingsState};
+
+// ============================================================================
+// MEETING TEMPLATES - Prompt and Categories per Meeting Type
+// ============================================================================
+//
+// Selecting a meeting type swaps in its category set and adds its guidance to
+// the intelligence prompt, so a sales call tracks OBJECTION/PRICING instead of
+// the general taxonomy. Templates live in the settings file and can be edited.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MeetingTemplate {
+    // Stable key for set_meeting_type, e.g. "sales_call"
+    pub id: String,
+    pub name: String,
+    // Appended to the system prompt while the template is active
+    pub guidance: String,
+    pub categories: Vec<String>,
+    // Replaces the base prompt entirely; `{categories}` is substituted as usual
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+fn template(id: &str, name: &str, guidance: &str, categories: &[&str]) -> MeetingTemplate {
+    MeetingTemplate {
+        id: id.to_string(),
+        name: name.to_string(),
+        guidance: guidance.to_string(),
+        categories: categories.iter().map(|c| c.to_string()).collect(),
+        prompt: None,
+    }
+}
+
+pub fn default_templates() -> Vec<MeetingTemplate> {
+    vec![
+        template(
+            "standup",
+            "Daily Standup",
+            "Participants report what they did, what they will do next and what blocks them. \
+             Tag progress reports as UPDATE, planned work as TASK and impediments as BLOCKER.",
+            &["UPDATE", "TASK", "BLOCKER", "DECISION", "DEADLINE", "QUERY", "OFF_TOPIC"],
+        ),
+        template(
+            "interview",
+            "Interview",
+            "One side is evaluating a candidate. Tag questions as QUERY, evidence of skills or \
+             experience as STRENGTH or CONCERN, and anything about compensation, notice period or \
+             availability as LOGISTICS.",
+            &["QUERY", "STRENGTH", "CONCERN", "EXPERIENCE", "CULTURE_FIT", "LOGISTICS", "NEXT_STEPS"],
+        ),
+        template(
+            "sales_call",
+            "Sales Call",
+            "A seller is talking to a prospect. Tag pushback and hesitation as OBJECTION, anything \
+             about cost, discounts or budget as PRICING, rival products as COMPETITOR and agreed \
+             follow-ups as NEXT_STEPS.",
+            &["OBJECTION", "PRICING", "NEXT_STEPS", "PAIN_POINT", "BUDGET", "DECISION_MAKER", "COMPETITOR", "TIMELINE", "AGREEMENT"],
+        ),
+        template(
+            "one_on_one",
+            "1:1",
+            "A manager and a report are meeting privately. Tag career and growth topics as GROWTH, \
+             praise or criticism as FEEDBACK and worries about workload or the team as CONCERN.",
+            &["FEEDBACK", "GROWTH", "CONCERN", "ACTION_ITEM", "DECISION", "SENTIMENT", "TASK"],
+        ),
+    ]
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct MeetingTemplates {
+    // None = general meeting, using the configured prompt and categories
+    pub active: Option<String>,
+    pub templates: Vec<MeetingTemplate>,
+}
+
+#[tauri::command]
+pub fn get_meeting_templates(settings: tauri::State<'_, SettingsState>) -> MeetingTemplates {
+    let settings = settings.get();
+    MeetingTemplates {
+        active: settings.meeting_type,
+        templates: settings.templates,
+    }
+}
+
+/// Switch the meeting type; None (or an empty id) goes back to the general setup
+#[tauri::command]
+pub fn set_meeting_type(
+    settings: tauri::State<'_, SettingsState>,
+    meeting_type: Option<String>,
+) -> Result<Option<MeetingTemplate>, String> {
+    let meeting_type = meeting_type.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    let current = settings.get();
+    let selected = match &meeting_type {
+        Some(id) => Some(current.templates.iter()
+            .find(|t| &t.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown meeting type '{}'", id))?),
+        None => None,
+    };
+
+    settings.update(|s| s.meeting_type = meeting_type)?;
+    info!("[SETTINGS] Meeting type: {}", selected.as_ref().map(|t| t.name.as_str()).unwrap_or("general"));
+    Ok(selected)
+}
+
+/// Add a template or replace the one with the same id
+#[tauri::command]
+pub fn save_meeting_template(
+    settings: tauri::State<'_, SettingsState>,
+    template: MeetingTemplate,
+) -> Result<Vec<MeetingTemplate>, String> {
+    let id = template.id.trim().to_lowercase().replace([' ', '-'], "_");
+    if id.is_empty() {
+        return Err("Template id is required".to_string());
+    }
+    let categories = normalize_categories(template.categories);
+    if categories.is_empty() {
+        return Err("At least one category is required".to_string());
+    }
+    let template = MeetingTemplate {
+        name: if template.name.trim().is_empty() { id.clone() } else { template.name.trim().to_string() },
+        id,
+        guidance: template.guidance.trim().to_string(),
+        categories,
+        prompt: template.prompt.filter(|p| !p.trim().is_empty()),
+    };
+
+    let settings = settings.update(|s| {
+        match s.templates.iter_mut().find(|t| t.id == template.id) {
+            Some(existing) => *existing = template.clone(),
+            None => s.templates.push(template.clone()),
+        }
+    })?;
+    info!("[SETTINGS] Saved meeting template '{}'", template.id);
+    Ok(settings.templates)
+}
+
+#[tauri::command]
+pub fn delete_meeting_template(
+    settings: tauri::State<'_, SettingsState>,
+    id: String,
+) -> Result<Vec<MeetingTemplate>, String> {
+    if !settings.get().templates.iter().any(|t| t.id == id) {
+        return Err(format!("Unknown meeting type '{}'", id));
+    }
+    let settings = settings.update(|s| {
+        s.templates.retain(|t| t.id != id);
+        if s.meeting_type.as_deref() == Some(id.as_str()) {
+            s.meeting_type = None;
+        }
+    })?;
+    info!("[SETTINGS] Deleted meeting template '{}'", id);
+    Ok(settings.templates)
+}
+
+/// Restore the built-in templates, dropping edits and custom ones
+#[tauri::command]
+pub fn reset_meeting_templates(settings: tauri::State<'_, SettingsState>) -> Result<Vec<MeetingTemplate>, String> {
+    let settings = settings.update(|s| {
+        s.templates = default_templates();
+        if !s.templates.iter().any(|t| Some(&t.id) == s.meeting_type.as_ref()) {
+            s.meeting_type = None;
+        }
+    })?;
+    Ok(settings.templates)
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+use crate::events::{self, SessionTitledEvent};
+use crate::gemini_client::{call_gemini, extract_json, GeminiState};
+use crate::live_session::LiveSessionState;
+use crate::session_manager::{SessionData, SessionManager};
+use crate::settings::SettingsState;
+
+// ============================================================================
+// TITLING - Descriptive Session Titles and Topic Tags
+// ============================================================================
+//
+// A live session is titled once after `after_minutes` and again when it
+// ends, with the whole transcript. Titles the user typed are never replaced
+// automatically; only sessions marked `default_title` (started without a
+// title) and earlier generated titles are. Generated tags are added to the
+// session's tags, never removed.
+
+const TITLE_PROMPT: &str = r#"You are naming ONE meeting from its transcript.
+
+INPUT: Transcript lines formatted as "[Speaker]: text", possibly cut short, optionally preceded by a summary.
+OUTPUT: JSON only, no markdown.
+
+FORMAT:
+{"title":"3-8 word descriptive title","tags":["topic", "..."]}
+
+RULES:
+- The title names what the meeting was about, not who attended or when
+- 2-5 tags, lowercase, 1-3 words each, most specific first
+- Write in the language of the transcript"#;
+
+const TRANSCRIPT_BUDGET_CHARS: usize = 8_000;
+// Too little to say what a meeting is about
+const MIN_SEGMENTS: usize = 5;
+const MAX_TAGS: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AutoTitleConfig {
+    pub enabled: bool,
+    // First title this far into a live session; it's refined when the session ends
+    pub after_minutes: u64,
+}
+
+impl Default for AutoTitleConfig {
+    fn default() -> Self {
+        Self { enabled: true, after_minutes: 3 }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct TitleResponse {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn title_input(session: &SessionData) -> String {
+    let mut text = String::new();
+    if let Some(summary) = session.summary.as_ref().filter(|s| !s.executive_summary.is_empty()) {
+        text.push_str(&format!("SUMMARY: {}\n\n", summary.executive_summary));
+    }
+    for t in &session.transcripts {
+        if text.len() >= TRANSCRIPT_BUDGET_CHARS { break; }
+        text.push_str(&format!("[{}]: {}\n", t.speaker_id, t.text.trim()));
+    }
+    text
+}
+
+/// Title and tag a stored session. Without `overwrite`, a title the user
+/// chose is kept and only tags are added.
+pub async fn generate(app: &AppHandle, session_id: &str, overwrite: bool) -> Result<SessionTitledEvent, String> {
+    let manager = SessionManager::new()?;
+    let session = manager.load_session(session_id)?;
+    if session.transcripts.len() < MIN_SEGMENTS {
+        return Err(format!("Session needs at least {} segments to be titled", MIN_SEGMENTS));
+    }
+
+    let config = app.state::<GeminiState>().request_config(app)?;
+    let response = call_gemini(&config, TITLE_PROMPT, &title_input(&session))
+        .await?
+        .ok_or("Empty response from model")?;
+    let named: TitleResponse = serde_json::from_str(extract_json(&response))
+        .map_err(|e| format!("Invalid title JSON: {}", e))?;
+    let title = named.title.trim().trim_matches('"').to_string();
+    if title.is_empty() {
+        return Err("Model returned no title".to_string());
+    }
+
+    // Reload: segments kept arriving while the model was busy
+    let (replace, session) = manager.update_session(session_id, |session| {
+        let replace = overwrite || session.metadata.auto_titled || session.metadata.default_title;
+        if replace {
+            session.metadata.title = title.clone();
+            session.metadata.auto_titled = true;
+            session.metadata.default_title = false;
+        }
+        for tag in named.tags.iter().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) {
+            if session.metadata.tags.len() < MAX_TAGS && !session.metadata.tags.contains(&tag) {
+                session.metadata.tags.push(tag);
+            }
+        }
+        Ok((replace, session.clone()))
+    })?;
+
+    if replace {
+        app.state::<LiveSessionState>().rename(session_id, &title);
+    }
+    let event = SessionTitledEvent {
+        session_id: session_id.to_string(),
+        title: session.metadata.title.clone(),
+        tags: session.metadata.tags.clone(),
+        title_changed: replace,
+    };
+    info!("[TITLE] Session {} {} '{}' {:?}", session_id, if replace { "titled" } else { "kept" }, event.title, event.tags);
+    events::emit(app, &event);
+    Ok(event)
+}
+
+/// Title a session in the background if auto-titling is on and a key is set
+pub fn title_in_background(app: &AppHandle, session_id: &str) {
+    let enabled = app.state::<SettingsState>().get().auto_title.enabled;
+    let has_key = app.state::<GeminiState>().api_key.lock().unwrap().is_some();
+    if !enabled || !has_key {
+        return;
+    }
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = generate(&app, &session_id, false).await {
+            warn!("[TITLE] Session {} not titled: {}", session_id, e);
+        }
+    });
+}
+
+/// The early title, once the live session has run for `after_minutes`
+pub fn schedule_early_title(app: &AppHandle, session_id: &str) {
+    let after_minutes = app.state::<SettingsState>().get().auto_title.after_minutes;
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        sleep(Duration::from_secs(after_minutes * 60)).await;
+        if app.state::<LiveSessionState>().active_id().as_deref() == Some(session_id.as_str()) {
+            title_in_background(&app, &session_id);
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Title a stored session now; `overwrite` also replaces a title the user chose
+#[tauri::command]
+pub async fn generate_session_title(app: AppHandle, session_id: String, overwrite: Option<bool>) -> Result<SessionTitledEvent, String> {
+    generate(&app, &session_id, overwrite.unwrap_or(true)).await
+}
+
+#[tauri::command]
+pub fn get_auto_title_config(settings: tauri::State<'_, SettingsState>) -> AutoTitleConfig {
+    settings.get().auto_title
+}
+
+#[tauri::command]
+pub fn set_auto_title_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: AutoTitleConfig,
+) -> Result<AutoTitleConfig, String> {
+    if config.after_minutes == 0 || config.after_minutes > 60 {
+        return Err("after_minutes must be between 1 and 60".to_string());
+    }
+    settings.update(|s| s.auto_title = config.clone())?;
+    info!("[TITLE] Auto titles {} (first after {} min)", if config.enabled { "on" } else { "off" }, config.after_minutes);
+    Ok(config)
+}
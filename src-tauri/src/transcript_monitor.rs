@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex as StdMutex;
+use chrono::Utc;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+use crate::events::{self, CognivoxEvent, MonitorLineEvent, TranscriptionEvent, TranslatedCaptionEvent};
+
+// ============================================================================
+// TRANSCRIPT MONITOR - Detached Rolling Transcript on a Second Display
+// ============================================================================
+//
+// Unlike the caption overlay, the backend feeds this window: while it's open
+// a forwarder copies finalized segments (and their translations) from the
+// event stream into a rolling buffer and sends them as cognivox:monitor_line
+// to this window only. The main window can stay hidden the whole time; the
+// page asks for the buffer on load, so a reload doesn't lose the backlog.
+
+const MONITOR_LABEL: &str = "transcript_monitor";
+const MONITOR_WIDTH: f64 = 960.0;
+const MONITOR_HEIGHT: f64 = 540.0;
+const MAX_LINES: usize = 200;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DisplayInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MonitorWindowInfo {
+    pub display: Option<String>,
+    pub fullscreen: bool,
+    pub opened_at: String,
+}
+
+/// The fields of a transcription payload the monitor shows
+#[derive(Deserialize)]
+struct Heard {
+    segment_id: Option<String>,
+    speaker: Option<String>,
+    text: String,
+    start_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Translated {
+    segment_id: String,
+    translation: String,
+}
+
+#[derive(Default)]
+pub struct TranscriptMonitorState {
+    window: StdMutex<Option<(MonitorWindowInfo, JoinHandle<()>)>>,
+    lines: StdMutex<VecDeque<MonitorLineEvent>>,
+}
+
+impl TranscriptMonitorState {
+    fn push(&self, line: MonitorLineEvent) {
+        let mut lines = self.lines.lock().unwrap();
+        lines.push_back(line);
+        while lines.len() > MAX_LINES {
+            lines.pop_front();
+        }
+    }
+
+    /// Attach a translation to its line; returns the updated line
+    fn translate(&self, segment_id: &str, translation: String) -> Option<MonitorLineEvent> {
+        let mut lines = self.lines.lock().unwrap();
+        let line = lines.iter_mut().rev().find(|l| l.segment_id.as_deref() == Some(segment_id))?;
+        line.translation = Some(translation);
+        Some(line.clone())
+    }
+
+    /// Stop feeding the window; called however it was closed
+    fn detach(&self) {
+        if let Some((_, forwarder)) = self.window.lock().unwrap().take() {
+            forwarder.abort();
+            self.lines.lock().unwrap().clear();
+            info!("[MONITOR] Transcript monitor closed");
+        }
+    }
+}
+
+fn monitor_window(app: &AppHandle) -> Option<WebviewWindow> {
+    app.get_webview_window(MONITOR_LABEL)
+}
+
+/// Copy segments from the event stream into the buffer and the window
+fn spawn_forwarder(app: AppHandle) -> JoinHandle<()> {
+    let mut rx = events::subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let (name, payload) = match rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("[MONITOR] Fell behind, {} event(s) not shown", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+            let state = app.state::<TranscriptMonitorState>();
+            let line = match name {
+                TranscriptionEvent::NAME => {
+                    let Ok(heard) = serde_json::from_value::<Heard>(payload) else { continue; };
+                    if heard.text.trim().is_empty() {
+                        continue;
+                    }
+                    let line = MonitorLineEvent {
+                        segment_id: heard.segment_id,
+                        speaker: heard.speaker.unwrap_or_else(|| "Speaker".to_string()),
+                        text: heard.text.trim().to_string(),
+                        start_ms: heard.start_ms,
+                        translation: None,
+                    };
+                    state.push(line.clone());
+                    line
+                }
+                TranslatedCaptionEvent::NAME => {
+                    let Ok(translated) = serde_json::from_value::<Translated>(payload) else { continue; };
+                    let Some(line) = state.translate(&translated.segment_id, translated.translation) else { continue; };
+                    line
+                }
+                _ => continue,
+            };
+            events::emit_to(&app, MONITOR_LABEL, &line);
+        }
+    })
+}
+
+fn displays(app: &AppHandle) -> Result<Vec<Monitor>, String> {
+    app.available_monitors().map_err(|e| format!("Failed to list displays: {}", e))
+}
+
+fn same_display(a: &Monitor, b: &Monitor) -> bool {
+    a.position() == b.position() && a.size() == b.size()
+}
+
+/// The requested display, or the first one the main window isn't on
+fn pick_display(app: &AppHandle, index: Option<usize>) -> Result<Monitor, String> {
+    let mut all = displays(app)?;
+    if let Some(index) = index {
+        let count = all.len();
+        return (index < count)
+            .then(|| all.swap_remove(index))
+            .ok_or_else(|| format!("Display {} not found ({} connected)", index, count));
+    }
+    let main = app.get_webview_window("main").and_then(|w| w.current_monitor().ok().flatten());
+    let other = all.iter().position(|m| main.as_ref().is_none_or(|main| !same_display(m, main)));
+    match other {
+        Some(i) => Ok(all.swap_remove(i)),
+        None => app.primary_monitor().ok().flatten()
+            .or_else(|| all.into_iter().next())
+            .ok_or_else(|| "No display found".to_string()),
+    }
+}
+
+/// Centre the window on `display`, then fill it if asked
+fn place(window: &WebviewWindow, display: &Monitor, fullscreen: bool) -> Result<(), String> {
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let left = display.position().x + (display.size().width as i32 - size.width as i32).max(0) / 2;
+    let top = display.position().y + (display.size().height as i32 - size.height as i32).max(0) / 2;
+    window.set_position(PhysicalPosition::new(left, top)).map_err(|e| e.to_string())?;
+    window.set_fullscreen(fullscreen).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_displays(app: AppHandle) -> Result<Vec<DisplayInfo>, String> {
+    let primary = app.primary_monitor().ok().flatten();
+    Ok(displays(&app)?
+        .iter()
+        .enumerate()
+        .map(|(index, m)| DisplayInfo {
+            index,
+            name: m.name().cloned(),
+            x: m.position().x,
+            y: m.position().y,
+            width: m.size().width,
+            height: m.size().height,
+            scale_factor: m.scale_factor(),
+            primary: primary.as_ref().is_some_and(|p| same_display(m, p)),
+        })
+        .collect())
+}
+
+/// Open (or move) the transcript monitor; `display` is an index from list_displays
+#[tauri::command]
+pub fn open_transcript_monitor(
+    app: AppHandle,
+    state: tauri::State<'_, TranscriptMonitorState>,
+    display: Option<usize>,
+    fullscreen: Option<bool>,
+) -> Result<MonitorWindowInfo, String> {
+    let target = pick_display(&app, display)?;
+    let fullscreen = fullscreen.unwrap_or(false);
+
+    let window = match monitor_window(&app) {
+        Some(window) => {
+            // Leave fullscreen first, or the move is ignored
+            let _ = window.set_fullscreen(false);
+            window
+        }
+        None => {
+            let window = WebviewWindowBuilder::new(&app, MONITOR_LABEL, WebviewUrl::App("monitor".into()))
+                .title("Cognivox Transcript")
+                .inner_size(MONITOR_WIDTH, MONITOR_HEIGHT)
+                .min_inner_size(400.0, 200.0)
+                .focused(false)
+                .visible(false)
+                .build()
+                .map_err(|e| format!("Failed to create transcript monitor: {}", e))?;
+            let handle = app.clone();
+            window.on_window_event(move |event| {
+                if matches!(event, WindowEvent::Destroyed) {
+                    handle.state::<TranscriptMonitorState>().detach();
+                }
+            });
+            window
+        }
+    };
+    place(&window, &target, fullscreen)?;
+    window.show().map_err(|e| e.to_string())?;
+
+    let info = MonitorWindowInfo {
+        display: target.name().cloned(),
+        fullscreen,
+        opened_at: Utc::now().to_rfc3339(),
+    };
+    let mut slot = state.window.lock().unwrap();
+    match slot.as_mut() {
+        Some((existing, _)) => {
+            existing.display = info.display.clone();
+            existing.fullscreen = fullscreen;
+        }
+        None => *slot = Some((info.clone(), spawn_forwarder(app.clone()))),
+    }
+    info!("[MONITOR] ✓ Transcript monitor on {}", info.display.as_deref().unwrap_or("display"));
+    Ok(slot.as_ref().map(|(info, _)| info.clone()).unwrap_or(info))
+}
+
+#[tauri::command]
+pub fn close_transcript_monitor(app: AppHandle, state: tauri::State<'_, TranscriptMonitorState>) -> Result<(), String> {
+    if let Some(window) = monitor_window(&app) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    state.detach();
+    Ok(())
+}
+
+/// None while the monitor is closed
+#[tauri::command]
+pub fn get_transcript_monitor(state: tauri::State<'_, TranscriptMonitorState>) -> Option<MonitorWindowInfo> {
+    state.window.lock().unwrap().as_ref().map(|(info, _)| info.clone())
+}
+
+/// Lines shown so far, oldest first; the monitor page loads these on start
+#[tauri::command]
+pub fn get_transcript_monitor_lines(state: tauri::State<'_, TranscriptMonitorState>) -> Vec<MonitorLineEvent> {
+    state.lines.lock().unwrap().iter().cloned().collect()
+}
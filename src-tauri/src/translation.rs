@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+use crate::events::{self, TranslatedCaptionEvent};
+use crate::gemini_client::{call_gemini, GeminiState};
+use crate::network::NetworkState;
+use crate::settings::SettingsState;
+use crate::whisper_client::{translate_audio_to_english, WhisperState};
+
+// ============================================================================
+// TRANSLATION - Live Captions in a Second Language
+// ============================================================================
+//
+// Each finalized live segment is translated off the audio loop, so a slow
+// translation never delays transcription or intelligence. The LLM handles
+// any target language; Whisper's translate task stays on-device but only
+// produces English.
+
+const TRANSLATION_PROMPT: &str = r#"You translate live meeting captions.
+
+INPUT: One transcribed segment of speech.
+OUTPUT: The translation only - no quotes, notes, or the original text.
+
+RULES:
+- Translate into: {language}
+- Keep names, product names and numbers as they are
+- Keep the tone; fix obvious transcription slips only when the meaning is clear
+- If the segment is already in the target language, return it unchanged"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationProvider {
+    #[default]
+    Llm,
+    // On-device; target must be English
+    Whisper,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TranslationConfig {
+    pub enabled: bool,
+    // Language code or name, e.g. "es" or "Spanish"
+    pub target_language: String,
+    pub provider: TranslationProvider,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_language: "en".to_string(),
+            provider: TranslationProvider::Llm,
+        }
+    }
+}
+
+fn is_english(language: &str) -> bool {
+    matches!(language.trim().to_lowercase().as_str(), "en" | "eng" | "english")
+}
+
+/// A finalized live segment to caption
+pub struct CaptionSegment {
+    pub segment_id: String,
+    pub session_id: Option<String>,
+    pub speaker: String,
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+async fn translate_with_llm(app: &AppHandle, text: &str, target: &str) -> Result<String, String> {
+    let config = app.state::<GeminiState>().request_config(app)?;
+    let prompt = TRANSLATION_PROMPT.replace("{language}", target);
+    call_gemini(&config, &prompt, text)
+        .await?
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "Empty translation".to_string())
+}
+
+async fn translate_with_whisper(app: &AppHandle, audio: &[f32]) -> Result<String, String> {
+    let whisper = app.state::<WhisperState>();
+    let model_path: PathBuf = whisper.model_path.lock().unwrap().clone().ok_or("Whisper model missing")?;
+    let language = whisper.language.lock().unwrap().clone();
+    let decoding = whisper.decoding.lock().unwrap().clone();
+    Ok(translate_audio_to_english(&model_path, &language, &decoding, audio).await?.text)
+}
+
+/// Translate a segment in the background when translated captions are on.
+/// `audio` is only needed (and only cloned) for the Whisper provider.
+pub fn caption_segment(app: &AppHandle, segment: CaptionSegment, source_language: &str, audio: &[f32]) {
+    let config = app.state::<SettingsState>().get().translation;
+    if !config.enabled || segment.text.trim().is_empty() {
+        return;
+    }
+    let source_language = source_language.to_string();
+    if source_language.eq_ignore_ascii_case(config.target_language.trim()) {
+        return;
+    }
+    let audio = match config.provider {
+        TranslationProvider::Whisper if is_english(&source_language) => return,
+        TranslationProvider::Whisper => audio.to_vec(),
+        TranslationProvider::Llm => Vec::new(),
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match config.provider {
+            TranslationProvider::Llm => translate_with_llm(&app, &segment.text, &config.target_language).await,
+            TranslationProvider::Whisper => translate_with_whisper(&app, &audio).await,
+        };
+        let translation = match result {
+            Ok(t) => t,
+            Err(e) => {
+                warn!("[TRANSLATE] ✗ Segment {} not translated: {}", segment.segment_id, e);
+                return;
+            }
+        };
+        debug!("[TRANSLATE] {} -> '{}'", segment.segment_id, translation);
+        events::emit(&app, &TranslatedCaptionEvent {
+            segment_id: segment.segment_id,
+            session_id: segment.session_id,
+            speaker: segment.speaker,
+            text: segment.text,
+            translation,
+            source_language,
+            target_language: config.target_language,
+            provider: config.provider,
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+        });
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_translation_config(settings: tauri::State<'_, SettingsState>) -> TranslationConfig {
+    settings.get().translation
+}
+
+#[tauri::command]
+pub fn set_translation_config(
+    settings: tauri::State<'_, SettingsState>,
+    network: tauri::State<'_, NetworkState>,
+    config: TranslationConfig,
+) -> Result<TranslationConfig, String> {
+    let config = TranslationConfig {
+        target_language: config.target_language.trim().to_string(),
+        ..config
+    };
+    if config.enabled {
+        if config.target_language.is_empty() {
+            return Err("A target language is required".to_string());
+        }
+        match config.provider {
+            TranslationProvider::Whisper if !is_english(&config.target_language) => {
+                return Err("Whisper can only translate into English - use the LLM provider for other languages".to_string());
+            }
+            TranslationProvider::Llm if network.is_local_only() => {
+                return Err("Local-only privacy mode is on - use the Whisper provider (English only)".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    settings.update(|s| s.translation = config.clone())?;
+    info!("[TRANSLATE] Captions: {}", if config.enabled {
+        format!("{} via {:?}", config.target_language, config.provider)
+    } else {
+        "off".to_string()
+    });
+    Ok(config)
+}
@@ -0,0 +1,224 @@
+use std::sync::Mutex as StdMutex;
+use tauri::{
+    image::Image,
+    menu::{Menu, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    App, AppHandle, Listener, Manager, Wry,
+};
+use tracing::{info, warn};
+use crate::events::{self, CognivoxEvent, PipelineState, StatusEvent, TrayOpenSummaryEvent};
+use crate::gemini_client::GeminiState;
+use crate::live_session;
+use crate::session_manager::SessionManager;
+
+// ============================================================================
+// STATION 6: TRAY - Pipeline Status & Quick Controls
+// ============================================================================
+//
+// The tray follows the same cognivox:status events the frontend shows, so
+// every existing status transition drives it without extra plumbing.
+
+const TRAY_ID: &str = "main";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineStatus {
+    Idle,
+    Listening,
+    Transcribing,
+    Analyzing,
+    RateLimited,
+    Paused,
+    Error,
+}
+
+impl PipelineStatus {
+    /// Collapse the pipeline's states into what the tray distinguishes
+    fn from_state(state: PipelineState) -> Self {
+        match state {
+            PipelineState::Idle | PipelineState::Connecting
+                | PipelineState::LoadingModel | PipelineState::Ready => Self::Idle,
+            PipelineState::Listening | PipelineState::SpeechDetected => Self::Listening,
+            PipelineState::Transcribing => Self::Transcribing,
+            PipelineState::Analyzing | PipelineState::Summarizing => Self::Analyzing,
+            PipelineState::Paused => Self::Paused,
+            PipelineState::RateLimited => Self::RateLimited,
+            PipelineState::Error => Self::Error,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Idle => "Idle",
+            Self::Listening => "Listening",
+            Self::Transcribing => "Transcribing",
+            Self::Analyzing => "Analyzing",
+            Self::RateLimited => "Rate limited",
+            Self::Paused => "Paused",
+            Self::Error => "Error",
+        }
+    }
+
+    /// Badge colour painted over the app icon
+    fn color(&self) -> Option<[u8; 3]> {
+        match self {
+            Self::Idle => None,
+            Self::Listening => Some([34, 197, 94]),
+            Self::Transcribing | Self::Analyzing => Some([59, 130, 246]),
+            Self::RateLimited | Self::Paused => Some([245, 158, 11]),
+            Self::Error => Some([239, 68, 68]),
+        }
+    }
+}
+
+pub struct TrayState {
+    status: StdMutex<PipelineStatus>,
+    status_item: MenuItem<Wry>,
+    pause_item: MenuItem<Wry>,
+    base_icon: Option<Image<'static>>,
+}
+
+/// App icon with a coloured status dot in the bottom-right corner
+fn badged_icon(base: &Image<'static>, color: [u8; 3]) -> Image<'static> {
+    let (w, h) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+    let radius = (w.min(h) as f32) * 0.22;
+    let (cx, cy) = (w as f32 - radius - 1.0, h as f32 - radius - 1.0);
+
+    for y in 0..h {
+        for x in 0..w {
+            let d = ((x as f32 - cx).powi(2) + (y as f32 - cy).powi(2)).sqrt();
+            if d <= radius {
+                let i = ((y * w + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[color[0], color[1], color[2], 255]);
+            }
+        }
+    }
+    Image::new_owned(rgba, w, h)
+}
+
+fn apply_status(app: &AppHandle, status: PipelineStatus, text: &str) {
+    let tray_state = app.state::<TrayState>();
+    {
+        let mut current = tray_state.status.lock().unwrap();
+        if *current == status { return; }
+        *current = status;
+    }
+
+    let _ = tray_state.status_item.set_text(format!("● {}", status.label()));
+    let _ = tray_state.pause_item.set_text(
+        if status == PipelineStatus::Paused { "Resume Listening" } else { "Pause Listening" }
+    );
+
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(format!("Cognivox - {}", text)));
+        if let Some(base) = &tray_state.base_icon {
+            let icon = match status.color() {
+                Some(color) => badged_icon(base, color),
+                None => base.clone(),
+            };
+            let _ = tray.set_icon(Some(icon));
+        }
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn open_last_summary(app: &AppHandle) {
+    let latest = SessionManager::new()
+        .and_then(|m| m.list_sessions())
+        .ok()
+        .and_then(|sessions| sessions.into_iter().find(|s| s.summary.is_some()));
+
+    match latest {
+        Some(session) => {
+            show_main_window(app);
+            events::emit_to(app, "main", &TrayOpenSummaryEvent { session_id: session.id });
+        }
+        None => info!("[TRAY] No summarized session yet"),
+    }
+}
+
+pub fn build_tray(app: &App) -> tauri::Result<()> {
+    let status_i = MenuItem::with_id(app, "status", "● Idle", false, None::<&str>)?;
+    let show_i = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
+    let record_i = MenuItem::with_id(app, "record", "Start Recording", true, None::<&str>)?;
+    let pause_i = MenuItem::with_id(app, "pause", "Pause Listening", true, None::<&str>)?;
+    let stop_i = MenuItem::with_id(app, "stop", "Stop Session", true, None::<&str>)?;
+    let summary_i = MenuItem::with_id(app, "summary", "Open Last Summary", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let separator2 = PredefinedMenuItem::separator(app)?;
+
+    let menu = Menu::with_items(app, &[
+        &status_i, &separator,
+        &show_i, &record_i, &pause_i, &stop_i, &summary_i,
+        &separator2, &quit_i,
+    ])?;
+
+    let base_icon = app.default_window_icon().cloned().map(|i| i.to_owned());
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("Cognivox - Idle")
+        .on_menu_event(|app, event| {
+            match event.id.as_ref() {
+                "show" => show_main_window(app),
+                "record" => {
+                    info!("[TRAY] Start recording triggered");
+                    if let Err(e) = live_session::start_listening(app) {
+                        warn!("[TRAY] Not started: {}", e);
+                    }
+                }
+                "pause" => {
+                    let paused = app.state::<GeminiState>().toggle_pause();
+                    info!("[TRAY] Listening {}", if paused { "paused" } else { "resumed" });
+                }
+                "stop" => {
+                    info!("[TRAY] Stop recording triggered");
+                    live_session::stop_listening(app);
+                }
+                "summary" => open_last_summary(app),
+                "quit" => {
+                    app.exit(0);
+                }
+                _ => {}
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = base_icon.clone() {
+        builder = builder.icon(icon);
+    }
+    builder.build(app)?;
+
+    app.manage(TrayState {
+        status: StdMutex::new(PipelineStatus::Idle),
+        status_item: status_i,
+        pause_item: pause_i,
+        base_icon,
+    });
+
+    // Follow the status events the pipeline already emits
+    let handle = app.handle().clone();
+    app.listen_any(<StatusEvent as CognivoxEvent>::NAME, move |event| {
+        if let Ok(status) = serde_json::from_str::<StatusEvent>(event.payload()) {
+            apply_status(&handle, PipelineStatus::from_state(status.state), &status.message);
+        }
+    });
+
+    info!("[STATION 6] Tray icon initialized - Shadow mode ready");
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use chrono::DateTime;
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+use crate::session_manager::{SessionData, SessionManager};
+use crate::settings::SettingsState;
+
+// ============================================================================
+// NOTES VAULT - Markdown Notes for Obsidian (or any folder of notes)
+// ============================================================================
+//
+// Each session becomes one note rendered from a Handlebars template. Users
+// can replace the template; the fields below are what it gets to work with.
+// Re-exporting a session overwrites its note; after a rename the note under
+// the old title is removed.
+
+const DEFAULT_TEMPLATE: &str = r#"---
+title: {{yaml title}}
+date: {{date}}
+time: {{yaml time}}
+duration_minutes: {{duration_minutes}}
+session_id: {{session_id}}
+attendees:
+{{#each attendees}}
+  - {{yaml this}}
+{{/each}}
+tags:
+{{#each tags}}
+  - {{yaml this}}
+{{/each}}
+---
+
+# {{title}}
+
+## Summary
+
+{{#if summary}}
+{{summary}}
+{{else}}
+_No summary yet._
+{{/if}}
+{{#if decisions}}
+
+## Decisions
+
+{{#each decisions}}
+- {{this}}
+{{/each}}
+{{/if}}
+
+## Action Items
+
+{{#each action_items}}
+- [ ] {{description}}{{#if assignee}} — @{{assignee}}{{/if}}{{#if deadline}} (due {{deadline}}){{/if}}
+{{else}}
+_None recorded._
+{{/each}}
+{{#if open_questions}}
+
+## Open Questions
+
+{{#each open_questions}}
+- {{this}}
+{{/each}}
+{{/if}}
+
+## Transcript
+
+{{#each transcript}}
+**{{time}} {{speaker}}:** {{text}}
+
+{{/each}}
+"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct VaultConfig {
+    // Folder notes are written to, e.g. ~/Obsidian/Work/Meetings
+    pub folder: Option<String>,
+    // None = DEFAULT_TEMPLATE
+    pub template: Option<String>,
+    // Write the note automatically once the meeting summary is generated
+    pub export_on_summary: bool,
+}
+
+#[derive(Serialize)]
+struct NoteActionItem {
+    description: String,
+    assignee: Option<String>,
+    deadline: Option<String>,
+    priority: String,
+}
+
+#[derive(Serialize)]
+struct NoteLine {
+    time: String,
+    speaker: String,
+    text: String,
+}
+
+/// Everything a template can reference
+#[derive(Serialize)]
+struct NoteContext {
+    session_id: String,
+    title: String,
+    date: String,
+    time: String,
+    duration_minutes: u64,
+    attendees: Vec<String>,
+    tags: Vec<String>,
+    summary: Option<String>,
+    decisions: Vec<String>,
+    action_items: Vec<NoteActionItem>,
+    risks: Vec<String>,
+    next_steps: Vec<String>,
+    open_questions: Vec<String>,
+    transcript: Vec<NoteLine>,
+}
+
+impl NoteContext {
+    fn from_session(session: &SessionData) -> Self {
+        let created = DateTime::parse_from_rfc3339(&session.created_at).ok()
+            .map(|d| d.with_timezone(&chrono::Local));
+        let attendees = match &session.calendar_event {
+            Some(event) if !event.attendees.is_empty() => event.attendees.clone(),
+            _ => session.transcripts.iter()
+                .map(|t| t.speaker_id.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        };
+        let mut tags = vec!["meeting".to_string()];
+        tags.extend(session.metadata.tags.iter().cloned());
+        let summary = session.summary.as_ref();
+
+        Self {
+            session_id: session.id.clone(),
+            title: session.metadata.title.clone(),
+            date: created.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            time: created.map(|d| d.format("%H:%M").to_string()).unwrap_or_default(),
+            duration_minutes: session.metadata.duration_seconds.div_ceil(60),
+            attendees,
+            tags,
+            summary: summary.map(|s| s.executive_summary.clone()).filter(|s| !s.is_empty()),
+            decisions: summary.map(|s| s.key_decisions.clone()).unwrap_or_default(),
+            action_items: summary.map(|s| s.action_items.iter()
+                .map(|a| NoteActionItem {
+                    description: a.description.clone(),
+                    assignee: a.assignee.clone(),
+                    deadline: a.deadline.clone(),
+                    priority: a.priority.clone(),
+                })
+                .collect()).unwrap_or_default(),
+            risks: summary.map(|s| s.risks_identified.clone()).unwrap_or_default(),
+            next_steps: summary.map(|s| s.next_steps.clone()).unwrap_or_default(),
+            open_questions: summary.map(|s| s.open_questions.clone()).unwrap_or_default(),
+            transcript: session.transcripts.iter()
+                .map(|t| NoteLine {
+                    time: t.start_ms
+                        .map(|ms| format!("{:02}:{:02}", ms / 60_000, (ms / 1000) % 60))
+                        .unwrap_or_default(),
+                    speaker: t.speaker_id.clone(),
+                    text: t.text.trim().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// Quoted scalar for frontmatter; a JSON string is valid YAML
+handlebars_helper!(yaml: |value: str| serde_json::to_string(value).unwrap_or_default());
+
+fn registry() -> Handlebars<'static> {
+    let mut hb = Handlebars::new();
+    // Markdown, not HTML
+    hb.register_escape_fn(no_escape);
+    hb.register_helper("yaml", Box::new(yaml));
+    hb
+}
+
+fn render(template: &str, session: &SessionData) -> Result<String, String> {
+    registry()
+        .render_template(template, &NoteContext::from_session(session))
+        .map_err(|e| format!("Template error: {}", e))
+}
+
+/// "2024-05-01 Weekly sync (1a2b3c4d).md", with characters vaults and
+/// filesystems reject removed. The short id keeps same-titled meetings apart.
+fn note_filename(session: &SessionData) -> String {
+    let date = NoteContext::from_session(session).date;
+    let title: String = session.metadata.title.chars()
+        .map(|c| if "\\/:*?\"<>|#^[]".contains(c) { ' ' } else { c })
+        .collect();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    let short_id: String = session.id.chars().filter(|c| c.is_ascii_alphanumeric()).take(8).collect();
+    let tag = format!("({})", short_id);
+    let name: Vec<&str> = [date.as_str(), title.as_str(), tag.as_str()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+    format!("{}.md", name.join(" "))
+}
+
+pub fn export_session_note(config: &VaultConfig, session: &SessionData) -> Result<String, String> {
+    let folder = config.folder.as_ref().ok_or("No vault folder configured")?;
+    let folder = PathBuf::from(folder);
+    fs::create_dir_all(&folder).map_err(|e| format!("Failed to create vault folder: {}", e))?;
+
+    let content = render(config.template.as_deref().unwrap_or(DEFAULT_TEMPLATE), session)?;
+    let path = folder.join(note_filename(session));
+    let tmp = path.with_extension("md.tmp");
+    fs::write(&tmp, content).map_err(|e| format!("Failed to write note: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to commit note: {}", e))?;
+    let written = path.to_string_lossy().to_string();
+
+    // Drop the note left under a previous title (or folder) and remember this one
+    let previous = SessionManager::new()?.update_session(&session.id, |s| {
+        Ok(s.vault_note.replace(written.clone()))
+    })?;
+    if let Some(old) = previous.filter(|old| *old != written) {
+        match fs::remove_file(&old) {
+            Ok(()) => info!("[VAULT] Removed stale note: {}", old),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("[VAULT] Could not remove stale note {}: {}", old, e),
+        }
+    }
+
+    info!("[VAULT] ✓ Note written: {}", path.display());
+    Ok(written)
+}
+
+pub fn export_after_summary(app: &AppHandle, session_id: &str) {
+    let config = app.state::<SettingsState>().get().vault;
+    if !config.export_on_summary || config.folder.is_none() { return; }
+    let result = SessionManager::new()
+        .and_then(|m| m.load_session(session_id))
+        .and_then(|session| export_session_note(&config, &session));
+    if let Err(e) = result {
+        error!("[VAULT] ✗ Auto-export failed: {}", e);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_vault_config(settings: tauri::State<'_, SettingsState>) -> VaultConfig {
+    settings.get().vault
+}
+
+/// The template in effect (the built-in one unless replaced), for editing
+#[tauri::command]
+pub fn get_vault_template(settings: tauri::State<'_, SettingsState>) -> String {
+    settings.get().vault.template.unwrap_or_else(|| DEFAULT_TEMPLATE.to_string())
+}
+
+/// Save vault settings. An empty template restores the built-in one; an
+/// invalid template is rejected rather than failing at export time.
+#[tauri::command]
+pub fn set_vault_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: VaultConfig,
+) -> Result<String, String> {
+    let config = VaultConfig {
+        folder: config.folder.map(|f| f.trim().to_string()).filter(|f| !f.is_empty()),
+        template: config.template.filter(|t| !t.trim().is_empty()),
+        export_on_summary: config.export_on_summary,
+    };
+    if let Some(template) = &config.template {
+        render(template, &SessionData::new("Template check".to_string()))?;
+    }
+
+    settings.update(|s| s.vault = config)?;
+    Ok("Vault settings saved".to_string())
+}
+
+#[tauri::command]
+pub fn export_to_vault(settings: tauri::State<'_, SettingsState>, session_id: String) -> Result<String, String> {
+    let session = SessionManager::new()?.load_session(&session_id)?;
+    export_session_note(&settings.get().vault, &session)
+}
@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::action_items;
+use crate::bookmarks::{self, BookmarkSource};
+use crate::events::{self, VoiceCommandEvent};
+use crate::gemini_client::extract_json;
+use crate::live_session::{record_segment, LiveSessionState, RecentSegment};
+use crate::session_manager::SessionManager;
+use crate::settings::SettingsState;
+use crate::summarizer;
+
+// ============================================================================
+// VOICE COMMANDS - "Cognivox, mark that as an action item"
+// ============================================================================
+//
+// Transcripts are scanned for the wake word before they go to Gemini. What
+// follows it is matched against a few command phrases and executed here; the
+// command itself is never analyzed as meeting content, but speech before the
+// wake word still is. Whisper rarely spells an invented name the same way
+// twice, so the wake word is matched with a small edit distance.
+
+const MARKED_CATEGORY: &str = "ACTION_ITEM";
+// Leading words dropped from an inline action item or bookmark note
+const FILLER_WORDS: &[&str] = &["this", "that", "it", "as", "an", "a", "to", "is", "with", "note", "moment", "for"];
+// Said right before the wake word
+const ADDRESS_WORDS: &[&str] = &["hey", "hi", "ok", "okay"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct VoiceCommandConfig {
+    pub enabled: bool,
+    pub wake_word: String,
+}
+
+impl Default for VoiceCommandConfig {
+    fn default() -> Self {
+        Self { enabled: true, wake_word: "Cognivox".to_string() }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceCommand {
+    // Inline text ("action item: send the deck") or, if None, the previous segment
+    MarkActionItem { text: Option<String> },
+    Bookmark { note: Option<String> },
+    StartSummary,
+    StopSummary,
+}
+
+#[derive(Default)]
+pub struct VoiceCommandState {
+    // Marked segments still waiting for their intelligence
+    marked: StdMutex<HashSet<String>>,
+}
+
+// ============================================================================
+// Detection
+// ============================================================================
+
+struct Token<'a> {
+    offset: usize,
+    text: &'a str,
+    norm: String,
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                let word = &text[s..i];
+                let norm: String = word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+                if !norm.is_empty() {
+                    tokens.push(Token { offset: s, text: word, norm });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + usize::from(ca != *cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Index of the first token and number of tokens matching the wake word.
+/// "Cogni vox" and "Cognovox" both match "Cognivox".
+fn find_wake_word(tokens: &[Token], wake_word: &str) -> Option<(usize, usize)> {
+    let wake: String = wake_word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+    if wake.is_empty() {
+        return None;
+    }
+    let tolerance = match wake.chars().count() {
+        0..=3 => 0,
+        4..=5 => 1,
+        _ => 2,
+    };
+    let max_tokens = wake_word.split_whitespace().count() + 1;
+
+    for start in 0..tokens.len() {
+        let mut joined = String::new();
+        for len in 1..=max_tokens.min(tokens.len() - start) {
+            joined.push_str(&tokens[start + len - 1].norm);
+            if edit_distance(&joined, &wake) <= tolerance {
+                return Some((start, len));
+            }
+        }
+    }
+    None
+}
+
+/// Original text following the first token whose normalized form is in `keywords`
+fn text_after(tokens: &[Token], keywords: &[&str]) -> Option<String> {
+    let at = tokens.iter().position(|t| keywords.contains(&t.norm.as_str()))?;
+    let rest: Vec<&Token> = tokens[at + 1..].iter()
+        .skip_while(|t| FILLER_WORDS.contains(&t.norm.as_str()))
+        .collect();
+    if rest.is_empty() {
+        return None;
+    }
+    let text = rest.iter().map(|t| t.text).collect::<Vec<_>>().join(" ");
+    Some(text.trim_matches(|c: char| !c.is_alphanumeric()).to_string()).filter(|t| !t.is_empty())
+}
+
+fn parse_command(tokens: &[Token]) -> Option<VoiceCommand> {
+    let has = |words: &[&str]| tokens.iter().any(|t| words.contains(&t.norm.as_str()));
+    let phrase = tokens.iter().map(|t| t.norm.as_str()).collect::<Vec<_>>().join(" ");
+
+    let summary = has(&["summary", "summarize", "summarise", "summarizing", "summarising", "recap"]);
+    if summary && has(&["stop", "cancel", "abort"]) {
+        return Some(VoiceCommand::StopSummary);
+    }
+    if summary {
+        return Some(VoiceCommand::StartSummary);
+    }
+    if phrase.contains("action item") || phrase.contains("to do") || has(&["todo", "task"]) {
+        // Two words or more after the keyword are the item itself
+        let text = text_after(tokens, &["item", "items", "todo", "do", "task"])
+            .filter(|t| t.split_whitespace().count() >= 2);
+        return Some(VoiceCommand::MarkActionItem { text });
+    }
+    if has(&["bookmark", "highlight", "flag"]) {
+        return Some(VoiceCommand::Bookmark { note: text_after(tokens, &["bookmark", "highlight", "flag"]) });
+    }
+    None
+}
+
+/// The command in `text`, if any, and the speech before the wake word
+fn detect(text: &str, wake_word: &str) -> Option<(VoiceCommand, String)> {
+    let tokens = tokenize(text);
+    let (start, len) = find_wake_word(&tokens, wake_word)?;
+    let command = parse_command(&tokens[start + len..])?;
+    // "Hey Cognivox" - the greeting belongs to the command
+    let cut = tokens[..start].iter()
+        .rposition(|t| !ADDRESS_WORDS.contains(&t.norm.as_str()))
+        .map_or(0, |i| i + 1);
+    let before = text[..tokens[cut.min(start)].offset]
+        .trim()
+        .trim_end_matches([',', ';', '-'])
+        .trim()
+        .to_string();
+    Some((command, before))
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Intelligence JSON for an item spoken inline, so the stored entry rebuilds as an action item
+fn action_item_intelligence(text: &str) -> String {
+    serde_json::json!({
+        "transcript": text,
+        "tone": "NEUTRAL",
+        "category": [MARKED_CATEGORY],
+        "confidence": 1.0,
+        "summary": text,
+    }).to_string()
+}
+
+fn mark_action_item(app: &AppHandle, segment: &RecentSegment, text: Option<String>) -> Result<String, String> {
+    let session_id = segment.session_id.as_deref().unwrap_or(action_items::LIVE_SESSION_ID);
+
+    if let Some(text) = text {
+        action_items::track_manual(app, session_id, &text, &segment.speaker, Some(segment.start_ms));
+        if let Some(id) = &segment.session_id {
+            // Own id: speech before the wake word is still stored under the segment's
+            record_segment(id, &format!("{}-voice", segment.segment_id), &text, &segment.speaker,
+                           Some(&action_item_intelligence(&text)), (Some(segment.start_ms), Some(segment.end_ms)), None);
+        }
+        return Ok(format!("Action item added: {}", text));
+    }
+
+    let previous = app.state::<LiveSessionState>().last_segment()
+        .filter(|p| p.session_id == segment.session_id)
+        .ok_or("Nothing said yet to mark")?;
+    action_items::track_manual(app, session_id, &previous.text, &previous.speaker, Some(previous.start_ms));
+
+    // Already stored: tag it now. Otherwise tag its intelligence when it arrives.
+    let stored = match &previous.session_id {
+        Some(id) => tag_stored_segment(id, &previous.segment_id)?,
+        None => false,
+    };
+    if !stored {
+        app.state::<VoiceCommandState>().marked.lock().unwrap().insert(previous.segment_id.clone());
+    }
+    Ok(format!("Marked as action item: {}", previous.text))
+}
+
+fn tag_stored_segment(session_id: &str, segment_id: &str) -> Result<bool, String> {
+    SessionManager::new()?.update_session(session_id, |session| {
+        let Some(entry) = session.transcripts.iter_mut().find(|t| t.segment_id.as_deref() == Some(segment_id)) else {
+            return Ok(false);
+        };
+        let categories = entry.category.get_or_insert_with(Vec::new);
+        if !categories.iter().any(|c| c == MARKED_CATEGORY) {
+            categories.push(MARKED_CATEGORY.to_string());
+        }
+        Ok(true)
+    })
+}
+
+fn execute(app: &AppHandle, command: &VoiceCommand, segment: &RecentSegment) -> Result<String, String> {
+    match command {
+        VoiceCommand::MarkActionItem { text } => mark_action_item(app, segment, text.clone()),
+        VoiceCommand::Bookmark { note } => {
+            let bookmark = bookmarks::add_to_active(app, note.clone(), BookmarkSource::Voice)?;
+            Ok(format!("Bookmarked at {}:{:02}", bookmark.at_ms / 60_000, (bookmark.at_ms / 1000) % 60))
+        }
+        VoiceCommand::StartSummary => {
+            let session_id = app.state::<LiveSessionState>().active_id().ok_or("No session is running")?;
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = summarizer::summarize_session(app, session_id).await {
+                    warn!("[VOICE] Summary failed: {}", e);
+                }
+            });
+            Ok("Summarizing the meeting so far".to_string())
+        }
+        VoiceCommand::StopSummary => {
+            if summarizer::request_cancel() {
+                Ok("Summary cancelled".to_string())
+            } else {
+                Err("No summary is running".to_string())
+            }
+        }
+    }
+}
+
+/// Run any voice command in a finalized live segment. Returns the text left
+/// for intelligence extraction, or None when the segment was only a command.
+pub fn intercept(app: &AppHandle, segment: &RecentSegment) -> Option<String> {
+    let config = app.state::<SettingsState>().get().voice_commands;
+    if !config.enabled {
+        return Some(segment.text.clone());
+    }
+    let Some((command, before)) = detect(&segment.text, &config.wake_word) else {
+        return Some(segment.text.clone());
+    };
+
+    let result = execute(app, &command, segment);
+    match &result {
+        Ok(message) => info!("[VOICE] ✓ {:?}: {}", command, message),
+        Err(e) => warn!("[VOICE] ✗ {:?}: {}", command, e),
+    }
+    events::emit(app, &VoiceCommandEvent {
+        command,
+        segment_id: segment.segment_id.clone(),
+        session_id: segment.session_id.clone(),
+        text: segment.text.clone(),
+        ok: result.is_ok(),
+        message: result.unwrap_or_else(|e| e),
+    });
+    Some(before).filter(|b| !b.is_empty())
+}
+
+/// Add the action-item category to a voice-marked segment's intelligence
+pub fn apply_mark(app: &AppHandle, segment_id: &str, intelligence: String) -> String {
+    if !app.state::<VoiceCommandState>().marked.lock().unwrap().remove(segment_id) {
+        return intelligence;
+    }
+    let Ok(mut parsed) = serde_json::from_str::<serde_json::Value>(extract_json(&intelligence)) else {
+        return intelligence;
+    };
+    match parsed["category"].as_array_mut() {
+        Some(categories) if !categories.iter().any(|c| c == MARKED_CATEGORY) => categories.push(MARKED_CATEGORY.into()),
+        Some(_) => {}
+        None => parsed["category"] = serde_json::json!([MARKED_CATEGORY]),
+    }
+    parsed.to_string()
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_voice_command_config(settings: tauri::State<'_, SettingsState>) -> VoiceCommandConfig {
+    settings.get().voice_commands
+}
+
+#[tauri::command]
+pub fn set_voice_command_config(
+    settings: tauri::State<'_, SettingsState>,
+    config: VoiceCommandConfig,
+) -> Result<VoiceCommandConfig, String> {
+    let wake_word = config.wake_word.split_whitespace().collect::<Vec<_>>().join(" ");
+    if wake_word.chars().filter(|c| c.is_alphanumeric()).count() < 4 {
+        return Err("The wake word needs at least 4 letters, or ordinary speech will trigger it".to_string());
+    }
+    if wake_word.split(' ').count() > 3 {
+        return Err("The wake word can be at most 3 words".to_string());
+    }
+    let config = VoiceCommandConfig { wake_word, ..config };
+
+    settings.update(|s| s.voice_commands = config.clone())?;
+    info!("[VOICE] Voice commands {} (wake word '{}')", if config.enabled { "on" } else { "off" }, config.wake_word);
+    Ok(config)
+}
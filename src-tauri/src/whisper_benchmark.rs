@@ -0,0 +1,264 @@
+use serde::Serialize;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::audio_capture::TARGET_SAMPLE_RATE;
+use crate::events::{self, PipelineState};
+use crate::file_import::load_samples;
+use crate::gemini_client::GeminiState;
+use crate::whisper_client::{cached_model, timed_transcription, WhisperDecodingConfig, WhisperState, MODEL_SIZES};
+
+// ============================================================================
+// WHISPER BENCHMARK - Pick the Largest Model this Machine Runs Live
+// ============================================================================
+//
+// Each downloaded model transcribes the same reference clip with the live
+// decoding settings. Real-time factor (transcription time / clip length)
+// decides the recommendation: live segments must transcribe well under
+// real time or the pipeline falls behind the conversation.
+
+// 11 s from John F. Kennedy's 1961 inaugural address (public domain),
+// 16 kHz mono - the clip whisper.cpp ships as its sample
+const REFERENCE_CLIP: &[u8] = include_bytes!("../resources/jfk.wav");
+const REFERENCE_TRANSCRIPT: &str = "And so my fellow Americans, ask not what your country can do for you, \
+    ask what you can do for your country.";
+// Largest model at or below this RTF is recommended; leaves headroom for the
+// rest of the pipeline and other apps
+const LIVE_RTF: f64 = 0.5;
+const MEMORY_SAMPLE_MS: u64 = 50;
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkOutcome {
+    Ok,
+    NotDownloaded,
+    Failed,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModelBenchmark {
+    pub model: String,
+    pub outcome: BenchmarkOutcome,
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    pub real_time_factor: f64,
+    // Peak resident memory above the level before the model loaded
+    pub peak_memory_mb: Option<f64>,
+    pub transcript: String,
+    // Against the known transcript; only for the reference clip
+    pub word_error_rate: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WhisperBenchmark {
+    pub clip_secs: f32,
+    // "reference" or the path of the clip passed in
+    pub clip: String,
+    pub results: Vec<ModelBenchmark>,
+    pub recommended: Option<String>,
+    pub recommendation: String,
+}
+
+/// The bundled reference clip as 16 kHz mono samples
+fn reference_clip() -> Result<Vec<f32>, String> {
+    let mut reader = hound::WavReader::new(Cursor::new(REFERENCE_CLIP))
+        .map_err(|e| format!("Failed to read reference clip: {}", e))?;
+    let spec = reader.spec();
+    if spec.sample_rate != TARGET_SAMPLE_RATE || spec.channels != 1 || spec.sample_format != hound::SampleFormat::Int {
+        return Err("Reference clip is not 16 kHz mono PCM".to_string());
+    }
+    let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+    reader.samples::<i32>()
+        .map(|s| s.map(|s| s as f32 / scale).map_err(|e| format!("Failed to read reference clip: {}", e)))
+        .collect()
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| w.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Word-level edit distance over the reference length, ignoring case and punctuation
+fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let (reference, hypothesis) = (words(reference), words(hypothesis));
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, r) in reference.iter().enumerate() {
+        let mut current = vec![i + 1; hypothesis.len() + 1];
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(r != h);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[hypothesis.len()] as f64 / reference.len() as f64
+}
+
+fn resident_bytes() -> Option<usize> {
+    memory_stats::memory_stats().map(|m| m.physical_mem)
+}
+
+/// Peak resident memory while `f` runs, sampled on a side thread
+fn with_peak_memory<R>(f: impl FnOnce() -> R) -> (R, Option<usize>) {
+    let Some(baseline) = resident_bytes() else { return (f(), None); };
+    let peak = Arc::new(AtomicUsize::new(baseline));
+    let done = Arc::new(AtomicBool::new(false));
+    let sampler = {
+        let (peak, done) = (peak.clone(), done.clone());
+        thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) {
+                if let Some(now) = resident_bytes() {
+                    peak.fetch_max(now, Ordering::SeqCst);
+                }
+                thread::sleep(Duration::from_millis(MEMORY_SAMPLE_MS));
+            }
+        })
+    };
+    let result = f();
+    if let Some(now) = resident_bytes() {
+        peak.fetch_max(now, Ordering::SeqCst);
+    }
+    done.store(true, Ordering::SeqCst);
+    let _ = sampler.join();
+    (result, Some(peak.load(Ordering::SeqCst).saturating_sub(baseline)))
+}
+
+fn benchmark_model(
+    cache: &hf_hub::Cache,
+    model: &str,
+    language: &str,
+    decoding: &WhisperDecodingConfig,
+    samples: &[f32],
+    expected: Option<&str>,
+) -> ModelBenchmark {
+    let mut result = ModelBenchmark {
+        model: model.to_string(),
+        outcome: BenchmarkOutcome::NotDownloaded,
+        load_ms: 0,
+        transcribe_ms: 0,
+        real_time_factor: 0.0,
+        peak_memory_mb: None,
+        transcript: String::new(),
+        word_error_rate: None,
+        error: None,
+    };
+    let Some(path) = cached_model(cache, model) else { return result; };
+
+    let (timed, peak) = with_peak_memory(|| timed_transcription(&path, language, decoding, samples));
+    match timed {
+        Ok((load, transcribe, text)) => {
+            let clip_secs = samples.len() as f64 / TARGET_SAMPLE_RATE as f64;
+            result.outcome = BenchmarkOutcome::Ok;
+            result.load_ms = load.as_millis() as u64;
+            result.transcribe_ms = transcribe.as_millis() as u64;
+            result.real_time_factor = transcribe.as_secs_f64() / clip_secs;
+            result.peak_memory_mb = peak.map(|b| b as f64 / (1024.0 * 1024.0));
+            result.word_error_rate = expected.map(|expected| word_error_rate(expected, &text));
+            result.transcript = text;
+        }
+        Err(e) => {
+            result.outcome = BenchmarkOutcome::Failed;
+            result.error = Some(e);
+        }
+    }
+    result
+}
+
+/// Largest model fast enough for live use
+fn recommend(results: &[ModelBenchmark]) -> (Option<String>, String) {
+    let measured: Vec<&ModelBenchmark> = results.iter()
+        .filter(|r| matches!(r.outcome, BenchmarkOutcome::Ok))
+        .collect();
+    let size_rank = |r: &ModelBenchmark| MODEL_SIZES.iter().position(|m| *m == r.model);
+
+    if let Some(best) = measured.iter().filter(|r| r.real_time_factor <= LIVE_RTF).max_by_key(|r| size_rank(r)) {
+        return (
+            Some(best.model.clone()),
+            format!("Use {} on this machine (transcribes {:.1}x faster than real time)", best.model, 1.0 / best.real_time_factor.max(0.001)),
+        );
+    }
+    match measured.iter().min_by(|a, b| a.real_time_factor.total_cmp(&b.real_time_factor)) {
+        Some(fastest) => (
+            Some(fastest.model.clone()),
+            format!(
+                "{} is the fastest model tested but runs at {:.2}x real time - live captions may lag; try a smaller model",
+                fastest.model, fastest.real_time_factor,
+            ),
+        ),
+        None => (None, "No downloaded models could be benchmarked - initialize a Whisper model first".to_string()),
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Benchmark downloaded models (all known sizes when `models` is empty) on
+/// the reference clip, or on `clip_path` when given
+#[tauri::command]
+pub async fn benchmark_whisper(
+    app: AppHandle,
+    models: Vec<String>,
+    clip_path: Option<String>,
+) -> Result<WhisperBenchmark, String> {
+    if app.state::<GeminiState>().audio_loop_running() {
+        return Err("Stop the live session before benchmarking - it would skew the timings".to_string());
+    }
+    let models: Vec<String> = if models.is_empty() {
+        MODEL_SIZES.iter().map(|m| m.to_string()).collect()
+    } else {
+        models.into_iter().map(|m| m.trim().to_lowercase()).collect()
+    };
+    if let Some(unknown) = models.iter().find(|m| !MODEL_SIZES.contains(&m.as_str())) {
+        return Err(format!("Unknown model '{}' (expected one of {})", unknown, MODEL_SIZES.join(", ")));
+    }
+
+    let (samples, clip, expected) = match clip_path {
+        Some(path) => (load_samples(&app, PathBuf::from(&path)).await?, path, None),
+        None => (reference_clip()?, "reference".to_string(), Some(REFERENCE_TRANSCRIPT)),
+    };
+    if samples.is_empty() {
+        return Err("Benchmark clip has no audio".to_string());
+    }
+    let clip_secs = samples.len() as f32 / TARGET_SAMPLE_RATE as f32;
+    let whisper = app.state::<WhisperState>();
+    let language = whisper.language.lock().unwrap().clone();
+    let cache = whisper.model_cache();
+    let decoding = whisper.decoding.lock().unwrap().clone();
+
+    let total = models.len();
+    let mut results = Vec::with_capacity(total);
+    for (i, model) in models.into_iter().enumerate() {
+        info!("[BENCHMARK] {} on {:.1}s clip", model, clip_secs);
+        events::emit_status(&app, PipelineState::LoadingModel, format!("Benchmarking {} ({}/{})...", model, i + 1, total));
+        let (cache, samples, language, decoding) = (cache.clone(), samples.clone(), language.clone(), decoding.clone());
+        let result = tauri::async_runtime::spawn_blocking(move || benchmark_model(&cache, &model, &language, &decoding, &samples, expected))
+            .await
+            .map_err(|e| format!("Benchmark task failed: {}", e))?;
+        match result.outcome {
+            BenchmarkOutcome::Ok => info!(
+                "[BENCHMARK] {}: load {} ms, RTF {:.2}, +{:.0} MB, WER {}",
+                result.model, result.load_ms, result.real_time_factor, result.peak_memory_mb.unwrap_or(0.0),
+                result.word_error_rate.map(|w| format!("{:.0}%", w * 100.0)).unwrap_or_else(|| "n/a".to_string()),
+            ),
+            BenchmarkOutcome::NotDownloaded => info!("[BENCHMARK] {} not downloaded, skipped", result.model),
+            BenchmarkOutcome::Failed => warn!("[BENCHMARK] ✗ {}: {}", result.model, result.error.as_deref().unwrap_or("")),
+        }
+        results.push(result);
+    }
+
+    let (recommended, recommendation) = recommend(&results);
+    info!("[BENCHMARK] ✓ {}", recommendation);
+    events::emit_status(&app, PipelineState::Ready, "Benchmark finished");
+    Ok(WhisperBenchmark { clip_secs, clip, results, recommended, recommendation })
+}
@@ -1,7 +1,14 @@
+use serde::{Deserialize, Serialize};
 use std::sync::Mutex as StdMutex;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Manager};
 use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::info;
+use crate::events::{self, PipelineState, TranscriptionEvent, TranscriptionSource};
+use crate::network::NetworkState;
+use crate::settings::SettingsState;
+use crate::whisper_models;
 
 // ============================================================================
 // WHISPER CLIENT - Local Speech-to-Text (v0.13 API)
@@ -11,6 +18,9 @@ pub struct WhisperState {
     pub is_initialized: StdMutex<bool>,
     pub model_path: StdMutex<Option<PathBuf>>,
     pub language: StdMutex<String>,
+    // Where models are downloaded; None = the shared Hugging Face cache
+    pub model_dir: StdMutex<Option<PathBuf>>,
+    pub decoding: StdMutex<WhisperDecodingConfig>,
 }
 
 impl Default for WhisperState {
@@ -19,6 +29,87 @@ impl Default for WhisperState {
             is_initialized: StdMutex::new(false),
             model_path: StdMutex::new(None),
             language: StdMutex::new("en".to_string()), // Default to English
+            model_dir: StdMutex::new(None),
+            decoding: StdMutex::new(WhisperDecodingConfig::default()),
+        }
+    }
+}
+
+impl WhisperState {
+    pub fn model_cache(&self) -> hf_hub::Cache {
+        match self.model_dir.lock().unwrap().clone() {
+            Some(dir) => hf_hub::Cache::new(dir),
+            None => hf_hub::Cache::default(),
+        }
+    }
+}
+
+/// whisper.cpp decoding knobs; defaults are whisper.cpp's own, with 4 threads
+/// and greedy sampling
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct WhisperDecodingConfig {
+    pub n_threads: u32,
+    // Above 1 = beam search with this many beams; 0 or 1 = greedy
+    pub beam_size: u32,
+    // Candidates sampled per fallback temperature (greedy only)
+    pub best_of: u32,
+    pub temperature: f32,
+    // Added on each fallback retry; 0 disables temperature fallback
+    pub temperature_inc: f32,
+    // Retry when a segment's token entropy is above this (repetition loops)
+    pub entropy_threshold: f32,
+    // Retry when a segment's average log probability is below this
+    pub logprob_threshold: f32,
+}
+
+impl Default for WhisperDecodingConfig {
+    fn default() -> Self {
+        Self {
+            n_threads: 4,
+            beam_size: 1,
+            best_of: 1,
+            temperature: 0.0,
+            temperature_inc: 0.2,
+            entropy_threshold: 2.4,
+            logprob_threshold: -1.0,
+        }
+    }
+}
+
+// whisper.cpp runs at most this many decoders (beams or best-of candidates)
+const MAX_DECODERS: u32 = 8;
+
+impl WhisperDecodingConfig {
+    fn validate(&self) -> Result<(), String> {
+        let cores = std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(4);
+        if self.n_threads == 0 || self.n_threads > cores {
+            return Err(format!("n_threads must be between 1 and {} (CPU cores on this machine)", cores));
+        }
+        if self.beam_size > MAX_DECODERS {
+            return Err(format!("beam_size must be at most {}", MAX_DECODERS));
+        }
+        if self.best_of == 0 || self.best_of > MAX_DECODERS {
+            return Err(format!("best_of must be between 1 and {}", MAX_DECODERS));
+        }
+        if !(0.0..=1.0).contains(&self.temperature) || !(0.0..=1.0).contains(&self.temperature_inc) {
+            return Err("temperature and temperature_inc must be between 0 and 1".to_string());
+        }
+        if self.entropy_threshold.is_nan() || self.entropy_threshold <= 0.0 {
+            return Err("entropy_threshold must be positive".to_string());
+        }
+        if self.logprob_threshold.is_nan() || self.logprob_threshold > 0.0 {
+            return Err("logprob_threshold must be zero or negative".to_string());
+        }
+        Ok(())
+    }
+
+    fn sampling(&self) -> SamplingStrategy {
+        if self.beam_size > 1 {
+            // Negative patience = whisper.cpp default (stop at beam_size finished beams)
+            SamplingStrategy::BeamSearch { beam_size: self.beam_size as i32, patience: -1.0 }
+        } else {
+            SamplingStrategy::Greedy { best_of: self.best_of as i32 }
         }
     }
 }
@@ -26,10 +117,36 @@ impl Default for WhisperState {
 #[derive(Clone)]
 pub struct TranscriptionResult {
     pub text: String,
+    // Detected per segment when the configured language is "auto"
     pub language: String,
+    // Detection probability; None when the language was fixed
+    pub language_probability: Option<f32>,
+    // Mean token probability, discounted by the chance the audio wasn't speech
     pub confidence: f32,
+    // Token-weighted across segments
+    pub no_speech_prob: f32,
+}
+
+/// Spoken language of one segment, stored with it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SegmentLanguage {
+    pub code: String,
+    pub probability: Option<f32>,
+}
+
+impl TranscriptionResult {
+    /// None when detection failed and the language is still "auto"
+    pub fn segment_language(&self) -> Option<SegmentLanguage> {
+        (self.language != "auto").then(|| SegmentLanguage {
+            code: self.language.clone(),
+            probability: self.language_probability,
+        })
+    }
 }
 
+/// Below this, Gemini is told the text may be misheard
+pub const LOW_CONFIDENCE: f32 = 0.5;
+
 // ============================================================================
 // Whisper Initialization
 // ============================================================================
@@ -42,11 +159,12 @@ pub async fn initialize_whisper(
 ) -> Result<String, String> {
     let size = model_size.unwrap_or_else(|| "base".to_string());
     
-    println!("[WHISPER] Initializing {} model...", size);
-    let _ = app.emit("cognivox:status", "Loading Whisper model...");
+    info!("[WHISPER] Initializing {} model...", size);
+    events::emit_status(&app, PipelineState::LoadingModel, "Loading Whisper model...");
     
-    // Download model from Hugging Face if needed
-    let model_path = download_whisper_model(&size)
+    // Download the model (or copy it from the local source) if needed
+    let source = app.state::<SettingsState>().get().model_source;
+    let model_path = whisper_models::fetch_model(state.model_cache(), &size, &source, &app.state::<NetworkState>())
         .await
         .map_err(|e| format!("Failed to load model: {}", e))?;
     
@@ -59,34 +177,32 @@ pub async fn initialize_whisper(
     
     *state.model_path.lock().unwrap() = Some(model_path.clone());
     *state.is_initialized.lock().unwrap() = true;
+    whisper_models::mark_used(&model_path);
     
-    println!("[WHISPER] ✓ Model loaded: {:?}", model_path);
-    let _ = app.emit("cognivox:status", "Whisper ready ✓");
+    info!("[WHISPER] ✓ Model loaded: {:?}", model_path);
+    events::emit_status(&app, PipelineState::Ready, "Whisper ready ✓");
     
     Ok(format!("Whisper {} model initialized", size))
 }
 
-async fn download_whisper_model(model_size: &str) -> Result<PathBuf, String> {
-    use hf_hub::api::sync::Api;
-    
-    let (model_id, filename) = match model_size {
+/// Known sizes, smallest (fastest) first
+pub const MODEL_SIZES: &[&str] = &["tiny", "base", "small", "medium"];
+
+/// Hugging Face repo and file for a model size; unknown sizes fall back to base
+pub fn model_file(model_size: &str) -> (&'static str, &'static str) {
+    match model_size {
         "tiny" => ("ggerganov/whisper.cpp", "ggml-tiny.bin"),
         "base" => ("ggerganov/whisper.cpp", "ggml-base.bin"),
         "small" => ("ggerganov/whisper.cpp", "ggml-small.bin"),
         "medium" => ("ggerganov/whisper.cpp", "ggml-medium.bin"),
         _ => ("ggerganov/whisper.cpp", "ggml-base.bin"),
-    };
-    
-    println!("[WHISPER] Downloading {} from Hugging Face...", filename);
-    
-    let api = Api::new().map_err(|e| e.to_string())?;
-    let model = api.model(model_id.to_string());
-    
-    let model_file = model
-        .get(filename)
-        .map_err(|e| format!("Failed to download model: {}", e))?;
-    
-    Ok(model_file)
+    }
+}
+
+/// Path of an already-downloaded model, without touching the network
+pub fn cached_model(cache: &hf_hub::Cache, model_size: &str) -> Option<PathBuf> {
+    let (model_id, filename) = model_file(model_size);
+    cache.model(model_id.to_string()).get(filename)
 }
 
 #[tauri::command]
@@ -95,10 +211,32 @@ pub fn set_whisper_language(
     language: String,
 ) -> Result<String, String> {
     *state.language.lock().unwrap() = language.clone();
-    println!("[WHISPER] Language set to: {}", language);
+    info!("[WHISPER] Language set to: {}", language);
     Ok(format!("Language: {}", language))
 }
 
+#[tauri::command]
+pub fn get_whisper_decoding_config(state: tauri::State<'_, WhisperState>) -> WhisperDecodingConfig {
+    state.decoding.lock().unwrap().clone()
+}
+
+/// Applies from the next segment; no model reload needed
+#[tauri::command]
+pub fn set_whisper_decoding_config(
+    state: tauri::State<'_, WhisperState>,
+    settings: tauri::State<'_, SettingsState>,
+    config: WhisperDecodingConfig,
+) -> Result<WhisperDecodingConfig, String> {
+    config.validate()?;
+    settings.update(|s| s.whisper_decoding = config.clone())?;
+    *state.decoding.lock().unwrap() = config.clone();
+    info!("[WHISPER] Decoding: {} threads, {}, temperature {} (+{}), entropy {} / logprob {}",
+        config.n_threads,
+        if config.beam_size > 1 { format!("beam {}", config.beam_size) } else { format!("greedy best_of {}", config.best_of) },
+        config.temperature, config.temperature_inc, config.entropy_threshold, config.logprob_threshold);
+    Ok(config)
+}
+
 #[tauri::command]
 pub fn get_whisper_status(state: tauri::State<'_, WhisperState>) -> Result<String, String> {
     let is_init = *state.is_initialized.lock().unwrap();
@@ -118,10 +256,76 @@ pub fn get_whisper_status(state: tauri::State<'_, WhisperState>) -> Result<Strin
 pub async fn transcribe_audio(
     model_path: &PathBuf,
     language: &str,
+    decoding: &WhisperDecodingConfig,
     audio_samples: &[f32],
+) -> Result<TranscriptionResult, String> {
+    run_whisper(model_path, language, decoding, audio_samples, false).await
+}
+
+/// Whisper's built-in translation task; it can only translate into English
+pub async fn translate_audio_to_english(
+    model_path: &PathBuf,
+    language: &str,
+    decoding: &WhisperDecodingConfig,
+    audio_samples: &[f32],
+) -> Result<TranscriptionResult, String> {
+    run_whisper(model_path, language, decoding, audio_samples, true).await
+}
+
+fn decoding_params<'a>(language: &'a str, decoding: &WhisperDecodingConfig, translate: bool) -> FullParams<'a, 'a> {
+    let mut params = FullParams::new(decoding.sampling());
+    params.set_language(Some(language));
+    params.set_translate(translate);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    params.set_single_segment(false);
+    params.set_n_threads(decoding.n_threads as i32);
+    params.set_temperature(decoding.temperature);
+    params.set_temperature_inc(decoding.temperature_inc);
+    params.set_entropy_thold(decoding.entropy_threshold);
+    params.set_logprob_thold(decoding.logprob_threshold);
+    params
+}
+
+/// Load a model and transcribe once with the live settings, timing each step.
+/// Blocking; returns (load time, transcription time, text).
+pub fn timed_transcription(
+    model_path: &Path,
+    language: &str,
+    decoding: &WhisperDecodingConfig,
+    audio_samples: &[f32],
+) -> Result<(Duration, Duration, String), String> {
+    let path_str = model_path.to_str().ok_or("Invalid model path")?;
+    let load_started = Instant::now();
+    let ctx = WhisperContext::new_with_params(path_str, WhisperContextParameters::default())
+        .map_err(|e| format!("Failed to create Whisper context: {:?}", e))?;
+    let mut state = ctx.create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {:?}", e))?;
+    let load_time = load_started.elapsed();
+
+    let transcribe_started = Instant::now();
+    state.full(decoding_params(language, decoding, false), audio_samples)
+        .map_err(|e| format!("Transcription failed: {:?}", e))?;
+    let transcribe_time = transcribe_started.elapsed();
+
+    let segments = state.full_n_segments().map_err(|e| format!("Failed to get segments: {:?}", e))?;
+    let text = (0..segments)
+        .filter_map(|i| state.full_get_segment_text(i).ok())
+        .collect::<String>();
+    Ok((load_time, transcribe_time, text.trim().to_string()))
+}
+
+async fn run_whisper(
+    model_path: &PathBuf,
+    language: &str,
+    decoding: &WhisperDecodingConfig,
+    audio_samples: &[f32],
+    translate: bool,
 ) -> Result<TranscriptionResult, String> {
     let duration_secs = audio_samples.len() as f32 / 16000.0;
-    println!("[WHISPER] Transcribing {:.1}s of audio ({} samples)...", duration_secs, audio_samples.len());
+    info!("[WHISPER] Transcribing {:.1}s of audio ({} samples)...", duration_secs, audio_samples.len());
     
     let path_str = model_path.to_str().ok_or("Invalid model path")?;
     
@@ -135,45 +339,97 @@ pub async fn transcribe_audio(
     let mut state = ctx.create_state()
         .map_err(|e| format!("Failed to create Whisper state: {:?}", e))?;
     
-    // Configure parameters
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    params.set_language(Some(language));
-    params.set_translate(false);
-    params.set_print_special(false);
-    params.set_print_progress(false);
-    params.set_print_realtime(false);
-    params.set_print_timestamps(false);
-    params.set_single_segment(false);
-    params.set_n_threads(4);
+    // "auto": detect per segment, so code-switched meetings get each segment's own language
+    let detected = if language == "auto" {
+        detect_language(&mut state, audio_samples, decoding.n_threads as usize)
+    } else {
+        None
+    };
+    let params = decoding_params(detected.as_ref().map_or(language, |(code, _)| code.as_str()), decoding, translate);
     
     // Run transcription
     state.full(params, audio_samples)
         .map_err(|e| format!("Transcription failed: {:?}", e))?;
+    // Detection failed up front: take what the decoder settled on, without a probability
+    let detected = detected.or_else(|| {
+        (language == "auto")
+            .then(|| state.full_lang_id_from_state().ok())
+            .flatten()
+            .and_then(whisper_rs::get_lang_str)
+            .map(|code| (code.to_string(), None))
+    });
     
     // Collect results
     let num_segments = state.full_n_segments()
         .map_err(|e| format!("Failed to get segments: {:?}", e))?;
     
     let mut full_result = String::new();
+    let mut weighted_confidence = 0.0f32;
+    let mut weighted_no_speech = 0.0f32;
+    let mut total_tokens = 0usize;
     for i in 0..num_segments {
         if let Ok(seg) = state.full_get_segment_text(i) {
             full_result.push_str(&seg);
         }
+        let (mean_p, tokens) = segment_token_prob(&state, i);
+        let no_speech = state.full_get_segment_no_speech_prob(i).unwrap_or(0.0);
+        weighted_confidence += mean_p * (1.0 - no_speech) * tokens as f32;
+        weighted_no_speech += no_speech * tokens as f32;
+        total_tokens += tokens;
     }
     
-    let confidence = 0.85;
+    let (confidence, no_speech_prob) = if total_tokens > 0 {
+        (weighted_confidence / total_tokens as f32, weighted_no_speech / total_tokens as f32)
+    } else {
+        (0.0, 1.0)
+    };
     
-    println!("[WHISPER] ✓ Transcription: '{}' (confidence: {:.2})", 
+    info!("[WHISPER] ✓ Transcription: '{}' (confidence: {:.2}, no-speech: {:.2})", 
              if full_result.len() > 80 { &full_result[..80] } else { &full_result },
-             confidence);
+             confidence, no_speech_prob);
     
+    let (language, language_probability) = match detected {
+        _ if translate => ("en".to_string(), None),
+        Some((code, probability)) => (code, probability),
+        None => (language.to_string(), None),
+    };
     Ok(TranscriptionResult {
         text: full_result.trim().to_string(),
-        language: language.to_string(),
+        language,
+        language_probability,
         confidence,
+        no_speech_prob,
     })
 }
 
+/// Most likely spoken language of the audio and its probability
+fn detect_language(state: &mut whisper_rs::WhisperState, samples: &[f32], threads: usize) -> Option<(String, Option<f32>)> {
+    state.pcm_to_mel(samples, threads).ok()?;
+    let (id, probabilities) = state.lang_detect(0, threads).ok()?;
+    let code = whisper_rs::get_lang_str(id)?;
+    let probability = usize::try_from(id).ok().and_then(|i| probabilities.get(i).copied());
+    Some((code.to_string(), probability))
+}
+
+/// Mean probability of a segment's text tokens, and how many there were.
+/// Special tokens ([_BEG_], <|en|>, timestamps) are skipped.
+fn segment_token_prob(state: &whisper_rs::WhisperState, segment: i32) -> (f32, usize) {
+    let n_tokens = state.full_n_tokens(segment).unwrap_or(0);
+    let mut sum = 0.0f32;
+    let mut count = 0usize;
+    for t in 0..n_tokens {
+        let special = state.full_get_token_text(segment, t)
+            .map(|text| text.starts_with("[_") || text.starts_with("<|"))
+            .unwrap_or(true);
+        if special { continue; }
+        if let Ok(p) = state.full_get_token_prob(segment, t) {
+            sum += p;
+            count += 1;
+        }
+    }
+    if count == 0 { (0.0, 0) } else { (sum / count as f32, count) }
+}
+
 // ============================================================================
 // Tauri Command for Direct Transcription
 // ============================================================================
@@ -193,21 +449,30 @@ pub async fn transcribe_audio_chunk(
         .ok_or("Model path not set")?;
     
     let language = state.language.lock().unwrap().clone();
+    let decoding = state.decoding.lock().unwrap().clone();
     
-    let _ = app.emit("cognivox:status", "Transcribing with Whisper...");
+    events::emit_status(&app, PipelineState::Transcribing, "Transcribing with Whisper...");
     
-    match transcribe_audio(&model_path, &language, &audio_data).await {
+    match transcribe_audio(&model_path, &language, &decoding, &audio_data).await {
         Ok(result) => {
-            let _ = app.emit("cognivox:whisper_transcription", serde_json::json!({
-                "text": result.text,
-                "language": result.language,
-                "confidence": result.confidence,
-                "source": "whisper"
-            }));
+            events::emit(&app, &TranscriptionEvent {
+                segment_id: None,
+                session_id: None,
+                text: result.text.clone(),
+                language: result.language,
+                language_probability: result.language_probability,
+                confidence: result.confidence,
+                no_speech_prob: result.no_speech_prob,
+                source: TranscriptionSource::Whisper,
+                speaker: None,
+                start_ms: None,
+                end_ms: None,
+                gain: None,
+            });
             Ok(result.text)
         }
         Err(e) => {
-            let _ = app.emit("cognivox:status", format!("Transcription error: {}", e));
+            events::emit_status(&app, PipelineState::Error, format!("Transcription error: {}", e));
             Err(e)
         }
     }
@@ -0,0 +1,512 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+use crate::gemini_client::GeminiState;
+use crate::network::{NetworkState, LOCAL_ONLY_ERROR};
+use crate::settings::{app_data_dir, SettingsState};
+use crate::whisper_client::{cached_model, model_file, WhisperState, MODEL_SIZES};
+
+// ============================================================================
+// WHISPER MODELS - Downloaded Model Files, Disk Usage and Cache Location
+// ============================================================================
+//
+// Models live in a Hugging Face hub cache: snapshots/<commit>/<file> is a
+// pointer (symlink on unix) to the real file under blobs/. Deleting or
+// moving a model has to handle both halves.
+//
+// Missing models come from a local directory when one is configured, else
+// from huggingface.co or a compatible mirror (HF_ENDPOINT-style), and are
+// checked against the SHA-256 the hub publishes before they're used.
+
+const DEFAULT_ENDPOINT: &str = "https://huggingface.co";
+// Same variable huggingface_hub reads
+const ENDPOINT_ENV: &str = "HF_ENDPOINT";
+const DOWNLOAD_TIMEOUT_SECS: u64 = 3600;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ModelSource {
+    // Hugging Face-compatible endpoint, e.g. https://hf-mirror.com;
+    // None = $HF_ENDPOINT, then huggingface.co
+    pub mirror_url: Option<String>,
+    // Directory of ggml-*.bin files, checked before downloading; a
+    // <file>.sha256 next to a model is used to verify it
+    pub local_dir: Option<String>,
+    pub verify_checksum: bool,
+}
+
+impl Default for ModelSource {
+    fn default() -> Self {
+        Self { mirror_url: None, local_dir: None, verify_checksum: true }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WhisperModelInfo {
+    pub size: String,
+    pub file: String,
+    pub path: String,
+    pub bytes: u64,
+    // Last time initialize_whisper loaded it; None if never loaded here
+    pub last_used: Option<String>,
+    pub loaded: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WhisperModels {
+    pub cache_dir: String,
+    // False when using the shared Hugging Face cache
+    pub custom_dir: bool,
+    pub total_bytes: u64,
+    pub models: Vec<WhisperModelInfo>,
+}
+
+fn usage_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join("whisper_models.json"))
+}
+
+/// File name -> last loaded (RFC 3339)
+fn load_usage() -> HashMap<String, String> {
+    usage_path()
+        .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+fn save_usage(usage: &HashMap<String, String>) {
+    let result = usage_path().and_then(|path| {
+        let json = serde_json::to_string_pretty(usage).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        warn!("[WHISPER] Failed to record model usage: {}", e);
+    }
+}
+
+/// Called whenever a model is loaded
+pub fn mark_used(model_path: &Path) {
+    let Some(file) = model_path.file_name() else { return; };
+    let mut usage = load_usage();
+    usage.insert(file.to_string_lossy().into_owned(), Utc::now().to_rfc3339());
+    save_usage(&usage);
+}
+
+/// Real file behind a cache pointer
+fn blob_of(pointer: &Path) -> PathBuf {
+    fs::canonicalize(pointer).unwrap_or_else(|_| pointer.to_path_buf())
+}
+
+/// Remove a cached model (pointer and blob); returns bytes freed
+fn remove_model_file(pointer: &Path) -> Result<u64, String> {
+    let blob = blob_of(pointer);
+    let bytes = fs::metadata(&blob).map(|m| m.len()).unwrap_or(0);
+    fs::remove_file(pointer)
+        .map_err(|e| format!("Failed to remove {}: {}", pointer.display(), e))?;
+    if blob != pointer && blob.exists() {
+        fs::remove_file(&blob)
+            .map_err(|e| format!("Failed to remove {}: {}", blob.display(), e))?;
+    }
+    Ok(bytes)
+}
+
+/// Copy one model into another cache under the same commit, then remove
+/// the original. Copy rather than rename: the new directory is often on
+/// another drive.
+fn move_model(from: &hf_hub::Cache, to: &hf_hub::Cache, size: &str) -> Result<Option<PathBuf>, String> {
+    let Some(source) = cached_model(from, size) else { return Ok(None); };
+    if let Some(existing) = cached_model(to, size) {
+        remove_model_file(&source)?;
+        return Ok(Some(existing));
+    }
+
+    let (model_id, filename) = model_file(size);
+    let commit = source.parent()
+        .and_then(|p| p.file_name())
+        .map(|c| c.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("Unexpected cache layout at {}", source.display()))?;
+    let snapshot = to.path()
+        .join(hf_hub::Repo::model(model_id.to_string()).folder_name())
+        .join("snapshots")
+        .join(&commit);
+    fs::create_dir_all(&snapshot)
+        .map_err(|e| format!("Failed to create {}: {}", snapshot.display(), e))?;
+
+    let target = snapshot.join(filename);
+    let tmp_path = target.with_extension("tmp");
+    fs::copy(blob_of(&source), &tmp_path)
+        .map_err(|e| format!("Failed to copy {}: {}", filename, e))?;
+    fs::rename(&tmp_path, &target)
+        .map_err(|e| format!("Failed to move {} into place: {}", filename, e))?;
+    to.model(model_id.to_string()).create_ref(&commit)
+        .map_err(|e| format!("Failed to write cache ref: {}", e))?;
+
+    remove_model_file(&source)?;
+    info!("[WHISPER] Moved {} to {}", filename, target.display());
+    Ok(Some(target))
+}
+
+// ============================================================================
+// Fetching Models
+// ============================================================================
+
+fn endpoint(source: &ModelSource) -> String {
+    source.mirror_url.clone()
+        .or_else(|| std::env::var(ENDPOINT_ENV).ok())
+        .map(|e| e.trim().trim_end_matches('/').to_string())
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+}
+
+fn repo_dir(cache: &hf_hub::Cache, model_id: &str) -> PathBuf {
+    cache.path().join(hf_hub::Repo::model(model_id.to_string()).folder_name())
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Expected hash from a `<file>.sha256` next to a local model ("<hash>  <name>" or just the hash)
+fn sidecar_checksum(path: &Path) -> Option<String> {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".sha256");
+    let text = fs::read_to_string(PathBuf::from(sidecar)).ok()?;
+    text.split_whitespace().next().map(str::to_lowercase).filter(|h| is_sha256(h))
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 { break; }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Compare against the expected hash; a mismatching file is deleted
+fn check_digest(path: &Path, filename: &str, expected: Option<&str>, actual: &str) -> Result<(), String> {
+    match expected {
+        Some(expected) if !expected.eq_ignore_ascii_case(actual) => {
+            let _ = fs::remove_file(path);
+            Err(format!("Checksum mismatch for {}: expected {}, got {} - the file was discarded", filename, expected, actual))
+        }
+        Some(_) => {
+            info!("[WHISPER] ✓ Checksum verified for {}", filename);
+            Ok(())
+        }
+        None => {
+            warn!("[WHISPER] No checksum available for {}; not verified", filename);
+            Ok(())
+        }
+    }
+}
+
+/// Move a verified file into the cache, where cached_model finds it. Goes
+/// under the cache's current snapshot so models already there stay visible.
+fn install(cache: &hf_hub::Cache, model_id: &str, filename: &str, file: &Path, commit: Option<String>) -> Result<PathBuf, String> {
+    let commit = fs::read_to_string(repo_dir(cache, model_id).join("refs").join("main")).ok()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .or(commit)
+        .unwrap_or_else(|| "local".to_string());
+    let snapshot = repo_dir(cache, model_id).join("snapshots").join(&commit);
+    fs::create_dir_all(&snapshot)
+        .map_err(|e| format!("Failed to create {}: {}", snapshot.display(), e))?;
+    let target = snapshot.join(filename);
+    fs::rename(file, &target)
+        .map_err(|e| format!("Failed to move {} into place: {}", filename, e))?;
+    cache.model(model_id.to_string()).create_ref(&commit)
+        .map_err(|e| format!("Failed to write cache ref: {}", e))?;
+    Ok(target)
+}
+
+/// Partial files go next to the cache so the final rename stays on one drive
+fn partial_path(cache: &hf_hub::Cache, model_id: &str, filename: &str) -> Result<PathBuf, String> {
+    let dir = repo_dir(cache, model_id).join("tmp");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir.join(format!("{}.part", filename)))
+}
+
+/// Copy a model from the configured local directory, if it's there
+fn copy_from_local_dir(cache: &hf_hub::Cache, source: &ModelSource, model_id: &str, filename: &str) -> Result<Option<PathBuf>, String> {
+    let Some(dir) = source.local_dir.as_deref() else { return Ok(None); };
+    let path = Path::new(dir).join(filename);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    info!("[WHISPER] Copying {} from {}", filename, dir);
+    let partial = partial_path(cache, model_id, filename)?;
+    fs::copy(&path, &partial).map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+    if source.verify_checksum {
+        check_digest(&partial, filename, sidecar_checksum(&path).as_deref(), &sha256_file(&partial)?)?;
+    }
+    install(cache, model_id, filename, &partial, None).map(Some)
+}
+
+/// SHA-256 of an LFS file, from the hub's tree listing (mirrors serve it too)
+async fn published_checksum(client: &reqwest::Client, endpoint: &str, model_id: &str, filename: &str) -> Option<String> {
+    let url = format!("{}/api/models/{}/tree/main", endpoint, model_id);
+    let listing: serde_json::Value = client.get(&url)
+        .timeout(Duration::from_secs(30))
+        .send().await.ok()?
+        .error_for_status().ok()?
+        .json().await.ok()?;
+    listing.as_array()?
+        .iter()
+        .find(|entry| entry["path"] == filename)
+        .and_then(|entry| entry["lfs"]["oid"].as_str())
+        .map(|oid| oid.trim_start_matches("sha256:").to_lowercase())
+        .filter(|oid| is_sha256(oid))
+}
+
+async fn download(
+    client: &reqwest::Client,
+    cache: &hf_hub::Cache,
+    source: &ModelSource,
+    model_id: &str,
+    filename: &str,
+) -> Result<PathBuf, String> {
+    let endpoint = endpoint(source);
+    let url = format!("{}/{}/resolve/main/{}", endpoint, model_id, filename);
+    let unreachable = |e: reqwest::Error| format!(
+        "Can't reach {} to download {} ({}). If Hugging Face is blocked on this network, \
+         set a mirror URL or a local model directory with set_model_source.",
+        endpoint, filename, e
+    );
+    info!("[WHISPER] Downloading {} from {}...", filename, endpoint);
+
+    let mut expected = match source.verify_checksum {
+        true => published_checksum(client, &endpoint, model_id, filename).await,
+        false => None,
+    };
+    let mut response = client.get(&url)
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(unreachable)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("{} answered {} for {} - is it a Hugging Face mirror?", endpoint, status, url));
+    }
+    let header = |name: &str| response.headers().get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim_start_matches("W/").trim_matches('"').to_lowercase());
+    let commit = header("x-repo-commit");
+    if source.verify_checksum && expected.is_none() {
+        expected = header("x-linked-etag").or_else(|| header("etag")).filter(|h| is_sha256(h));
+    }
+
+    let partial = partial_path(cache, model_id, filename)?;
+    let mut file = fs::File::create(&partial)
+        .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut bytes: u64 = 0;
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Download of {} interrupted: {}", filename, e))? {
+        hasher.update(&chunk);
+        file.write_all(&chunk).map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        bytes += chunk.len() as u64;
+    }
+    drop(file);
+    info!("[WHISPER] Downloaded {} ({} MB)", filename, bytes / 1_000_000);
+
+    if source.verify_checksum {
+        check_digest(&partial, filename, expected.as_deref(), &hex::encode(hasher.finalize()))?;
+    }
+    install(cache, model_id, filename, &partial, commit)
+}
+
+/// Path of a model, fetching it first if it isn't in the cache yet
+pub(crate) async fn fetch_model(
+    cache: hf_hub::Cache,
+    model_size: &str,
+    source: &ModelSource,
+    network: &NetworkState,
+) -> Result<PathBuf, String> {
+    if let Some(path) = cached_model(&cache, model_size) {
+        return Ok(path);
+    }
+    let (model_id, filename) = model_file(model_size);
+    if let Some(path) = copy_from_local_dir(&cache, source, model_id, filename)? {
+        return Ok(path);
+    }
+
+    // Local-only: an already-downloaded model is fine, fetching one is not
+    if network.is_local_only() {
+        return Err(match &source.local_dir {
+            Some(dir) => format!("{} is not downloaded yet and not in {} ({})", filename, dir, LOCAL_ONLY_ERROR),
+            None => format!("{} is not downloaded yet ({})", filename, LOCAL_ONLY_ERROR),
+        });
+    }
+    download(&network.client()?, &cache, source, model_id, filename).await
+}
+
+/// Most recently loaded model that is still downloaded
+pub fn last_used_size(cache: &hf_hub::Cache) -> Option<&'static str> {
+    let usage = load_usage();
+    MODEL_SIZES.iter()
+        .filter(|size| cached_model(cache, size).is_some())
+        .filter_map(|size| usage.get(model_file(size).1).map(|at| (*size, at)))
+        .max_by(|a, b| a.1.cmp(b.1))
+        .map(|(size, _)| size)
+}
+
+fn list_models(whisper: &WhisperState) -> WhisperModels {
+    let cache = whisper.model_cache();
+    let loaded = whisper.model_path.lock().unwrap().clone();
+    let usage = load_usage();
+
+    let models: Vec<WhisperModelInfo> = MODEL_SIZES.iter()
+        .filter_map(|size| {
+            let path = cached_model(&cache, size)?;
+            let file = model_file(size).1.to_string();
+            Some(WhisperModelInfo {
+                size: size.to_string(),
+                bytes: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+                last_used: usage.get(&file).cloned(),
+                loaded: loaded.as_deref() == Some(path.as_path()),
+                path: path.to_string_lossy().into_owned(),
+                file,
+            })
+        })
+        .collect();
+
+    WhisperModels {
+        cache_dir: cache.path().to_string_lossy().into_owned(),
+        custom_dir: whisper.model_dir.lock().unwrap().is_some(),
+        total_bytes: models.iter().map(|m| m.bytes).sum(),
+        models,
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_whisper_models(state: tauri::State<'_, WhisperState>) -> WhisperModels {
+    list_models(&state)
+}
+
+/// Delete a downloaded model; returns bytes freed
+#[tauri::command]
+pub fn delete_whisper_model(app: AppHandle, size: String) -> Result<u64, String> {
+    let size = size.trim().to_lowercase();
+    if !MODEL_SIZES.contains(&size.as_str()) {
+        return Err(format!("Unknown model '{}' (expected one of {})", size, MODEL_SIZES.join(", ")));
+    }
+    let whisper = app.state::<WhisperState>();
+    let path = cached_model(&whisper.model_cache(), &size)
+        .ok_or_else(|| format!("The {} model is not downloaded", size))?;
+
+    let is_loaded = whisper.model_path.lock().unwrap().as_deref() == Some(path.as_path());
+    if is_loaded {
+        if app.state::<GeminiState>().audio_loop_running() {
+            return Err("This model is in use - stop the live session before deleting it".to_string());
+        }
+        *whisper.is_initialized.lock().unwrap() = false;
+        *whisper.model_path.lock().unwrap() = None;
+    }
+
+    let freed = remove_model_file(&path)?;
+    let mut usage = load_usage();
+    if usage.remove(model_file(&size).1).is_some() {
+        save_usage(&usage);
+    }
+    info!("[WHISPER] Deleted {} model ({} bytes)", size, freed);
+    Ok(freed)
+}
+
+/// Download models to `dir` from now on (None = shared Hugging Face cache).
+/// With `move_models`, already-downloaded models are moved there too.
+#[tauri::command]
+pub fn set_whisper_model_dir(
+    app: AppHandle,
+    dir: Option<String>,
+    move_models: bool,
+) -> Result<WhisperModels, String> {
+    if app.state::<GeminiState>().audio_loop_running() {
+        return Err("Stop the live session before moving the model cache".to_string());
+    }
+    let dir = match dir.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(d) => {
+            let path = PathBuf::from(d);
+            if !path.is_absolute() {
+                return Err(format!("Model directory must be an absolute path: {}", d));
+            }
+            fs::create_dir_all(&path)
+                .map_err(|e| format!("Failed to create model directory: {}", e))?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    let whisper = app.state::<WhisperState>();
+    let from = whisper.model_cache();
+    let to = match &dir {
+        Some(d) => hf_hub::Cache::new(d.clone()),
+        None => hf_hub::Cache::default(),
+    };
+
+    if move_models && from.path() != to.path() {
+        for size in MODEL_SIZES {
+            let loaded = whisper.model_path.lock().unwrap().clone();
+            let was_loaded = loaded.is_some() && loaded == cached_model(&from, size);
+            if let Some(moved) = move_model(&from, &to, size)? {
+                if was_loaded {
+                    *whisper.model_path.lock().unwrap() = Some(moved);
+                }
+            }
+        }
+    }
+
+    let stored = dir.as_ref().map(|d| d.to_string_lossy().into_owned());
+    app.state::<SettingsState>().update(|s| s.whisper_model_dir = stored)?;
+    *whisper.model_dir.lock().unwrap() = dir;
+    info!("[WHISPER] Model cache: {}", to.path().display());
+    Ok(list_models(&whisper))
+}
+
+#[tauri::command]
+pub fn get_model_source(settings: tauri::State<'_, SettingsState>) -> ModelSource {
+    settings.get().model_source
+}
+
+/// Where missing Whisper models come from: a mirror, a local directory, or both
+#[tauri::command]
+pub fn set_model_source(
+    settings: tauri::State<'_, SettingsState>,
+    source: ModelSource,
+) -> Result<ModelSource, String> {
+    let trimmed = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let source = ModelSource {
+        mirror_url: trimmed(source.mirror_url).map(|u| u.trim_end_matches('/').to_string()),
+        local_dir: trimmed(source.local_dir),
+        ..source
+    };
+    if let Some(url) = &source.mirror_url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("Mirror URL must start with http:// or https://: {}", url));
+        }
+    }
+    if let Some(dir) = &source.local_dir {
+        if !Path::new(dir).is_absolute() || !Path::new(dir).is_dir() {
+            return Err(format!("Local model directory must be an existing absolute path: {}", dir));
+        }
+    }
+    let settings = settings.update(|s| s.model_source = source)?;
+    info!("[WHISPER] Model source: {} (local dir: {}, checksums {})",
+        endpoint(&settings.model_source),
+        settings.model_source.local_dir.as_deref().unwrap_or("none"),
+        if settings.model_source.verify_checksum { "on" } else { "off" });
+    Ok(settings.model_source)
+}